@@ -0,0 +1,235 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::script::Script;
+use crate::{SigningError, SigningErrorType, SigningResult};
+use tw_encoding::base58::{self, Alphabet};
+use tw_encoding::bech32;
+use tw_hash::sha2::sha256d;
+
+/// The length of a decoded Base58Check address: the prefix byte and the 20 byte hash.
+/// cbindgen:ignore
+const LEGACY_ADDRESS_LEN: usize = 21;
+/// Bitcoin accepts testnet segwit addresses as well.
+/// cbindgen:ignore
+const BITCOIN_TESTNET_HRP: &str = "tb";
+
+/// Address and fee estimation parameters of a Bitcoin-like coin as defined in `registry.json`.
+#[derive(Debug, PartialEq)]
+pub struct CoinParams {
+    pub coin_type: u32,
+    pub p2pkh_prefix: u8,
+    pub p2sh_prefix: u8,
+    /// The human-readable part of segwit addresses if the coin supports them.
+    pub hrp: Option<&'static str>,
+    /// Whether the default derivation is BIP84.
+    /// If so, the fee is estimated from the virtual size of a transaction signed with placeholders.
+    pub segwit: bool,
+}
+
+/// cbindgen:ignore
+pub const BITCOIN: CoinParams = CoinParams {
+    coin_type: 0,
+    p2pkh_prefix: 0,
+    p2sh_prefix: 5,
+    hrp: Some("bc"),
+    segwit: true,
+};
+
+/// cbindgen:ignore
+const COINS: [CoinParams; 9] = [
+    BITCOIN,
+    // Litecoin
+    CoinParams {
+        coin_type: 2,
+        p2pkh_prefix: 48,
+        p2sh_prefix: 50,
+        hrp: Some("ltc"),
+        segwit: true,
+    },
+    // Dogecoin
+    CoinParams {
+        coin_type: 3,
+        p2pkh_prefix: 30,
+        p2sh_prefix: 22,
+        hrp: None,
+        segwit: false,
+    },
+    // Dash
+    CoinParams {
+        coin_type: 5,
+        p2pkh_prefix: 76,
+        p2sh_prefix: 16,
+        hrp: None,
+        segwit: false,
+    },
+    // Viacoin
+    CoinParams {
+        coin_type: 14,
+        p2pkh_prefix: 71,
+        p2sh_prefix: 33,
+        hrp: Some("via"),
+        segwit: true,
+    },
+    // DigiByte
+    CoinParams {
+        coin_type: 20,
+        p2pkh_prefix: 30,
+        p2sh_prefix: 63,
+        hrp: Some("dgb"),
+        segwit: true,
+    },
+    // Monacoin
+    CoinParams {
+        coin_type: 22,
+        p2pkh_prefix: 50,
+        p2sh_prefix: 55,
+        hrp: Some("mona"),
+        segwit: false,
+    },
+    // Bitcoin Gold
+    CoinParams {
+        coin_type: 156,
+        p2pkh_prefix: 38,
+        p2sh_prefix: 23,
+        hrp: Some("btg"),
+        segwit: true,
+    },
+    // Qtum
+    CoinParams {
+        coin_type: 2301,
+        p2pkh_prefix: 58,
+        p2sh_prefix: 50,
+        hrp: Some("qc"),
+        segwit: false,
+    },
+];
+
+impl CoinParams {
+    /// Returns the parameters of a supported coin by its SLIP-44 `coin_type`.
+    pub fn for_coin(coin_type: u32) -> SigningResult<&'static CoinParams> {
+        COINS
+            .iter()
+            .find(|coin| coin.coin_type == coin_type)
+            .ok_or_else(|| {
+                SigningError::new(
+                    SigningErrorType::Error_invalid_params,
+                    format!("Unsupported coin type: {coin_type}"),
+                )
+            })
+    }
+
+    /// Returns a locking script for a legacy (P2PKH, P2SH) or segwit (v0, P2TR) `address`.
+    pub fn lock_script_for_address(&self, address: &str) -> SigningResult<Script> {
+        self.try_lock_script(address).ok_or_else(|| {
+            SigningError::new(
+                SigningErrorType::Error_invalid_address,
+                format!("Invalid address: {address}"),
+            )
+        })
+    }
+
+    fn try_lock_script(&self, address: &str) -> Option<Script> {
+        if let Ok(decoded) = base58::decode_check(address, Alphabet::BITCOIN, sha256d) {
+            return match decoded.as_slice() {
+                [prefix, hash @ ..] if decoded.len() == LEGACY_ADDRESS_LEN => {
+                    if *prefix == self.p2pkh_prefix {
+                        Some(Script::p2pkh(hash))
+                    } else if *prefix == self.p2sh_prefix {
+                        Some(Script::p2sh(hash))
+                    } else {
+                        None
+                    }
+                },
+                _ => None,
+            };
+        }
+
+        let witness = self
+            .hrp
+            .and_then(|hrp| bech32::decode_segwit(hrp, address).ok())
+            .or_else(|| {
+                if *self == BITCOIN {
+                    bech32::decode_segwit(BITCOIN_TESTNET_HRP, address).ok()
+                } else {
+                    None
+                }
+            })?;
+        match witness.version {
+            0 => Some(Script::witness_program(0, &witness.program)),
+            // Only 32 byte v1 programs are taproot outputs.
+            1 if witness.program.len() == 32 => Some(Script::p2tr(&witness.program)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    fn lock_script(coin_type: u32, address: &str) -> String {
+        let script = CoinParams::for_coin(coin_type)
+            .unwrap()
+            .lock_script_for_address(address)
+            .unwrap();
+        hex::encode(script.as_bytes(), false)
+    }
+
+    #[test]
+    fn test_lock_script_for_address() {
+        assert_eq!(
+            lock_script(0, "1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx"),
+            "76a914769bdff96a02f9135a1d19b749db6a78fe07dc9088ac"
+        );
+        assert_eq!(
+            lock_script(0, "3Hv6oV8BYCoocW4eqZaEXsaR5tHhCxiMSk"),
+            "a914b1fb7e043152fd1eed7bfaf66679ad3b6c9068f387"
+        );
+        assert_eq!(
+            lock_script(0, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+        assert_eq!(
+            lock_script(0, "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"),
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+        assert_eq!(
+            lock_script(
+                0,
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0"
+            ),
+            "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+        assert_eq!(
+            lock_script(2, "ltc1qt36tu30tgk35tyzsve6jjq3dnhu2rm8l8v5q00"),
+            "00145c74be45eb45a3459050667529022d9df8a1ecff"
+        );
+    }
+
+    #[test]
+    fn test_lock_script_for_invalid_address() {
+        let bitcoin = CoinParams::for_coin(0).unwrap();
+        // Litecoin addresses are not valid for Bitcoin.
+        for address in [
+            "LV7LV7Z4bWDEjYkfx9dQo6k6RjGbXsg6hS",
+            "ltc1qt36tu30tgk35tyzsve6jjq3dnhu2rm8l8v5q00",
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+            "",
+        ] {
+            assert_eq!(
+                bitcoin.lock_script_for_address(address).unwrap_err().error,
+                SigningErrorType::Error_invalid_address
+            );
+        }
+
+        assert_eq!(
+            CoinParams::for_coin(60).unwrap_err().error,
+            SigningErrorType::Error_invalid_params
+        );
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::address::CoinParams;
+use crate::Amount;
+
+/// Estimates the fee from the number of inputs and outputs linearly.
+pub(crate) struct LinearFeeCalculator {
+    bytes_per_input: f64,
+    bytes_per_output: f64,
+    bytes_base: f64,
+}
+
+/// Sizes of a P2PKH transaction.
+/// cbindgen:ignore
+const DEFAULT_FEE_CALCULATOR: LinearFeeCalculator = LinearFeeCalculator {
+    bytes_per_input: 148.0,
+    bytes_per_output: 34.0,
+    bytes_base: 10.0,
+};
+
+/// Virtual sizes of a P2WPKH transaction.
+/// cbindgen:ignore
+const SEGWIT_FEE_CALCULATOR: LinearFeeCalculator = LinearFeeCalculator {
+    bytes_per_input: 101.25,
+    bytes_per_output: 31.0,
+    bytes_base: 10.0,
+};
+
+impl LinearFeeCalculator {
+    pub fn for_coin(coin: &CoinParams) -> &'static LinearFeeCalculator {
+        if coin.segwit {
+            &SEGWIT_FEE_CALCULATOR
+        } else {
+            &DEFAULT_FEE_CALCULATOR
+        }
+    }
+
+    pub fn calculate(&self, inputs: usize, outputs: usize, byte_fee: Amount) -> Amount {
+        let size = self.bytes_per_input * inputs as f64
+            + self.bytes_per_output * outputs as f64
+            + self.bytes_base;
+        size.ceil() as Amount * byte_fee
+    }
+
+    /// Returns the fee of spending one input, the UTXOs of less or equal amount are considered dust.
+    pub fn calculate_single_input(&self, byte_fee: Amount) -> Amount {
+        self.bytes_per_input.ceil() as Amount * byte_fee
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_fee_calculator() {
+        assert_eq!(DEFAULT_FEE_CALCULATOR.calculate(1, 2, 1), 226);
+        assert_eq!(DEFAULT_FEE_CALCULATOR.calculate_single_input(10), 1480);
+        assert_eq!(SEGWIT_FEE_CALCULATOR.calculate(1, 2, 1), 174);
+        assert_eq!(SEGWIT_FEE_CALCULATOR.calculate(2, 2, 1), 275);
+        assert_eq!(SEGWIT_FEE_CALCULATOR.calculate_single_input(1), 102);
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#![allow(clippy::missing_safety_doc)]
+
+use crate::signer::Signer;
+use crate::SigningErrorType;
+use std::borrow::Cow;
+use tw_memory::ffi::c_byte_array::CByteArray;
+use tw_memory::ffi::c_byte_array_ref::CByteArrayRef;
+use tw_proto::Bitcoin::Proto;
+
+/// Plans, builds and signs a Bitcoin transaction.
+/// \param input *non-null* byte array of a serialized `Bitcoin::Proto::SigningInput` message.
+/// \param input_len length of the input byte array.
+/// \return a serialized `Bitcoin::Proto::SigningOutput` message.
+#[no_mangle]
+pub unsafe extern "C" fn bitcoin_sign(input: *const u8, input_len: usize) -> CByteArray {
+    let input = CByteArrayRef::new(input, input_len)
+        .as_slice()
+        .unwrap_or_default();
+
+    let output = match tw_proto::deserialize::<Proto::SigningInput>(input) {
+        Ok(input) => Signer::sign_proto(input),
+        Err(_) => Proto::SigningOutput {
+            error: SigningErrorType::Error_input_parse,
+            error_message: Cow::Borrowed("Error deserializing 'SigningInput'"),
+            ..Proto::SigningOutput::default()
+        },
+    };
+    tw_proto::serialize(&output)
+        .map(CByteArray::from)
+        .unwrap_or_default()
+}
+
+/// Plans a Bitcoin transaction: selects UTXOs and estimates the fee.
+/// \param input *non-null* byte array of a serialized `Bitcoin::Proto::SigningInput` message.
+/// \param input_len length of the input byte array.
+/// \return a serialized `Bitcoin::Proto::TransactionPlan` message.
+#[no_mangle]
+pub unsafe extern "C" fn bitcoin_plan(input: *const u8, input_len: usize) -> CByteArray {
+    let input = CByteArrayRef::new(input, input_len)
+        .as_slice()
+        .unwrap_or_default();
+
+    let plan = match tw_proto::deserialize::<Proto::SigningInput>(input) {
+        Ok(input) => Signer::plan_proto(input),
+        Err(_) => Proto::TransactionPlan {
+            error: SigningErrorType::Error_input_parse,
+            ..Proto::TransactionPlan::default()
+        },
+    };
+    tw_proto::serialize(&plan)
+        .map(CByteArray::from)
+        .unwrap_or_default()
+}
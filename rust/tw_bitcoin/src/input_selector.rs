@@ -0,0 +1,237 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::fee::LinearFeeCalculator;
+use crate::signing_input::Utxo;
+use crate::Amount;
+
+pub(crate) fn sum(utxos: &[Utxo]) -> Amount {
+    utxos.iter().map(|utxo| utxo.amount).sum()
+}
+
+/// Selects UTXOs to spend as the C++ implementation does, so that plans are the same.
+pub(crate) struct InputSelector<'a> {
+    utxos: &'a [Utxo],
+    fee_calculator: &'a LinearFeeCalculator,
+}
+
+impl<'a> InputSelector<'a> {
+    pub fn new(utxos: &'a [Utxo], fee_calculator: &'a LinearFeeCalculator) -> Self {
+        InputSelector {
+            utxos,
+            fee_calculator,
+        }
+    }
+
+    /// Selects the fewest UTXOs covering the `target` with the fee.
+    /// Prefers the ones whose sum is the closest to twice the target and doesn't leave dust change.
+    /// Returns an empty list if the UTXOs are insufficient.
+    pub fn select(&self, target: Amount, byte_fee: Amount, outputs: usize) -> Vec<Utxo> {
+        if target == 0 || self.utxos.is_empty() || sum(self.utxos) < target {
+            return Vec::new();
+        }
+
+        let mut sorted = self.utxos.to_vec();
+        sorted.sort_by_key(|utxo| utxo.amount);
+
+        // The maximum amount that can be obtained with the given number of inputs.
+        let max_with_inputs: Vec<Amount> = std::iter::once(0)
+            .chain(sorted.iter().rev().scan(0, |max, utxo| {
+                *max += utxo.amount;
+                Some(*max)
+            }))
+            .collect();
+
+        let double_target = target * 2;
+        let dist_from_double_target = |slice: &[Utxo]| (sum(slice) - double_target).abs();
+        let dust_threshold = self.fee_calculator.calculate_single_input(byte_fee);
+
+        // 1. Find a combination of the fewest inputs that is bigger than what we need,
+        //    closer to twice the target and does not produce dust change.
+        for (inputs, max) in max_with_inputs.iter().enumerate().skip(1) {
+            let fee = self.fee_calculator.calculate(inputs, outputs, byte_fee);
+            let target_with_fee_and_dust = target + fee + dust_threshold;
+            if *max < target_with_fee_and_dust {
+                continue;
+            }
+            let best = sorted
+                .windows(inputs)
+                .filter(|slice| sum(slice) >= target_with_fee_and_dust)
+                .min_by_key(|slice| dist_from_double_target(slice));
+            if let Some(best) = best {
+                return self.filter_out_dust(best, byte_fee);
+            }
+        }
+
+        // 2. If not, find a valid combination of inputs even if they produce dust change.
+        for (inputs, max) in max_with_inputs.iter().enumerate().skip(1) {
+            let fee = self.fee_calculator.calculate(inputs, outputs, byte_fee);
+            let target_with_fee = target + fee;
+            if *max < target_with_fee {
+                continue;
+            }
+            let first = sorted
+                .windows(inputs)
+                .find(|slice| sum(slice) >= target_with_fee);
+            if let Some(first) = first {
+                return self.filter_out_dust(first, byte_fee);
+            }
+        }
+
+        Vec::new()
+    }
+
+    /// Selects non-dust UTXOs in a single pass in their original order
+    /// until they cover a 10% increased target with the fee of spending all UTXOs.
+    /// It's used instead of [`InputSelector::select`] if there are too many UTXOs.
+    pub fn select_simple(&self, target: Amount, byte_fee: Amount, outputs: usize) -> Vec<Utxo> {
+        if target == 0 || self.utxos.is_empty() {
+            return Vec::new();
+        }
+
+        let increased_target = (target as f64 * 1.1
+            + self
+                .fee_calculator
+                .calculate(self.utxos.len(), outputs, byte_fee) as f64
+            + 1000.0) as Amount;
+        let dust_threshold = self.fee_calculator.calculate_single_input(byte_fee);
+
+        let mut selected = Vec::new();
+        let mut selected_sum = 0;
+        for utxo in self
+            .utxos
+            .iter()
+            .filter(|utxo| utxo.amount > dust_threshold)
+        {
+            selected.push(utxo.clone());
+            selected_sum += utxo.amount;
+            if selected_sum >= increased_target {
+                return selected;
+            }
+        }
+        Vec::new()
+    }
+
+    /// Selects all UTXOs except dust ones that cost more to spend than they're worth.
+    pub fn select_max_amount(&self, byte_fee: Amount) -> Vec<Utxo> {
+        self.filter_out_dust(self.utxos, byte_fee)
+    }
+
+    fn filter_out_dust(&self, utxos: &[Utxo], byte_fee: Amount) -> Vec<Utxo> {
+        let dust_threshold = self.fee_calculator.calculate_single_input(byte_fee);
+        utxos
+            .iter()
+            .filter(|utxo| utxo.amount > dust_threshold)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::BITCOIN;
+    use crate::script::Script;
+    use crate::transaction::OutPoint;
+    use tw_hash::H256;
+
+    fn utxos(amounts: &[Amount]) -> Vec<Utxo> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(index, amount)| Utxo {
+                out_point: OutPoint {
+                    hash: H256::default(),
+                    index: index as u32,
+                },
+                sequence: u32::MAX,
+                script: Script::default(),
+                amount: *amount,
+            })
+            .collect()
+    }
+
+    fn amounts(utxos: Vec<Utxo>) -> Vec<Amount> {
+        utxos.into_iter().map(|utxo| utxo.amount).collect()
+    }
+
+    fn select(amounts_: &[Amount], target: Amount, byte_fee: Amount) -> Vec<Amount> {
+        let utxos = utxos(amounts_);
+        let selector = InputSelector::new(&utxos, LinearFeeCalculator::for_coin(&BITCOIN));
+        amounts(selector.select(target, byte_fee, 2))
+    }
+
+    fn select_simple(amounts_: &[Amount], target: Amount, byte_fee: Amount) -> Vec<Amount> {
+        let utxos = utxos(amounts_);
+        let selector = InputSelector::new(&utxos, LinearFeeCalculator::for_coin(&BITCOIN));
+        amounts(selector.select_simple(target, byte_fee, 2))
+    }
+
+    fn select_max_amount(amounts_: &[Amount], byte_fee: Amount) -> Vec<Amount> {
+        let utxos = utxos(amounts_);
+        let selector = InputSelector::new(&utxos, LinearFeeCalculator::for_coin(&BITCOIN));
+        amounts(selector.select_max_amount(byte_fee))
+    }
+
+    #[test]
+    fn test_select() {
+        let all = [4000, 2000, 6000, 1000, 11000, 12000];
+        assert_eq!(select(&all, 5000, 1), [11000]);
+        let all = [4000, 2000, 6000, 1000, 50000, 120000];
+        assert_eq!(select(&all, 10000, 1), [50000]);
+        assert_eq!(select(&[4000, 2000, 5000], 6000, 1), [4000, 5000]);
+        assert_eq!(select(&[40000, 30000, 30000], 50000, 1), [30000, 40000]);
+        let all = [1000, 2000, 3000, 4000, 5000, 6000, 7000, 8000, 9000];
+        assert_eq!(select(&all, 28000, 1), [6000, 7000, 8000, 9000]);
+        assert_eq!(select(&[794121, 2289357], 2287189, 61), [794121, 2289357]);
+    }
+
+    #[test]
+    fn test_select_insufficient() {
+        assert!(select(&[4000, 4000, 4000], 15000, 1).is_empty());
+        assert!(select(&[], 100000, 1).is_empty());
+        assert!(select(&[100000], 0, 1).is_empty());
+    }
+
+    #[test]
+    fn test_select_exact() {
+        let all = [
+            1000, 2000, 100000, 3000, 4000, 5000, 125000, 6000, 150000, 7000,
+        ];
+        let dust_limit = 102;
+        assert_eq!(
+            select(&all, 375000 - 376 - dust_limit, 1),
+            [100000, 125000, 150000]
+        );
+        // One more, and it's too much.
+        assert_eq!(
+            select(&all, 375000 - 376 - dust_limit + 1, 1),
+            [7000, 100000, 125000, 150000]
+        );
+    }
+
+    #[test]
+    fn test_select_simple() {
+        let all = [1000, 2000, 3000, 4000, 5000, 6000, 7000, 8000, 9000];
+        assert_eq!(
+            select_simple(&all, 28000, 1),
+            [1000, 2000, 3000, 4000, 5000, 6000, 7000, 8000]
+        );
+        let all = [
+            150000, 125000, 100000, 7000, 6000, 5000, 4000, 3000, 2000, 1000,
+        ];
+        assert_eq!(select_simple(&all, 300000, 1), [150000, 125000, 100000]);
+    }
+
+    #[test]
+    fn test_select_max_amount() {
+        let all = [400, 500, 600, 800, 1000];
+        assert_eq!(select_max_amount(&all, 1), all);
+        assert_eq!(select_max_amount(&all, 4), [500, 600, 800, 1000]);
+        assert_eq!(select_max_amount(&all, 8), [1000]);
+        assert!(select_max_amount(&all, 10).is_empty());
+    }
+}
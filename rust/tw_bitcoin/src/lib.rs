@@ -0,0 +1,49 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! `tw_bitcoin` crate plans, builds and signs Bitcoin and Bitcoin-like transactions.
+//! It spends P2PK, P2PKH, P2SH, P2WPKH, P2WSH (including nested in P2SH) and P2TR key-path outputs
+//! computing legacy, BIP143 and BIP341 signature hashes.
+//! The [`psbt`] module reads, signs, combines and finalizes Partially Signed Bitcoin Transactions.
+
+pub mod address;
+pub mod ffi;
+pub mod planner;
+pub mod psbt;
+pub mod script;
+pub mod sighash;
+pub mod signer;
+pub mod signing_input;
+pub mod transaction;
+
+mod fee;
+mod input_selector;
+mod reader;
+mod signature_builder;
+mod tx_builder;
+
+pub use tw_proto::Common::Proto::SigningError as SigningErrorType;
+
+/// An amount in the smallest units, e.g. satoshis.
+pub type Amount = i64;
+
+pub type SigningResult<T> = Result<T, SigningError>;
+
+/// An error that is reported through the `error` and `error_message` fields of `SigningOutput`.
+#[derive(Debug, PartialEq)]
+pub struct SigningError {
+    pub error: SigningErrorType,
+    pub message: String,
+}
+
+impl SigningError {
+    pub fn new<M: Into<String>>(error: SigningErrorType, message: M) -> SigningError {
+        SigningError {
+            error,
+            message: message.into(),
+        }
+    }
+}
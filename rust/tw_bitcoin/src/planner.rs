@@ -0,0 +1,169 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::fee::LinearFeeCalculator;
+use crate::input_selector::{sum, InputSelector};
+use crate::signature_builder::{sign_transaction, SigningMode};
+use crate::signing_input::{SigningInput, Utxo};
+use crate::{Amount, SigningErrorType, SigningResult};
+use std::borrow::Cow;
+use tw_proto::Bitcoin::Proto;
+
+/// Above this number of UTXOs the simple selection is used.
+/// cbindgen:ignore
+const SIMPLE_MODE_LIMIT: usize = 1000;
+/// The maximum number of UTXOs a plan can spend, the other selected ones are cut off.
+/// cbindgen:ignore
+pub const MAX_UTXOS_HARD_LIMIT: usize = 3000;
+
+/// A plan of a transaction: selected UTXOs, the amounts and the fee.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionPlan {
+    /// The amount to be received by the recipient.
+    pub amount: Amount,
+    /// The sum of the selected UTXOs.
+    pub available_amount: Amount,
+    pub fee: Amount,
+    pub change: Amount,
+    pub utxos: Vec<Utxo>,
+    /// Zcash branch id, kept as is.
+    pub branch_id: Vec<u8>,
+    pub output_op_return: Vec<u8>,
+    pub error: SigningErrorType,
+}
+
+impl Default for TransactionPlan {
+    fn default() -> Self {
+        TransactionPlan {
+            amount: 0,
+            available_amount: 0,
+            fee: 0,
+            change: 0,
+            utxos: Vec::new(),
+            branch_id: Vec::new(),
+            output_op_return: Vec::new(),
+            error: SigningErrorType::OK,
+        }
+    }
+}
+
+impl TransactionPlan {
+    pub fn from_proto(plan: &Proto::TransactionPlan<'_>) -> SigningResult<TransactionPlan> {
+        Ok(TransactionPlan {
+            amount: plan.amount,
+            available_amount: plan.available_amount,
+            fee: plan.fee,
+            change: plan.change,
+            utxos: plan
+                .utxos
+                .iter()
+                .map(Utxo::from_proto)
+                .collect::<SigningResult<Vec<_>>>()?,
+            branch_id: plan.branch_id.to_vec(),
+            output_op_return: plan.output_op_return.to_vec(),
+            error: plan.error,
+        })
+    }
+
+    pub fn to_proto(&self) -> Proto::TransactionPlan<'static> {
+        Proto::TransactionPlan {
+            amount: self.amount,
+            available_amount: self.available_amount,
+            fee: self.fee,
+            change: self.change,
+            utxos: self.utxos.iter().map(Utxo::to_proto).collect(),
+            branch_id: Cow::Owned(self.branch_id.clone()),
+            error: self.error,
+            output_op_return: Cow::Owned(self.output_op_return.clone()),
+        }
+    }
+}
+
+/// Selects UTXOs and estimates the fee.
+/// If the requested amount is not less than the sum of all UTXOs, the maximum available amount is sent.
+pub fn plan(input: &SigningInput) -> TransactionPlan {
+    let mut plan = TransactionPlan {
+        output_op_return: input.output_op_return.clone(),
+        ..TransactionPlan::default()
+    };
+
+    let mut max_amount = input.use_max_amount;
+    if input.amount == 0 && !max_amount {
+        plan.error = SigningErrorType::Error_zero_amount_requested;
+        return plan;
+    }
+    if input.utxos.is_empty() {
+        plan.error = SigningErrorType::Error_missing_input_utxos;
+        return plan;
+    }
+
+    let fee_calculator = LinearFeeCalculator::for_coin(input.coin);
+    let selector = InputSelector::new(&input.utxos, fee_calculator);
+    if !max_amount && input.amount >= sum(&input.utxos) {
+        max_amount = true;
+    }
+
+    let extra_outputs = usize::from(!input.output_op_return.is_empty());
+    let (outputs, mut selected) = if max_amount {
+        // The recipient output only, there is no change.
+        let outputs = 1 + extra_outputs;
+        (outputs, selector.select_max_amount(input.byte_fee))
+    } else {
+        let outputs = 2 + extra_outputs;
+        let selected = if input.utxos.len() <= SIMPLE_MODE_LIMIT {
+            selector.select(input.amount, input.byte_fee, outputs)
+        } else {
+            selector.select_simple(input.amount, input.byte_fee, outputs)
+        };
+        (outputs, selected)
+    };
+    selected.truncate(MAX_UTXOS_HARD_LIMIT);
+    plan.utxos = selected;
+
+    if plan.utxos.is_empty() {
+        plan.error = SigningErrorType::Error_not_enough_utxos;
+        return plan;
+    }
+
+    plan.available_amount = sum(&plan.utxos);
+    // The change must be set preliminary, so that the change output is taken into account.
+    if max_amount {
+        plan.amount = plan.available_amount;
+    } else {
+        plan.amount = input.amount;
+        plan.change = plan.available_amount - plan.amount;
+    }
+
+    // The fee may be larger than the available amount in the max amount case.
+    // Reduce it then, and hope that the transaction will go through.
+    plan.fee = estimate_fee(input, &plan, outputs, fee_calculator).min(plan.available_amount);
+    plan.amount = if max_amount {
+        plan.available_amount - plan.fee
+    } else {
+        plan.amount.min(plan.available_amount - plan.fee)
+    }
+    .max(0);
+    plan.change = plan.available_amount - plan.amount - plan.fee;
+    plan
+}
+
+/// Estimates the fee from the virtual size of the transaction signed with placeholders for segwit coins,
+/// falls back to the linear estimation if the transaction cannot be signed.
+fn estimate_fee(
+    input: &SigningInput,
+    plan: &TransactionPlan,
+    outputs: usize,
+    fee_calculator: &LinearFeeCalculator,
+) -> Amount {
+    let linear_fee = || fee_calculator.calculate(plan.utxos.len(), outputs, input.byte_fee);
+    if !input.coin.segwit {
+        return linear_fee();
+    }
+    match sign_transaction(input, plan, SigningMode::SizeEstimation) {
+        Ok(tx) => tx.virtual_size() as Amount * input.byte_fee,
+        Err(_) => linear_fee(),
+    }
+}
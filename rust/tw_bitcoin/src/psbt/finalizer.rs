@@ -0,0 +1,115 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::psbt::signer::{ScriptKind, SpendingScript};
+use crate::psbt::{Psbt, PsbtInput, PsbtResult};
+use crate::script::{hash160, Script};
+use crate::{SigningError, SigningErrorType};
+use std::collections::BTreeMap;
+use std::mem;
+
+impl Psbt {
+    /// Builds the final script signatures and witnesses of the inputs from the collected signatures.
+    /// Fails if any input can't be finalized, e.g. if there are not enough signatures.
+    pub fn finalize(&mut self) -> PsbtResult<()> {
+        let unsigned = self.unsigned_transaction()?;
+        for (input, tx_input) in self.inputs.iter_mut().zip(unsigned.inputs.iter()) {
+            if input.is_finalized() {
+                continue;
+            }
+            let utxo = input
+                .spent_output(&tx_input.previous_output)
+                .ok_or_else(|| {
+                    SigningError::new(
+                        SigningErrorType::Error_missing_input_utxos,
+                        "Missing UTXO of the input",
+                    )
+                })?;
+            finalize_input(input, &utxo.script_pubkey)?;
+        }
+        Ok(())
+    }
+}
+
+fn finalize_input(input: &mut PsbtInput, script_pubkey: &Script) -> PsbtResult<()> {
+    if script_pubkey.match_p2tr().is_some() {
+        let signature = input
+            .tap_key_sig
+            .clone()
+            .ok_or_else(|| signatures_error("Missing taproot key signature"))?;
+        input.final_script_witness = Some(vec![signature]);
+        input.clear_finalized();
+        return Ok(());
+    }
+
+    let spending = SpendingScript::new(input, script_pubkey)?;
+    let mut script_sig_items = satisfy(&spending.script_code, &input.partial_sigs)?;
+    let mut witness = Vec::new();
+    match spending.kind {
+        ScriptKind::Legacy => (),
+        ScriptKind::WitnessKeyHash => witness = mem::take(&mut script_sig_items),
+        ScriptKind::WitnessScriptHash => {
+            witness = mem::take(&mut script_sig_items);
+            witness.push(spending.script_code.into_bytes());
+        },
+    }
+    if let Some(redeem_script) = spending.redeem_script {
+        script_sig_items.push(redeem_script.into_bytes());
+    }
+
+    if !script_sig_items.is_empty() {
+        input.final_script_sig = Some(Script::push_all(&script_sig_items));
+    }
+    if !witness.is_empty() {
+        input.final_script_witness = Some(witness);
+    }
+    input.clear_finalized();
+    Ok(())
+}
+
+/// Returns the stack items satisfying the `script` with the partial signatures.
+fn satisfy(script: &Script, partial_sigs: &BTreeMap<Vec<u8>, Vec<u8>>) -> PsbtResult<Vec<Vec<u8>>> {
+    if let Some(public_key_hash) = script.match_p2pkh() {
+        return partial_sigs
+            .iter()
+            .find(|(public_key, _)| hash160(public_key).as_slice() == public_key_hash)
+            .map(|(public_key, signature)| vec![signature.clone(), public_key.clone()])
+            .ok_or_else(|| signatures_error("Missing signature"));
+    }
+    if let Some(public_key) = script.match_p2pk() {
+        return partial_sigs
+            .get(public_key)
+            .map(|signature| vec![signature.clone()])
+            .ok_or_else(|| signatures_error("Missing signature"));
+    }
+    if let Some((public_keys, required)) = script.match_multisig() {
+        // An extra item is consumed by the `OP_CHECKMULTISIG` bug.
+        // The signatures must be in the order of the public keys.
+        let mut items = vec![Vec::new()];
+        items.extend(
+            public_keys
+                .iter()
+                .filter_map(|public_key| partial_sigs.get(*public_key).cloned())
+                .take(required),
+        );
+        if items.len() <= required {
+            return Err(signatures_error(format!(
+                "Expected {required} signatures, found {}",
+                items.len() - 1
+            )));
+        }
+        return Ok(items);
+    }
+
+    Err(SigningError::new(
+        SigningErrorType::Error_script_output,
+        "Unsupported script",
+    ))
+}
+
+fn signatures_error<M: Into<String>>(message: M) -> SigningError {
+    SigningError::new(SigningErrorType::Error_signatures_count, message)
+}
@@ -0,0 +1,399 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::psbt::map::{
+    decode_exact, decode_u32, decode_witness, encode_witness, expect_no_key_data, write_map, Key,
+};
+use crate::psbt::{psbt_error, utxo_error, PsbtResult, PSBT_V0, PSBT_V2};
+use crate::script::Script;
+use crate::transaction::{OutPoint, Transaction, TransactionOutput};
+use std::collections::BTreeMap;
+use tw_hash::H256;
+use tw_keypair::secp256k1::PublicKey;
+
+/// cbindgen:ignore
+const PSBT_IN_NON_WITNESS_UTXO: u64 = 0x00;
+/// cbindgen:ignore
+const PSBT_IN_WITNESS_UTXO: u64 = 0x01;
+/// cbindgen:ignore
+const PSBT_IN_PARTIAL_SIG: u64 = 0x02;
+/// cbindgen:ignore
+const PSBT_IN_SIGHASH_TYPE: u64 = 0x03;
+/// cbindgen:ignore
+const PSBT_IN_REDEEM_SCRIPT: u64 = 0x04;
+/// cbindgen:ignore
+const PSBT_IN_WITNESS_SCRIPT: u64 = 0x05;
+/// cbindgen:ignore
+const PSBT_IN_BIP32_DERIVATION: u64 = 0x06;
+/// cbindgen:ignore
+const PSBT_IN_FINAL_SCRIPTSIG: u64 = 0x07;
+/// cbindgen:ignore
+const PSBT_IN_FINAL_SCRIPTWITNESS: u64 = 0x08;
+/// cbindgen:ignore
+const PSBT_IN_PREVIOUS_TXID: u64 = 0x0e;
+/// cbindgen:ignore
+const PSBT_IN_OUTPUT_INDEX: u64 = 0x0f;
+/// cbindgen:ignore
+const PSBT_IN_SEQUENCE: u64 = 0x10;
+/// cbindgen:ignore
+const PSBT_IN_REQUIRED_TIME_LOCKTIME: u64 = 0x11;
+/// cbindgen:ignore
+const PSBT_IN_REQUIRED_HEIGHT_LOCKTIME: u64 = 0x12;
+/// cbindgen:ignore
+const PSBT_IN_TAP_KEY_SIG: u64 = 0x13;
+/// cbindgen:ignore
+const PSBT_IN_TAP_INTERNAL_KEY: u64 = 0x17;
+/// cbindgen:ignore
+const PSBT_IN_TAP_MERKLE_ROOT: u64 = 0x18;
+
+/// The types of the hash preimages, taproot script signatures, leaf scripts and key origins.
+/// They are kept as unknown pairs, but must be removed once the input is finalized.
+/// cbindgen:ignore
+const PSBT_IN_CLEARED_ON_FINALIZE: [u64; 7] = [0x0a, 0x0b, 0x0c, 0x0d, 0x14, 0x15, 0x16];
+
+/// Lock time values below this threshold are block heights, the other ones are timestamps.
+/// cbindgen:ignore
+pub const LOCK_TIME_THRESHOLD: u32 = 500_000_000;
+
+/// A PSBT input map.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PsbtInput {
+    /// The full transaction of the spent output.
+    pub non_witness_utxo: Option<Transaction>,
+    /// The spent output, sufficient for segwit inputs.
+    pub witness_utxo: Option<TransactionOutput>,
+    /// DER encoded ECDSA signatures with the sighash type byte by the serialized public key.
+    pub partial_sigs: BTreeMap<Vec<u8>, Vec<u8>>,
+    pub sighash_type: Option<u32>,
+    pub redeem_script: Option<Script>,
+    pub witness_script: Option<Script>,
+    /// The encoded master key fingerprint and derivation path by the serialized public key.
+    pub bip32_derivation: BTreeMap<Vec<u8>, Vec<u8>>,
+    pub final_script_sig: Option<Script>,
+    pub final_script_witness: Option<Vec<Vec<u8>>>,
+    /// The spent output of a PSBT v2 input. The hash is in the internal byte order.
+    pub previous_output: Option<OutPoint>,
+    /// The sequence of a PSBT v2 input, `0xffffffff` if not set.
+    pub sequence: Option<u32>,
+    pub required_time_lock_time: Option<u32>,
+    pub required_height_lock_time: Option<u32>,
+    /// The BIP340 signature for the taproot key path spending with the sighash type byte if it's not default.
+    pub tap_key_sig: Option<Vec<u8>>,
+    pub tap_internal_key: Option<H256>,
+    pub tap_merkle_root: Option<H256>,
+    /// The pairs not interpreted by this implementation, kept for serialization.
+    pub unknown: BTreeMap<Key, Vec<u8>>,
+}
+
+impl PsbtInput {
+    pub(crate) fn from_pairs(pairs: BTreeMap<Key, Vec<u8>>, version: u32) -> PsbtResult<Self> {
+        let mut input = PsbtInput::default();
+        let mut previous_txid = None;
+        let mut output_index = None;
+
+        for (key, value) in pairs {
+            match key.key_type {
+                PSBT_IN_NON_WITNESS_UTXO => {
+                    expect_no_key_data(&key)?;
+                    input.non_witness_utxo = Some(Transaction::decode(&value)?);
+                },
+                PSBT_IN_WITNESS_UTXO => {
+                    expect_no_key_data(&key)?;
+                    input.witness_utxo = Some(decode_exact(&value, TransactionOutput::decode)?);
+                },
+                PSBT_IN_PARTIAL_SIG => {
+                    check_public_key(&key.key_data)?;
+                    if value.is_empty() {
+                        return Err(psbt_error("Empty partial signature"));
+                    }
+                    input.partial_sigs.insert(key.key_data, value);
+                },
+                PSBT_IN_SIGHASH_TYPE => {
+                    expect_no_key_data(&key)?;
+                    input.sighash_type = Some(decode_u32(&value)?);
+                },
+                PSBT_IN_REDEEM_SCRIPT => {
+                    expect_no_key_data(&key)?;
+                    input.redeem_script = Some(Script::new(value));
+                },
+                PSBT_IN_WITNESS_SCRIPT => {
+                    expect_no_key_data(&key)?;
+                    input.witness_script = Some(Script::new(value));
+                },
+                PSBT_IN_BIP32_DERIVATION => {
+                    check_public_key(&key.key_data)?;
+                    check_key_origin(&value)?;
+                    input.bip32_derivation.insert(key.key_data, value);
+                },
+                PSBT_IN_FINAL_SCRIPTSIG => {
+                    expect_no_key_data(&key)?;
+                    input.final_script_sig = Some(Script::new(value));
+                },
+                PSBT_IN_FINAL_SCRIPTWITNESS => {
+                    expect_no_key_data(&key)?;
+                    input.final_script_witness = Some(decode_witness(&value)?);
+                },
+                PSBT_IN_PREVIOUS_TXID..=PSBT_IN_REQUIRED_HEIGHT_LOCKTIME if version == PSBT_V0 => {
+                    return Err(psbt_error(format!(
+                        "Input key of the type {:#04x} is not allowed in PSBT v0",
+                        key.key_type
+                    )));
+                },
+                PSBT_IN_PREVIOUS_TXID => {
+                    expect_no_key_data(&key)?;
+                    let txid = H256::try_from(value.as_slice())
+                        .map_err(|_| psbt_error("Previous txid must be 32 bytes"))?;
+                    previous_txid = Some(txid);
+                },
+                PSBT_IN_OUTPUT_INDEX => {
+                    expect_no_key_data(&key)?;
+                    output_index = Some(decode_u32(&value)?);
+                },
+                PSBT_IN_SEQUENCE => {
+                    expect_no_key_data(&key)?;
+                    input.sequence = Some(decode_u32(&value)?);
+                },
+                PSBT_IN_REQUIRED_TIME_LOCKTIME => {
+                    expect_no_key_data(&key)?;
+                    let lock_time = decode_u32(&value)?;
+                    if lock_time < LOCK_TIME_THRESHOLD {
+                        return Err(psbt_error("Required time lock time must be a timestamp"));
+                    }
+                    input.required_time_lock_time = Some(lock_time);
+                },
+                PSBT_IN_REQUIRED_HEIGHT_LOCKTIME => {
+                    expect_no_key_data(&key)?;
+                    let lock_time = decode_u32(&value)?;
+                    if lock_time == 0 || lock_time >= LOCK_TIME_THRESHOLD {
+                        return Err(psbt_error(
+                            "Required height lock time must be a block height",
+                        ));
+                    }
+                    input.required_height_lock_time = Some(lock_time);
+                },
+                PSBT_IN_TAP_KEY_SIG => {
+                    expect_no_key_data(&key)?;
+                    if value.len() != 64 && value.len() != 65 {
+                        return Err(psbt_error("Taproot key signature must be 64 or 65 bytes"));
+                    }
+                    input.tap_key_sig = Some(value);
+                },
+                PSBT_IN_TAP_INTERNAL_KEY => {
+                    expect_no_key_data(&key)?;
+                    input.tap_internal_key = Some(
+                        H256::try_from(value.as_slice())
+                            .map_err(|_| psbt_error("Taproot internal key must be 32 bytes"))?,
+                    );
+                },
+                PSBT_IN_TAP_MERKLE_ROOT => {
+                    expect_no_key_data(&key)?;
+                    input.tap_merkle_root = Some(
+                        H256::try_from(value.as_slice())
+                            .map_err(|_| psbt_error("Taproot merkle root must be 32 bytes"))?,
+                    );
+                },
+                _ => {
+                    input.unknown.insert(key, value);
+                },
+            }
+        }
+
+        if version == PSBT_V2 {
+            match (previous_txid, output_index) {
+                (Some(hash), Some(index)) => {
+                    input.previous_output = Some(OutPoint { hash, index });
+                },
+                _ => {
+                    return Err(psbt_error(
+                        "PSBT v2 input must have the previous txid and the output index",
+                    ))
+                },
+            }
+        }
+        Ok(input)
+    }
+
+    pub(crate) fn encode(&self, out: &mut Vec<u8>) {
+        let mut pairs = self.unknown.clone();
+        let mut insert = |key_type, value| pairs.insert(Key::of_type(key_type), value);
+
+        if let Some(ref tx) = self.non_witness_utxo {
+            insert(PSBT_IN_NON_WITNESS_UTXO, tx.encode());
+        }
+        if let Some(ref output) = self.witness_utxo {
+            let mut value = Vec::new();
+            output.encode(&mut value);
+            insert(PSBT_IN_WITNESS_UTXO, value);
+        }
+        if let Some(sighash_type) = self.sighash_type {
+            insert(PSBT_IN_SIGHASH_TYPE, sighash_type.to_le_bytes().to_vec());
+        }
+        if let Some(ref script) = self.redeem_script {
+            insert(PSBT_IN_REDEEM_SCRIPT, script.as_bytes().to_vec());
+        }
+        if let Some(ref script) = self.witness_script {
+            insert(PSBT_IN_WITNESS_SCRIPT, script.as_bytes().to_vec());
+        }
+        if let Some(ref script) = self.final_script_sig {
+            insert(PSBT_IN_FINAL_SCRIPTSIG, script.as_bytes().to_vec());
+        }
+        if let Some(ref witness) = self.final_script_witness {
+            insert(PSBT_IN_FINAL_SCRIPTWITNESS, encode_witness(witness));
+        }
+        if let Some(ref previous_output) = self.previous_output {
+            insert(
+                PSBT_IN_PREVIOUS_TXID,
+                previous_output.hash.as_slice().to_vec(),
+            );
+            insert(
+                PSBT_IN_OUTPUT_INDEX,
+                previous_output.index.to_le_bytes().to_vec(),
+            );
+        }
+        if let Some(sequence) = self.sequence {
+            insert(PSBT_IN_SEQUENCE, sequence.to_le_bytes().to_vec());
+        }
+        if let Some(lock_time) = self.required_time_lock_time {
+            insert(
+                PSBT_IN_REQUIRED_TIME_LOCKTIME,
+                lock_time.to_le_bytes().to_vec(),
+            );
+        }
+        if let Some(lock_time) = self.required_height_lock_time {
+            insert(
+                PSBT_IN_REQUIRED_HEIGHT_LOCKTIME,
+                lock_time.to_le_bytes().to_vec(),
+            );
+        }
+        if let Some(ref signature) = self.tap_key_sig {
+            insert(PSBT_IN_TAP_KEY_SIG, signature.clone());
+        }
+        if let Some(ref key) = self.tap_internal_key {
+            insert(PSBT_IN_TAP_INTERNAL_KEY, key.as_slice().to_vec());
+        }
+        if let Some(ref root) = self.tap_merkle_root {
+            insert(PSBT_IN_TAP_MERKLE_ROOT, root.as_slice().to_vec());
+        }
+
+        for (public_key, signature) in self.partial_sigs.iter() {
+            let key = Key::new(PSBT_IN_PARTIAL_SIG, public_key.clone());
+            pairs.insert(key, signature.clone());
+        }
+        for (public_key, origin) in self.bip32_derivation.iter() {
+            let key = Key::new(PSBT_IN_BIP32_DERIVATION, public_key.clone());
+            pairs.insert(key, origin.clone());
+        }
+        write_map(pairs, out);
+    }
+
+    /// Checks if the input has the final script signature or witness.
+    pub fn is_finalized(&self) -> bool {
+        self.final_script_sig.is_some() || self.final_script_witness.is_some()
+    }
+
+    /// Returns the output spent by the input.
+    /// Prefers the witness UTXO, the consistency with the non-witness UTXO is checked by [`PsbtInput::validate`].
+    pub fn spent_output(&self, previous_output: &OutPoint) -> Option<TransactionOutput> {
+        if let Some(ref output) = self.witness_utxo {
+            return Some(output.clone());
+        }
+        self.non_witness_utxo
+            .as_ref()
+            .and_then(|tx| tx.outputs.get(previous_output.index as usize))
+            .cloned()
+    }
+
+    /// Checks that the non-witness UTXO is the transaction referenced by the `previous_output`,
+    /// and that it's consistent with the witness UTXO.
+    pub fn validate(&self, previous_output: &OutPoint) -> PsbtResult<()> {
+        let Some(ref tx) = self.non_witness_utxo else {
+            return Ok(());
+        };
+        if tx.hash() != previous_output.hash {
+            return Err(utxo_error(
+                "Non-witness UTXO doesn't match the previous txid",
+            ));
+        }
+        let output = tx
+            .outputs
+            .get(previous_output.index as usize)
+            .ok_or_else(|| utxo_error("Previous output index is out of range"))?;
+        match self.witness_utxo {
+            Some(ref witness_utxo) if witness_utxo != output => Err(utxo_error(
+                "Witness UTXO doesn't match the non-witness UTXO",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds the fields of the `other` input that are missing in this one.
+    pub(crate) fn combine(&mut self, other: PsbtInput) {
+        combine_option(&mut self.non_witness_utxo, other.non_witness_utxo);
+        combine_option(&mut self.witness_utxo, other.witness_utxo);
+        combine_map(&mut self.partial_sigs, other.partial_sigs);
+        combine_option(&mut self.sighash_type, other.sighash_type);
+        combine_option(&mut self.redeem_script, other.redeem_script);
+        combine_option(&mut self.witness_script, other.witness_script);
+        combine_map(&mut self.bip32_derivation, other.bip32_derivation);
+        combine_option(&mut self.final_script_sig, other.final_script_sig);
+        combine_option(&mut self.final_script_witness, other.final_script_witness);
+        combine_option(&mut self.previous_output, other.previous_output);
+        combine_option(&mut self.sequence, other.sequence);
+        combine_option(
+            &mut self.required_time_lock_time,
+            other.required_time_lock_time,
+        );
+        combine_option(
+            &mut self.required_height_lock_time,
+            other.required_height_lock_time,
+        );
+        combine_option(&mut self.tap_key_sig, other.tap_key_sig);
+        combine_option(&mut self.tap_internal_key, other.tap_internal_key);
+        combine_option(&mut self.tap_merkle_root, other.tap_merkle_root);
+        combine_map(&mut self.unknown, other.unknown);
+    }
+
+    /// Removes the data that is not needed once the input is finalized.
+    /// The UTXOs, the PSBT v2 fields and the unknown pairs are kept.
+    pub(crate) fn clear_finalized(&mut self) {
+        self.partial_sigs.clear();
+        self.sighash_type = None;
+        self.redeem_script = None;
+        self.witness_script = None;
+        self.bip32_derivation.clear();
+        self.tap_key_sig = None;
+        self.tap_internal_key = None;
+        self.tap_merkle_root = None;
+        self.unknown
+            .retain(|key, _| !PSBT_IN_CLEARED_ON_FINALIZE.contains(&key.key_type));
+    }
+}
+
+pub(crate) fn combine_option<T>(this: &mut Option<T>, other: Option<T>) {
+    if this.is_none() {
+        *this = other;
+    }
+}
+
+pub(crate) fn combine_map<K: Ord, V>(this: &mut BTreeMap<K, V>, other: BTreeMap<K, V>) {
+    for (key, value) in other {
+        this.entry(key).or_insert(value);
+    }
+}
+
+pub(crate) fn check_public_key(key_data: &[u8]) -> PsbtResult<()> {
+    PublicKey::try_from(key_data)
+        .map(|_| ())
+        .map_err(|_| psbt_error("Invalid public key in the key data"))
+}
+
+/// Checks that the value is a 4 bytes fingerprint followed by 4 bytes derivation indexes.
+pub(crate) fn check_key_origin(value: &[u8]) -> PsbtResult<()> {
+    if value.len() < 4 || !value.len().is_multiple_of(4) {
+        return Err(psbt_error("Invalid BIP32 key origin"));
+    }
+    Ok(())
+}
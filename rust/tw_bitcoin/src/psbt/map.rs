@@ -0,0 +1,118 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! The key-value map format shared by the global, input and output sections.
+
+use crate::psbt::{psbt_error, PsbtResult};
+use crate::reader::Reader;
+use crate::transaction::encode_var_int;
+use std::collections::BTreeMap;
+
+/// A key of a key-value pair: the type followed by the type specific data.
+/// The keys are ordered by the type first, so the serialized maps are sorted by the type.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Key {
+    pub key_type: u64,
+    pub key_data: Vec<u8>,
+}
+
+impl Key {
+    pub fn new(key_type: u64, key_data: Vec<u8>) -> Key {
+        Key { key_type, key_data }
+    }
+
+    /// Returns a key without the key data.
+    pub fn of_type(key_type: u64) -> Key {
+        Key::new(key_type, Vec::new())
+    }
+}
+
+/// Reads the key-value pairs up to the `0x00` separator. Fails on duplicate keys.
+pub(crate) fn read_map(reader: &mut Reader<'_>) -> PsbtResult<BTreeMap<Key, Vec<u8>>> {
+    let mut pairs = BTreeMap::new();
+    loop {
+        let key = reader.read_var_bytes()?;
+        if key.is_empty() {
+            return Ok(pairs);
+        }
+        let mut key_reader = Reader::new(key);
+        let key_type = key_reader.read_var_int()?;
+        let key = Key::new(key_type, key_reader.remaining().to_vec());
+        let value = reader.read_var_bytes()?.to_vec();
+        if pairs.insert(key, value).is_some() {
+            return Err(psbt_error(format!(
+                "Duplicate key of the type {key_type:#04x}"
+            )));
+        }
+    }
+}
+
+/// Appends the key-value pairs sorted by the key, followed by the `0x00` separator.
+pub(crate) fn write_map(map: BTreeMap<Key, Vec<u8>>, out: &mut Vec<u8>) {
+    for (key, value) in map {
+        let mut key_bytes = Vec::new();
+        encode_var_int(key.key_type, &mut key_bytes);
+        key_bytes.extend_from_slice(&key.key_data);
+
+        encode_var_int(key_bytes.len() as u64, out);
+        out.extend_from_slice(&key_bytes);
+        encode_var_int(value.len() as u64, out);
+        out.extend_from_slice(&value);
+    }
+    out.push(0x00);
+}
+
+/// Fails if the key of a single-valued field has the key data.
+pub(crate) fn expect_no_key_data(key: &Key) -> PsbtResult<()> {
+    if key.key_data.is_empty() {
+        Ok(())
+    } else {
+        Err(psbt_error(format!(
+            "Key of the type {:#04x} must not have the key data",
+            key.key_type
+        )))
+    }
+}
+
+pub(crate) fn decode_u32(value: &[u8]) -> PsbtResult<u32> {
+    let bytes = value
+        .try_into()
+        .map_err(|_| psbt_error("Expected a 4 bytes value"))?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Reads a value of the whole `value` slice failing if there are extra bytes.
+pub(crate) fn decode_exact<T, F>(value: &[u8], decode: F) -> PsbtResult<T>
+where
+    F: FnOnce(&mut Reader<'_>) -> PsbtResult<T>,
+{
+    let mut reader = Reader::new(value);
+    let result = decode(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(psbt_error("Unexpected data after the value"));
+    }
+    Ok(result)
+}
+
+/// Encodes the witness stack items as the `PSBT_IN_FINAL_SCRIPTWITNESS` value.
+pub(crate) fn encode_witness(witness: &[Vec<u8>]) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_var_int(witness.len() as u64, &mut out);
+    for item in witness {
+        encode_var_int(item.len() as u64, &mut out);
+        out.extend_from_slice(item);
+    }
+    out
+}
+
+pub(crate) fn decode_witness(value: &[u8]) -> PsbtResult<Vec<Vec<u8>>> {
+    decode_exact(value, |reader| {
+        let items = reader.read_len()?;
+        (0..items)
+            .map(|_| Ok(reader.read_var_bytes()?.to_vec()))
+            .collect()
+    })
+}
@@ -0,0 +1,431 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! Partially Signed Bitcoin Transactions as specified by BIP174 (version 0) and BIP370 (version 2).
+//!
+//! A [`Psbt`] is parsed from the binary or base64 format, validated against the UTXOs it carries,
+//! signed with the keys the signer holds, combined with the PSBTs signed by other signers,
+//! finalized and extracted as a network transaction.
+//! The pairs that are not interpreted by this implementation are kept as is.
+
+use crate::psbt::input::{combine_map, combine_option};
+use crate::psbt::map::{decode_exact, decode_u32, expect_no_key_data, read_map, write_map, Key};
+use crate::reader::Reader;
+use crate::transaction::{Transaction, TransactionInput, TransactionOutput};
+use crate::{SigningError, SigningErrorType, SigningResult};
+use std::collections::BTreeMap;
+use tw_encoding::base64;
+
+pub mod finalizer;
+pub mod input;
+pub mod map;
+pub mod output;
+pub mod signer;
+
+pub use input::PsbtInput;
+pub use output::PsbtOutput;
+
+pub type PsbtResult<T> = SigningResult<T>;
+
+/// cbindgen:ignore
+pub const PSBT_MAGIC: &[u8] = b"psbt\xff";
+/// cbindgen:ignore
+pub const PSBT_V0: u32 = 0;
+/// cbindgen:ignore
+pub const PSBT_V2: u32 = 2;
+
+/// cbindgen:ignore
+const PSBT_GLOBAL_UNSIGNED_TX: u64 = 0x00;
+/// cbindgen:ignore
+const PSBT_GLOBAL_TX_VERSION: u64 = 0x02;
+/// cbindgen:ignore
+const PSBT_GLOBAL_FALLBACK_LOCKTIME: u64 = 0x03;
+/// cbindgen:ignore
+const PSBT_GLOBAL_INPUT_COUNT: u64 = 0x04;
+/// cbindgen:ignore
+const PSBT_GLOBAL_OUTPUT_COUNT: u64 = 0x05;
+/// cbindgen:ignore
+const PSBT_GLOBAL_TX_MODIFIABLE: u64 = 0x06;
+/// cbindgen:ignore
+const PSBT_GLOBAL_VERSION: u64 = 0xfb;
+
+/// The default sequence of a PSBT v2 input.
+/// cbindgen:ignore
+const DEFAULT_SEQUENCE: u32 = 0xffff_ffff;
+
+pub(crate) fn psbt_error<M: Into<String>>(message: M) -> SigningError {
+    SigningError::new(SigningErrorType::Error_input_parse, message)
+}
+
+pub(crate) fn utxo_error<M: Into<String>>(message: M) -> SigningError {
+    SigningError::new(SigningErrorType::Error_invalid_utxo, message)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Psbt {
+    /// [`PSBT_V0`] or [`PSBT_V2`].
+    pub version: u32,
+    /// The transaction with empty script signatures and witnesses. Set for PSBT v0 only.
+    pub unsigned_tx: Option<Transaction>,
+    /// The following fields are used by PSBT v2 only, the inputs and outputs are defined by the maps.
+    pub tx_version: i32,
+    pub fallback_lock_time: Option<u32>,
+    pub tx_modifiable: Option<u8>,
+    pub inputs: Vec<PsbtInput>,
+    pub outputs: Vec<PsbtOutput>,
+    /// The global pairs not interpreted by this implementation, including extended public keys.
+    pub unknown: BTreeMap<Key, Vec<u8>>,
+}
+
+impl Psbt {
+    /// Creates a PSBT v0 with empty input and output maps for the given unsigned transaction.
+    pub fn from_unsigned_transaction(tx: Transaction) -> PsbtResult<Psbt> {
+        check_unsigned(&tx)?;
+        Ok(Psbt {
+            version: PSBT_V0,
+            tx_version: tx.version,
+            fallback_lock_time: None,
+            tx_modifiable: None,
+            inputs: vec![PsbtInput::default(); tx.inputs.len()],
+            outputs: vec![PsbtOutput::default(); tx.outputs.len()],
+            unsigned_tx: Some(tx),
+            unknown: BTreeMap::new(),
+        })
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> PsbtResult<Psbt> {
+        let mut reader = Reader::new(bytes);
+        if reader.read_bytes(PSBT_MAGIC.len()).ok() != Some(PSBT_MAGIC) {
+            return Err(psbt_error("Invalid PSBT magic bytes"));
+        }
+
+        let mut global = read_map(&mut reader)?;
+        let version = match global.remove(&Key::of_type(PSBT_GLOBAL_VERSION)) {
+            Some(value) => decode_u32(&value)?,
+            None => PSBT_V0,
+        };
+
+        let mut psbt = Psbt {
+            version,
+            unsigned_tx: None,
+            tx_version: 0,
+            fallback_lock_time: None,
+            tx_modifiable: None,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            unknown: BTreeMap::new(),
+        };
+        let (input_count, output_count) = match version {
+            PSBT_V0 => psbt.read_v0_global(global)?,
+            PSBT_V2 => psbt.read_v2_global(global)?,
+            _ => return Err(psbt_error(format!("Unsupported PSBT version: {version}"))),
+        };
+
+        for _ in 0..input_count {
+            let pairs = read_map(&mut reader)?;
+            psbt.inputs.push(PsbtInput::from_pairs(pairs, version)?);
+        }
+        for _ in 0..output_count {
+            let pairs = read_map(&mut reader)?;
+            psbt.outputs.push(PsbtOutput::from_pairs(pairs, version)?);
+        }
+        if !reader.is_empty() {
+            return Err(psbt_error("Unexpected data after the PSBT"));
+        }
+        Ok(psbt)
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut global = self.unknown.clone();
+        if let Some(ref tx) = self.unsigned_tx {
+            global.insert(
+                Key::of_type(PSBT_GLOBAL_UNSIGNED_TX),
+                tx.encode_without_witness(),
+            );
+        }
+        if self.version == PSBT_V2 {
+            let mut insert = |key_type, value| global.insert(Key::of_type(key_type), value);
+            insert(
+                PSBT_GLOBAL_TX_VERSION,
+                self.tx_version.to_le_bytes().to_vec(),
+            );
+            if let Some(lock_time) = self.fallback_lock_time {
+                insert(
+                    PSBT_GLOBAL_FALLBACK_LOCKTIME,
+                    lock_time.to_le_bytes().to_vec(),
+                );
+            }
+            insert(PSBT_GLOBAL_INPUT_COUNT, var_int(self.inputs.len()));
+            insert(PSBT_GLOBAL_OUTPUT_COUNT, var_int(self.outputs.len()));
+            if let Some(modifiable) = self.tx_modifiable {
+                insert(PSBT_GLOBAL_TX_MODIFIABLE, vec![modifiable]);
+            }
+        }
+        if self.version != PSBT_V0 {
+            global.insert(
+                Key::of_type(PSBT_GLOBAL_VERSION),
+                self.version.to_le_bytes().to_vec(),
+            );
+        }
+
+        let mut out = PSBT_MAGIC.to_vec();
+        write_map(global, &mut out);
+        for input in self.inputs.iter() {
+            input.encode(&mut out);
+        }
+        for output in self.outputs.iter() {
+            output.encode(&mut out);
+        }
+        out
+    }
+
+    pub fn from_base64(s: &str) -> PsbtResult<Psbt> {
+        let bytes = base64::decode(s, false).map_err(|_| psbt_error("Invalid base64 string"))?;
+        Psbt::deserialize(&bytes)
+    }
+
+    pub fn to_base64(&self) -> String {
+        base64::encode(&self.serialize(), false)
+    }
+
+    /// Returns the transaction without signatures.
+    /// For PSBT v2, it's built from the input and output maps and the lock time is determined as BIP370 specifies.
+    pub fn unsigned_transaction(&self) -> PsbtResult<Transaction> {
+        if let Some(ref tx) = self.unsigned_tx {
+            return Ok(tx.clone());
+        }
+
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| {
+                let previous_output = input
+                    .previous_output
+                    .clone()
+                    .ok_or_else(|| psbt_error("PSBT v2 input must have the previous output"))?;
+                Ok(TransactionInput {
+                    previous_output,
+                    script_sig: Default::default(),
+                    sequence: input.sequence.unwrap_or(DEFAULT_SEQUENCE),
+                    witness: Vec::new(),
+                })
+            })
+            .collect::<PsbtResult<Vec<_>>>()?;
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| match (output.amount, &output.script) {
+                (Some(value), Some(script_pubkey)) => Ok(TransactionOutput {
+                    value,
+                    script_pubkey: script_pubkey.clone(),
+                }),
+                _ => Err(psbt_error(
+                    "PSBT v2 output must have the amount and the script",
+                )),
+            })
+            .collect::<PsbtResult<Vec<_>>>()?;
+
+        Ok(Transaction {
+            version: self.tx_version,
+            lock_time: self.lock_time()?,
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Checks that the UTXOs of every input match the outputs it spends.
+    pub fn validate(&self) -> PsbtResult<()> {
+        let unsigned = self.unsigned_transaction()?;
+        for (input, tx_input) in self.inputs.iter().zip(unsigned.inputs.iter()) {
+            input.validate(&tx_input.previous_output)?;
+        }
+        Ok(())
+    }
+
+    /// Merges the signatures and other data of a PSBT of the same transaction, e.g. signed by another signer.
+    /// The values already present in this PSBT are kept.
+    pub fn combine(&mut self, other: Psbt) -> PsbtResult<()> {
+        let is_same_tx = self.version == other.version
+            && self.inputs.len() == other.inputs.len()
+            && self.outputs.len() == other.outputs.len()
+            && self.unsigned_transaction()?.id() == other.unsigned_transaction()?.id();
+        if !is_same_tx {
+            return Err(SigningError::new(
+                SigningErrorType::Error_invalid_params,
+                "PSBTs of different transactions can't be combined",
+            ));
+        }
+
+        for (input, other_input) in self.inputs.iter_mut().zip(other.inputs) {
+            input.combine(other_input);
+        }
+        for (output, other_output) in self.outputs.iter_mut().zip(other.outputs) {
+            output.combine(other_output);
+        }
+        combine_option(&mut self.fallback_lock_time, other.fallback_lock_time);
+        combine_option(&mut self.tx_modifiable, other.tx_modifiable);
+        combine_map(&mut self.unknown, other.unknown);
+        Ok(())
+    }
+
+    /// Returns the signed transaction. Fails if any input is not finalized.
+    pub fn extract_transaction(&self) -> PsbtResult<Transaction> {
+        let mut tx = self.unsigned_transaction()?;
+        for (input, tx_input) in self.inputs.iter().zip(tx.inputs.iter_mut()) {
+            if !input.is_finalized() {
+                return Err(SigningError::new(
+                    SigningErrorType::Error_signatures_count,
+                    "All the inputs must be finalized",
+                ));
+            }
+            tx_input.script_sig = input.final_script_sig.clone().unwrap_or_default();
+            tx_input.witness = input.final_script_witness.clone().unwrap_or_default();
+        }
+        Ok(tx)
+    }
+
+    /// Returns the number of inputs and outputs.
+    fn read_v0_global(&mut self, global: BTreeMap<Key, Vec<u8>>) -> PsbtResult<(usize, usize)> {
+        for (key, value) in global {
+            match key.key_type {
+                PSBT_GLOBAL_UNSIGNED_TX => {
+                    expect_no_key_data(&key)?;
+                    let tx = Transaction::decode(&value)?;
+                    check_unsigned(&tx)?;
+                    self.tx_version = tx.version;
+                    self.unsigned_tx = Some(tx);
+                },
+                PSBT_GLOBAL_TX_VERSION..=PSBT_GLOBAL_TX_MODIFIABLE => {
+                    return Err(psbt_error(format!(
+                        "Global key of the type {:#04x} is not allowed in PSBT v0",
+                        key.key_type
+                    )))
+                },
+                _ => {
+                    self.unknown.insert(key, value);
+                },
+            }
+        }
+
+        let tx = self
+            .unsigned_tx
+            .as_ref()
+            .ok_or_else(|| psbt_error("PSBT v0 must have the unsigned transaction"))?;
+        Ok((tx.inputs.len(), tx.outputs.len()))
+    }
+
+    /// Returns the number of inputs and outputs.
+    fn read_v2_global(&mut self, global: BTreeMap<Key, Vec<u8>>) -> PsbtResult<(usize, usize)> {
+        let mut tx_version = None;
+        let mut input_count = None;
+        let mut output_count = None;
+        for (key, value) in global {
+            match key.key_type {
+                PSBT_GLOBAL_UNSIGNED_TX => {
+                    return Err(psbt_error("PSBT v2 must not have the unsigned transaction"))
+                },
+                PSBT_GLOBAL_TX_VERSION => {
+                    expect_no_key_data(&key)?;
+                    tx_version = Some(decode_u32(&value)? as i32);
+                },
+                PSBT_GLOBAL_FALLBACK_LOCKTIME => {
+                    expect_no_key_data(&key)?;
+                    self.fallback_lock_time = Some(decode_u32(&value)?);
+                },
+                PSBT_GLOBAL_INPUT_COUNT => {
+                    expect_no_key_data(&key)?;
+                    input_count = Some(decode_count(&value)?);
+                },
+                PSBT_GLOBAL_OUTPUT_COUNT => {
+                    expect_no_key_data(&key)?;
+                    output_count = Some(decode_count(&value)?);
+                },
+                PSBT_GLOBAL_TX_MODIFIABLE => {
+                    expect_no_key_data(&key)?;
+                    let [modifiable] = value.as_slice() else {
+                        return Err(psbt_error("Transaction modifiable flags must be 1 byte"));
+                    };
+                    self.tx_modifiable = Some(*modifiable);
+                },
+                _ => {
+                    self.unknown.insert(key, value);
+                },
+            }
+        }
+
+        match (tx_version, input_count, output_count) {
+            (Some(tx_version), Some(input_count), Some(output_count)) => {
+                self.tx_version = tx_version;
+                Ok((input_count, output_count))
+            },
+            _ => Err(psbt_error(
+                "PSBT v2 must have the transaction version, the input and output counts",
+            )),
+        }
+    }
+
+    /// Determines the lock time of a PSBT v2 transaction.
+    /// If any input requires a lock time, the maximum required height is used if all of these inputs
+    /// accept a height, otherwise the maximum required time if all of them accept a time.
+    fn lock_time(&self) -> PsbtResult<u32> {
+        let constrained: Vec<_> = self
+            .inputs
+            .iter()
+            .filter(|input| {
+                input.required_height_lock_time.is_some() || input.required_time_lock_time.is_some()
+            })
+            .collect();
+        if constrained.is_empty() {
+            return Ok(self.fallback_lock_time.unwrap_or_default());
+        }
+
+        let max_lock_time = |lock_times: Option<Vec<u32>>| {
+            lock_times.and_then(|lock_times| lock_times.into_iter().max())
+        };
+        let heights = constrained
+            .iter()
+            .map(|input| input.required_height_lock_time)
+            .collect();
+        if let Some(height) = max_lock_time(heights) {
+            return Ok(height);
+        }
+        let times = constrained
+            .iter()
+            .map(|input| input.required_time_lock_time)
+            .collect();
+        match max_lock_time(times) {
+            Some(time) => Ok(time),
+            _ => Err(SigningError::new(
+                SigningErrorType::Error_invalid_params,
+                "Inputs require incompatible lock time types",
+            )),
+        }
+    }
+}
+
+/// Checks that the transaction has no script signatures and witnesses.
+fn check_unsigned(tx: &Transaction) -> PsbtResult<()> {
+    let is_unsigned = tx
+        .inputs
+        .iter()
+        .all(|input| input.script_sig.is_empty() && input.witness.is_empty());
+    if !is_unsigned {
+        return Err(psbt_error(
+            "Unsigned transaction must have empty script signatures and witnesses",
+        ));
+    }
+    Ok(())
+}
+
+fn decode_count(value: &[u8]) -> PsbtResult<usize> {
+    decode_exact(value, |reader| Ok(reader.read_var_int()? as usize))
+}
+
+fn var_int(value: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    crate::transaction::encode_var_int(value as u64, &mut out);
+    out
+}
@@ -0,0 +1,138 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::psbt::input::{check_key_origin, check_public_key, combine_map, combine_option};
+use crate::psbt::map::{expect_no_key_data, write_map, Key};
+use crate::psbt::{psbt_error, PsbtResult, PSBT_V0, PSBT_V2};
+use crate::script::Script;
+use crate::Amount;
+use std::collections::BTreeMap;
+use tw_hash::H256;
+
+/// cbindgen:ignore
+const PSBT_OUT_REDEEM_SCRIPT: u64 = 0x00;
+/// cbindgen:ignore
+const PSBT_OUT_WITNESS_SCRIPT: u64 = 0x01;
+/// cbindgen:ignore
+const PSBT_OUT_BIP32_DERIVATION: u64 = 0x02;
+/// cbindgen:ignore
+const PSBT_OUT_AMOUNT: u64 = 0x03;
+/// cbindgen:ignore
+const PSBT_OUT_SCRIPT: u64 = 0x04;
+/// cbindgen:ignore
+const PSBT_OUT_TAP_INTERNAL_KEY: u64 = 0x05;
+
+/// A PSBT output map.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PsbtOutput {
+    pub redeem_script: Option<Script>,
+    pub witness_script: Option<Script>,
+    /// The encoded master key fingerprint and derivation path by the serialized public key.
+    pub bip32_derivation: BTreeMap<Vec<u8>, Vec<u8>>,
+    /// The value of a PSBT v2 output.
+    pub amount: Option<Amount>,
+    /// The script of a PSBT v2 output.
+    pub script: Option<Script>,
+    pub tap_internal_key: Option<H256>,
+    /// The pairs not interpreted by this implementation, kept for serialization.
+    pub unknown: BTreeMap<Key, Vec<u8>>,
+}
+
+impl PsbtOutput {
+    pub(crate) fn from_pairs(pairs: BTreeMap<Key, Vec<u8>>, version: u32) -> PsbtResult<Self> {
+        let mut output = PsbtOutput::default();
+        for (key, value) in pairs {
+            match key.key_type {
+                PSBT_OUT_REDEEM_SCRIPT => {
+                    expect_no_key_data(&key)?;
+                    output.redeem_script = Some(Script::new(value));
+                },
+                PSBT_OUT_WITNESS_SCRIPT => {
+                    expect_no_key_data(&key)?;
+                    output.witness_script = Some(Script::new(value));
+                },
+                PSBT_OUT_BIP32_DERIVATION => {
+                    check_public_key(&key.key_data)?;
+                    check_key_origin(&value)?;
+                    output.bip32_derivation.insert(key.key_data, value);
+                },
+                PSBT_OUT_AMOUNT | PSBT_OUT_SCRIPT if version == PSBT_V0 => {
+                    return Err(psbt_error(format!(
+                        "Output key of the type {:#04x} is not allowed in PSBT v0",
+                        key.key_type
+                    )));
+                },
+                PSBT_OUT_AMOUNT => {
+                    expect_no_key_data(&key)?;
+                    let bytes = value
+                        .as_slice()
+                        .try_into()
+                        .map_err(|_| psbt_error("Output amount must be 8 bytes"))?;
+                    output.amount = Some(Amount::from_le_bytes(bytes));
+                },
+                PSBT_OUT_SCRIPT => {
+                    expect_no_key_data(&key)?;
+                    output.script = Some(Script::new(value));
+                },
+                PSBT_OUT_TAP_INTERNAL_KEY => {
+                    expect_no_key_data(&key)?;
+                    output.tap_internal_key = Some(
+                        H256::try_from(value.as_slice())
+                            .map_err(|_| psbt_error("Taproot internal key must be 32 bytes"))?,
+                    );
+                },
+                _ => {
+                    output.unknown.insert(key, value);
+                },
+            }
+        }
+
+        if version == PSBT_V2 && (output.amount.is_none() || output.script.is_none()) {
+            return Err(psbt_error(
+                "PSBT v2 output must have the amount and the script",
+            ));
+        }
+        Ok(output)
+    }
+
+    pub(crate) fn encode(&self, out: &mut Vec<u8>) {
+        let mut pairs = self.unknown.clone();
+        let mut insert = |key_type, value| pairs.insert(Key::of_type(key_type), value);
+
+        if let Some(ref script) = self.redeem_script {
+            insert(PSBT_OUT_REDEEM_SCRIPT, script.as_bytes().to_vec());
+        }
+        if let Some(ref script) = self.witness_script {
+            insert(PSBT_OUT_WITNESS_SCRIPT, script.as_bytes().to_vec());
+        }
+        if let Some(amount) = self.amount {
+            insert(PSBT_OUT_AMOUNT, amount.to_le_bytes().to_vec());
+        }
+        if let Some(ref script) = self.script {
+            insert(PSBT_OUT_SCRIPT, script.as_bytes().to_vec());
+        }
+        if let Some(ref key) = self.tap_internal_key {
+            insert(PSBT_OUT_TAP_INTERNAL_KEY, key.as_slice().to_vec());
+        }
+
+        for (public_key, origin) in self.bip32_derivation.iter() {
+            let key = Key::new(PSBT_OUT_BIP32_DERIVATION, public_key.clone());
+            pairs.insert(key, origin.clone());
+        }
+        write_map(pairs, out);
+    }
+
+    /// Adds the fields of the `other` output that are missing in this one.
+    pub(crate) fn combine(&mut self, other: PsbtOutput) {
+        combine_option(&mut self.redeem_script, other.redeem_script);
+        combine_option(&mut self.witness_script, other.witness_script);
+        combine_map(&mut self.bip32_derivation, other.bip32_derivation);
+        combine_option(&mut self.amount, other.amount);
+        combine_option(&mut self.script, other.script);
+        combine_option(&mut self.tap_internal_key, other.tap_internal_key);
+        combine_map(&mut self.unknown, other.unknown);
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::psbt::{Psbt, PsbtInput, PsbtResult};
+use crate::script::{hash160, Script};
+use crate::sighash::{SighashType, SignatureVersion, SIGHASH_ALL, SIGHASH_DEFAULT};
+use crate::transaction::{Transaction, TransactionOutput};
+use crate::{SigningError, SigningErrorType};
+use tw_hash::sha2::sha256;
+use tw_hash::H256;
+use tw_keypair::secp256k1::PrivateKey;
+use tw_keypair::traits::SigningKeyTrait;
+
+/// How the script code of an input is wrapped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ScriptKind {
+    /// The script code is satisfied by the script signature.
+    Legacy,
+    /// P2WPKH, the script code is the corresponding P2PKH script.
+    WitnessKeyHash,
+    /// P2WSH, the script code is the witness script.
+    WitnessScriptHash,
+}
+
+/// The script satisfied by the signatures of an ECDSA input.
+pub(crate) struct SpendingScript {
+    pub script_code: Script,
+    pub kind: ScriptKind,
+    /// The redeem script if the spent output is P2SH.
+    pub redeem_script: Option<Script>,
+}
+
+impl SpendingScript {
+    /// Resolves the script code of the `input` spending the `script_pubkey`.
+    /// The redeem and witness scripts are checked against the hashes they are locked to.
+    pub(crate) fn new(input: &PsbtInput, script_pubkey: &Script) -> PsbtResult<SpendingScript> {
+        let mut script = script_pubkey.clone();
+        let mut redeem_script = None;
+        if let Some(script_hash) = script_pubkey.match_p2sh() {
+            let redeem = input
+                .redeem_script
+                .clone()
+                .ok_or_else(|| redeem_error("Missing redeem script"))?;
+            if hash160(redeem.as_bytes()).as_slice() != script_hash {
+                return Err(redeem_error("Redeem script doesn't match the script hash"));
+            }
+            script = redeem.clone();
+            redeem_script = Some(redeem);
+        }
+
+        if let Some(public_key_hash) = script.match_p2wpkh() {
+            return Ok(SpendingScript {
+                script_code: Script::p2pkh(public_key_hash),
+                kind: ScriptKind::WitnessKeyHash,
+                redeem_script,
+            });
+        }
+        if let Some(witness_script_hash) = script.match_p2wsh() {
+            let witness_script = input
+                .witness_script
+                .clone()
+                .ok_or_else(|| redeem_error("Missing witness script"))?;
+            if sha256(witness_script.as_bytes()) != witness_script_hash {
+                return Err(redeem_error(
+                    "Witness script doesn't match the witness program",
+                ));
+            }
+            return Ok(SpendingScript {
+                script_code: witness_script,
+                kind: ScriptKind::WitnessScriptHash,
+                redeem_script,
+            });
+        }
+        if script.is_witness_program() {
+            return Err(SigningError::new(
+                SigningErrorType::Error_script_witness_program,
+                "Unsupported witness program",
+            ));
+        }
+
+        Ok(SpendingScript {
+            script_code: script,
+            kind: ScriptKind::Legacy,
+            redeem_script,
+        })
+    }
+}
+
+impl Psbt {
+    /// Signs the inputs that can be spent by the `private_keys`.
+    /// Finalized inputs and inputs without UTXOs are skipped, the existing signatures are kept.
+    /// Returns the number of signatures added.
+    pub fn sign(&mut self, private_keys: &[PrivateKey]) -> PsbtResult<usize> {
+        self.validate()?;
+        let unsigned = self.unsigned_transaction()?;
+        let spent_outputs: Vec<_> = self
+            .inputs
+            .iter()
+            .zip(unsigned.inputs.iter())
+            .map(|(input, tx_input)| input.spent_output(&tx_input.previous_output))
+            .collect();
+
+        let mut signed = 0;
+        for (index, input) in self.inputs.iter_mut().enumerate() {
+            let Some(ref utxo) = spent_outputs[index] else {
+                continue;
+            };
+            if input.is_finalized() {
+                continue;
+            }
+            let signer = InputSigner {
+                unsigned: &unsigned,
+                index,
+                utxo,
+                spent_outputs: &spent_outputs,
+                private_keys,
+            };
+            signed += signer.sign(input)?;
+        }
+        Ok(signed)
+    }
+}
+
+struct InputSigner<'a> {
+    unsigned: &'a Transaction,
+    index: usize,
+    utxo: &'a TransactionOutput,
+    spent_outputs: &'a [Option<TransactionOutput>],
+    private_keys: &'a [PrivateKey],
+}
+
+impl<'a> InputSigner<'a> {
+    fn sign(&self, input: &mut PsbtInput) -> PsbtResult<usize> {
+        if let Some(output_key) = self.utxo.script_pubkey.match_p2tr() {
+            return self.sign_taproot_key_path(input, output_key);
+        }
+
+        let spending = SpendingScript::new(input, &self.utxo.script_pubkey)?;
+        let sighash_type = SighashType::new(input.sighash_type.unwrap_or(SIGHASH_ALL));
+        let version = match spending.kind {
+            ScriptKind::Legacy if !sighash_type.has_fork_id() => SignatureVersion::Base,
+            _ => SignatureVersion::WitnessV0,
+        };
+        // The value of a legacy output is not committed by its signature, so the full transaction is required.
+        if version == SignatureVersion::Base && input.non_witness_utxo.is_none() {
+            return Err(SigningError::new(
+                SigningErrorType::Error_missing_input_utxos,
+                "Non-witness UTXO is required to sign a non-segwit input",
+            ));
+        }
+
+        let mut signed = 0;
+        for key in self.private_keys {
+            let public = key.public();
+            let mut public_keys = vec![public.compressed().into_vec()];
+            // Uncompressed public keys are not standard in segwit scripts.
+            if spending.kind == ScriptKind::Legacy {
+                public_keys.push(public.uncompressed().into_vec());
+            }
+
+            for public_key in public_keys {
+                if !is_locked_to(&spending.script_code, &public_key)
+                    || input.partial_sigs.contains_key(&public_key)
+                {
+                    continue;
+                }
+                let hash = self.unsigned.signature_hash(
+                    &spending.script_code,
+                    self.index,
+                    sighash_type,
+                    self.utxo.value,
+                    version,
+                );
+                let mut signature = key.sign(hash).map_err(signing_error)?.to_der();
+                signature.push(sighash_type.byte());
+                input.partial_sigs.insert(public_key, signature);
+                signed += 1;
+            }
+        }
+        Ok(signed)
+    }
+
+    /// Signs the key path of a taproot output if one of the keys tweaked with the merkle root is the output key.
+    fn sign_taproot_key_path(&self, input: &mut PsbtInput, output_key: &[u8]) -> PsbtResult<usize> {
+        if input.tap_key_sig.is_some() {
+            return Ok(0);
+        }
+        let merkle_root = input.tap_merkle_root;
+        let key = self.private_keys.iter().find(|key| {
+            let internal_key = key.x_only_public();
+            let is_internal_key = input
+                .tap_internal_key
+                .is_none_or(|expected| expected == internal_key.to_bytes());
+            is_internal_key
+                && internal_key
+                    .taproot_tweak(merkle_root.as_ref())
+                    .map(|(tweaked, _)| tweaked.to_bytes().as_slice() == output_key)
+                    .unwrap_or(false)
+        });
+        let Some(key) = key else {
+            return Ok(0);
+        };
+
+        let spent_outputs = self
+            .spent_outputs
+            .iter()
+            .cloned()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                SigningError::new(
+                    SigningErrorType::Error_missing_input_utxos,
+                    "All the spent outputs are required to sign a taproot input",
+                )
+            })?;
+        let sighash_type = SighashType::new(input.sighash_type.unwrap_or(SIGHASH_DEFAULT));
+        let hash =
+            self.unsigned
+                .taproot_signature_hash(self.index, sighash_type, &spent_outputs)?;
+        let signature = key
+            .taproot_tweak(merkle_root.as_ref())
+            .and_then(|tweaked| tweaked.sign_schnorr(hash, &H256::default()))
+            .map_err(signing_error)?;
+
+        let mut signature = signature.to_bytes().into_vec();
+        if sighash_type.raw() != SIGHASH_DEFAULT {
+            signature.push(sighash_type.byte());
+        }
+        input.tap_key_sig = Some(signature);
+        input.tap_internal_key = Some(key.x_only_public().to_bytes());
+        Ok(1)
+    }
+}
+
+/// Checks if the `script` is satisfied by a signature of the serialized `public_key`.
+fn is_locked_to(script: &Script, public_key: &[u8]) -> bool {
+    if let Some(public_key_hash) = script.match_p2pkh() {
+        return hash160(public_key).as_slice() == public_key_hash;
+    }
+    if let Some(script_key) = script.match_p2pk() {
+        return script_key == public_key;
+    }
+    if let Some((public_keys, _)) = script.match_multisig() {
+        return public_keys.contains(&public_key);
+    }
+    false
+}
+
+fn redeem_error(message: &str) -> SigningError {
+    SigningError::new(SigningErrorType::Error_script_redeem, message)
+}
+
+fn signing_error<E>(_: E) -> SigningError {
+    SigningError::new(SigningErrorType::Error_signing, "Error signing")
+}
@@ -0,0 +1,117 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::{SigningError, SigningErrorType, SigningResult};
+
+/// Reads Bitcoin serialized values from a byte slice.
+/// All the methods fail with `Error_input_parse` if there are not enough bytes.
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the bytes that have not been read yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> SigningResult<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(SigningError::new(
+                SigningErrorType::Error_input_parse,
+                "Unexpected end of data",
+            ));
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> SigningResult<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    pub fn read_u8(&mut self) -> SigningResult<u8> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    pub fn read_u32(&mut self) -> SigningResult<u32> {
+        self.read_array().map(u32::from_le_bytes)
+    }
+
+    pub fn read_i32(&mut self) -> SigningResult<i32> {
+        self.read_array().map(i32::from_le_bytes)
+    }
+
+    pub fn read_i64(&mut self) -> SigningResult<i64> {
+        self.read_array().map(i64::from_le_bytes)
+    }
+
+    /// Reads a variable length integer (CompactSize) in the canonical (shortest) encoding.
+    pub fn read_var_int(&mut self) -> SigningResult<u64> {
+        let (value, min) = match self.read_u8()? {
+            0xfd => (u16::from_le_bytes(self.read_array()?) as u64, 0xfd),
+            0xfe => (u32::from_le_bytes(self.read_array()?) as u64, 0x10000),
+            0xff => (u64::from_le_bytes(self.read_array()?), 0x1_0000_0000),
+            value => return Ok(value as u64),
+        };
+        if value < min {
+            return Err(SigningError::new(
+                SigningErrorType::Error_input_parse,
+                "Non-canonical variable length integer",
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Reads a length that must not exceed the number of remaining bytes.
+    pub fn read_len(&mut self) -> SigningResult<usize> {
+        let len = self.read_var_int()?;
+        if len > self.bytes.len() as u64 {
+            return Err(SigningError::new(
+                SigningErrorType::Error_input_parse,
+                "Length exceeds the data size",
+            ));
+        }
+        Ok(len as usize)
+    }
+
+    /// Reads bytes prefixed with their length.
+    pub fn read_var_bytes(&mut self) -> SigningResult<&'a [u8]> {
+        let len = self.read_len()?;
+        self.read_bytes(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_var_int() {
+        let read = |bytes: &[u8]| Reader::new(bytes).read_var_int();
+        assert_eq!(read(&[0xfc]), Ok(0xfc));
+        assert_eq!(read(&[0xfd, 0xfd, 0x00]), Ok(0xfd));
+        assert_eq!(read(&[0xfe, 0x00, 0x00, 0x01, 0x00]), Ok(0x10000));
+        assert_eq!(read(&[0xff, 0, 0, 0, 0, 1, 0, 0, 0]), Ok(0x1_0000_0000));
+
+        // Non-canonical and truncated encodings.
+        assert!(read(&[0xfd, 0xfc, 0x00]).is_err());
+        assert!(read(&[0xfe, 0xff, 0xff, 0x00, 0x00]).is_err());
+        assert!(read(&[0xfd, 0xfd]).is_err());
+        assert!(read(&[]).is_err());
+    }
+}
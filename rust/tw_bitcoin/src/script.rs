@@ -0,0 +1,418 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::transaction::encode_var_int;
+use tw_hash::ripemd::ripemd_160;
+use tw_hash::sha2::sha256;
+use tw_hash::H160;
+use tw_keypair::secp256k1::PublicKey;
+
+/// cbindgen:ignore
+pub const OP_0: u8 = 0x00;
+/// cbindgen:ignore
+pub const OP_PUSHDATA1: u8 = 0x4c;
+/// cbindgen:ignore
+pub const OP_PUSHDATA2: u8 = 0x4d;
+/// cbindgen:ignore
+pub const OP_PUSHDATA4: u8 = 0x4e;
+/// cbindgen:ignore
+pub const OP_1: u8 = 0x51;
+/// cbindgen:ignore
+pub const OP_16: u8 = 0x60;
+/// cbindgen:ignore
+pub const OP_RETURN: u8 = 0x6a;
+/// cbindgen:ignore
+pub const OP_DUP: u8 = 0x76;
+/// cbindgen:ignore
+pub const OP_EQUAL: u8 = 0x87;
+/// cbindgen:ignore
+pub const OP_EQUALVERIFY: u8 = 0x88;
+/// cbindgen:ignore
+pub const OP_HASH160: u8 = 0xa9;
+/// cbindgen:ignore
+pub const OP_CHECKSIG: u8 = 0xac;
+/// cbindgen:ignore
+pub const OP_CHECKMULTISIG: u8 = 0xae;
+
+/// The maximum length of data that can be embedded into an `OP_RETURN` output.
+/// cbindgen:ignore
+pub const MAX_OP_RETURN_LEN: usize = 80;
+
+/// cbindgen:ignore
+const COMPRESSED_PUBLIC_KEY_LEN: usize = 33;
+/// cbindgen:ignore
+const UNCOMPRESSED_PUBLIC_KEY_LEN: usize = 65;
+/// cbindgen:ignore
+const HASH160_LEN: usize = 20;
+/// cbindgen:ignore
+const WITNESS_SCRIPT_HASH_LEN: usize = 32;
+/// cbindgen:ignore
+const TAPROOT_OUTPUT_KEY_LEN: usize = 32;
+
+/// Returns `RIPEMD160(SHA256(data))` used to hash public keys and scripts.
+pub fn hash160(data: &[u8]) -> H160 {
+    H160::try_from(ripemd_160(&sha256(data)).as_slice()).expect("ripemd160 hash must be 20 bytes")
+}
+
+/// A Bitcoin script.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Script {
+    bytes: Vec<u8>,
+}
+
+impl Script {
+    pub fn new(bytes: Vec<u8>) -> Script {
+        Script { bytes }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Builds a pay-to-public-key script.
+    pub fn p2pk(public_key: &[u8]) -> Script {
+        let mut bytes = Vec::with_capacity(public_key.len() + 2);
+        push_data(&mut bytes, public_key);
+        bytes.push(OP_CHECKSIG);
+        Script { bytes }
+    }
+
+    /// Builds a pay-to-public-key-hash script.
+    pub fn p2pkh(public_key_hash: &[u8]) -> Script {
+        let mut bytes = vec![OP_DUP, OP_HASH160];
+        push_data(&mut bytes, public_key_hash);
+        bytes.extend_from_slice(&[OP_EQUALVERIFY, OP_CHECKSIG]);
+        Script { bytes }
+    }
+
+    /// Builds a pay-to-script-hash script.
+    pub fn p2sh(script_hash: &[u8]) -> Script {
+        let mut bytes = vec![OP_HASH160];
+        push_data(&mut bytes, script_hash);
+        bytes.push(OP_EQUAL);
+        Script { bytes }
+    }
+
+    /// Builds a script locking to the witness `program` of the given `version`.
+    pub fn witness_program(version: u8, program: &[u8]) -> Script {
+        let mut bytes = vec![encode_small_number(version)];
+        push_data(&mut bytes, program);
+        Script { bytes }
+    }
+
+    /// Builds a pay-to-witness-public-key-hash script.
+    pub fn p2wpkh(public_key_hash: &[u8]) -> Script {
+        Script::witness_program(0, public_key_hash)
+    }
+
+    /// Builds a pay-to-witness-script-hash script.
+    pub fn p2wsh(script_hash: &[u8]) -> Script {
+        Script::witness_program(0, script_hash)
+    }
+
+    /// Builds a pay-to-taproot script locking to the tweaked x-only `output_key`.
+    pub fn p2tr(output_key: &[u8]) -> Script {
+        Script::witness_program(1, output_key)
+    }
+
+    /// Builds an `OP_RETURN` script embedding the `data`.
+    /// Returns `None` if the data is longer than [`MAX_OP_RETURN_LEN`].
+    pub fn op_return(data: &[u8]) -> Option<Script> {
+        if data.len() > MAX_OP_RETURN_LEN {
+            return None;
+        }
+        let mut bytes = vec![OP_RETURN];
+        push_data(&mut bytes, data);
+        Some(Script { bytes })
+    }
+
+    /// Builds a script pushing all the `items` as the C++ implementation does:
+    /// empty items as `OP_0`, single bytes of 1..16 as `OP_1`..`OP_16`, others as data pushes.
+    pub fn push_all<I: AsRef<[u8]>>(items: &[I]) -> Script {
+        let mut bytes = Vec::new();
+        for item in items {
+            match item.as_ref() {
+                [] => bytes.push(OP_0),
+                [n @ 1..=16] => bytes.push(encode_small_number(*n)),
+                data => push_data(&mut bytes, data),
+            }
+        }
+        Script { bytes }
+    }
+
+    /// Returns the public key if the script is pay-to-public-key.
+    pub fn match_p2pk(&self) -> Option<&[u8]> {
+        match self.bytes.as_slice() {
+            [len, public_key @ .., OP_CHECKSIG]
+                if (*len as usize == COMPRESSED_PUBLIC_KEY_LEN
+                    || *len as usize == UNCOMPRESSED_PUBLIC_KEY_LEN)
+                    && public_key.len() == *len as usize =>
+            {
+                Some(public_key)
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the public key hash if the script is pay-to-public-key-hash.
+    pub fn match_p2pkh(&self) -> Option<&[u8]> {
+        match self.bytes.as_slice() {
+            [OP_DUP, OP_HASH160, 20, hash @ .., OP_EQUALVERIFY, OP_CHECKSIG]
+                if hash.len() == HASH160_LEN =>
+            {
+                Some(hash)
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the script hash if the script is pay-to-script-hash.
+    pub fn match_p2sh(&self) -> Option<&[u8]> {
+        match self.bytes.as_slice() {
+            [OP_HASH160, 20, hash @ .., OP_EQUAL] if hash.len() == HASH160_LEN => Some(hash),
+            _ => None,
+        }
+    }
+
+    /// Returns the public key hash if the script is pay-to-witness-public-key-hash.
+    pub fn match_p2wpkh(&self) -> Option<&[u8]> {
+        self.match_witness_program(0, HASH160_LEN)
+    }
+
+    /// Returns the script hash if the script is pay-to-witness-script-hash.
+    pub fn match_p2wsh(&self) -> Option<&[u8]> {
+        self.match_witness_program(0, WITNESS_SCRIPT_HASH_LEN)
+    }
+
+    /// Returns the x-only output key if the script is pay-to-taproot.
+    pub fn match_p2tr(&self) -> Option<&[u8]> {
+        self.match_witness_program(1, TAPROOT_OUTPUT_KEY_LEN)
+    }
+
+    /// Checks if the script is a witness program of any version as defined in BIP141.
+    pub fn is_witness_program(&self) -> bool {
+        match self.bytes.as_slice() {
+            [version, len, program @ ..] => {
+                (4..=42).contains(&self.bytes.len())
+                    && (*version == OP_0 || (OP_1..=OP_16).contains(version))
+                    && *len as usize == program.len()
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the public keys and the number of required signatures
+    /// if the script is a bare `m-of-n` multisig.
+    pub fn match_multisig(&self) -> Option<(Vec<&[u8]>, usize)> {
+        if self.bytes.last() != Some(&OP_CHECKMULTISIG) {
+            return None;
+        }
+
+        let mut index = 0;
+        let (opcode, _) = self.next_op(&mut index)?;
+        let required = decode_small_number(opcode)?;
+
+        let mut keys = Vec::new();
+        let mut opcode = opcode;
+        while let Some((next_opcode, operand)) = self.next_op(&mut index) {
+            opcode = next_opcode;
+            if PublicKey::try_from(operand).is_err() {
+                break;
+            }
+            keys.push(operand);
+        }
+
+        let expected = decode_small_number(opcode)?;
+        if keys.len() != expected || expected < required || index + 1 != self.bytes.len() {
+            return None;
+        }
+        Some((keys, required))
+    }
+
+    /// Appends the script prefixed with its length.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        encode_var_int(self.bytes.len() as u64, out);
+        out.extend_from_slice(&self.bytes);
+    }
+
+    fn match_witness_program(&self, version: u8, program_len: usize) -> Option<&[u8]> {
+        match self.bytes.as_slice() {
+            [op, len, program @ ..]
+                if *op == encode_small_number(version)
+                    && *len as usize == program_len
+                    && program.len() == program_len =>
+            {
+                Some(program)
+            },
+            _ => None,
+        }
+    }
+
+    /// Reads an operation at the `index` and returns the opcode and its immediate operand.
+    fn next_op<'a>(&'a self, index: &mut usize) -> Option<(u8, &'a [u8])> {
+        let opcode = *self.bytes.get(*index)?;
+        *index += 1;
+        if opcode > OP_PUSHDATA4 {
+            return Some((opcode, &[]));
+        }
+
+        let len_size = match opcode {
+            OP_PUSHDATA1 => 1,
+            OP_PUSHDATA2 => 2,
+            OP_PUSHDATA4 => 4,
+            _ => 0,
+        };
+        let len = if len_size == 0 {
+            opcode as usize
+        } else {
+            let len_bytes = self.bytes.get(*index..*index + len_size)?;
+            *index += len_size;
+            len_bytes
+                .iter()
+                .rev()
+                .fold(0usize, |len, byte| (len << 8) | *byte as usize)
+        };
+
+        let operand = self.bytes.get(*index..index.checked_add(len)?)?;
+        *index += len;
+        Some((opcode, operand))
+    }
+}
+
+impl From<Vec<u8>> for Script {
+    fn from(bytes: Vec<u8>) -> Self {
+        Script { bytes }
+    }
+}
+
+/// Appends the shortest push operation of the `data`.
+fn push_data(out: &mut Vec<u8>, data: &[u8]) {
+    let len = data.len();
+    if len < OP_PUSHDATA1 as usize {
+        out.push(len as u8);
+    } else if len <= u8::MAX as usize {
+        out.extend_from_slice(&[OP_PUSHDATA1, len as u8]);
+    } else if len <= u16::MAX as usize {
+        out.push(OP_PUSHDATA2);
+        out.extend_from_slice(&(len as u16).to_le_bytes());
+    } else {
+        out.push(OP_PUSHDATA4);
+        out.extend_from_slice(&(len as u32).to_le_bytes());
+    }
+    out.extend_from_slice(data);
+}
+
+/// Returns the opcode pushing the number of 0..16.
+fn encode_small_number(n: u8) -> u8 {
+    debug_assert!(n <= 16);
+    if n == 0 {
+        OP_0
+    } else {
+        OP_1 + n - 1
+    }
+}
+
+/// Returns the number pushed by `OP_0`, `OP_1`..`OP_16`.
+fn decode_small_number(opcode: u8) -> Option<usize> {
+    match opcode {
+        OP_0 => Some(0),
+        OP_1..=OP_16 => Some((opcode - OP_1 + 1) as usize),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    fn script(s: &str) -> Script {
+        Script::new(hex::decode(s).unwrap())
+    }
+
+    #[test]
+    fn test_build_scripts() {
+        let hash = hex::decode("1d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        assert_eq!(
+            hex::encode(Script::p2pkh(&hash).as_bytes(), false),
+            "76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac"
+        );
+        assert_eq!(
+            hex::encode(Script::p2sh(&hash).as_bytes(), false),
+            "a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a187"
+        );
+        assert_eq!(
+            hex::encode(Script::p2wpkh(&hash).as_bytes(), false),
+            "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1"
+        );
+
+        let output_key =
+            hex::decode("a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c")
+                .unwrap();
+        assert_eq!(
+            hex::encode(Script::p2tr(&output_key).as_bytes(), false),
+            "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c"
+        );
+
+        assert_eq!(
+            hex::encode(Script::op_return(b"hello").unwrap().as_bytes(), false),
+            "6a0568656c6c6f"
+        );
+        assert_eq!(Script::op_return(&[0; MAX_OP_RETURN_LEN + 1]), None);
+    }
+
+    #[test]
+    fn test_match_scripts() {
+        let p2pkh = script("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac");
+        assert_eq!(
+            p2pkh.match_p2pkh().map(|hash| hex::encode(hash, false)),
+            Some("1d0f172a0ecb48aee1be1f2687d2963ae33f71a1".to_string())
+        );
+        assert_eq!(p2pkh.match_p2sh(), None);
+        assert!(!p2pkh.is_witness_program());
+
+        let p2wsh = script("0020ff25429251b5a84f452230a3c75fd886b7fc5a7865ce4a7bb7a9d7c5be6da3db");
+        assert!(p2wsh.match_p2wsh().is_some());
+        assert!(p2wsh.match_p2wpkh().is_none());
+        assert!(p2wsh.match_p2tr().is_none());
+        assert!(p2wsh.is_witness_program());
+
+        let p2pk = script("2103c9f4836b9a4f77fc0d81f7bcb01b7f1b35916864b9476c241ce9fc198bd25432ac");
+        assert_eq!(p2pk.match_p2pk().map(<[u8]>::len), Some(33));
+    }
+
+    #[test]
+    fn test_match_multisig() {
+        let multisig = script("56210307b8ae49ac90a048e9b53357a2354b3334e9c8bee813ecb98e99a7e07e8c3ba32103b28f0c28bfab54554ae8c658ac5c3e0ce6e79ad336331f78c428dd43eea8449b21034b8113d703413d57761b8b9781957b8c0ac1dfe69f492580ca4195f50376ba4a21033400f6afecb833092a9a21cfdf1ed1376e58c5d1f47de74683123987e967a8f42103a6d48b1131e94ba04d9737d61acdaa1322008af9602b3b14862c07a1789aac162102d8b661b0b3302ee2f162b09e07a55ad5dfbe673a9f01d9f0c19617681024306b56ae");
+        let (keys, required) = multisig.match_multisig().unwrap();
+        assert_eq!(keys.len(), 6);
+        assert_eq!(required, 6);
+
+        // The number of keys doesn't match the declared one.
+        let mut invalid = multisig.as_bytes().to_vec();
+        let declared_keys = invalid.len() - 2;
+        invalid[declared_keys] = OP_1 + 4;
+        assert_eq!(Script::new(invalid).match_multisig(), None);
+    }
+
+    #[test]
+    fn test_push_all() {
+        let items: Vec<Vec<u8>> = vec![vec![], vec![1], vec![16], vec![17], vec![0xab; 76]];
+        let pushed = Script::push_all(&items);
+        let bytes = pushed.as_bytes();
+        assert_eq!(&bytes[..6], &[OP_0, OP_1, OP_16, 0x01, 0x11, OP_PUSHDATA1]);
+        assert_eq!(bytes[6], 76);
+        assert_eq!(bytes.len(), 7 + 76);
+    }
+}
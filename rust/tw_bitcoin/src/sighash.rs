@@ -0,0 +1,396 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::script::Script;
+use crate::transaction::{encode_var_int, Transaction, TransactionOutput};
+use crate::{Amount, SigningError, SigningErrorType, SigningResult};
+use tw_hash::sha2::{sha256, sha256_tagged, sha256d};
+use tw_hash::H256;
+
+/// Taproot only: signs all inputs and outputs, the signature is not suffixed with the type byte.
+/// cbindgen:ignore
+pub const SIGHASH_DEFAULT: u32 = 0x00;
+/// cbindgen:ignore
+pub const SIGHASH_ALL: u32 = 0x01;
+/// cbindgen:ignore
+pub const SIGHASH_NONE: u32 = 0x02;
+/// cbindgen:ignore
+pub const SIGHASH_SINGLE: u32 = 0x03;
+/// Used by Bitcoin forks with replay protection, e.g. Bitcoin Gold, that always hash as BIP143.
+/// cbindgen:ignore
+pub const SIGHASH_FORK_ID: u32 = 0x40;
+/// cbindgen:ignore
+pub const SIGHASH_ANYONE_CAN_PAY: u32 = 0x80;
+
+/// cbindgen:ignore
+const SIGHASH_BASE_MASK: u32 = 0x1f;
+/// cbindgen:ignore
+const TAPROOT_OUTPUT_MASK: u8 = 0x03;
+/// cbindgen:ignore
+const TAPROOT_SIGHASH_TAG: &[u8] = b"TapSighash";
+/// cbindgen:ignore
+const TAPROOT_SIGHASH_EPOCH: u8 = 0x00;
+
+/// The hash type of a signature.
+/// The lowest byte of it is appended to ECDSA and non-default Schnorr signatures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SighashType(u32);
+
+impl SighashType {
+    pub fn new(raw: u32) -> SighashType {
+        SighashType(raw)
+    }
+
+    pub fn raw(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the byte appended to signatures.
+    pub fn byte(&self) -> u8 {
+        self.0 as u8
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.0 & SIGHASH_BASE_MASK == SIGHASH_NONE
+    }
+
+    pub fn is_single(&self) -> bool {
+        self.0 & SIGHASH_BASE_MASK == SIGHASH_SINGLE
+    }
+
+    pub fn anyone_can_pay(&self) -> bool {
+        self.0 & SIGHASH_ANYONE_CAN_PAY != 0
+    }
+
+    pub fn has_fork_id(&self) -> bool {
+        self.0 & SIGHASH_FORK_ID != 0
+    }
+}
+
+/// The signature hash algorithm of ECDSA signatures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SignatureVersion {
+    /// The original algorithm used by non-segwit inputs.
+    Base,
+    /// BIP143 algorithm used by segwit v0 inputs.
+    WitnessV0,
+}
+
+impl Transaction {
+    /// Returns the hash to be signed by an ECDSA signature of the input at `index`.
+    /// `amount` is the value of the spent output, it's used by [`SignatureVersion::WitnessV0`] only.
+    pub fn signature_hash(
+        &self,
+        script_code: &Script,
+        index: usize,
+        sighash_type: SighashType,
+        amount: Amount,
+        version: SignatureVersion,
+    ) -> H256 {
+        let hash = match version {
+            SignatureVersion::Base => self.legacy_signature_hash(script_code, index, sighash_type),
+            SignatureVersion::WitnessV0 => {
+                sha256d(&self.witness_v0_preimage(script_code, index, sighash_type, amount))
+            },
+        };
+        H256::try_from(hash.as_slice()).expect("sha256d hash must be 32 bytes")
+    }
+
+    /// Returns the BIP341 hash to be signed by a key-path spending Schnorr signature of the input at `index`.
+    /// `spent_outputs` are the outputs spent by all the inputs of the transaction.
+    pub fn taproot_signature_hash(
+        &self,
+        index: usize,
+        sighash_type: SighashType,
+        spent_outputs: &[TransactionOutput],
+    ) -> SigningResult<H256> {
+        let hash_type = sighash_type.byte();
+        if !matches!(hash_type, 0x00..=0x03 | 0x81..=0x83) || sighash_type.raw() > 0xff {
+            return Err(SigningError::new(
+                SigningErrorType::Error_invalid_params,
+                format!("Invalid taproot sighash type: {}", sighash_type.raw()),
+            ));
+        }
+        if spent_outputs.len() != self.inputs.len() || index >= self.inputs.len() {
+            return Err(SigningError::new(
+                SigningErrorType::Error_invalid_params,
+                "Spent outputs must be provided for every input",
+            ));
+        }
+        let output_type = hash_type & TAPROOT_OUTPUT_MASK;
+        let is_single = output_type == SIGHASH_SINGLE as u8;
+        let is_none = output_type == SIGHASH_NONE as u8;
+        if is_single && index >= self.outputs.len() {
+            return Err(SigningError::new(
+                SigningErrorType::Error_invalid_params,
+                "There is no output corresponding to the SIGHASH_SINGLE input",
+            ));
+        }
+
+        let mut msg = vec![TAPROOT_SIGHASH_EPOCH, hash_type];
+        msg.extend_from_slice(&self.version.to_le_bytes());
+        msg.extend_from_slice(&self.lock_time.to_le_bytes());
+
+        if !sighash_type.anyone_can_pay() {
+            let mut prevouts = Vec::new();
+            let mut amounts = Vec::new();
+            let mut script_pubkeys = Vec::new();
+            let mut sequences = Vec::new();
+            for (input, spent) in self.inputs.iter().zip(spent_outputs) {
+                input.previous_output.encode(&mut prevouts);
+                amounts.extend_from_slice(&spent.value.to_le_bytes());
+                spent.script_pubkey.encode(&mut script_pubkeys);
+                sequences.extend_from_slice(&input.sequence.to_le_bytes());
+            }
+            msg.extend(sha256(&prevouts));
+            msg.extend(sha256(&amounts));
+            msg.extend(sha256(&script_pubkeys));
+            msg.extend(sha256(&sequences));
+        }
+        if !is_single && !is_none {
+            let mut outputs = Vec::new();
+            for output in self.outputs.iter() {
+                output.encode(&mut outputs);
+            }
+            msg.extend(sha256(&outputs));
+        }
+
+        // Key path spending without an annex.
+        let spend_type = 0;
+        msg.push(spend_type);
+
+        if sighash_type.anyone_can_pay() {
+            let input = &self.inputs[index];
+            input.previous_output.encode(&mut msg);
+            msg.extend_from_slice(&spent_outputs[index].value.to_le_bytes());
+            spent_outputs[index].script_pubkey.encode(&mut msg);
+            msg.extend_from_slice(&input.sequence.to_le_bytes());
+        } else {
+            msg.extend_from_slice(&(index as u32).to_le_bytes());
+        }
+
+        if is_single {
+            let mut output = Vec::new();
+            self.outputs[index].encode(&mut output);
+            msg.extend(sha256(&output));
+        }
+
+        let hash = sha256_tagged(TAPROOT_SIGHASH_TAG, &msg);
+        Ok(H256::try_from(hash.as_slice()).expect("sha256 hash must be 32 bytes"))
+    }
+
+    fn legacy_signature_hash(
+        &self,
+        script_code: &Script,
+        index: usize,
+        sighash_type: SighashType,
+    ) -> Vec<u8> {
+        // Bitcoin Core signs the `1` number in this case instead of failing, keep the behaviour.
+        if sighash_type.is_single() && index >= self.outputs.len() {
+            let mut one = vec![0; H256::len()];
+            one[0] = 1;
+            return one;
+        }
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.version.to_le_bytes());
+
+        // Only the signed input is serialized in case of `SIGHASH_ANYONECANPAY`.
+        let input_indexes = if sighash_type.anyone_can_pay() {
+            index..index + 1
+        } else {
+            0..self.inputs.len()
+        };
+        encode_var_int(input_indexes.len() as u64, &mut data);
+        for subindex in input_indexes {
+            let input = &self.inputs[subindex];
+            input.previous_output.encode(&mut data);
+            if subindex == index {
+                script_code.encode(&mut data);
+            } else {
+                encode_var_int(0, &mut data);
+            }
+
+            let sequence =
+                if subindex != index && (sighash_type.is_single() || sighash_type.is_none()) {
+                    0
+                } else {
+                    input.sequence
+                };
+            data.extend_from_slice(&sequence.to_le_bytes());
+        }
+
+        let output_count = if sighash_type.is_none() {
+            0
+        } else if sighash_type.is_single() {
+            index + 1
+        } else {
+            self.outputs.len()
+        };
+        encode_var_int(output_count as u64, &mut data);
+        for (subindex, output) in self.outputs.iter().take(output_count).enumerate() {
+            if sighash_type.is_single() && subindex != index {
+                let blank = TransactionOutput {
+                    value: -1,
+                    script_pubkey: Script::default(),
+                };
+                blank.encode(&mut data);
+            } else {
+                output.encode(&mut data);
+            }
+        }
+
+        data.extend_from_slice(&self.lock_time.to_le_bytes());
+        data.extend_from_slice(&sighash_type.raw().to_le_bytes());
+        sha256d(&data)
+    }
+
+    /// Returns the BIP143 signature preimage.
+    fn witness_v0_preimage(
+        &self,
+        script_code: &Script,
+        index: usize,
+        sighash_type: SighashType,
+        amount: Amount,
+    ) -> Vec<u8> {
+        let zero_hash = [0; 32];
+        let input = &self.inputs[index];
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.version.to_le_bytes());
+
+        if sighash_type.anyone_can_pay() {
+            data.extend_from_slice(&zero_hash);
+        } else {
+            let mut prevouts = Vec::new();
+            for input in self.inputs.iter() {
+                input.previous_output.encode(&mut prevouts);
+            }
+            data.extend(sha256d(&prevouts));
+        }
+
+        if sighash_type.anyone_can_pay() || sighash_type.is_single() || sighash_type.is_none() {
+            data.extend_from_slice(&zero_hash);
+        } else {
+            let mut sequences = Vec::new();
+            for input in self.inputs.iter() {
+                sequences.extend_from_slice(&input.sequence.to_le_bytes());
+            }
+            data.extend(sha256d(&sequences));
+        }
+
+        input.previous_output.encode(&mut data);
+        script_code.encode(&mut data);
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&input.sequence.to_le_bytes());
+
+        if !sighash_type.is_single() && !sighash_type.is_none() {
+            let mut outputs = Vec::new();
+            for output in self.outputs.iter() {
+                output.encode(&mut outputs);
+            }
+            data.extend(sha256d(&outputs));
+        } else if sighash_type.is_single() && index < self.outputs.len() {
+            let mut output = Vec::new();
+            self.outputs[index].encode(&mut output);
+            data.extend(sha256d(&output));
+        } else {
+            data.extend_from_slice(&zero_hash);
+        }
+
+        data.extend_from_slice(&self.lock_time.to_le_bytes());
+        data.extend_from_slice(&sighash_type.raw().to_le_bytes());
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::{OutPoint, TransactionInput, TRANSACTION_VERSION};
+    use tw_encoding::hex;
+
+    fn p2pkh(hash: &str) -> Script {
+        Script::p2pkh(&hex::decode(hash).unwrap())
+    }
+
+    /// The native P2WPKH example from BIP143.
+    fn bip143_transaction() -> Transaction {
+        let input = |hash: &'static str, index, sequence| TransactionInput {
+            previous_output: OutPoint {
+                hash: H256::from(hash),
+                index,
+            },
+            script_sig: Script::default(),
+            sequence,
+            witness: Vec::new(),
+        };
+        Transaction {
+            version: TRANSACTION_VERSION,
+            lock_time: 17,
+            inputs: vec![
+                input(
+                    "fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f",
+                    0,
+                    0xffffffee,
+                ),
+                input(
+                    "ef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a",
+                    1,
+                    0xffffffff,
+                ),
+            ],
+            outputs: vec![
+                TransactionOutput {
+                    value: 112_340_000,
+                    script_pubkey: p2pkh("8280b37df378db99f66f85c95a783a76ac7a6d59"),
+                },
+                TransactionOutput {
+                    value: 223_450_000,
+                    script_pubkey: p2pkh("3bde42dbee7e4dbe6a21b2d50ce2f0167faa8159"),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_witness_v0_signature_hash() {
+        let tx = bip143_transaction();
+        let hash = tx.signature_hash(
+            &p2pkh("1d0f172a0ecb48aee1be1f2687d2963ae33f71a1"),
+            1,
+            SighashType::new(SIGHASH_ALL),
+            600_000_000,
+            SignatureVersion::WitnessV0,
+        );
+        assert_eq!(
+            hash,
+            H256::from("c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670")
+        );
+    }
+
+    #[test]
+    fn test_taproot_signature_hash_invalid() {
+        let tx = bip143_transaction();
+        let spent = vec![
+            TransactionOutput {
+                value: 1000,
+                script_pubkey: Script::p2tr(&[1; 32]),
+            };
+            2
+        ];
+
+        for hash_type in [0x04, 0x80, 0x41, 0x101] {
+            let err = tx
+                .taproot_signature_hash(0, SighashType::new(hash_type), &spent)
+                .unwrap_err();
+            assert_eq!(err.error, SigningErrorType::Error_invalid_params);
+        }
+        let err = tx
+            .taproot_signature_hash(0, SighashType::new(SIGHASH_DEFAULT), &spent[..1])
+            .unwrap_err();
+        assert_eq!(err.error, SigningErrorType::Error_invalid_params);
+    }
+}
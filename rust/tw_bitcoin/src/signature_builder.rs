@@ -0,0 +1,327 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::planner::TransactionPlan;
+use crate::script::{hash160, Script};
+use crate::sighash::{SignatureVersion, SIGHASH_DEFAULT};
+use crate::signing_input::{SigningInput, Utxo};
+use crate::transaction::{Transaction, TransactionOutput};
+use crate::tx_builder::build_transaction;
+use crate::{SigningError, SigningErrorType, SigningResult};
+use tw_encoding::hex;
+use tw_hash::ripemd::ripemd_160;
+use tw_hash::H256;
+use tw_keypair::secp256k1::PrivateKey;
+use tw_keypair::traits::SigningKeyTrait;
+
+/// The length of the placeholder used instead of an ECDSA signature, DER signatures are 71-72 bytes long.
+/// cbindgen:ignore
+const ECDSA_SIGNATURE_PLACEHOLDER_LEN: usize = 72;
+/// The length of a Schnorr signature without the sighash type byte.
+/// cbindgen:ignore
+const SCHNORR_SIGNATURE_LEN: usize = 64;
+/// cbindgen:ignore
+const PUBLIC_KEY_PLACEHOLDER_LEN: usize = 33;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SigningMode {
+    /// Sign with the private keys, fail if a key is missing.
+    Normal,
+    /// Use placeholders instead of signatures and missing public keys to estimate the transaction size.
+    SizeEstimation,
+}
+
+/// Builds a transaction from the `plan` and signs all its inputs.
+pub(crate) fn sign_transaction(
+    input: &SigningInput,
+    plan: &TransactionPlan,
+    mode: SigningMode,
+) -> SigningResult<Transaction> {
+    let unsigned = build_transaction(input, plan)?;
+    SignatureBuilder {
+        input,
+        plan,
+        unsigned: &unsigned,
+        mode,
+    }
+    .sign()
+}
+
+/// A private key with the serialized public key whose hash is locked to.
+struct KeyWithPublic<'a> {
+    key: &'a PrivateKey,
+    public: Vec<u8>,
+}
+
+struct SignatureBuilder<'a> {
+    input: &'a SigningInput,
+    plan: &'a TransactionPlan,
+    unsigned: &'a Transaction,
+    mode: SigningMode,
+}
+
+impl<'a> SignatureBuilder<'a> {
+    fn sign(&self) -> SigningResult<Transaction> {
+        if self.plan.error != SigningErrorType::OK {
+            return Err(SigningError::new(
+                self.plan.error,
+                "The transaction plan has an error",
+            ));
+        }
+        if self.unsigned.inputs.is_empty() || self.plan.utxos.is_empty() {
+            return Err(SigningError::new(
+                SigningErrorType::Error_missing_input_utxos,
+                "There are no UTXOs to spend",
+            ));
+        }
+
+        let mut signed = self.unsigned.clone();
+        let single = self.input.sighash_type.is_single();
+        for (index, utxo) in self.plan.utxos.iter().enumerate() {
+            // `SIGHASH_SINGLE` inputs are signed only if there is a corresponding output.
+            if (single && index >= self.unsigned.outputs.len())
+                || index >= self.unsigned.inputs.len()
+            {
+                continue;
+            }
+            let (script_sig, witness) = self.sign_input(index, utxo)?;
+            signed.inputs[index].script_sig = script_sig;
+            signed.inputs[index].witness = witness;
+        }
+        Ok(signed)
+    }
+
+    /// Returns the script signature and the witness of the input at `index`.
+    fn sign_input(&self, index: usize, utxo: &Utxo) -> SigningResult<(Script, Vec<Vec<u8>>)> {
+        if let Some(output_key) = utxo.script.match_p2tr() {
+            let signature = self.sign_taproot_key_path(index, output_key)?;
+            return Ok((Script::default(), vec![signature]));
+        }
+
+        let version = if self.input.sighash_type.has_fork_id() {
+            SignatureVersion::WitnessV0
+        } else {
+            SignatureVersion::Base
+        };
+
+        let mut script = utxo.script.clone();
+        let mut results = self.sign_step(&script, index, utxo, version)?;
+        let mut redeem_script = None;
+        if script.match_p2sh().is_some() {
+            script = Script::new(results.swap_remove(0));
+            results = self.sign_step(&script, index, utxo, version)?;
+            redeem_script = Some(script.clone());
+        }
+
+        let mut witness = Vec::new();
+        if script.match_p2wpkh().is_some() {
+            let witness_script = Script::p2pkh(&results[0]);
+            witness = self.sign_step(&witness_script, index, utxo, SignatureVersion::WitnessV0)?;
+            results.clear();
+        } else if script.match_p2wsh().is_some() {
+            let witness_script = Script::new(results.swap_remove(0));
+            witness = self.sign_step(&witness_script, index, utxo, SignatureVersion::WitnessV0)?;
+            witness.push(witness_script.into_bytes());
+            results.clear();
+        } else if script.is_witness_program() {
+            return Err(SigningError::new(
+                SigningErrorType::Error_script_witness_program,
+                "Unrecognized witness program",
+            ));
+        }
+
+        if let Some(redeem_script) = redeem_script {
+            results.push(redeem_script.into_bytes());
+        }
+        Ok((Script::push_all(&results), witness))
+    }
+
+    /// Returns the items satisfying the `script`.
+    /// These are signatures with public keys, or the redeem script for P2SH and P2WSH scripts,
+    /// or the public key hash for P2WPKH scripts.
+    fn sign_step(
+        &self,
+        script: &Script,
+        index: usize,
+        utxo: &Utxo,
+        version: SignatureVersion,
+    ) -> SigningResult<Vec<Vec<u8>>> {
+        if let Some(script_hash) = script.match_p2sh() {
+            return Ok(vec![self.script_for_script_hash(script_hash)?]);
+        }
+        if let Some(witness_script_hash) = script.match_p2wsh() {
+            // The scripts are looked up by HASH160 that is RIPEMD160 of the witness program.
+            let script_hash = ripemd_160(witness_script_hash);
+            return Ok(vec![self.script_for_script_hash(&script_hash)?]);
+        }
+        if let Some(public_key_hash) = script.match_p2wpkh() {
+            return Ok(vec![public_key_hash.to_vec()]);
+        }
+        if script.is_witness_program() {
+            return Err(SigningError::new(
+                SigningErrorType::Error_script_output,
+                "Invalid output script",
+            ));
+        }
+
+        if let Some((public_keys, required)) = script.match_multisig() {
+            // An extra item is consumed by the `OP_CHECKMULTISIG` bug.
+            let mut results = vec![Vec::new()];
+            for public_key in public_keys {
+                if results.len() > required {
+                    break;
+                }
+                let key = self.key_for_public_key_hash(hash160(public_key).as_slice())?;
+                results.push(self.create_signature(script, key, index, utxo, version)?);
+            }
+            results.resize(required + 1, Vec::new());
+            return Ok(results);
+        }
+        if let Some(public_key) = script.match_p2pk() {
+            let key = self.key_for_public_key_hash(hash160(public_key).as_slice())?;
+            return Ok(vec![
+                self.create_signature(script, key, index, utxo, version)?
+            ]);
+        }
+        if let Some(public_key_hash) = script.match_p2pkh() {
+            let key = self.key_for_public_key_hash(public_key_hash)?;
+            let public_key = match key {
+                Some(ref key) => key.public.clone(),
+                None => vec![0; PUBLIC_KEY_PLACEHOLDER_LEN],
+            };
+            let signature = self.create_signature(script, key, index, utxo, version)?;
+            return Ok(vec![signature, public_key]);
+        }
+
+        Err(SigningError::new(
+            SigningErrorType::Error_script_output,
+            "Invalid output script",
+        ))
+    }
+
+    /// Returns a DER encoded ECDSA signature with the sighash type byte appended.
+    fn create_signature(
+        &self,
+        script_code: &Script,
+        key: Option<KeyWithPublic>,
+        index: usize,
+        utxo: &Utxo,
+        version: SignatureVersion,
+    ) -> SigningResult<Vec<u8>> {
+        let (Some(key), SigningMode::Normal) = (key, self.mode) else {
+            return Ok(vec![0; ECDSA_SIGNATURE_PLACEHOLDER_LEN]);
+        };
+
+        let sighash_type = self.input.sighash_type;
+        let hash =
+            self.unsigned
+                .signature_hash(script_code, index, sighash_type, utxo.amount, version);
+        let signature = key
+            .key
+            .sign(hash)
+            .map_err(|_| SigningError::new(SigningErrorType::Error_signing, "Error signing"))?;
+
+        let mut der = signature.to_der();
+        der.push(sighash_type.byte());
+        Ok(der)
+    }
+
+    /// Returns a BIP340 signature of the BIP341 sighash by the key tweaked with no script tree.
+    /// The sighash type byte is appended unless it's `SIGHASH_DEFAULT`.
+    fn sign_taproot_key_path(&self, index: usize, output_key: &[u8]) -> SigningResult<Vec<u8>> {
+        let sighash_type = self.input.sighash_type;
+        let signature_len = if sighash_type.raw() == SIGHASH_DEFAULT {
+            SCHNORR_SIGNATURE_LEN
+        } else {
+            SCHNORR_SIGNATURE_LEN + 1
+        };
+
+        let key = self.input.private_keys.iter().find(|key| {
+            key.x_only_public()
+                .taproot_tweak(None)
+                .map(|(tweaked, _)| tweaked.to_bytes().as_slice() == output_key)
+                .unwrap_or(false)
+        });
+        let key = match (key, self.mode) {
+            (Some(key), SigningMode::Normal) => key,
+            (_, SigningMode::SizeEstimation) => return Ok(vec![0; signature_len]),
+            (None, SigningMode::Normal) => {
+                return Err(SigningError::new(
+                    SigningErrorType::Error_missing_private_key,
+                    "Missing private key",
+                ))
+            },
+        };
+
+        let spent_outputs: Vec<_> = self
+            .plan
+            .utxos
+            .iter()
+            .map(|utxo| TransactionOutput {
+                value: utxo.amount,
+                script_pubkey: utxo.script.clone(),
+            })
+            .collect();
+        let hash = self
+            .unsigned
+            .taproot_signature_hash(index, sighash_type, &spent_outputs)?;
+
+        let signing_error = |_| SigningError::new(SigningErrorType::Error_signing, "Error signing");
+        let signature = key
+            .taproot_tweak(None)
+            .and_then(|tweaked| tweaked.sign_schnorr(hash, &H256::default()))
+            .map_err(signing_error)?;
+
+        let mut signature = signature.to_bytes().into_vec();
+        if sighash_type.raw() != SIGHASH_DEFAULT {
+            signature.push(sighash_type.byte());
+        }
+        Ok(signature)
+    }
+
+    /// Returns the private key whose compressed or uncompressed public key hash is `hash`.
+    /// Fails if there is no such key unless the size is estimated.
+    fn key_for_public_key_hash(&self, hash: &[u8]) -> SigningResult<Option<KeyWithPublic<'a>>> {
+        for key in self.input.private_keys.iter() {
+            let public = key.public();
+            let compressed = public.compressed();
+            if hash160(compressed.as_slice()).as_slice() == hash {
+                return Ok(Some(KeyWithPublic {
+                    key,
+                    public: compressed.into_vec(),
+                }));
+            }
+            let uncompressed = public.uncompressed();
+            if hash160(uncompressed.as_slice()).as_slice() == hash {
+                return Ok(Some(KeyWithPublic {
+                    key,
+                    public: uncompressed.into_vec(),
+                }));
+            }
+        }
+
+        match self.mode {
+            SigningMode::Normal => Err(SigningError::new(
+                SigningErrorType::Error_missing_private_key,
+                "Missing private key",
+            )),
+            SigningMode::SizeEstimation => Ok(None),
+        }
+    }
+
+    fn script_for_script_hash(&self, hash: &[u8]) -> SigningResult<Vec<u8>> {
+        self.input
+            .scripts
+            .get(&hex::encode(hash, false))
+            .map(|script| script.as_bytes().to_vec())
+            .ok_or_else(|| {
+                SigningError::new(
+                    SigningErrorType::Error_script_redeem,
+                    "Missing redeem script",
+                )
+            })
+    }
+}
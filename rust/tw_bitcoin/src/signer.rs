@@ -0,0 +1,56 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::planner::{self, TransactionPlan};
+use crate::signature_builder::{sign_transaction, SigningMode};
+use crate::signing_input::SigningInput;
+use crate::SigningResult;
+use std::borrow::Cow;
+use tw_encoding::hex;
+use tw_proto::Bitcoin::Proto;
+
+pub struct Signer;
+
+impl Signer {
+    /// Plans a transaction: selects UTXOs and estimates the fee.
+    /// The error is reported through the `error` plan field.
+    pub fn plan_proto(input: Proto::SigningInput<'_>) -> Proto::TransactionPlan<'static> {
+        match SigningInput::from_proto(&input) {
+            Ok(input) => planner::plan(&input).to_proto(),
+            Err(e) => TransactionPlan {
+                error: e.error,
+                ..TransactionPlan::default()
+            }
+            .to_proto(),
+        }
+    }
+
+    /// Builds and signs a transaction following the `plan` of the input, or a new one if it's not set.
+    /// The error is reported through the `error` and `error_message` output fields.
+    pub fn sign_proto(input: Proto::SigningInput<'_>) -> Proto::SigningOutput<'static> {
+        Self::sign_impl(&input).unwrap_or_else(|e| Proto::SigningOutput {
+            error: e.error,
+            error_message: Cow::Owned(e.message),
+            ..Proto::SigningOutput::default()
+        })
+    }
+
+    fn sign_impl(input: &Proto::SigningInput<'_>) -> SigningResult<Proto::SigningOutput<'static>> {
+        let input = SigningInput::from_proto(input)?;
+        let plan = match input.plan {
+            Some(ref plan) => plan.clone(),
+            None => planner::plan(&input),
+        };
+
+        let tx = sign_transaction(&input, &plan, SigningMode::Normal)?;
+        Ok(Proto::SigningOutput {
+            transaction: Some(tx.to_proto()),
+            encoded: Cow::Owned(tx.encode()),
+            transaction_id: Cow::Owned(hex::encode(tx.id(), false)),
+            ..Proto::SigningOutput::default()
+        })
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::address::CoinParams;
+use crate::planner::TransactionPlan;
+use crate::script::Script;
+use crate::sighash::SighashType;
+use crate::transaction::OutPoint;
+use crate::{Amount, SigningError, SigningErrorType, SigningResult};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use tw_hash::H256;
+use tw_keypair::secp256k1::PrivateKey;
+use tw_proto::Bitcoin::Proto;
+
+/// An unspent transaction output to be spent by an input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Utxo {
+    pub out_point: OutPoint,
+    /// The sequence of the spending input.
+    pub sequence: u32,
+    /// The locking script of the output.
+    pub script: Script,
+    pub amount: Amount,
+}
+
+impl Utxo {
+    pub fn from_proto(utxo: &Proto::UnspentTransaction<'_>) -> SigningResult<Utxo> {
+        let out_point = utxo.out_point.as_ref().ok_or_else(|| {
+            SigningError::new(
+                SigningErrorType::Error_invalid_utxo,
+                "Missing UTXO out point",
+            )
+        })?;
+        let hash = H256::try_from(out_point.hash.as_ref()).map_err(|_| {
+            SigningError::new(
+                SigningErrorType::Error_invalid_utxo,
+                "UTXO transaction hash must be 32 bytes",
+            )
+        })?;
+        if utxo.amount < 0 {
+            return Err(SigningError::new(
+                SigningErrorType::Error_invalid_utxo_amount,
+                format!("Invalid UTXO amount: {}", utxo.amount),
+            ));
+        }
+
+        Ok(Utxo {
+            out_point: OutPoint {
+                hash,
+                index: out_point.index,
+            },
+            sequence: out_point.sequence,
+            script: Script::new(utxo.script.to_vec()),
+            amount: utxo.amount,
+        })
+    }
+
+    pub fn to_proto(&self) -> Proto::UnspentTransaction<'static> {
+        Proto::UnspentTransaction {
+            out_point: Some(Proto::OutPoint {
+                hash: Cow::Owned(self.out_point.hash.as_slice().to_vec()),
+                index: self.out_point.index,
+                sequence: self.sequence,
+            }),
+            script: Cow::Owned(self.script.as_bytes().to_vec()),
+            amount: self.amount,
+        }
+    }
+}
+
+/// A parsed `Bitcoin::Proto::SigningInput`.
+pub struct SigningInput {
+    pub coin: &'static CoinParams,
+    pub sighash_type: SighashType,
+    pub amount: Amount,
+    /// The fee per virtual byte.
+    pub byte_fee: Amount,
+    pub to_address: String,
+    pub change_address: String,
+    pub private_keys: Vec<PrivateKey>,
+    /// Redeem and witness scripts by the hex of their HASH160.
+    pub scripts: HashMap<String, Script>,
+    pub utxos: Vec<Utxo>,
+    pub use_max_amount: bool,
+    /// A plan to follow instead of selecting UTXOs and estimating the fee.
+    pub plan: Option<TransactionPlan>,
+    pub lock_time: u32,
+    pub output_op_return: Vec<u8>,
+}
+
+impl SigningInput {
+    pub fn from_proto(input: &Proto::SigningInput<'_>) -> SigningResult<SigningInput> {
+        let private_keys = input
+            .private_key
+            .iter()
+            .map(|key| {
+                PrivateKey::try_from(key.as_ref()).map_err(|_| {
+                    SigningError::new(
+                        SigningErrorType::Error_invalid_private_key,
+                        "Invalid private key",
+                    )
+                })
+            })
+            .collect::<SigningResult<Vec<_>>>()?;
+        let scripts = input
+            .scripts
+            .iter()
+            .map(|(hash, script)| (hash.to_string(), Script::new(script.to_vec())))
+            .collect();
+        let utxos = input
+            .utxo
+            .iter()
+            .map(Utxo::from_proto)
+            .collect::<SigningResult<Vec<_>>>()?;
+        let plan = input
+            .plan
+            .as_ref()
+            .map(TransactionPlan::from_proto)
+            .transpose()?;
+
+        Ok(SigningInput {
+            coin: CoinParams::for_coin(input.coin_type)?,
+            sighash_type: SighashType::new(input.hash_type),
+            amount: input.amount,
+            byte_fee: input.byte_fee,
+            to_address: input.to_address.to_string(),
+            change_address: input.change_address.to_string(),
+            private_keys,
+            scripts,
+            utxos,
+            use_max_amount: input.use_max_amount,
+            plan,
+            lock_time: input.lock_time,
+            output_op_return: input.output_op_return.to_vec(),
+        })
+    }
+}
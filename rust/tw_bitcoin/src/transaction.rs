@@ -0,0 +1,330 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::reader::Reader;
+use crate::script::Script;
+use crate::{Amount, SigningError, SigningErrorType, SigningResult};
+use std::borrow::Cow;
+use tw_hash::sha2::sha256d;
+use tw_hash::H256;
+use tw_proto::Bitcoin::Proto;
+
+/// cbindgen:ignore
+pub const TRANSACTION_VERSION: i32 = 1;
+/// The marker and flag bytes that precede inputs of a transaction with witnesses.
+/// cbindgen:ignore
+const SEGWIT_MARKER_FLAG: [u8; 2] = [0x00, 0x01];
+
+/// Appends the `value` as a Bitcoin variable length integer (CompactSize).
+pub(crate) fn encode_var_int(value: u64, out: &mut Vec<u8>) {
+    match value {
+        0..=0xfc => out.push(value as u8),
+        0xfd..=0xffff => {
+            out.push(0xfd);
+            out.extend_from_slice(&(value as u16).to_le_bytes());
+        },
+        0x10000..=0xffff_ffff => {
+            out.push(0xfe);
+            out.extend_from_slice(&(value as u32).to_le_bytes());
+        },
+        _ => {
+            out.push(0xff);
+            out.extend_from_slice(&value.to_le_bytes());
+        },
+    }
+}
+
+/// A reference to an output of a previous transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutPoint {
+    /// The previous transaction hash in the internal (not reversed) byte order.
+    pub hash: H256,
+    pub index: u32,
+}
+
+impl OutPoint {
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.hash.as_slice());
+        out.extend_from_slice(&self.index.to_le_bytes());
+    }
+
+    pub(crate) fn decode(reader: &mut Reader<'_>) -> SigningResult<OutPoint> {
+        Ok(OutPoint {
+            hash: H256::from(reader.read_array::<32>()?),
+            index: reader.read_u32()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionInput {
+    pub previous_output: OutPoint,
+    pub script_sig: Script,
+    pub sequence: u32,
+    /// The witness stack items, empty for non-segwit inputs.
+    pub witness: Vec<Vec<u8>>,
+}
+
+impl TransactionInput {
+    /// Appends the input without the witness.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        self.previous_output.encode(out);
+        self.script_sig.encode(out);
+        out.extend_from_slice(&self.sequence.to_le_bytes());
+    }
+
+    /// Appends the number of witness items followed by the length-prefixed items.
+    pub fn encode_witness(&self, out: &mut Vec<u8>) {
+        encode_var_int(self.witness.len() as u64, out);
+        for item in self.witness.iter() {
+            encode_var_int(item.len() as u64, out);
+            out.extend_from_slice(item);
+        }
+    }
+
+    /// Reads the input without the witness.
+    pub(crate) fn decode(reader: &mut Reader<'_>) -> SigningResult<TransactionInput> {
+        Ok(TransactionInput {
+            previous_output: OutPoint::decode(reader)?,
+            script_sig: Script::new(reader.read_var_bytes()?.to_vec()),
+            sequence: reader.read_u32()?,
+            witness: Vec::new(),
+        })
+    }
+
+    /// Reads the witness stack items of the input.
+    pub(crate) fn decode_witness(&mut self, reader: &mut Reader<'_>) -> SigningResult<()> {
+        let items = reader.read_len()?;
+        self.witness = (0..items)
+            .map(|_| reader.read_var_bytes().map(<[u8]>::to_vec))
+            .collect::<SigningResult<_>>()?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionOutput {
+    pub value: Amount,
+    pub script_pubkey: Script,
+}
+
+impl TransactionOutput {
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.value.to_le_bytes());
+        self.script_pubkey.encode(out);
+    }
+
+    pub(crate) fn decode(reader: &mut Reader<'_>) -> SigningResult<TransactionOutput> {
+        Ok(TransactionOutput {
+            value: reader.read_i64()?,
+            script_pubkey: Script::new(reader.read_var_bytes()?.to_vec()),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transaction {
+    pub version: i32,
+    pub lock_time: u32,
+    pub inputs: Vec<TransactionInput>,
+    pub outputs: Vec<TransactionOutput>,
+}
+
+impl Transaction {
+    /// Checks if any of the inputs has a witness.
+    pub fn has_witness(&self) -> bool {
+        self.inputs.iter().any(|input| !input.witness.is_empty())
+    }
+
+    /// Returns the transaction encoded for broadcasting.
+    /// The BIP144 format is used only if there are witnesses.
+    pub fn encode(&self) -> Vec<u8> {
+        self.encode_impl(self.has_witness())
+    }
+
+    /// Returns the transaction encoded in the legacy format, i.e. without witnesses.
+    pub fn encode_without_witness(&self) -> Vec<u8> {
+        self.encode_impl(false)
+    }
+
+    /// Decodes a transaction in the legacy or BIP144 format.
+    /// Fails with `Error_input_parse` if the data is invalid or there are extra bytes.
+    pub fn decode(bytes: &[u8]) -> SigningResult<Transaction> {
+        let mut reader = Reader::new(bytes);
+        let version = reader.read_i32()?;
+
+        let with_witness = reader.remaining().starts_with(&SEGWIT_MARKER_FLAG);
+        if with_witness {
+            reader.read_bytes(SEGWIT_MARKER_FLAG.len())?;
+        }
+
+        let inputs_len = reader.read_len()?;
+        let mut inputs = (0..inputs_len)
+            .map(|_| TransactionInput::decode(&mut reader))
+            .collect::<SigningResult<Vec<_>>>()?;
+        let outputs_len = reader.read_len()?;
+        let outputs = (0..outputs_len)
+            .map(|_| TransactionOutput::decode(&mut reader))
+            .collect::<SigningResult<Vec<_>>>()?;
+
+        if with_witness {
+            for input in inputs.iter_mut() {
+                input.decode_witness(&mut reader)?;
+            }
+            if !inputs.iter().any(|input| !input.witness.is_empty()) {
+                return Err(SigningError::new(
+                    SigningErrorType::Error_input_parse,
+                    "Transaction has the witness flag but no witnesses",
+                ));
+            }
+        }
+
+        let lock_time = reader.read_u32()?;
+        if !reader.is_empty() {
+            return Err(SigningError::new(
+                SigningErrorType::Error_input_parse,
+                "Unexpected data after the transaction",
+            ));
+        }
+        Ok(Transaction {
+            version,
+            lock_time,
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Appends the witnesses of all inputs.
+    pub fn encode_witness(&self, out: &mut Vec<u8>) {
+        for input in self.inputs.iter() {
+            input.encode_witness(out);
+        }
+    }
+
+    /// Returns the transaction hash in the internal byte order as it's referenced by [`OutPoint::hash`].
+    pub fn hash(&self) -> H256 {
+        H256::try_from(sha256d(&self.encode_without_witness()).as_slice())
+            .expect("sha256d hash must be 32 bytes")
+    }
+
+    /// Returns the transaction id in the displayed (reversed) byte order.
+    pub fn id(&self) -> H256 {
+        let mut hash = self.hash();
+        hash.reverse();
+        hash
+    }
+
+    /// Returns the virtual size used for the fee estimation.
+    /// The witness data, including the marker and flag bytes, is discounted by 4.
+    pub fn virtual_size(&self) -> usize {
+        let size = self.encode_without_witness().len();
+        if !self.has_witness() {
+            return size;
+        }
+        let mut witness = Vec::new();
+        self.encode_witness(&mut witness);
+        size + (SEGWIT_MARKER_FLAG.len() + witness.len()).div_ceil(4)
+    }
+
+    pub fn to_proto(&self) -> Proto::Transaction<'static> {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| Proto::TransactionInput {
+                previousOutput: Some(Proto::OutPoint {
+                    hash: Cow::Owned(input.previous_output.hash.as_slice().to_vec()),
+                    index: input.previous_output.index,
+                    ..Proto::OutPoint::default()
+                }),
+                sequence: input.sequence,
+                script: Cow::Owned(input.script_sig.as_bytes().to_vec()),
+            })
+            .collect();
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| Proto::TransactionOutput {
+                value: output.value,
+                script: Cow::Owned(output.script_pubkey.as_bytes().to_vec()),
+            })
+            .collect();
+        Proto::Transaction {
+            version: self.version,
+            lockTime: self.lock_time,
+            inputs,
+            outputs,
+        }
+    }
+
+    fn encode_impl(&self, with_witness: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&self.version.to_le_bytes());
+        if with_witness {
+            out.extend_from_slice(&SEGWIT_MARKER_FLAG);
+        }
+
+        encode_var_int(self.inputs.len() as u64, &mut out);
+        for input in self.inputs.iter() {
+            input.encode(&mut out);
+        }
+        encode_var_int(self.outputs.len() as u64, &mut out);
+        for output in self.outputs.iter() {
+            output.encode(&mut out);
+        }
+
+        if with_witness {
+            self.encode_witness(&mut out);
+        }
+        out.extend_from_slice(&self.lock_time.to_le_bytes());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    #[test]
+    fn test_encode_var_int() {
+        let encode = |value| {
+            let mut out = Vec::new();
+            encode_var_int(value, &mut out);
+            hex::encode(out, false)
+        };
+        assert_eq!(encode(0), "00");
+        assert_eq!(encode(0xfc), "fc");
+        assert_eq!(encode(0xfd), "fdfd00");
+        assert_eq!(encode(0xffff), "fdffff");
+        assert_eq!(encode(0x10000), "fe00000100");
+        assert_eq!(encode(0x1_0000_0000), "ff0000000001000000");
+    }
+
+    #[test]
+    fn test_decode_transaction() {
+        // The signed transaction of the BIP143 native P2WPKH example.
+        let encoded = "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000";
+        let encoded = hex::decode(encoded).unwrap();
+        let tx = Transaction::decode(&encoded).unwrap();
+        assert_eq!(tx.version, 1);
+        assert_eq!(tx.lock_time, 0x11);
+        assert_eq!(tx.inputs.len(), 2);
+        assert_eq!(tx.inputs[0].sequence, 0xffff_ffee);
+        assert!(tx.inputs[0].witness.is_empty());
+        assert_eq!(tx.inputs[1].witness.len(), 2);
+        assert_eq!(tx.outputs[1].value, 223_450_000);
+        assert_eq!(tx.encode(), encoded);
+
+        // The legacy format.
+        let legacy = tx.encode_without_witness();
+        assert_eq!(Transaction::decode(&legacy).unwrap().encode(), legacy);
+
+        let mut extra = encoded.clone();
+        extra.push(0);
+        assert!(Transaction::decode(&extra).is_err());
+        assert!(Transaction::decode(&encoded[..encoded.len() - 1]).is_err());
+    }
+}
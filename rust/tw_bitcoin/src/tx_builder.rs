@@ -0,0 +1,61 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::planner::TransactionPlan;
+use crate::script::Script;
+use crate::signing_input::SigningInput;
+use crate::transaction::{Transaction, TransactionInput, TransactionOutput, TRANSACTION_VERSION};
+use crate::{SigningError, SigningErrorType, SigningResult};
+
+/// Builds an unsigned transaction spending the UTXOs of the `plan`.
+/// The outputs are: the recipient one, the change one if there is a change,
+/// and an `OP_RETURN` one if there is data to embed.
+pub(crate) fn build_transaction(
+    input: &SigningInput,
+    plan: &TransactionPlan,
+) -> SigningResult<Transaction> {
+    let mut outputs = vec![TransactionOutput {
+        value: plan.amount,
+        script_pubkey: input.coin.lock_script_for_address(&input.to_address)?,
+    }];
+    if plan.change > 0 {
+        outputs.push(TransactionOutput {
+            value: plan.change,
+            script_pubkey: input.coin.lock_script_for_address(&input.change_address)?,
+        });
+    }
+
+    let inputs = plan
+        .utxos
+        .iter()
+        .map(|utxo| TransactionInput {
+            previous_output: utxo.out_point.clone(),
+            script_sig: Script::default(),
+            sequence: utxo.sequence,
+            witness: Vec::new(),
+        })
+        .collect();
+
+    if !plan.output_op_return.is_empty() {
+        let script_pubkey = Script::op_return(&plan.output_op_return).ok_or_else(|| {
+            SigningError::new(
+                SigningErrorType::Error_invalid_memo,
+                "OP_RETURN data is too long",
+            )
+        })?;
+        outputs.push(TransactionOutput {
+            value: 0,
+            script_pubkey,
+        });
+    }
+
+    Ok(Transaction {
+        version: TRANSACTION_VERSION,
+        lock_time: input.lock_time,
+        inputs,
+        outputs,
+    })
+}
@@ -0,0 +1,90 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::borrow::Cow;
+use tw_bitcoin::ffi::{bitcoin_plan, bitcoin_sign};
+use tw_encoding::hex;
+use tw_proto::Bitcoin::Proto;
+use tw_proto::Common::Proto::SigningError;
+
+fn bitcoin_sign_impl(input: &[u8]) -> Vec<u8> {
+    unsafe { bitcoin_sign(input.as_ptr(), input.len()).into_vec() }
+}
+
+fn bitcoin_plan_impl(input: &[u8]) -> Vec<u8> {
+    unsafe { bitcoin_plan(input.as_ptr(), input.len()).into_vec() }
+}
+
+/// The `SignP2PKH` test from the C++ implementation.
+fn p2pkh_input() -> Vec<u8> {
+    let input = Proto::SigningInput {
+        hash_type: 1,
+        amount: 335_790_000,
+        byte_fee: 1,
+        to_address: Cow::Borrowed("1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx"),
+        change_address: Cow::Borrowed("1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU"),
+        private_key: vec![Cow::Owned(
+            hex::decode("bbc27228ddcb9209d7fd6f36b02f7dfa6252af40bb2f1cbc7a557da8027ff866")
+                .unwrap(),
+        )],
+        utxo: vec![Proto::UnspentTransaction {
+            out_point: Some(Proto::OutPoint {
+                hash: Cow::Owned(
+                    hex::decode("fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f")
+                        .unwrap(),
+                ),
+                index: 0,
+                sequence: u32::MAX,
+            }),
+            script: Cow::Owned(
+                hex::decode("76a914b7cd046b6d522a3d61dbcb5235c0e9cc9726545788ac").unwrap(),
+            ),
+            amount: 625_000_000,
+        }],
+        ..Proto::SigningInput::default()
+    };
+    tw_proto::serialize(&input).unwrap()
+}
+
+#[test]
+fn test_bitcoin_sign() {
+    let output = bitcoin_sign_impl(&p2pkh_input());
+    let output: Proto::SigningOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "0100000001fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f000000006a47304402202819d70d4bec472113a1392cadc0860a7a1b34ea0869abb4bdce3290c3aba086022023eff75f410ad19cdbe6c6a017362bd554ce5fb906c13534ddc306be117ad30a012103c9f4836b9a4f77fc0d81f7bcb01b7f1b35916864b9476c241ce9fc198bd25432ffffffff02b0bf0314000000001976a914769bdff96a02f9135a1d19b749db6a78fe07dc9088acaefd3c11000000001976a9149e089b6889e032d46e3b915a3392edfd616fb1c488ac00000000"
+    );
+}
+
+#[test]
+fn test_bitcoin_plan() {
+    let plan = bitcoin_plan_impl(&p2pkh_input());
+    let plan: Proto::TransactionPlan = tw_proto::deserialize(&plan).unwrap();
+    assert_eq!(plan.error, SigningError::OK);
+    assert_eq!(plan.amount, 335_790_000);
+    assert_eq!(plan.available_amount, 625_000_000);
+    assert_eq!(plan.fee, 226);
+    assert_eq!(plan.change, 625_000_000 - 335_790_000 - 226);
+    assert_eq!(plan.utxos.len(), 1);
+}
+
+#[test]
+fn test_bitcoin_sign_invalid_input() {
+    let output = bitcoin_sign_impl(&[0xff, 0xff]);
+    let output: Proto::SigningOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::Error_input_parse);
+    assert!(output.encoded.is_empty());
+
+    let plan = bitcoin_plan_impl(&[0xff, 0xff]);
+    let plan: Proto::TransactionPlan = tw_proto::deserialize(&plan).unwrap();
+    assert_eq!(plan.error, SigningError::Error_input_parse);
+
+    // An empty input is deserialized as a default `SigningInput` without an amount.
+    let plan = unsafe { bitcoin_plan(std::ptr::null(), 0).into_vec() };
+    let plan: Proto::TransactionPlan = tw_proto::deserialize(&plan).unwrap();
+    assert_eq!(plan.error, SigningError::Error_zero_amount_requested);
+}
@@ -0,0 +1,1004 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::borrow::Cow;
+use tw_bitcoin::sighash::{
+    SIGHASH_ALL, SIGHASH_ANYONE_CAN_PAY, SIGHASH_DEFAULT, SIGHASH_FORK_ID, SIGHASH_NONE,
+    SIGHASH_SINGLE,
+};
+use tw_bitcoin::signer::Signer;
+use tw_bitcoin::SigningErrorType;
+use tw_encoding::hex;
+use tw_proto::Bitcoin::Proto;
+
+const HASH0: &str = "fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f";
+const HASH1: &str = "ef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a";
+const KEY0: &str = "bbc27228ddcb9209d7fd6f36b02f7dfa6252af40bb2f1cbc7a557da8027ff866";
+const KEY1: &str = "619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9";
+const P2SH_P2WPKH_KEY: &str = "eb696a065ef48a2192da5b28b694f87544b30fae8327c4510137a922f32c6dcf";
+const TO_ADDRESS: &str = "1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx";
+const CHANGE_ADDRESS: &str = "1FQc5LdgGHMHEN9nwkjmz6tWkxhPpxBvBU";
+const BITCOIN: u32 = 0;
+const LITECOIN: u32 = 2;
+
+fn bytes(hex: &str) -> Cow<'static, [u8]> {
+    Cow::Owned(hex::decode(hex).unwrap())
+}
+
+/// Transaction hashes are displayed in the reversed byte order.
+fn reversed(hex: &str) -> Cow<'static, [u8]> {
+    let mut hash = hex::decode(hex).unwrap();
+    hash.reverse();
+    Cow::Owned(hash)
+}
+
+fn utxo(
+    hash: Cow<'static, [u8]>,
+    index: u32,
+    sequence: u32,
+    script: &str,
+    amount: i64,
+) -> Proto::UnspentTransaction<'static> {
+    Proto::UnspentTransaction {
+        out_point: Some(Proto::OutPoint {
+            hash,
+            index,
+            sequence,
+        }),
+        script: bytes(script),
+        amount,
+    }
+}
+
+fn sign(input: Proto::SigningInput<'_>) -> String {
+    let output = Signer::sign_proto(input);
+    assert_eq!(
+        output.error,
+        SigningErrorType::OK,
+        "{}",
+        output.error_message
+    );
+    hex::encode(output.encoded, false)
+}
+
+fn sign_error(input: Proto::SigningInput<'_>) -> SigningErrorType {
+    let output = Signer::sign_proto(input);
+    assert!(output.encoded.is_empty());
+    output.error
+}
+
+fn assert_plan(input: &Proto::SigningInput<'_>, utxo_amounts: &[i64], amount: i64, fee: i64) {
+    let plan = Signer::plan_proto(input.clone());
+    assert_eq!(plan.error, SigningErrorType::OK);
+    let actual_amounts: Vec<_> = plan.utxos.iter().map(|utxo| utxo.amount).collect();
+    assert_eq!(actual_amounts, utxo_amounts);
+    assert_eq!(plan.amount, amount);
+    assert_eq!(plan.fee, fee);
+    assert_eq!(plan.available_amount, plan.amount + plan.change + plan.fee);
+}
+
+fn p2pkh_input(omit_key: bool) -> Proto::SigningInput<'static> {
+    let private_key = if omit_key {
+        Vec::new()
+    } else {
+        vec![bytes(KEY0), bytes(KEY1)]
+    };
+    Proto::SigningInput {
+        hash_type: SIGHASH_ALL,
+        amount: 335_790_000,
+        byte_fee: 1,
+        to_address: Cow::Borrowed(TO_ADDRESS),
+        change_address: Cow::Borrowed(CHANGE_ADDRESS),
+        coin_type: BITCOIN,
+        private_key,
+        utxo: vec![
+            utxo(
+                bytes(HASH0),
+                0,
+                u32::MAX,
+                "76a914b7cd046b6d522a3d61dbcb5235c0e9cc9726545788ac",
+                625_000_000,
+            ),
+            utxo(
+                bytes(HASH1),
+                1,
+                u32::MAX,
+                "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1",
+                600_000_000,
+            ),
+        ],
+        ..Proto::SigningInput::default()
+    }
+}
+
+#[test]
+fn test_sign_p2pkh() {
+    let input = p2pkh_input(false);
+    assert_plan(&input, &[625_000_000], 335_790_000, 226);
+
+    let output = Signer::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert_eq!(
+        hex::encode(&output.encoded, false),
+        "01000000\
+        01\
+        fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f000000006a47304402202819d70d4bec472113a1392cadc0860a7a1b34ea0869abb4bdce3290c3aba086022023eff75f410ad19cdbe6c6a017362bd554ce5fb906c13534ddc306be117ad30a012103c9f4836b9a4f77fc0d81f7bcb01b7f1b35916864b9476c241ce9fc198bd25432ffffffff\
+        02\
+        b0bf0314000000001976a914769bdff96a02f9135a1d19b749db6a78fe07dc9088ac\
+        aefd3c11000000001976a9149e089b6889e032d46e3b915a3392edfd616fb1c488ac\
+        00000000"
+    );
+
+    let tx = output.transaction.unwrap();
+    assert_eq!(tx.version, 1);
+    assert_eq!(tx.inputs.len(), 1);
+    assert_eq!(tx.outputs.len(), 2);
+    assert_eq!(tx.outputs[0].value, 335_790_000);
+    // The transaction ID is displayed in the reversed byte order.
+    let mut id = tw_hash::sha2::sha256d(&output.encoded);
+    id.reverse();
+    assert_eq!(output.transaction_id, hex::encode(id, false));
+}
+
+#[test]
+fn test_sign_p2pkh_missing_key() {
+    let input = p2pkh_input(true);
+    // The plan doesn't require the keys.
+    assert_plan(&input, &[625_000_000], 335_790_000, 226);
+    assert_eq!(
+        sign_error(input),
+        SigningErrorType::Error_missing_private_key
+    );
+}
+
+/// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#native-p2wpkh
+#[test]
+fn test_sign_p2wpkh_bip143() {
+    let utxos = vec![
+        utxo(
+            bytes(HASH0),
+            0,
+            0xffff_ffee,
+            "2103c9f4836b9a4f77fc0d81f7bcb01b7f1b35916864b9476c241ce9fc198bd25432ac",
+            1_000_000,
+        ),
+        utxo(
+            bytes(HASH1),
+            1,
+            u32::MAX,
+            "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1",
+            600_000_000,
+        ),
+    ];
+    // Force both UTXOs and the exact output amounts.
+    let plan = Proto::TransactionPlan {
+        amount: 112_340_000,
+        available_amount: 601_000_000,
+        fee: 265_210_000,
+        change: 223_450_000,
+        utxos: utxos.clone(),
+        branch_id: Cow::Owned(vec![0]),
+        ..Proto::TransactionPlan::default()
+    };
+    let input = Proto::SigningInput {
+        hash_type: SIGHASH_ALL,
+        amount: 112_340_000,
+        byte_fee: 20,
+        to_address: "1Cu32FVupVCgHkMMRJdYJugxwo2Aprgk7H".into(),
+        change_address: "16TZ8J6Q5iZKBWizWzFAYnrsaox5Z5aBRV".into(),
+        private_key: vec![bytes(KEY0), bytes(KEY1)],
+        utxo: utxos,
+        plan: Some(plan),
+        lock_time: 0x11,
+        ..Proto::SigningInput::default()
+    };
+
+    assert_eq!(
+        sign(input),
+        "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000"
+    );
+}
+
+fn p2wpkh_input(
+    amount: i64,
+    hash_type: u32,
+    utxo0_amount: i64,
+    utxo1_amount: i64,
+    use_max_amount: bool,
+) -> Proto::SigningInput<'static> {
+    let mut scripts = std::collections::HashMap::new();
+    scripts.insert(
+        "1d0f172a0ecb48aee1be1f2687d2963ae33f71a1".into(),
+        bytes("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac"),
+    );
+    Proto::SigningInput {
+        hash_type,
+        amount,
+        use_max_amount,
+        byte_fee: 1,
+        to_address: Cow::Borrowed(TO_ADDRESS),
+        change_address: Cow::Borrowed(CHANGE_ADDRESS),
+        coin_type: BITCOIN,
+        private_key: vec![bytes(KEY0), bytes(KEY1)],
+        scripts,
+        utxo: vec![
+            utxo(
+                bytes(HASH0),
+                0,
+                u32::MAX,
+                "2103c9f4836b9a4f77fc0d81f7bcb01b7f1b35916864b9476c241ce9fc198bd25432ac",
+                utxo0_amount,
+            ),
+            utxo(
+                bytes(HASH1),
+                1,
+                u32::MAX,
+                "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1",
+                utxo1_amount,
+            ),
+        ],
+        ..Proto::SigningInput::default()
+    }
+}
+
+#[test]
+fn test_sign_p2wpkh() {
+    let input = p2wpkh_input(335_790_000, SIGHASH_ALL, 625_000_000, 600_000_000, false);
+    assert_plan(&input, &[625_000_000], 335_790_000, 192);
+
+    // Only the P2PK UTXO is spent, so the transaction is not segwit.
+    assert_eq!(
+        sign(input),
+        "01000000\
+        01\
+        fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000049483045022100c327babdd370f0fc5b24cf920736446bf7d9c5660e4a5f7df432386fd652fe280220269c4fc3690c1c248e50c8bf2435c20b4ef00f308b403575f4437f862a91c53a01ffffffff\
+        02\
+        b0bf0314000000001976a914769bdff96a02f9135a1d19b749db6a78fe07dc9088ac\
+        d0fd3c11000000001976a9149e089b6889e032d46e3b915a3392edfd616fb1c488ac\
+        00000000"
+    );
+}
+
+#[test]
+fn test_sign_p2wpkh_hash_single_two_inputs() {
+    let input = p2wpkh_input(335_790_000, SIGHASH_SINGLE, 210_000_000, 210_000_000, false);
+    assert_plan(&input, &[210_000_000, 210_000_000], 335_790_000, 261);
+
+    assert_eq!(
+        sign(input),
+        "01000000\
+        0001\
+        02\
+        fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000049483045022100fd8591c3611a07b55f509ec850534c7a9c49713c9b8fa0e844ea06c2e65e19d702205e3806676192e790bc93dd4c28e937c4bf97b15f189158ba1a30d7ecff5ee75503ffffffff\
+        ef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff\
+        02\
+        b0bf0314000000001976a914769bdff96a02f9135a1d19b749db6a78fe07dc9088ac\
+        4bf00405000000001976a9149e089b6889e032d46e3b915a3392edfd616fb1c488ac\
+        00\
+        024730440220096d20c7e92f991c2bf38dc28118feb34019ae74ec1c17179b28cb041de7517402204594f46a911f24bdc7109ca192e6860ebf2f3a0087579b3c128d5ce0cd5ed4680321025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee6357\
+        00000000"
+    );
+}
+
+#[test]
+fn test_sign_p2wpkh_anyone_can_pay_two_inputs() {
+    let input = p2wpkh_input(
+        335_790_000,
+        SIGHASH_ANYONE_CAN_PAY,
+        210_000_000,
+        210_000_000,
+        false,
+    );
+    assert_plan(&input, &[210_000_000, 210_000_000], 335_790_000, 261);
+
+    assert_eq!(
+        sign(input),
+        "01000000\
+        0001\
+        02\
+        fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000049483045022100e21fb2f1cfd59bdb3703fd45db38fd680d0c06e5d0be86fb7dc233c07ee7ab2f02207367220a73e43df4352a6831f6f31d8dc172c83c9f613a9caf679f0f15621c5e80ffffffff\
+        ef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff\
+        02\
+        b0bf0314000000001976a914769bdff96a02f9135a1d19b749db6a78fe07dc9088ac\
+        4bf00405000000001976a9149e089b6889e032d46e3b915a3392edfd616fb1c488ac\
+        00\
+        0248304502210095f9cc913d2f0892b953f2380112533e8930b67c53e00a7bbd7a01d547156adc022026efe3a684aa7432a00a919dbf81b63e635fb92d3149453e95b4a7ccea59f7c48021025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee6357\
+        00000000"
+    );
+}
+
+#[test]
+fn test_sign_p2wpkh_max_amount() {
+    let input = p2wpkh_input(1_000, SIGHASH_ALL, 625_000_000, 600_000_000, true);
+    assert_plan(&input, &[625_000_000, 600_000_000], 1_224_999_773, 227);
+
+    assert_eq!(
+        sign(input),
+        "01000000\
+        0001\
+        02\
+        fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000049483045022100a8b3c1619e985923994e80efdc0be0eac12f2419e11ce5e4286a0a5ac27c775d02205d6feee85ffe19ae0835cba1562beb3beb172107cd02ac4caf24a8be3749811f01ffffffff\
+        ef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff\
+        01\
+        5d030449000000001976a914769bdff96a02f9135a1d19b749db6a78fe07dc9088ac\
+        00\
+        02483045022100db1199de92f6fb638a0ba706d13ec686bb01138a254dec2c397616cd74bad30e02200d7286d6d2d4e00d145955bf3d3b848b03c0d1eef8899e4645687a3035d7def40121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee6357\
+        00000000"
+    );
+}
+
+const P2WSH_REDEEM_SCRIPT: &str =
+    "2103596d3451025c19dbbdeb932d6bf8bfb4ad499b95b6f88db8899efac102e5fc71ac";
+
+fn p2wsh_input(hash_type: u32, omit_script: bool, omit_keys: bool) -> Proto::SigningInput<'static> {
+    let private_key = if omit_keys {
+        Vec::new()
+    } else {
+        vec![
+            bytes("ed00a0841cd53aedf89b0c616742d1d2a930f8ae2b0fb514765a17bb62c7521a"),
+            bytes(KEY1),
+        ]
+    };
+    let mut scripts = std::collections::HashMap::new();
+    if !omit_script {
+        scripts.insert(
+            "593128f9f90e38b706c18623151e37d2da05c229".into(),
+            bytes(P2WSH_REDEEM_SCRIPT),
+        );
+    }
+    Proto::SigningInput {
+        hash_type,
+        amount: 1_000,
+        byte_fee: 1,
+        to_address: Cow::Borrowed(TO_ADDRESS),
+        change_address: Cow::Borrowed(CHANGE_ADDRESS),
+        private_key,
+        scripts,
+        utxo: vec![utxo(
+            bytes("0001000000000000000000000000000000000000000000000000000000000000"),
+            0,
+            u32::MAX,
+            "0020ff25429251b5a84f452230a3c75fd886b7fc5a7865ce4a7bb7a9d7c5be6da3db",
+            1_226,
+        )],
+        ..Proto::SigningInput::default()
+    }
+}
+
+#[test]
+fn test_sign_p2wsh() {
+    let test_cases = [
+        (
+            SIGHASH_ALL,
+            "4830450221009eefc1befe96158f82b74e6804f1f713768c6172636ca11fcc975c316ea86f75022057914c48bc24f717498b851a47a2926f96242e3943ebdf08d5a97a499efc8b9001",
+        ),
+        (
+            SIGHASH_NONE,
+            "483045022100caa585732cfc50226a90834a306d23d5d2ab1e94af2c66136a637e3d9bad3688022069028750908e53a663bb1f434fd655bcc0cf8d394c6fa1fd5a4983790135722e02",
+        ),
+        (
+            SIGHASH_SINGLE,
+            "47304402201ba80b2c48fe82915297dc9782ae2141e40263001fafd21b02c04a092503f01e0220666d6c63475c6c52abd09371c200ac319bcf4a7c72eb3782e95790f5c847f0b903",
+        ),
+        (
+            SIGHASH_ANYONE_CAN_PAY,
+            "483045022100d14699fc9b7337768bcd1430098d279cfaf05f6abfa75dd542da2dc038ae1700022063f0751c08796c086ac23b39c25f4320f432092e0c11bec46af0723cc4f55a3980",
+        ),
+    ];
+
+    for (hash_type, signature) in test_cases {
+        let input = p2wsh_input(hash_type, false, false);
+        assert_plan(&input, &[1_226], 1_000, 147);
+
+        let expected = format!(
+            "01000000\
+            0001\
+            01\
+            00010000000000000000000000000000000000000000000000000000000000000000000000ffffffff\
+            02\
+            e8030000000000001976a914769bdff96a02f9135a1d19b749db6a78fe07dc9088ac\
+            4f000000000000001976a9149e089b6889e032d46e3b915a3392edfd616fb1c488ac\
+            02{signature}23{P2WSH_REDEEM_SCRIPT}\
+            00000000"
+        );
+        assert_eq!(sign(input), expected, "hash type: {hash_type}");
+    }
+}
+
+#[test]
+fn test_sign_p2wsh_negative() {
+    let input = p2wsh_input(SIGHASH_ALL, true, false);
+    // The linear fee is used if the redeem script is missing.
+    assert_plan(&input, &[1_226], 1_000, 174);
+    assert_eq!(sign_error(input), SigningErrorType::Error_script_redeem);
+
+    let input = p2wsh_input(SIGHASH_ALL, false, true);
+    assert_plan(&input, &[1_226], 1_000, 147);
+    assert_eq!(
+        sign_error(input),
+        SigningErrorType::Error_missing_private_key
+    );
+
+    let input = Proto::SigningInput {
+        plan: Some(Proto::TransactionPlan {
+            error: SigningErrorType::Error_missing_input_utxos,
+            ..Proto::TransactionPlan::default()
+        }),
+        ..p2wsh_input(SIGHASH_ALL, false, false)
+    };
+    assert_eq!(
+        sign_error(input),
+        SigningErrorType::Error_missing_input_utxos
+    );
+
+    let input = Proto::SigningInput {
+        utxo: Vec::new(),
+        ..p2wsh_input(SIGHASH_ALL, false, false)
+    };
+    let plan = Signer::plan_proto(input.clone());
+    assert_eq!(plan.error, SigningErrorType::Error_missing_input_utxos);
+    assert_eq!(
+        sign_error(input),
+        SigningErrorType::Error_missing_input_utxos
+    );
+
+    let input = p2wsh_input(SIGHASH_ALL, false, false);
+    let plan = Proto::TransactionPlan {
+        utxos: Vec::new(),
+        ..Signer::plan_proto(input.clone())
+    };
+    let input = Proto::SigningInput {
+        plan: Some(plan),
+        ..input
+    };
+    assert_eq!(
+        sign_error(input),
+        SigningErrorType::Error_missing_input_utxos
+    );
+}
+
+fn p2sh_p2wpkh_input(
+    omit_script: bool,
+    omit_keys: bool,
+    invalid_output_script: bool,
+    invalid_redeem_script: bool,
+) -> Proto::SigningInput<'static> {
+    let private_key = if omit_keys {
+        Vec::new()
+    } else {
+        vec![bytes(P2SH_P2WPKH_KEY)]
+    };
+    let mut scripts = std::collections::HashMap::new();
+    if invalid_redeem_script {
+        let redeem_script = hex::decode("fafbfcfdfe").unwrap();
+        let script_hash = tw_bitcoin::script::hash160(&redeem_script);
+        scripts.insert(
+            hex::encode(script_hash, false).into(),
+            Cow::Owned(redeem_script),
+        );
+    } else if !omit_script {
+        scripts.insert(
+            "4733f37cf4db86fbc2efed2500b4f4e49f312023".into(),
+            bytes("001479091972186c449eb1ded22b78e40d009bdf0089"),
+        );
+    }
+    let utxo_script = if invalid_output_script {
+        "fffefdfcfb"
+    } else {
+        "a9144733f37cf4db86fbc2efed2500b4f4e49f31202387"
+    };
+    Proto::SigningInput {
+        hash_type: SIGHASH_ALL,
+        amount: 200_000_000,
+        byte_fee: 1,
+        to_address: Cow::Borrowed(TO_ADDRESS),
+        change_address: Cow::Borrowed(CHANGE_ADDRESS),
+        coin_type: BITCOIN,
+        private_key,
+        scripts,
+        utxo: vec![utxo(
+            bytes("db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a5477"),
+            1,
+            u32::MAX,
+            utxo_script,
+            1_000_000_000,
+        )],
+        ..Proto::SigningInput::default()
+    }
+}
+
+#[test]
+fn test_sign_p2sh_p2wpkh() {
+    let input = p2sh_p2wpkh_input(false, false, false, false);
+    assert_plan(&input, &[1_000_000_000], 200_000_000, 170);
+
+    assert_eq!(
+        sign(input),
+        "01000000\
+        0001\
+        01\
+        db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a5477010000001716001479091972186c449eb1ded22b78e40d009bdf0089ffffffff\
+        02\
+        00c2eb0b000000001976a914769bdff96a02f9135a1d19b749db6a78fe07dc9088ac\
+        5607af2f000000001976a9149e089b6889e032d46e3b915a3392edfd616fb1c488ac\
+        02473044022062b408cc7f92c8add622f3297b8992d68403849c6421ef58274ed6fc077102f30220250696eacc0aad022f55882d742dda7178bea780c03705bf9cdbee9f812f7853012103ad1d8e89212f0b92c74d23bb710c00662ad1470198ac48c43f7d6f93a2a26873\
+        00000000"
+    );
+}
+
+#[test]
+fn test_sign_p2sh_p2wpkh_negative() {
+    let input = p2sh_p2wpkh_input(true, false, false, false);
+    assert_plan(&input, &[1_000_000_000], 200_000_000, 174);
+    assert_eq!(sign_error(input), SigningErrorType::Error_script_redeem);
+
+    let input = p2sh_p2wpkh_input(false, false, true, false);
+    assert_plan(&input, &[1_000_000_000], 200_000_000, 174);
+    assert_eq!(sign_error(input), SigningErrorType::Error_script_output);
+
+    let input = p2sh_p2wpkh_input(false, false, false, true);
+    assert_plan(&input, &[1_000_000_000], 200_000_000, 174);
+    assert_eq!(sign_error(input), SigningErrorType::Error_script_redeem);
+
+    let input = p2sh_p2wpkh_input(false, true, false, false);
+    assert_plan(&input, &[1_000_000_000], 200_000_000, 170);
+    assert_eq!(
+        sign_error(input),
+        SigningErrorType::Error_missing_private_key
+    );
+}
+
+#[test]
+fn test_sign_p2sh_p2wsh_multisig() {
+    let redeem_script = "0020a16b5755f7f6f96dbd65f5f0d6ab9418b89af4b1f14a1bb8a09062c35f0dcb54";
+    let witness_script = "56\
+        210307b8ae49ac90a048e9b53357a2354b3334e9c8bee813ecb98e99a7e07e8c3ba3\
+        2103b28f0c28bfab54554ae8c658ac5c3e0ce6e79ad336331f78c428dd43eea8449b\
+        21034b8113d703413d57761b8b9781957b8c0ac1dfe69f492580ca4195f50376ba4a\
+        21033400f6afecb833092a9a21cfdf1ed1376e58c5d1f47de74683123987e967a8f4\
+        2103a6d48b1131e94ba04d9737d61acdaa1322008af9602b3b14862c07a1789aac16\
+        2102d8b661b0b3302ee2f162b09e07a55ad5dfbe673a9f01d9f0c19617681024306b\
+        56ae";
+
+    let mut scripts = std::collections::HashMap::new();
+    for script in [redeem_script, witness_script] {
+        let script = hex::decode(script).unwrap();
+        let script_hash = tw_bitcoin::script::hash160(&script);
+        scripts.insert(hex::encode(script_hash, false).into(), Cow::Owned(script));
+    }
+    let utxos = vec![utxo(
+        bytes("36641869ca081e70f394c6948e8af409e18b619df2ed74aa106c1ca29787b96e"),
+        1,
+        u32::MAX,
+        "a9149993a429037b5d912407a71c252019287b8d27a587",
+        987_654_321,
+    )];
+    let plan = Proto::TransactionPlan {
+        amount: 900_000_000,
+        available_amount: 987_654_321,
+        change: 87_000_000,
+        fee: 987_654_321 - 900_000_000 - 87_000_000,
+        utxos: utxos.clone(),
+        ..Proto::TransactionPlan::default()
+    };
+    let private_key = [
+        "730fff80e1413068a05b57d6a58261f07551163369787f349438ea38ca80fac6",
+        "11fa3d25a17cbc22b29c44a484ba552b5a53149d106d3d853e22fdd05a2d8bb3",
+        "77bf4141a87d55bdd7f3cd0bdccf6e9e642935fec45f2f30047be7b799120661",
+        "14af36970f5025ea3e8b5542c0f8ebe7763e674838d08808896b63c3351ffe49",
+        "fe9a95c19eef81dde2b95c1284ef39be497d128e2aa46916fb02d552485e0323",
+        "428a7aee9f0c2af0cd19af3cf1c78149951ea528726989b2e83e4778d2c3f890",
+    ]
+    .into_iter()
+    .map(bytes)
+    .collect();
+    let input = Proto::SigningInput {
+        hash_type: 0,
+        amount: 900_000_000,
+        to_address: "16AQVuBMt818u2HBcbxztAZTT2VTDKupPS".into(),
+        change_address: "1Bd1VA2bnLjoBk4ook3H19tZWETk8s6Ym5".into(),
+        private_key,
+        scripts,
+        utxo: utxos,
+        plan: Some(plan),
+        ..Proto::SigningInput::default()
+    };
+
+    let expected = format!(
+        "01000000\
+        0001\
+        01\
+        36641869ca081e70f394c6948e8af409e18b619df2ed74aa106c1ca29787b96e0100000023220020a16b5755f7f6f96dbd65f5f0d6ab9418b89af4b1f14a1bb8a09062c35f0dcb54ffffffff\
+        02\
+        00e9a435000000001976a914389ffce9cd9ae88dcc0631e88a821ffdbe9bfe2688ac\
+        c0832f05000000001976a9147480a33f950689af511e6e84c138dbbd3c3ee41588ac\
+        08\
+        00\
+        47304402201992f5426ae0bab04cf206d7640b7e00410297bfe5487637f6c2427ee8496be002204ad4e64dc2d269f593cc4820db1fc1e8dc34774f602945115ce485940e05c64200\
+        47304402201e412363fa554b994528fd44149f3985b18bb901289ef6b71105b27c7d0e336c0220595e4a1e67154337757562ed5869127533e3e5084c3c2e128518f5f0b85b721800\
+        473044022003b0a20ccf545b3f12c5ade10db8717e97b44da2e800387adfd82c95caf529d902206aee3a2395530d52f476d0ddd9d20ba062820ae6f4e1be4921c3630395743ad900\
+        483045022100ed7a0eeaf72b84351bceac474b0c0510f67065b1b334f77e6843ed102f968afe022004d97d0cfc4bf5651e46487d6f87bd4af6aef894459f9778f2293b0b2c5b7bc700\
+        483045022100934a0c364820588154aed2d519cbcc61969d837b91960f4abbf0e374f03aa39d022036b5c58b754bd44cb5c7d34806c89d9778ea1a1c900618a841e9fbfbe805ff9b00\
+        473044022044e3b59b06931d46f857c82fa1d53d89b116a40a581527eac35c5eb5b7f0785302207d0f8b5d063ffc6749fb4e133db7916162b540c70dee40ec0b21e142d8843b3a00\
+        cf{witness_script}\
+        00000000"
+    );
+    assert_eq!(sign(input), expected);
+}
+
+#[test]
+fn test_sign_invalid_address() {
+    let input = Proto::SigningInput {
+        to_address: "THIS-IS-NOT-A-BITCOIN-ADDRESS".into(),
+        change_address: "THIS-IS-NOT-A-BITCOIN-ADDRESS-EITHER".into(),
+        ..p2wpkh_input(335_790_000, SIGHASH_ALL, 625_000_000, 600_000_000, false)
+    };
+    // The linear fee is used as the transaction cannot be built.
+    assert_plan(&input, &[625_000_000], 335_790_000, 174);
+    assert_eq!(sign_error(input), SigningErrorType::Error_invalid_address);
+}
+
+/// https://blockchair.com/litecoin/transaction/a85fd6a9a7f2f54cacb57e83dfd408e51c0a5fc82885e3fa06be8692962bc407
+#[test]
+fn test_sign_litecoin_a85f() {
+    let utxos = vec![utxo(
+        reversed("7051cd18189401a844abf0f9c67e791315c4c154393870453f8ad98a818efdb5"),
+        9,
+        u32::MAX - 1,
+        "00145c74be45eb45a3459050667529022d9df8a1ecff",
+        3_900_000,
+    )];
+    // Set the plan to match the real transaction.
+    let plan = Proto::TransactionPlan {
+        amount: 3_899_774,
+        available_amount: 3_900_000,
+        fee: 226,
+        change: 0,
+        utxos: utxos.clone(),
+        ..Proto::TransactionPlan::default()
+    };
+    let input = Proto::SigningInput {
+        coin_type: LITECOIN,
+        hash_type: SIGHASH_ALL,
+        amount: 3_899_774,
+        use_max_amount: true,
+        byte_fee: 1,
+        to_address: "ltc1q0dvup9kzplv6yulzgzzxkge8d35axkq4n45hum".into(),
+        change_address: "ltc1qt36tu30tgk35tyzsve6jjq3dnhu2rm8l8v5q00".into(),
+        private_key: vec![bytes(
+            "b820f41f96c8b7442f3260acd23b3897e1450b8c7c6580136a3c2d3a14e34674",
+        )],
+        utxo: utxos,
+        plan: Some(plan),
+        ..Proto::SigningInput::default()
+    };
+
+    assert_eq!(
+        sign(input),
+        "01000000\
+        0001\
+        01\
+        b5fd8e818ad98a3f4570383954c1c41513797ec6f9f0ab44a801941818cd51700900000000feffffff\
+        01\
+        7e813b00000000001600147b59c096c20fd9a273e240846b23276c69d35815\
+        02473044022029153096af176f9cca0ba9b827e947689a8bb8d11dda570c880f9108bc590b3002202410c78b666722ade1ef4547ad85a128ddcbd4695c40f942457bea3d043b9bb30121036739829f2cfec79cfe6aaf1c22ecb7d4867dfd8ab4deb7121b36a00ab646caed\
+        00000000"
+    );
+}
+
+/// https://blockchair.com/litecoin/transaction/8435d205614ee70066060734adf03af4194d0c3bc66dd01bb124ab7fd25e2ef8
+#[test]
+fn test_plan_and_sign_litecoin_8435() {
+    let input = Proto::SigningInput {
+        coin_type: LITECOIN,
+        hash_type: SIGHASH_ALL,
+        amount: 1_200_000,
+        byte_fee: 1,
+        to_address: "ltc1qt36tu30tgk35tyzsve6jjq3dnhu2rm8l8v5q00".into(),
+        change_address: "ltc1q0dvup9kzplv6yulzgzzxkge8d35axkq4n45hum".into(),
+        utxo: vec![utxo(
+            reversed("a85fd6a9a7f2f54cacb57e83dfd408e51c0a5fc82885e3fa06be8692962bc407"),
+            0,
+            u32::MAX,
+            "00147b59c096c20fd9a273e240846b23276c69d35815",
+            3_899_774,
+        )],
+        ..Proto::SigningInput::default()
+    };
+    assert_plan(&input, &[3_899_774], 1_200_000, 141);
+
+    // Extend the input with the key and the plan.
+    let input = Proto::SigningInput {
+        private_key: vec![bytes(
+            "690b34763f34e0226ad2a4d47098269322e0402f847c97166e8f39959fcaff5a",
+        )],
+        plan: Some(Signer::plan_proto(input.clone())),
+        ..input
+    };
+    assert_eq!(
+        sign(input),
+        "01000000\
+        0001\
+        01\
+        07c42b969286be06fae38528c85f0a1ce508d4df837eb5ac4cf5f2a7a9d65fa80000000000ffffffff\
+        02\
+        804f1200000000001600145c74be45eb45a3459050667529022d9df8a1ecff\
+        71312900000000001600147b59c096c20fd9a273e240846b23276c69d35815\
+        0247304402204139b82927dd80445f27a5d2c29fa4881dbd2911714452a4a706145bc43cc4bf022016fbdf4b09bc5a9c43e79edb1c1061759779a20c35535082bdc469a61ed0771f012102499e327a05cc8bb4b3c34c8347ecfcb152517c9927c092fa273be5379fde3226\
+        00000000"
+    );
+}
+
+fn many_utxos_input(
+    utxos: usize,
+    amount: i64,
+    use_max_amount: bool,
+) -> Proto::SigningInput<'static> {
+    let utxo = (0..utxos as i64)
+        .map(|i| {
+            utxo(
+                reversed("a85fd6a9a7f2f54cacb57e83dfd408e51c0a5fc82885e3fa06be8692962bc407"),
+                0,
+                u32::MAX,
+                "001479091972186c449eb1ded22b78e40d009bdf0089",
+                1_000 + (i + 1) * 10,
+            )
+        })
+        .collect();
+    Proto::SigningInput {
+        coin_type: BITCOIN,
+        hash_type: SIGHASH_ALL,
+        amount,
+        use_max_amount,
+        byte_fee: 1,
+        to_address: "bc1qauwlpmzamwlf9tah6z4w0t8sunh6pnyyjgk0ne".into(),
+        change_address: "bc1q0yy3juscd3zfavw76g4h3eqdqzda7qyf58rj4m".into(),
+        utxo,
+        ..Proto::SigningInput::default()
+    }
+}
+
+/// Plans the transaction first, then signs it with the plan.
+fn plan_and_sign(input: Proto::SigningInput<'static>) -> String {
+    let input = Proto::SigningInput {
+        private_key: vec![bytes(P2SH_P2WPKH_KEY)],
+        plan: Some(Signer::plan_proto(input.clone())),
+        ..input
+    };
+    sign(input)
+}
+
+#[test]
+fn test_plan_10_inputs_max_amount() {
+    let input = Proto::SigningInput {
+        utxo: (0..10)
+            .map(|i| {
+                utxo(
+                    reversed("a85fd6a9a7f2f54cacb57e83dfd408e51c0a5fc82885e3fa06be8692962bc407"),
+                    0,
+                    u32::MAX,
+                    "001479091972186c449eb1ded22b78e40d009bdf0089",
+                    1_000_000 + i * 10_000,
+                )
+            })
+            .collect(),
+        ..many_utxos_input(0, 2_000_000, true)
+    };
+    let amounts: Vec<_> = (0..10).map(|i| 1_000_000 + i * 10_000).collect();
+    assert_plan(&input, &amounts, 10_449_278, 722);
+
+    let encoded = plan_and_sign(input);
+    assert_eq!(encoded.len() / 2, 1529);
+}
+
+#[test]
+fn test_sign_many_utxos_400() {
+    let input = many_utxos_input(400, 300_000, false);
+    // The 66 largest UTXOs are expected.
+    let amounts: Vec<_> = (400 - 66..400).map(|i| 1_000 + (i + 1) * 10).collect();
+    assert_eq!(amounts.iter().sum::<i64>(), 308_550);
+    assert_plan(&input, &amounts, 300_000, 4_561);
+
+    let encoded = plan_and_sign(input);
+    assert_eq!(encoded.len() / 2, 9871);
+}
+
+#[test]
+fn test_sign_many_utxos_2000() {
+    let input = many_utxos_input(2000, 2_000_000, false);
+    // The simple selection takes the 601 first UTXOs, that are the smallest ones.
+    let amounts: Vec<_> = (0..601).map(|i| 1_000 + (i + 1) * 10).collect();
+    assert_eq!(amounts.iter().sum::<i64>(), 2_410_010);
+    assert_plan(&input, &amounts, 2_000_000, 40_943);
+
+    let encoded = plan_and_sign(input);
+    assert_eq!(encoded.len() / 2, 89_339);
+}
+
+/// https://mempool.space/tx/5df51e13bfeb79f386e1e17237f06d1b5c87c5bfcaa907c0c1cfe51cd7ca446d
+#[test]
+fn test_sign_p2wpkh_to_p2tr_5df51e() {
+    let input = Proto::SigningInput {
+        coin_type: BITCOIN,
+        hash_type: SIGHASH_ALL,
+        amount: 1_100,
+        byte_fee: 1,
+        to_address: "bc1ptmsk7c2yut2xah4pgflpygh2s7fh0cpfkrza9cjj29awapv53mrslgd5cf".into(),
+        change_address: "bc1qpjult34k9spjfym8hss2jrwjgf0xjf40ze0pp8".into(),
+        private_key: vec![bytes(
+            "13fcaabaf9e71ffaf915e242ec58a743d55f102cf836968e5bd4881135e0c52c",
+        )],
+        utxo: vec![utxo(
+            reversed("c24bd72e3eaea797bd5c879480a0db90980297bc7085efda97df2bf7d31413fb"),
+            1,
+            u32::MAX,
+            "00140cb9f5c6b62c03249367bc20a90dd2425e6926af",
+            49_429,
+        )],
+        ..Proto::SigningInput::default()
+    };
+    assert_plan(&input, &[49_429], 1_100, 153);
+
+    assert_eq!(
+        sign(input),
+        "01000000\
+        0001\
+        01\
+        fb1314d3f72bdf97daef8570bc97029890dba08094875cbd97a7ae3e2ed74bc20100000000ffffffff\
+        02\
+        4c0400000000000022\
+        51205ee16f6144e2d46edea1427e1222ea879377e029b0c5d2e252517aee85948ec7\
+        30bc00000000000016\
+        00140cb9f5c6b62c03249367bc20a90dd2425e6926af\
+        02473044022021cea91157fdab33226e38ee7c1a686538fc323f5e28feb35775cf82ba8c62210220723743b150cea8ead877d8b8d059499779a5df69f9bdc755c9f968c56cfb528f0121021e582a887bd94d648a9267143eb600449a8d59a0db0653740b1378067a6d0cee\
+        00000000"
+    );
+}
+
+/// Spends the UTXOs locked to the taproot output key of the `13fcaaba...` private key.
+fn p2tr_key_path_input(hash_type: u32) -> Proto::SigningInput<'static> {
+    Proto::SigningInput {
+        coin_type: BITCOIN,
+        hash_type,
+        amount: 20_000,
+        byte_fee: 2,
+        to_address: "bc1qpjult34k9spjfym8hss2jrwjgf0xjf40ze0pp8".into(),
+        change_address: "bc1ptmsk7c2yut2xah4pgflpygh2s7fh0cpfkrza9cjj29awapv53mrslgd5cf".into(),
+        private_key: vec![bytes(
+            "13fcaabaf9e71ffaf915e242ec58a743d55f102cf836968e5bd4881135e0c52c",
+        )],
+        utxo: vec![
+            utxo(
+                reversed("5df51e13bfeb79f386e1e17237f06d1b5c87c5bfcaa907c0c1cfe51cd7ca446d"),
+                0,
+                u32::MAX,
+                "51203f1e08c2ff56bebdc711bf9677f8f713348aa79993113bf94a1603615a2974b9",
+                15_000,
+            ),
+            utxo(
+                reversed("5df51e13bfeb79f386e1e17237f06d1b5c87c5bfcaa907c0c1cfe51cd7ca446d"),
+                1,
+                u32::MAX,
+                "51203f1e08c2ff56bebdc711bf9677f8f713348aa79993113bf94a1603615a2974b9",
+                12_000,
+            ),
+        ],
+        ..Proto::SigningInput::default()
+    }
+}
+
+/// The signatures are checked against rust-bitcoin with the zero auxiliary randomness.
+#[test]
+fn test_sign_p2tr_key_path() {
+    let test_cases = [
+        (
+            SIGHASH_DEFAULT,
+            "01409df23c7befffe675aac84eafe3ece74d566b50cad17009b45b36c763fde4f4861126cdccf4f3ab1cee57c5f8aba4ea3931ec8197a62d2a026c5b4f9363f97f28\
+            0140871274cfbca634506e593c76e2a081608d69f4a72187c447d7c8425b049f811a0911522dccab2883383e9a34b967cd573ad33f0bf88911dc32f4d7e2f3a25cea",
+        ),
+        (
+            SIGHASH_ALL,
+            "01419b6831a0897aa053c0b603dfa0c398b1dd55b4a72cabf277954f421346b4217e7a327e3c3aac2dad2961ca3d4a1014a9e559841b7fecdf824da23964ae194f5701\
+            0141ee0c152ccaa90fcb120e95fd7c152b4c1eb44143a6d6490e35f9dc550f18aa93346206ca7d405fe26b805569a04024c54d0d45750703fe6256c08707765a248401",
+        ),
+        (
+            SIGHASH_NONE,
+            "01414eab3c2daa77f6fa384f6efad8c548b7fee2f47a4ad05a886a6bcfe6e8a9cf93d7f8001476c08b69c491b626f8fa08d066a88c6ec8fb75fcd70e8da16b7a43f002\
+            0141a24840b44663e357818c6ffc2e60f94b45b73753dba604929332d3fde6e716cc220424802376b9e5196fafcfd32b21443951dd67fea2016a6b4a5c9c87a2643d02",
+        ),
+        (
+            SIGHASH_SINGLE | SIGHASH_ANYONE_CAN_PAY,
+            "01412734fdeeb7a1c42f7f88c9cb547eba9f42e139c24cedfbb11e649654e46e5e1b0b8f9c6ac213a60f1b3052517cc04dd6effa203ec84894059951007948380cb483\
+            01412de9c5b8d0947ce721e8d39dcd16ffa1878b7b3af535da84213e027c6f5ad140b5da121ddcc88c98210292f76c9e4af952f766f631b4f6bede782423147f089083",
+        ),
+    ];
+
+    for (hash_type, witness) in test_cases {
+        let input = p2tr_key_path_input(hash_type);
+        // The UTXOs are ordered by amount.
+        assert_plan(&input, &[12_000, 15_000], 20_000, 400);
+
+        let expected = format!(
+            "01000000\
+            0001\
+            02\
+            6d44cad71ce5cfc1c007a9cabfc5875c1b6df03772e1e186f379ebbf131ef55d0100000000ffffffff\
+            6d44cad71ce5cfc1c007a9cabfc5875c1b6df03772e1e186f379ebbf131ef55d0000000000ffffffff\
+            02\
+            204e0000000000001600140cb9f5c6b62c03249367bc20a90dd2425e6926af\
+            c8190000000000002251205ee16f6144e2d46edea1427e1222ea879377e029b0c5d2e252517aee85948ec7\
+            {witness}\
+            00000000"
+        );
+        assert_eq!(sign(input), expected, "hash type: {hash_type}");
+    }
+}
+
+#[test]
+fn test_sign_p2tr_key_path_negative() {
+    let input = Proto::SigningInput {
+        private_key: vec![bytes(KEY0)],
+        ..p2tr_key_path_input(SIGHASH_ALL)
+    };
+    assert_eq!(
+        sign_error(input),
+        SigningErrorType::Error_missing_private_key
+    );
+
+    let input = p2tr_key_path_input(SIGHASH_FORK_ID | SIGHASH_ALL);
+    assert_eq!(sign_error(input), SigningErrorType::Error_invalid_params);
+}
+
+#[test]
+fn test_sign_op_return_thorchain_swap() {
+    let input = Proto::SigningInput {
+        coin_type: BITCOIN,
+        hash_type: SIGHASH_ALL,
+        amount: 300_000,
+        byte_fee: 126,
+        to_address: "bc1qxu5a8gtnjxw3xwdlmr2gl9d76h9fysu3zl656e".into(),
+        change_address: "bc1q2gzg42w98ytatvmsgxfc8vrg6l24c25pydup9u".into(),
+        private_key: vec![bytes(
+            "6bd4096fa6f08bd3af2b437244ba0ca2d35045c5233b8d6796df37e61e974de5",
+        )],
+        utxo: vec![utxo(
+            reversed("30b82960291a39de3664ec4c844a815e3e680e29b4d3a919e450f0c119cf4e35"),
+            1,
+            u32::MAX,
+            "001452048aa9c53917d5b370419383b068d7d55c2a81",
+            342_101,
+        )],
+        output_op_return: Cow::Borrowed(
+            b"SWAP:THOR.RUNE:thor1tpercamkkxec0q0jk6ltdnlqvsw29guap8wmcl:",
+        ),
+        ..Proto::SigningInput::default()
+    };
+    assert_plan(&input, &[342_101], 300_000, 26_586);
+    assert_eq!(Signer::plan_proto(input.clone()).output_op_return.len(), 59);
+
+    assert_eq!(
+        sign(input),
+        "01000000\
+        0001\
+        01\
+        354ecf19c1f050e419a9d3b4290e683e5e814a844cec6436de391a296029b8300100000000ffffffff\
+        03\
+        e0930400000000001600143729d3a173919d1339bfd8d48f95bed5ca924391\
+        9b3c00000000000016001452048aa9c53917d5b370419383b068d7d55c2a81\
+        00000000000000003d6a3b535741503a54484f522e52554e453a74686f72317470657263616d6b6b7865633071306a6b366c74646e6c7176737732396775617038776d636c3a\
+        02483045022100ff6c0aaef512aa52f3036161bfbcef39046ac89eb9617fa461a0c9c43fe45eb3022055d208d3f81736e72e3ad8ef761dc79ac5dd3dc00721174bc69db416a74960e3012102c2e5c8b4927812fb37444a7862466ad23978a4ac626f8eaf93e1d1a60d6abb80\
+        00000000"
+    );
+}
+
+#[test]
+fn test_sign_op_return_too_long() {
+    let input = Proto::SigningInput {
+        output_op_return: Cow::Owned(vec![0; 81]),
+        ..p2pkh_input(false)
+    };
+    assert_eq!(sign_error(input), SigningErrorType::Error_invalid_memo);
+}
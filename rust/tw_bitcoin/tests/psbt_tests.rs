@@ -0,0 +1,335 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use tw_bitcoin::psbt::map::Key;
+use tw_bitcoin::psbt::{Psbt, PSBT_V2};
+use tw_bitcoin::transaction::TransactionOutput;
+use tw_bitcoin::SigningErrorType;
+use tw_encoding::{base64, hex};
+use tw_keypair::secp256k1::PrivateKey;
+
+// A transaction spending P2PKH, P2WPKH (`SIGHASH_ALL | SIGHASH_ANYONECANPAY`), P2SH-P2WPKH,
+// P2WSH 2-of-2 multisig, P2SH 2-of-3 multisig and P2TR key path outputs of a single funding transaction.
+// The PSBTs are created, signed and finalized with rust-bitcoin.
+const UNSIGNED_PSBT: &str = "cHNidP8BAP1BAQIAAAAGppfy3aTCH5yZA7mCRyg6aTj1QLglxFENQ7oTc+xYkucAAAAAAP3///+ml/LdpMIfnJkDuYJHKDppOPVAuCXEUQ1DuhNz7FiS5wEAAAAA/f///6aX8t2kwh+cmQO5gkcoOmk49UC4JcRRDUO6E3PsWJLnAgAAAAD9////ppfy3aTCH5yZA7mCRyg6aTj1QLglxFENQ7oTc+xYkucDAAAAAP3///+ml/LdpMIfnJkDuYJHKDppOPVAuCXEUQ1DuhNz7FiS5wQAAAAA/f///6aX8t2kwh+cmQO5gkcoOmk49UC4JcRRDUO6E3PsWJLnBQAAAAD9////AmDjFgAAAAAAFgAUO8KNbZLZBz+14630gXler0Rrzu2wAAkAAAAAABl2qRT8clCiEd7dxw7lonON5fB4FzUc74isADUMAAABAP0LAQEAAAABxLOikYB/bl1MOyqcDx9uLh2hOrvR88THSloM/OKkyaUBAAAAAVH/////BqCGAQAAAAAAGXapFPxyUKIR3t3HDuWic43l8HgXNRzviKxADQMAAAAAABYAFPxyUKIR3t3HDuWic43l8HgXNRzv4JMEAAAAAAAXqRT68q4f3UT5N6hkDPk6Ri0fTxMtHIeAGgYAAAAAACIAIAgnS0bnLNLNYmOzLRGuKHiTcCRKjg1kdDDM5KLlZKYsIKEHAAAAAAAXqRQZEwgXo1XhpN+cseJQUtOTdLg76IfAJwkAAAAAACJRIPjoV5wSb0ne0zfBnE9fHBlR8HUhYtamHwqeFVhVlDlLAAAAAAABAR9ADQMAAAAAABYAFPxyUKIR3t3HDuWic43l8HgXNRzvAQMEgQAAACIGA081W9y3zAr3KO88zrlhXZBoS7Wyyl+FmrDwtwQHWHGqGNNNsz9UAACAAAAAgAAAAIAAAAAAAAAAAAABASDgkwQAAAAAABepFPryrh/dRPk3qGQM+TpGLR9PEy0chwEEFgAUUxJgqioZniKMU336Qsgr6ix8H00AAQErgBoGAAAAAAAiACAIJ0tG5yzSzWJjsy0Rrih4k3AkSo4NZHQwzOSi5WSmLAEDBAEAAAABBUdSIQNPNVvct8wK9yjvPM65YV2QaEu1sspfhZqw8LcEB1hxqiECRm1/yuVj5csJoNGHC7WANEgEYXh5oUlJzyIoXxuuPydSrgABAP0LAQEAAAABxLOikYB/bl1MOyqcDx9uLh2hOrvR88THSloM/OKkyaUBAAAAAVH/////BqCGAQAAAAAAGXapFPxyUKIR3t3HDuWic43l8HgXNRzviKxADQMAAAAAABYAFPxyUKIR3t3HDuWic43l8HgXNRzv4JMEAAAAAAAXqRT68q4f3UT5N6hkDPk6Ri0fTxMtHIeAGgYAAAAAACIAIAgnS0bnLNLNYmOzLRGuKHiTcCRKjg1kdDDM5KLlZKYsIKEHAAAAAAAXqRQZEwgXo1XhpN+cseJQUtOTdLg76IfAJwkAAAAAACJRIPjoV5wSb0ne0zfBnE9fHBlR8HUhYtamHwqeFVhVlDlLAAAAAAEEaVIhA081W9y3zAr3KO88zrlhXZBoS7Wyyl+FmrDwtwQHWHGqIQJGbX/K5WPlywmg0YcLtYA0SARheHmhSUnPIihfG64/JyECPHKt20/fCa+U8MlNf+kqOGp+cM+KHYWRY4a7JTXHsbFTrgABASvAJwkAAAAAACJRIPjoV5wSb0ne0zfBnE9fHBlR8HUhYtamHwqeFVhVlDlLARcgPHKt20/fCa+U8MlNf+kqOGp+cM+KHYWRY4a7JTXHsbEAIgICPHKt20/fCa+U8MlNf+kqOGp+cM+KHYWRY4a7JTXHsbEY002zP1QAAIAAAACAAAAAgAEAAAAFAAAAAAA=";
+const FIRST_KEY_SIGNED_PSBT: &str = "cHNidP8BAP1BAQIAAAAGppfy3aTCH5yZA7mCRyg6aTj1QLglxFENQ7oTc+xYkucAAAAAAP3///+ml/LdpMIfnJkDuYJHKDppOPVAuCXEUQ1DuhNz7FiS5wEAAAAA/f///6aX8t2kwh+cmQO5gkcoOmk49UC4JcRRDUO6E3PsWJLnAgAAAAD9////ppfy3aTCH5yZA7mCRyg6aTj1QLglxFENQ7oTc+xYkucDAAAAAP3///+ml/LdpMIfnJkDuYJHKDppOPVAuCXEUQ1DuhNz7FiS5wQAAAAA/f///6aX8t2kwh+cmQO5gkcoOmk49UC4JcRRDUO6E3PsWJLnBQAAAAD9////AmDjFgAAAAAAFgAUO8KNbZLZBz+14630gXler0Rrzu2wAAkAAAAAABl2qRT8clCiEd7dxw7lonON5fB4FzUc74isADUMAAABAP0LAQEAAAABxLOikYB/bl1MOyqcDx9uLh2hOrvR88THSloM/OKkyaUBAAAAAVH/////BqCGAQAAAAAAGXapFPxyUKIR3t3HDuWic43l8HgXNRzviKxADQMAAAAAABYAFPxyUKIR3t3HDuWic43l8HgXNRzv4JMEAAAAAAAXqRT68q4f3UT5N6hkDPk6Ri0fTxMtHIeAGgYAAAAAACIAIAgnS0bnLNLNYmOzLRGuKHiTcCRKjg1kdDDM5KLlZKYsIKEHAAAAAAAXqRQZEwgXo1XhpN+cseJQUtOTdLg76IfAJwkAAAAAACJRIPjoV5wSb0ne0zfBnE9fHBlR8HUhYtamHwqeFVhVlDlLAAAAACICA081W9y3zAr3KO88zrlhXZBoS7Wyyl+FmrDwtwQHWHGqSDBFAiEA7sXhZ6v/fE2FFGO4Lf6nDsIRFE0rpFUodPJy+jWJnBcCIE1tct5w+k9Lje6O+W5VIYzXQApnblWOpZMX6Pbsmkv3AQABAR9ADQMAAAAAABYAFPxyUKIR3t3HDuWic43l8HgXNRzvIgIDTzVb3LfMCvco7zzOuWFdkGhLtbLKX4WasPC3BAdYcapHMEQCIAP5qvjgcGMWTnDK0iKhCh6Wlnl7NKw/1mE1hA8F/xdaAiA3+mA9W8CG81Jidw+ggVhYafgRn3npl/TbHc3mmv7hJIEBAwSBAAAAIgYDTzVb3LfMCvco7zzOuWFdkGhLtbLKX4WasPC3BAdYcaoY002zP1QAAIAAAACAAAAAgAAAAAAAAAAAAAEBIOCTBAAAAAAAF6kU+vKuH91E+TeoZAz5OkYtH08TLRyHAQQWABRTEmCqKhmeIoxTffpCyCvqLHwfTQABASuAGgYAAAAAACIAIAgnS0bnLNLNYmOzLRGuKHiTcCRKjg1kdDDM5KLlZKYsIgIDTzVb3LfMCvco7zzOuWFdkGhLtbLKX4WasPC3BAdYcapIMEUCIQC8NtAx0L/OmYJ9pNp1wQp1f7gnpFAF7NIZtA4MaOavagIgUDx7J3BdnsG0J65+fyS5+PlAMPd5a3JlDZhWq2AVaSYBAQMEAQAAAAEFR1IhA081W9y3zAr3KO88zrlhXZBoS7Wyyl+FmrDwtwQHWHGqIQJGbX/K5WPlywmg0YcLtYA0SARheHmhSUnPIihfG64/J1KuAAEA/QsBAQAAAAHEs6KRgH9uXUw7KpwPH24uHaE6u9HzxMdKWgz84qTJpQEAAAABUf////8GoIYBAAAAAAAZdqkU/HJQohHe3ccO5aJzjeXweBc1HO+IrEANAwAAAAAAFgAU/HJQohHe3ccO5aJzjeXweBc1HO/gkwQAAAAAABepFPryrh/dRPk3qGQM+TpGLR9PEy0ch4AaBgAAAAAAIgAgCCdLRucs0s1iY7MtEa4oeJNwJEqODWR0MMzkouVkpiwgoQcAAAAAABepFBkTCBejVeGk35yx4lBS05N0uDvoh8AnCQAAAAAAIlEg+OhXnBJvSd7TN8GcT18cGVHwdSFi1qYfCp4VWFWUOUsAAAAAIgIDTzVb3LfMCvco7zzOuWFdkGhLtbLKX4WasPC3BAdYcapHMEQCIE0RrZBAe+Y4d/DMFKLTCGn5pYmgrfdB7SONCw+dN3UkAiBU61BWg3vI2TkYk/8G7WoVjhB3ic64ZTbkGUpYp2fYzgEBBGlSIQNPNVvct8wK9yjvPM65YV2QaEu1sspfhZqw8LcEB1hxqiECRm1/yuVj5csJoNGHC7WANEgEYXh5oUlJzyIoXxuuPychAjxyrdtP3wmvlPDJTX/pKjhqfnDPih2FkWOGuyU1x7GxU64AAQErwCcJAAAAAAAiUSD46FecEm9J3tM3wZxPXxwZUfB1IWLWph8KnhVYVZQ5SwEXIDxyrdtP3wmvlPDJTX/pKjhqfnDPih2FkWOGuyU1x7GxACICAjxyrdtP3wmvlPDJTX/pKjhqfnDPih2FkWOGuyU1x7GxGNNNsz9UAACAAAAAgAAAAIABAAAABQAAAAAA";
+const SIGNED_PSBT: &str = "cHNidP8BAP1BAQIAAAAGppfy3aTCH5yZA7mCRyg6aTj1QLglxFENQ7oTc+xYkucAAAAAAP3///+ml/LdpMIfnJkDuYJHKDppOPVAuCXEUQ1DuhNz7FiS5wEAAAAA/f///6aX8t2kwh+cmQO5gkcoOmk49UC4JcRRDUO6E3PsWJLnAgAAAAD9////ppfy3aTCH5yZA7mCRyg6aTj1QLglxFENQ7oTc+xYkucDAAAAAP3///+ml/LdpMIfnJkDuYJHKDppOPVAuCXEUQ1DuhNz7FiS5wQAAAAA/f///6aX8t2kwh+cmQO5gkcoOmk49UC4JcRRDUO6E3PsWJLnBQAAAAD9////AmDjFgAAAAAAFgAUO8KNbZLZBz+14630gXler0Rrzu2wAAkAAAAAABl2qRT8clCiEd7dxw7lonON5fB4FzUc74isADUMAAABAP0LAQEAAAABxLOikYB/bl1MOyqcDx9uLh2hOrvR88THSloM/OKkyaUBAAAAAVH/////BqCGAQAAAAAAGXapFPxyUKIR3t3HDuWic43l8HgXNRzviKxADQMAAAAAABYAFPxyUKIR3t3HDuWic43l8HgXNRzv4JMEAAAAAAAXqRT68q4f3UT5N6hkDPk6Ri0fTxMtHIeAGgYAAAAAACIAIAgnS0bnLNLNYmOzLRGuKHiTcCRKjg1kdDDM5KLlZKYsIKEHAAAAAAAXqRQZEwgXo1XhpN+cseJQUtOTdLg76IfAJwkAAAAAACJRIPjoV5wSb0ne0zfBnE9fHBlR8HUhYtamHwqeFVhVlDlLAAAAACICA081W9y3zAr3KO88zrlhXZBoS7Wyyl+FmrDwtwQHWHGqSDBFAiEA7sXhZ6v/fE2FFGO4Lf6nDsIRFE0rpFUodPJy+jWJnBcCIE1tct5w+k9Lje6O+W5VIYzXQApnblWOpZMX6Pbsmkv3AQABAR9ADQMAAAAAABYAFPxyUKIR3t3HDuWic43l8HgXNRzvIgIDTzVb3LfMCvco7zzOuWFdkGhLtbLKX4WasPC3BAdYcapHMEQCIAP5qvjgcGMWTnDK0iKhCh6Wlnl7NKw/1mE1hA8F/xdaAiA3+mA9W8CG81Jidw+ggVhYafgRn3npl/TbHc3mmv7hJIEBAwSBAAAAIgYDTzVb3LfMCvco7zzOuWFdkGhLtbLKX4WasPC3BAdYcaoY002zP1QAAIAAAACAAAAAgAAAAAAAAAAAAAEBIOCTBAAAAAAAF6kU+vKuH91E+TeoZAz5OkYtH08TLRyHIgICRm1/yuVj5csJoNGHC7WANEgEYXh5oUlJzyIoXxuuPydHMEQCIBjmYg7931m6NBwFNvDu2RtNRAOak5zMy/qdtdpJ4GjfAiBj9roASwrI9BKlYdXEYhsSIUHGCBE5HtqkF4kthPeq6QEBBBYAFFMSYKoqGZ4ijFN9+kLIK+osfB9NAAEBK4AaBgAAAAAAIgAgCCdLRucs0s1iY7MtEa4oeJNwJEqODWR0MMzkouVkpiwiAgJGbX/K5WPlywmg0YcLtYA0SARheHmhSUnPIihfG64/J0gwRQIhAMM4CfXObQh0LhoxznaNCKwqA8Paqa9eRGbm3oVzHBtYAiAZknCiqMdIr2dFhy14SzYiFhqW35bCiLh7avWc3ed9iQEiAgNPNVvct8wK9yjvPM65YV2QaEu1sspfhZqw8LcEB1hxqkgwRQIhALw20DHQv86Zgn2k2nXBCnV/uCekUAXs0hm0Dgxo5q9qAiBQPHsncF2ewbQnrn5/JLn4+UAw93lrcmUNmFarYBVpJgEBAwQBAAAAAQVHUiEDTzVb3LfMCvco7zzOuWFdkGhLtbLKX4WasPC3BAdYcaohAkZtf8rlY+XLCaDRhwu1gDRIBGF4eaFJSc8iKF8brj8nUq4AAQD9CwEBAAAAAcSzopGAf25dTDsqnA8fbi4doTq70fPEx0paDPzipMmlAQAAAAFR/////waghgEAAAAAABl2qRT8clCiEd7dxw7lonON5fB4FzUc74isQA0DAAAAAAAWABT8clCiEd7dxw7lonON5fB4FzUc7+CTBAAAAAAAF6kU+vKuH91E+TeoZAz5OkYtH08TLRyHgBoGAAAAAAAiACAIJ0tG5yzSzWJjsy0Rrih4k3AkSo4NZHQwzOSi5WSmLCChBwAAAAAAF6kUGRMIF6NV4aTfnLHiUFLTk3S4O+iHwCcJAAAAAAAiUSD46FecEm9J3tM3wZxPXxwZUfB1IWLWph8KnhVYVZQ5SwAAAAAiAgI8cq3bT98Jr5TwyU1/6So4an5wz4odhZFjhrslNcexsUcwRAIgd5m1fzuNh8NsSYUTa260Ojy5DS5Eam6g/uo853sHzW8CIE5ejeRPWE7TDmxPsV9S9ES4X9B6As9gPzd9e2/Q0QI8ASICAkZtf8rlY+XLCaDRhwu1gDRIBGF4eaFJSc8iKF8brj8nRzBEAiBcA83+f0KRLdqqojs8BrGny2dhHYk7O74Hh2bFrkYkrwIgeCHzqujgrkAqmIj3mSw6oxk/JijchL6zI8cvGWg1df4BIgIDTzVb3LfMCvco7zzOuWFdkGhLtbLKX4WasPC3BAdYcapHMEQCIE0RrZBAe+Y4d/DMFKLTCGn5pYmgrfdB7SONCw+dN3UkAiBU61BWg3vI2TkYk/8G7WoVjhB3ic64ZTbkGUpYp2fYzgEBBGlSIQNPNVvct8wK9yjvPM65YV2QaEu1sspfhZqw8LcEB1hxqiECRm1/yuVj5csJoNGHC7WANEgEYXh5oUlJzyIoXxuuPychAjxyrdtP3wmvlPDJTX/pKjhqfnDPih2FkWOGuyU1x7GxU64AAQErwCcJAAAAAAAiUSD46FecEm9J3tM3wZxPXxwZUfB1IWLWph8KnhVYVZQ5SwETQIlX23LktCED7ZEVrKsXUw1n/LuzLSSuvBz0K8nG0SL88jVUpMnXSER+BSToX23PaS25KCHdLYFwUd5GUdTEzucBFyA8cq3bT98Jr5TwyU1/6So4an5wz4odhZFjhrslNcexsQAiAgI8cq3bT98Jr5TwyU1/6So4an5wz4odhZFjhrslNcexsRjTTbM/VAAAgAAAAIAAAACAAQAAAAUAAAAAAA==";
+const FINAL_PSBT: &str = "cHNidP8BAP1BAQIAAAAGppfy3aTCH5yZA7mCRyg6aTj1QLglxFENQ7oTc+xYkucAAAAAAP3///+ml/LdpMIfnJkDuYJHKDppOPVAuCXEUQ1DuhNz7FiS5wEAAAAA/f///6aX8t2kwh+cmQO5gkcoOmk49UC4JcRRDUO6E3PsWJLnAgAAAAD9////ppfy3aTCH5yZA7mCRyg6aTj1QLglxFENQ7oTc+xYkucDAAAAAP3///+ml/LdpMIfnJkDuYJHKDppOPVAuCXEUQ1DuhNz7FiS5wQAAAAA/f///6aX8t2kwh+cmQO5gkcoOmk49UC4JcRRDUO6E3PsWJLnBQAAAAD9////AmDjFgAAAAAAFgAUO8KNbZLZBz+14630gXler0Rrzu2wAAkAAAAAABl2qRT8clCiEd7dxw7lonON5fB4FzUc74isADUMAAABAP0LAQEAAAABxLOikYB/bl1MOyqcDx9uLh2hOrvR88THSloM/OKkyaUBAAAAAVH/////BqCGAQAAAAAAGXapFPxyUKIR3t3HDuWic43l8HgXNRzviKxADQMAAAAAABYAFPxyUKIR3t3HDuWic43l8HgXNRzv4JMEAAAAAAAXqRT68q4f3UT5N6hkDPk6Ri0fTxMtHIeAGgYAAAAAACIAIAgnS0bnLNLNYmOzLRGuKHiTcCRKjg1kdDDM5KLlZKYsIKEHAAAAAAAXqRQZEwgXo1XhpN+cseJQUtOTdLg76IfAJwkAAAAAACJRIPjoV5wSb0ne0zfBnE9fHBlR8HUhYtamHwqeFVhVlDlLAAAAAAEHa0gwRQIhAO7F4Wer/3xNhRRjuC3+pw7CERRNK6RVKHTycvo1iZwXAiBNbXLecPpPS43ujvluVSGM10AKZ25VjqWTF+j27JpL9wEhA081W9y3zAr3KO88zrlhXZBoS7Wyyl+FmrDwtwQHWHGqAAEBH0ANAwAAAAAAFgAU/HJQohHe3ccO5aJzjeXweBc1HO8BCGsCRzBEAiAD+ar44HBjFk5wytIioQoelpZ5ezSsP9ZhNYQPBf8XWgIgN/pgPVvAhvNSYncPoIFYWGn4EZ956Zf02x3N5pr+4SSBIQNPNVvct8wK9yjvPM65YV2QaEu1sspfhZqw8LcEB1hxqgABASDgkwQAAAAAABepFPryrh/dRPk3qGQM+TpGLR9PEy0chwEHFxYAFFMSYKoqGZ4ijFN9+kLIK+osfB9NAQhrAkcwRAIgGOZiDv3fWbo0HAU28O7ZG01EA5qTnMzL+p212kngaN8CIGP2ugBLCsj0EqVh1cRiGxIhQcYIETke2qQXiS2E96rpASECRm1/yuVj5csJoNGHC7WANEgEYXh5oUlJzyIoXxuuPycAAQErgBoGAAAAAAAiACAIJ0tG5yzSzWJjsy0Rrih4k3AkSo4NZHQwzOSi5WSmLAEI3AQASDBFAiEAvDbQMdC/zpmCfaTadcEKdX+4J6RQBezSGbQODGjmr2oCIFA8eydwXZ7BtCeufn8kufj5QDD3eWtyZQ2YVqtgFWkmAUgwRQIhAMM4CfXObQh0LhoxznaNCKwqA8Paqa9eRGbm3oVzHBtYAiAZknCiqMdIr2dFhy14SzYiFhqW35bCiLh7avWc3ed9iQFHUiEDTzVb3LfMCvco7zzOuWFdkGhLtbLKX4WasPC3BAdYcaohAkZtf8rlY+XLCaDRhwu1gDRIBGF4eaFJSc8iKF8brj8nUq4AAQD9CwEBAAAAAcSzopGAf25dTDsqnA8fbi4doTq70fPEx0paDPzipMmlAQAAAAFR/////waghgEAAAAAABl2qRT8clCiEd7dxw7lonON5fB4FzUc74isQA0DAAAAAAAWABT8clCiEd7dxw7lonON5fB4FzUc7+CTBAAAAAAAF6kU+vKuH91E+TeoZAz5OkYtH08TLRyHgBoGAAAAAAAiACAIJ0tG5yzSzWJjsy0Rrih4k3AkSo4NZHQwzOSi5WSmLCChBwAAAAAAF6kUGRMIF6NV4aTfnLHiUFLTk3S4O+iHwCcJAAAAAAAiUSD46FecEm9J3tM3wZxPXxwZUfB1IWLWph8KnhVYVZQ5SwAAAAABB/wARzBEAiBNEa2QQHvmOHfwzBSi0whp+aWJoK33Qe0jjQsPnTd1JAIgVOtQVoN7yNk5GJP/Bu1qFY4Qd4nOuGU25BlKWKdn2M4BRzBEAiBcA83+f0KRLdqqojs8BrGny2dhHYk7O74Hh2bFrkYkrwIgeCHzqujgrkAqmIj3mSw6oxk/JijchL6zI8cvGWg1df4BTGlSIQNPNVvct8wK9yjvPM65YV2QaEu1sspfhZqw8LcEB1hxqiECRm1/yuVj5csJoNGHC7WANEgEYXh5oUlJzyIoXxuuPychAjxyrdtP3wmvlPDJTX/pKjhqfnDPih2FkWOGuyU1x7GxU64AAQErwCcJAAAAAAAiUSD46FecEm9J3tM3wZxPXxwZUfB1IWLWph8KnhVYVZQ5SwEIQgFAiVfbcuS0IQPtkRWsqxdTDWf8u7MtJK68HPQrycbRIvzyNVSkyddIRH4FJOhfbc9pLbkoId0tgXBR3kZR1MTO5wAiAgI8cq3bT98Jr5TwyU1/6So4an5wz4odhZFjhrslNcexsRjTTbM/VAAAgAAAAIAAAACAAQAAAAUAAAAAAA==";
+const SIGNED_TX: &str = "02000000000106a697f2dda4c21f9c9903b98247283a6938f540b825c4510d43ba1373ec5892e7000000006b483045022100eec5e167abff7c4d851463b82dfea70ec211144d2ba4552874f272fa35899c1702204d6d72de70fa4f4b8dee8ef96e55218cd7400a676e558ea59317e8f6ec9a4bf70121034f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aafdffffffa697f2dda4c21f9c9903b98247283a6938f540b825c4510d43ba1373ec5892e70100000000fdffffffa697f2dda4c21f9c9903b98247283a6938f540b825c4510d43ba1373ec5892e70200000017160014531260aa2a199e228c537dfa42c82bea2c7c1f4dfdffffffa697f2dda4c21f9c9903b98247283a6938f540b825c4510d43ba1373ec5892e70300000000fdffffffa697f2dda4c21f9c9903b98247283a6938f540b825c4510d43ba1373ec5892e704000000fc0047304402204d11ad90407be63877f0cc14a2d30869f9a589a0adf741ed238d0b0f9d377524022054eb5056837bc8d9391893ff06ed6a158e107789ceb86536e4194a58a767d8ce0147304402205c03cdfe7f42912ddaaaa23b3c06b1a7cb67611d893b3bbe078766c5ae4624af02207821f3aae8e0ae402a9888f7992c3aa3193f2628dc84beb323c72f19683575fe014c695221034f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa2102466d7fcae563e5cb09a0d1870bb580344804617879a14949cf22285f1bae3f2721023c72addb4fdf09af94f0c94d7fe92a386a7e70cf8a1d85916386bb2535c7b1b153aefdffffffa697f2dda4c21f9c9903b98247283a6938f540b825c4510d43ba1373ec5892e70500000000fdffffff0260e31600000000001600143bc28d6d92d9073fb5e3adf481795eaf446bceedb0000900000000001976a914fc7250a211deddc70ee5a2738de5f07817351cef88ac0002473044022003f9aaf8e07063164e70cad222a10a1e9696797b34ac3fd66135840f05ff175a022037fa603d5bc086f35262770fa081585869f8119f79e997f4db1dcde69afee1248121034f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa02473044022018e6620efddf59ba341c0536f0eed91b4d44039a939ccccbfa9db5da49e068df022063f6ba004b0ac8f412a561d5c4621b122141c60811391edaa417892d84f7aae9012102466d7fcae563e5cb09a0d1870bb580344804617879a14949cf22285f1bae3f270400483045022100bc36d031d0bfce99827da4da75c10a757fb827a45005ecd219b40e0c68e6af6a0220503c7b27705d9ec1b427ae7e7f24b9f8f94030f7796b72650d9856ab6015692601483045022100c33809f5ce6d08742e1a31ce768d08ac2a03c3daa9af5e4466e6de85731c1b580220199270a2a8c748af6745872d784b3622161a96df96c288b87b6af59cdde77d8901475221034f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa2102466d7fcae563e5cb09a0d1870bb580344804617879a14949cf22285f1bae3f2752ae0001408957db72e4b42103ed9115acab17530d67fcbbb32d24aebc1cf42bc9c6d122fcf23554a4c9d748447e0524e85f6dcf692db92821dd2d817051de4651d4c4cee700350c00";
+const SIGNED_TX_ID: &str = "2078309bad25454a17c2487ba6dbed379d2a62d3315a6b7e8a2bbfaf93ada0e3";
+
+/// The keys `0x11..11`, `0x22..22` and `0x33..33`.
+fn private_keys() -> Vec<PrivateKey> {
+    [0x11, 0x22, 0x33]
+        .iter()
+        .map(|byte| PrivateKey::try_from([*byte; 32].as_slice()).unwrap())
+        .collect()
+}
+
+fn psbt_error(base64: &str) -> SigningErrorType {
+    Psbt::from_base64(base64).unwrap_err().error
+}
+
+fn psbt_bytes_error(bytes: &[u8]) -> SigningErrorType {
+    Psbt::deserialize(bytes).unwrap_err().error
+}
+
+fn unsigned_bytes() -> Vec<u8> {
+    base64::decode(UNSIGNED_PSBT, false).unwrap()
+}
+
+/// Converts the PSBT v0 to PSBT v2 with the same transaction.
+fn to_v2(mut psbt: Psbt) -> Psbt {
+    let tx = psbt.unsigned_tx.take().unwrap();
+    psbt.version = PSBT_V2;
+    psbt.fallback_lock_time = Some(tx.lock_time);
+    for (input, tx_input) in psbt.inputs.iter_mut().zip(tx.inputs) {
+        input.previous_output = Some(tx_input.previous_output);
+        input.sequence = Some(tx_input.sequence);
+    }
+    for (output, tx_output) in psbt.outputs.iter_mut().zip(tx.outputs) {
+        output.amount = Some(tx_output.value);
+        output.script = Some(tx_output.script_pubkey);
+    }
+    psbt
+}
+
+#[test]
+fn test_psbt_round_trip() {
+    for base64 in [
+        UNSIGNED_PSBT,
+        FIRST_KEY_SIGNED_PSBT,
+        SIGNED_PSBT,
+        FINAL_PSBT,
+    ] {
+        let psbt = Psbt::from_base64(base64).unwrap();
+        assert_eq!(psbt.to_base64(), base64);
+    }
+
+    let psbt = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    assert_eq!(psbt.inputs.len(), 6);
+    assert_eq!(psbt.outputs.len(), 2);
+    assert_eq!(psbt.inputs[1].sighash_type, Some(0x81));
+    assert_eq!(psbt.inputs[1].bip32_derivation.len(), 1);
+    assert_eq!(psbt.outputs[0].bip32_derivation.len(), 1);
+    psbt.validate().unwrap();
+
+    let empty = Psbt::from_unsigned_transaction(psbt.unsigned_tx.clone().unwrap()).unwrap();
+    assert_eq!(empty.inputs.len(), 6);
+    assert!(empty
+        .inputs
+        .iter()
+        .all(|input| input == &Default::default()));
+}
+
+#[test]
+fn test_psbt_sign() {
+    let mut psbt = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    // One signature for every input but P2WSH 2-of-2 (2 keys) and P2SH 2-of-3 (3 keys).
+    assert_eq!(psbt.sign(&private_keys()).unwrap(), 9);
+    assert_eq!(psbt.to_base64(), SIGNED_PSBT);
+
+    // The existing signatures are kept.
+    assert_eq!(psbt.sign(&private_keys()).unwrap(), 0);
+    assert_eq!(psbt.to_base64(), SIGNED_PSBT);
+
+    let mut psbt = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    assert_eq!(psbt.sign(&private_keys()[..1]).unwrap(), 4);
+    assert_eq!(psbt.to_base64(), FIRST_KEY_SIGNED_PSBT);
+}
+
+#[test]
+fn test_psbt_finalize_and_extract() {
+    let mut psbt = Psbt::from_base64(SIGNED_PSBT).unwrap();
+    psbt.finalize().unwrap();
+    assert_eq!(psbt.to_base64(), FINAL_PSBT);
+
+    let tx = psbt.extract_transaction().unwrap();
+    assert_eq!(hex::encode(tx.encode(), false), SIGNED_TX);
+    assert_eq!(hex::encode(tx.id(), false), SIGNED_TX_ID);
+}
+
+#[test]
+fn test_psbt_combine() {
+    let mut psbt = Psbt::from_base64(FIRST_KEY_SIGNED_PSBT).unwrap();
+    let mut other = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    other.sign(&private_keys()[1..]).unwrap();
+
+    psbt.combine(other).unwrap();
+    assert_eq!(psbt.to_base64(), SIGNED_PSBT);
+
+    // The multisig inputs can't be finalized with the signatures of the first key only.
+    let mut psbt = Psbt::from_base64(FIRST_KEY_SIGNED_PSBT).unwrap();
+    let error = psbt.finalize().unwrap_err();
+    assert_eq!(error.error, SigningErrorType::Error_signatures_count);
+    assert_eq!(
+        psbt.extract_transaction().unwrap_err().error,
+        SigningErrorType::Error_signatures_count
+    );
+
+    let mut other = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    other.unsigned_tx.as_mut().unwrap().lock_time = 0;
+    assert_eq!(
+        psbt.combine(other).unwrap_err().error,
+        SigningErrorType::Error_invalid_params
+    );
+}
+
+#[test]
+fn test_psbt_v2() {
+    let v0 = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    let unsigned = to_v2(v0.clone());
+    assert_eq!(
+        unsigned.unsigned_transaction().unwrap(),
+        v0.unsigned_transaction().unwrap()
+    );
+
+    let encoded = unsigned.to_base64();
+    let mut psbt = Psbt::from_base64(&encoded).unwrap();
+    assert_eq!(psbt, unsigned);
+    assert_eq!(psbt.to_base64(), encoded);
+
+    assert_eq!(psbt.sign(&private_keys()).unwrap(), 9);
+    let signed = Psbt::from_base64(&psbt.to_base64()).unwrap();
+    assert_eq!(signed, psbt);
+    assert_eq!(signed, to_v2(Psbt::from_base64(SIGNED_PSBT).unwrap()));
+
+    psbt.finalize().unwrap();
+    let tx = psbt.extract_transaction().unwrap();
+    assert_eq!(hex::encode(tx.encode(), false), SIGNED_TX);
+}
+
+#[test]
+fn test_psbt_v2_lock_time() {
+    let mut psbt = to_v2(Psbt::from_base64(UNSIGNED_PSBT).unwrap());
+    psbt.fallback_lock_time = None;
+    assert_eq!(psbt.unsigned_transaction().unwrap().lock_time, 0);
+
+    psbt.inputs[0].required_height_lock_time = Some(800_100);
+    psbt.inputs[1].required_height_lock_time = Some(800_200);
+    psbt.inputs[1].required_time_lock_time = Some(1_700_000_000);
+    assert_eq!(psbt.unsigned_transaction().unwrap().lock_time, 800_200);
+
+    // The first input accepts a time lock only, so the height can't be used.
+    psbt.inputs[0].required_height_lock_time = None;
+    psbt.inputs[0].required_time_lock_time = Some(1_600_000_000);
+    assert_eq!(
+        psbt.unsigned_transaction().unwrap().lock_time,
+        1_700_000_000
+    );
+
+    psbt.inputs[1].required_time_lock_time = None;
+    assert_eq!(
+        psbt.unsigned_transaction().unwrap_err().error,
+        SigningErrorType::Error_invalid_params
+    );
+
+    // The lock time fields survive the serialization.
+    psbt.inputs[1].required_time_lock_time = Some(1_700_000_000);
+    let decoded = Psbt::from_base64(&psbt.to_base64()).unwrap();
+    assert_eq!(decoded, psbt);
+}
+
+#[test]
+fn test_psbt_unknown_pairs() {
+    let mut psbt = Psbt::from_base64(SIGNED_PSBT).unwrap();
+    // A global extended public key, a hash preimage and a proprietary output pair.
+    psbt.unknown
+        .insert(Key::new(0x01, vec![0x04; 79]), vec![0; 8]);
+    psbt.inputs[3]
+        .unknown
+        .insert(Key::new(0x0a, vec![0xaa; 20]), vec![0xbb; 4]);
+    psbt.outputs[1]
+        .unknown
+        .insert(Key::new(0xfc, b"tw".to_vec()), vec![0x01]);
+
+    let mut decoded = Psbt::from_base64(&psbt.to_base64()).unwrap();
+    assert_eq!(decoded, psbt);
+
+    // The hash preimages are removed on finalization, other unknown pairs are kept.
+    decoded.finalize().unwrap();
+    assert!(decoded.inputs[3].unknown.is_empty());
+    assert_eq!(decoded.unknown.len(), 1);
+    assert_eq!(decoded.outputs[1].unknown.len(), 1);
+}
+
+#[test]
+fn test_psbt_invalid_encoding() {
+    assert_eq!(
+        psbt_error("not base64!"),
+        SigningErrorType::Error_input_parse
+    );
+
+    let bytes = unsigned_bytes();
+    let mut invalid_magic = bytes.clone();
+    invalid_magic[4] = 0x00;
+    assert_eq!(
+        psbt_bytes_error(&invalid_magic),
+        SigningErrorType::Error_input_parse
+    );
+
+    let mut trailing = bytes.clone();
+    trailing.push(0x00);
+    assert_eq!(
+        psbt_bytes_error(&trailing),
+        SigningErrorType::Error_input_parse
+    );
+
+    let truncated = &bytes[..bytes.len() - 1];
+    assert_eq!(
+        psbt_bytes_error(truncated),
+        SigningErrorType::Error_input_parse
+    );
+
+    // The global map without the unsigned transaction.
+    assert_eq!(
+        psbt_bytes_error(b"psbt\xff\x00"),
+        SigningErrorType::Error_input_parse
+    );
+
+    // The sighash type of the first input repeated in the second input map.
+    let mut psbt = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    psbt.inputs[0].sighash_type = Some(1);
+    let encoded = psbt.serialize();
+    let pair = [0x01, 0x03, 0x04, 0x01, 0x00, 0x00, 0x00];
+    let position = encoded
+        .windows(pair.len())
+        .position(|window| window == pair)
+        .unwrap();
+    let mut duplicate = encoded.clone();
+    duplicate.splice(position..position, pair);
+    assert_eq!(
+        psbt_bytes_error(&duplicate),
+        SigningErrorType::Error_input_parse
+    );
+
+    // PSBT v2 fields are not allowed in PSBT v0.
+    let mut psbt = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    psbt.inputs[0].sequence = Some(0);
+    assert_eq!(
+        psbt_bytes_error(&psbt.serialize()),
+        SigningErrorType::Error_input_parse
+    );
+
+    // PSBT v2 output must have the amount and the script.
+    let mut psbt = to_v2(Psbt::from_base64(UNSIGNED_PSBT).unwrap());
+    psbt.outputs[0].script = None;
+    assert_eq!(
+        psbt_bytes_error(&psbt.serialize()),
+        SigningErrorType::Error_input_parse
+    );
+}
+
+#[test]
+fn test_psbt_invalid_utxo() {
+    let mut psbt = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    let utxo = psbt.inputs[0].non_witness_utxo.clone().unwrap();
+    psbt.inputs[0].witness_utxo = Some(TransactionOutput {
+        value: utxo.outputs[0].value + 1,
+        script_pubkey: utxo.outputs[0].script_pubkey.clone(),
+    });
+    assert_eq!(
+        psbt.validate().unwrap_err().error,
+        SigningErrorType::Error_invalid_utxo
+    );
+    assert_eq!(
+        psbt.sign(&private_keys()).unwrap_err().error,
+        SigningErrorType::Error_invalid_utxo
+    );
+
+    // The non-witness UTXO of another transaction.
+    let mut psbt = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    psbt.inputs[4].non_witness_utxo.as_mut().unwrap().lock_time = 1;
+    assert_eq!(
+        psbt.validate().unwrap_err().error,
+        SigningErrorType::Error_invalid_utxo
+    );
+
+    // A legacy input can't be signed with the witness UTXO only.
+    let mut psbt = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    psbt.inputs[0].witness_utxo = Some(utxo.outputs[0].clone());
+    psbt.inputs[0].non_witness_utxo = None;
+    assert_eq!(
+        psbt.sign(&private_keys()).unwrap_err().error,
+        SigningErrorType::Error_missing_input_utxos
+    );
+
+    // A redeem script that doesn't match the P2SH output.
+    let mut psbt = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    psbt.inputs[4].redeem_script = psbt.inputs[2].redeem_script.clone();
+    assert_eq!(
+        psbt.sign(&private_keys()).unwrap_err().error,
+        SigningErrorType::Error_script_redeem
+    );
+
+    // The taproot input is not signed if the UTXO of any input is missing.
+    let mut psbt = Psbt::from_base64(UNSIGNED_PSBT).unwrap();
+    psbt.inputs[1].witness_utxo = None;
+    assert_eq!(
+        psbt.sign(&private_keys()).unwrap_err().error,
+        SigningErrorType::Error_missing_input_utxos
+    );
+}
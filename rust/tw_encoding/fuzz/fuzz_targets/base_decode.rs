@@ -1,7 +1,8 @@
 #![no_main]
 
 use libfuzzer_sys::{arbitrary, fuzz_target};
-use tw_encoding::{base32, base58, base64, ffi::Base58Alphabet};
+use tw_encoding::{base32, base58, base64, bech32, ffi::Base58Alphabet};
+use tw_hash::sha2::sha256d;
 
 #[derive(arbitrary::Arbitrary, Debug)]
 struct BaseDecodeInput<'a> {
@@ -14,5 +15,8 @@ struct BaseDecodeInput<'a> {
 fuzz_target!(|input: BaseDecodeInput<'_>| {
     base32::decode(input.data, None, input.padding).ok();
     base58::decode(input.data, input.alphabet_base58.into()).ok();
+    base58::decode_check(input.data, input.alphabet_base58.into(), sha256d).ok();
     base64::decode(input.data, input.is_url).ok();
+    bech32::decode_with_limit(input.data, usize::MAX).ok();
+    bech32::decode_segwit("bc", input.data).ok();
 });
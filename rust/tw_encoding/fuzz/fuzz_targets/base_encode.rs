@@ -1,7 +1,8 @@
 #![no_main]
 
 use libfuzzer_sys::{arbitrary, fuzz_target};
-use tw_encoding::{base32, base58, base64, ffi::Base58Alphabet};
+use tw_encoding::{base32, base58, base64, bech32, ffi::Base58Alphabet};
+use tw_hash::sha2::sha256d;
 
 #[derive(arbitrary::Arbitrary, Debug)]
 struct BaseEncodeInput<'a> {
@@ -14,5 +15,13 @@ struct BaseEncodeInput<'a> {
 fuzz_target!(|input: BaseEncodeInput<'_>| {
     base32::encode(input.data, None, input.padding).ok();
     base58::encode(input.data, input.alphabet_base58.into());
+    base58::encode_check(input.data, input.alphabet_base58.into(), sha256d);
     base64::encode(input.data, input.is_url);
+    bech32::encode_with_limit(
+        "bc",
+        &bech32::to_base32(input.data),
+        bech32::Variant::Bech32m,
+        usize::MAX,
+    )
+    .ok();
 });
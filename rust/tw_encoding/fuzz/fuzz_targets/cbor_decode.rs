@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tw_encoding::cbor;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(value) = cbor::decode(data) {
+        let _ = value.to_string();
+        // A decoded value must be encoded without errors and decoded back.
+        let encoded = cbor::encode(&value).unwrap();
+        cbor::decode(&encoded).unwrap();
+        cbor::encode_canonical(&value).ok();
+    }
+});
@@ -0,0 +1,39 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tw_encoding::cbor::{self, Value};
+
+fn depth(value: &Value) -> usize {
+    match value {
+        Value::Array(items) | Value::IndefiniteArray(items) => {
+            1 + items.iter().map(depth).max().unwrap_or_default()
+        },
+        Value::Map(pairs) | Value::IndefiniteMap(pairs) => {
+            1 + pairs
+                .iter()
+                .map(|(key, value)| depth(key).max(depth(value)))
+                .max()
+                .unwrap_or_default()
+        },
+        Value::Tag(_, item) => 1 + depth(item),
+        _ => 0,
+    }
+}
+
+fuzz_target!(|value: Value| {
+    let encoded = cbor::encode(&value);
+    let canonical = cbor::encode_canonical(&value);
+
+    // Every encoded value must be well-formed, and too deeply nested values must be rejected.
+    if depth(&value) <= cbor::MAX_DEPTH {
+        if let Ok(encoded) = encoded {
+            cbor::decode(&encoded).unwrap();
+        }
+        if let Ok(canonical) = canonical {
+            cbor::decode(&canonical).unwrap();
+        }
+    } else {
+        assert!(encoded.is_err());
+        assert!(canonical.is_err());
+    }
+});
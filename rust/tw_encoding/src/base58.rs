@@ -8,6 +8,14 @@ use crate::{EncodingError, EncodingResult};
 use bs58::decode::Error;
 pub use bs58::Alphabet;
 
+/// The number of checksum bytes appended to the payload.
+pub const CHECKSUM_LEN: usize = 4;
+
+/// Computes the hash of the payload whose first [`CHECKSUM_LEN`] bytes are used as the checksum,
+/// e.g. `tw_hash::sha2::sha256d` for Bitcoin.
+/// The hash must be at least [`CHECKSUM_LEN`] bytes long.
+pub type ChecksumHasher = fn(&[u8]) -> Vec<u8>;
+
 impl From<Error> for EncodingError {
     fn from(_: Error) -> Self {
         EncodingError::InvalidInput
@@ -25,10 +33,45 @@ pub fn decode(input: &str, alphabet: &Alphabet) -> EncodingResult<Vec<u8>> {
         .map_err(EncodingError::from)
 }
 
+/// Encodes the `input` data as base58 with a 4-byte checksum appended.
+pub fn encode_check(input: &[u8], alphabet: &Alphabet, hasher: ChecksumHasher) -> String {
+    let mut data = Vec::with_capacity(input.len() + CHECKSUM_LEN);
+    data.extend_from_slice(input);
+    data.extend_from_slice(&hasher(input)[..CHECKSUM_LEN]);
+    encode(&data, alphabet)
+}
+
+/// Decodes the base58 `input` string and validates its 4-byte checksum.
+/// Returns the payload without the checksum.
+pub fn decode_check(
+    input: &str,
+    alphabet: &Alphabet,
+    hasher: ChecksumHasher,
+) -> EncodingResult<Vec<u8>> {
+    let mut data = decode(input, alphabet)?;
+    if data.len() < CHECKSUM_LEN {
+        return Err(EncodingError::InvalidInput);
+    }
+
+    let checksum_pos = data.len() - CHECKSUM_LEN;
+    if hasher(&data[..checksum_pos]).get(..CHECKSUM_LEN) != Some(&data[checksum_pos..]) {
+        return Err(EncodingError::InvalidChecksum);
+    }
+
+    data.truncate(checksum_pos);
+    Ok(data)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// A simple checksum to test the Base58Check format without depending on the hash functions.
+    fn sum_checksum(input: &[u8]) -> Vec<u8> {
+        let sum = input.iter().fold(0_u8, |sum, byte| sum.wrapping_add(*byte));
+        vec![sum; CHECKSUM_LEN]
+    }
+
     #[test]
     fn test_base58_encode() {
         let data = b"Hello, world!";
@@ -46,4 +89,39 @@ mod tests {
         let result = decode(data, Alphabet::BITCOIN).unwrap();
         assert_eq!(result, expected.to_vec());
     }
+
+    #[test]
+    fn test_base58_check() {
+        let data = b"Hello, world!";
+        let encoded = encode_check(data, Alphabet::BITCOIN, sum_checksum);
+
+        let mut expected = data.to_vec();
+        expected.extend_from_slice(&sum_checksum(data));
+        assert_eq!(encoded, encode(&expected, Alphabet::BITCOIN));
+        assert_eq!(
+            decode_check(&encoded, Alphabet::BITCOIN, sum_checksum).unwrap(),
+            data.to_vec()
+        );
+    }
+
+    #[test]
+    fn test_base58_check_invalid() {
+        let mut data = b"Hello, world!".to_vec();
+        data.extend_from_slice(&[0; CHECKSUM_LEN]);
+        let encoded = encode(&data, Alphabet::BITCOIN);
+        assert_eq!(
+            decode_check(&encoded, Alphabet::BITCOIN, sum_checksum),
+            Err(EncodingError::InvalidChecksum)
+        );
+        // Too short to contain a checksum.
+        assert_eq!(
+            decode_check("111", Alphabet::BITCOIN, sum_checksum),
+            Err(EncodingError::InvalidInput)
+        );
+        // Invalid character.
+        assert_eq!(
+            decode_check("0OIl", Alphabet::BITCOIN, sum_checksum),
+            Err(EncodingError::InvalidInput)
+        );
+    }
 }
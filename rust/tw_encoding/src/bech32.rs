@@ -0,0 +1,553 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! [BIP173](https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki) Bech32 and
+//! [BIP350](https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki) Bech32m encoding.
+//! Ported from `TW::Bech32` and `TW::Bitcoin::SegwitAddress`.
+
+use crate::{EncodingError, EncodingResult};
+
+/// The maximum length of a Bech32 string according to BIP173.
+/// Some chains (e.g. Cardano) use longer strings, see [`encode_with_limit`] and [`decode_with_limit`].
+pub const BECH32_MAX_LENGTH: usize = 90;
+
+/// cbindgen:ignore
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+/// cbindgen:ignore
+const SEPARATOR: char = '1';
+/// cbindgen:ignore
+const CHECKSUM_LEN: usize = 6;
+/// cbindgen:ignore
+const BECH32_CONST: u32 = 0x01;
+/// cbindgen:ignore
+const BECH32M_CONST: u32 = 0x2bc830a3;
+/// cbindgen:ignore
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+/// cbindgen:ignore
+const WITNESS_VERSION_MAX: u8 = 16;
+/// cbindgen:ignore
+const WITNESS_PROGRAM_MIN_LEN: usize = 2;
+/// cbindgen:ignore
+const WITNESS_PROGRAM_MAX_LEN: usize = 40;
+/// cbindgen:ignore
+const WITNESS_V0_PROGRAM_LENS: [usize; 2] = [20, 32];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Variant {
+    fn checksum_const(self) -> u32 {
+        match self {
+            Variant::Bech32 => BECH32_CONST,
+            Variant::Bech32m => BECH32M_CONST,
+        }
+    }
+}
+
+/// A decoded Bech32 string.
+#[derive(Debug, PartialEq)]
+pub struct Decoded {
+    /// The human-readable part in lowercase.
+    pub hrp: String,
+    /// 5-bit values without the checksum.
+    pub data: Vec<u8>,
+    pub variant: Variant,
+}
+
+/// Encodes the 5-bit `data` values as a Bech32 string of at most [`BECH32_MAX_LENGTH`] characters.
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> EncodingResult<String> {
+    encode_with_limit(hrp, data, variant, BECH32_MAX_LENGTH)
+}
+
+/// Encodes the 5-bit `data` values as a Bech32 string of at most `max_len` characters.
+/// The `hrp` must be lowercase.
+pub fn encode_with_limit(
+    hrp: &str,
+    data: &[u8],
+    variant: Variant,
+    max_len: usize,
+) -> EncodingResult<String> {
+    if hrp.is_empty() || !hrp.bytes().all(is_valid_hrp_char) || hrp.bytes().any(is_upper) {
+        return Err(EncodingError::InvalidInput);
+    }
+    if data.iter().any(|value| *value >= 32) {
+        return Err(EncodingError::InvalidInput);
+    }
+    if hrp.len() + 1 + data.len() + CHECKSUM_LEN > max_len {
+        return Err(EncodingError::InvalidInput);
+    }
+
+    let checksum = create_checksum(hrp, data, variant);
+    let mut res = String::with_capacity(hrp.len() + 1 + data.len() + CHECKSUM_LEN);
+    res.push_str(hrp);
+    res.push(SEPARATOR);
+    res.extend(
+        data.iter()
+            .chain(checksum.iter())
+            .map(|value| CHARSET[*value as usize] as char),
+    );
+    Ok(res)
+}
+
+/// Decodes a Bech32 or Bech32m string of at most [`BECH32_MAX_LENGTH`] characters.
+pub fn decode(s: &str) -> EncodingResult<Decoded> {
+    decode_with_limit(s, BECH32_MAX_LENGTH)
+}
+
+/// Decodes a Bech32 or Bech32m string of at most `max_len` characters.
+/// Mixed-case strings are rejected.
+pub fn decode_with_limit(s: &str, max_len: usize) -> EncodingResult<Decoded> {
+    if s.len() > max_len || !s.bytes().all(is_valid_hrp_char) {
+        return Err(EncodingError::InvalidInput);
+    }
+    if s.bytes().any(is_upper) && s.bytes().any(|c| c.is_ascii_lowercase()) {
+        return Err(EncodingError::InvalidInput);
+    }
+    let s = s.to_ascii_lowercase();
+
+    let separator_pos = s.rfind(SEPARATOR).ok_or(EncodingError::InvalidInput)?;
+    if separator_pos == 0 || separator_pos + 1 + CHECKSUM_LEN > s.len() {
+        return Err(EncodingError::InvalidInput);
+    }
+
+    let hrp = &s[..separator_pos];
+    let values = s[separator_pos + 1..]
+        .bytes()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|ch| *ch == c)
+                .map(|value| value as u8)
+                .ok_or(EncodingError::InvalidInput)
+        })
+        .collect::<EncodingResult<Vec<u8>>>()?;
+
+    let variant = verify_checksum(hrp, &values).ok_or(EncodingError::InvalidInput)?;
+    let data_len = values.len() - CHECKSUM_LEN;
+    Ok(Decoded {
+        hrp: hrp.to_string(),
+        data: values[..data_len].to_vec(),
+        variant,
+    })
+}
+
+/// Regroups the `data` bits from `from_bits`-bit to `to_bits`-bit values.
+/// If `pad` is false, the incomplete trailing group must consist of zero bits only and is dropped.
+pub fn convert_bits(
+    data: &[u8],
+    from_bits: u32,
+    to_bits: u32,
+    pad: bool,
+) -> EncodingResult<Vec<u8>> {
+    if !(1..=8).contains(&from_bits) || !(1..=8).contains(&to_bits) {
+        return Err(EncodingError::InvalidInput);
+    }
+
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value: u32 = (1 << to_bits) - 1;
+    let mut res = Vec::with_capacity((data.len() * from_bits as usize).div_ceil(to_bits as usize));
+    for value in data {
+        let value = *value as u32;
+        if value >> from_bits != 0 {
+            return Err(EncodingError::InvalidInput);
+        }
+        acc = (acc << from_bits) | value;
+        bits += from_bits;
+        while bits >= to_bits {
+            bits -= to_bits;
+            res.push(((acc >> bits) & max_value) as u8);
+        }
+        // Keep the unprocessed bits only.
+        acc &= (1 << bits) - 1;
+    }
+
+    if pad {
+        if bits > 0 {
+            res.push(((acc << (to_bits - bits)) & max_value) as u8);
+        }
+    } else if bits >= from_bits || ((acc << (to_bits - bits)) & max_value) != 0 {
+        return Err(EncodingError::InvalidInput);
+    }
+    Ok(res)
+}
+
+/// Regroups the bytes into 5-bit values padding the last one with zeros.
+pub fn to_base32(bytes: &[u8]) -> Vec<u8> {
+    convert_bits(bytes, 8, 5, true).expect("Regrouping bytes into 5-bit values cannot fail")
+}
+
+/// Regroups the 5-bit values into bytes.
+pub fn from_base32(data: &[u8]) -> EncodingResult<Vec<u8>> {
+    convert_bits(data, 5, 8, false)
+}
+
+/// A segregated witness program.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WitnessProgram {
+    pub version: u8,
+    pub program: Vec<u8>,
+}
+
+impl WitnessProgram {
+    /// Creates a witness program checking the version and program length according to BIP141.
+    pub fn new(version: u8, program: Vec<u8>) -> EncodingResult<WitnessProgram> {
+        if version > WITNESS_VERSION_MAX {
+            return Err(EncodingError::InvalidInput);
+        }
+        if !(WITNESS_PROGRAM_MIN_LEN..=WITNESS_PROGRAM_MAX_LEN).contains(&program.len()) {
+            return Err(EncodingError::InvalidInput);
+        }
+        if version == 0 && !WITNESS_V0_PROGRAM_LENS.contains(&program.len()) {
+            return Err(EncodingError::InvalidInput);
+        }
+        Ok(WitnessProgram { version, program })
+    }
+
+    /// Returns the checksum variant required by BIP350 for the witness version.
+    pub fn variant(&self) -> Variant {
+        if self.version == 0 {
+            Variant::Bech32
+        } else {
+            Variant::Bech32m
+        }
+    }
+}
+
+/// Encodes the witness program as a segwit address with the given `hrp`, e.g. "bc".
+pub fn encode_segwit(hrp: &str, witness: &WitnessProgram) -> EncodingResult<String> {
+    let mut data = vec![witness.version];
+    data.extend(to_base32(&witness.program));
+    encode(hrp, &data, witness.variant())
+}
+
+/// Decodes the segwit `address` checking if its human-readable part equals to `hrp`.
+pub fn decode_segwit(hrp: &str, address: &str) -> EncodingResult<WitnessProgram> {
+    let decoded = decode(address)?;
+    if decoded.hrp != hrp.to_ascii_lowercase() {
+        return Err(EncodingError::InvalidInput);
+    }
+
+    let (version, program) = decoded
+        .data
+        .split_first()
+        .ok_or(EncodingError::InvalidInput)?;
+    let witness = WitnessProgram::new(*version, from_base32(program)?)?;
+    if witness.variant() != decoded.variant {
+        return Err(EncodingError::InvalidInput);
+    }
+    Ok(witness)
+}
+
+fn is_valid_hrp_char(c: u8) -> bool {
+    (33..=126).contains(&c)
+}
+
+fn is_upper(c: u8) -> bool {
+    c.is_ascii_uppercase()
+}
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    let mut chk: u32 = 1;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+/// Expands the human-readable part for the checksum computation.
+fn expand_hrp(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes()
+        .map(|c| c >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|c| c & 0x1f))
+}
+
+fn create_checksum(hrp: &str, data: &[u8], variant: Variant) -> [u8; CHECKSUM_LEN] {
+    let values = expand_hrp(hrp)
+        .chain(data.iter().copied())
+        .chain([0; CHECKSUM_LEN]);
+    let modulo = polymod(values) ^ variant.checksum_const();
+
+    let mut checksum = [0; CHECKSUM_LEN];
+    for (i, value) in checksum.iter_mut().enumerate() {
+        *value = ((modulo >> (5 * (CHECKSUM_LEN - 1 - i))) & 0x1f) as u8;
+    }
+    checksum
+}
+
+fn verify_checksum(hrp: &str, values: &[u8]) -> Option<Variant> {
+    match polymod(expand_hrp(hrp).chain(values.iter().copied())) {
+        BECH32_CONST => Some(Variant::Bech32),
+        BECH32M_CONST => Some(Variant::Bech32m),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    struct DecodeTestData {
+        encoded: &'static str,
+        variant: Option<Variant>,
+        hrp: &'static str,
+        data_hex: &'static str,
+    }
+
+    const fn valid(
+        encoded: &'static str,
+        variant: Variant,
+        hrp: &'static str,
+        data_hex: &'static str,
+    ) -> DecodeTestData {
+        DecodeTestData {
+            encoded,
+            variant: Some(variant),
+            hrp,
+            data_hex,
+        }
+    }
+
+    const fn invalid(encoded: &'static str) -> DecodeTestData {
+        DecodeTestData {
+            encoded,
+            variant: None,
+            hrp: "",
+            data_hex: "",
+        }
+    }
+
+    /// Ported from `tests/common/Bech32Tests.cpp`.
+    const DECODE_TEST_DATA: &[DecodeTestData] = &[
+        valid("bnb1grpf0955h0ykzq3ar5nmum7y6gdfl6lxfn46h2", Variant::Bech32, "bnb", "080301090f051414170f04160200111d0314131b1c1b1e041a080d091f1a1f06"),
+        valid("bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx", Variant::Bech32, "bc", "010e140f070d1a001912060b0d081504140311021d030c1d03040f1814060e1e160e140f070d1a001912060b0d081504140311021d030c1d03040f1814060e1e16"),
+        valid("bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y", Variant::Bech32m, "bc", "010e140f070d1a001912060b0d081504140311021d030c1d03040f1814060e1e160e140f070d1a001912060b0d081504140311021d030c1d03040f1814060e1e16"),
+        // 1-char diff
+        invalid("bnb1grpf0955h0ykzq3ar6nmum7y6gdfl6lxfn46h2"),
+        // BIP173
+        valid("A12UEL5L", Variant::Bech32, "a", ""),
+        valid("a12uel5l", Variant::Bech32, "a", ""),
+        valid("an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs", Variant::Bech32, "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio", ""),
+        valid("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw", Variant::Bech32, "abcdef", "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+        valid("11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j", Variant::Bech32, "1", "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+        valid("split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w", Variant::Bech32, "split", "18171918161c01100b1d0819171d130d10171d16191c01100b03191d1b1903031d130b190303190d181d01190303190d"),
+        valid("?1ezyfcl", Variant::Bech32, "?", ""),
+        // BIP350
+        valid("A1LQFN3A", Variant::Bech32m, "a", ""),
+        valid("a1lqfn3a", Variant::Bech32m, "a", ""),
+        valid("an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6", Variant::Bech32m, "an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber1", ""),
+        valid("abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx", Variant::Bech32m, "abcdef", "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100"),
+        valid("11llllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllludsr8", Variant::Bech32m, "1", "1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f"),
+        valid("split1checkupstagehandshakeupstreamerranterredcaperredlc445v", Variant::Bech32m, "split", "18171918161c01100b1d0819171d130d10171d16191c01100b03191d1b1903031d130b190303190d181d01190303190d"),
+        valid("?1v759aa", Variant::Bech32m, "?", ""),
+        // Invalid BIP173
+        invalid("\x201nwldj5"),
+        invalid("\x7F1axkwrx"),
+        invalid("\u{80}1eym55h"),
+        invalid("pzry9x0s0muk"),
+        invalid("1pzry9x0s0muk"),
+        invalid("x1b4n0q5v"),
+        invalid("lt1igcx5c0"),
+        invalid("li1dgmt3"),
+        invalid("de1lg7wt\u{FF}"),
+        invalid("A1G7SGD8"),
+        invalid("10a06t8"),
+        invalid("1qzzfhee"),
+        // Invalid BIP350
+        invalid("\x201xj0phk"),
+        invalid("\x7F1g6xzxy"),
+        invalid("\u{80}1vctc34"),
+        invalid("qyrz8wqd2c9m"),
+        invalid("1qyrz8wqd2c9m"),
+        invalid("y1b0jsk6g"),
+        invalid("in1muywd"),
+        invalid("mm1crxm3i"),
+        invalid("au1s5cgom"),
+        invalid("M1VUXWEZ"),
+        invalid("16plkw9"),
+        invalid("1p2gdwpf"),
+        // Too long
+        invalid("an84characterslonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11d6pts4"),
+    ];
+
+    #[test]
+    fn test_bech32_decode() {
+        for test in DECODE_TEST_DATA {
+            let res = decode(test.encoded);
+            let Some(variant) = test.variant else {
+                assert!(res.is_err(), "'{}' must be invalid", test.encoded);
+                continue;
+            };
+
+            let decoded = res.unwrap();
+            assert_eq!(decoded.variant, variant, "{}", test.encoded);
+            assert_eq!(decoded.hrp, test.hrp);
+            assert_eq!(hex::encode(decoded.data, false), test.data_hex);
+        }
+    }
+
+    #[test]
+    fn test_bech32_encode() {
+        for test in DECODE_TEST_DATA {
+            let Some(variant) = test.variant else {
+                continue;
+            };
+            let data = hex::decode(test.data_hex).unwrap();
+            let encoded = encode(test.hrp, &data, variant).unwrap();
+            assert_eq!(encoded, test.encoded.to_ascii_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_bech32_encode_invalid() {
+        encode("", &[], Variant::Bech32).unwrap_err();
+        encode("BC", &[], Variant::Bech32).unwrap_err();
+        encode("b c", &[], Variant::Bech32).unwrap_err();
+        encode("bc", &[32], Variant::Bech32).unwrap_err();
+        encode("bc", &[0; 82], Variant::Bech32).unwrap_err();
+    }
+
+    #[test]
+    fn test_bech32_length_limit() {
+        // Cardano Shelley base address.
+        let address = "addr1q8043m5heeaydnvtmmkyuhe6qv5havvhsf0d26q3jygsspxlyfpyk6yqkw0yhtyvtr0flekj84u64az82cufmqn65zdsylzk23";
+        assert!(decode(address).is_err());
+
+        let decoded = decode_with_limit(address, 120).unwrap();
+        assert_eq!(decoded.hrp, "addr");
+        assert_eq!(decoded.variant, Variant::Bech32);
+        assert_eq!(from_base32(&decoded.data).unwrap().len(), 57);
+
+        assert!(encode("addr", &decoded.data, Variant::Bech32).is_err());
+        let encoded = encode_with_limit("addr", &decoded.data, Variant::Bech32, 120).unwrap();
+        assert_eq!(encoded, address);
+    }
+
+    #[test]
+    fn test_convert_bits() {
+        let bytes = hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+        let data = to_base32(&bytes);
+        assert_eq!(data.len(), 32);
+        assert_eq!(from_base32(&data).unwrap(), bytes);
+
+        // Non-zero padding.
+        from_base32(&[0x1f]).unwrap_err();
+        // Padding of more than 4 bits.
+        from_base32(&[0, 0, 0]).unwrap_err();
+        // Values out of range.
+        from_base32(&[32]).unwrap_err();
+        convert_bits(&[1], 0, 8, true).unwrap_err();
+    }
+
+    struct SegwitTestData {
+        address: &'static str,
+        script_pubkey: &'static str,
+    }
+
+    /// Ported from `tests/chains/Bitcoin/SegwitAddressTests.cpp`.
+    const VALID_SEGWIT_ADDRESSES: &[SegwitTestData] = &[
+        SegwitTestData { address: "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", script_pubkey: "0014751e76e8199196d454941c45d1b3a323f1433bd6" },
+        SegwitTestData { address: "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7", script_pubkey: "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262" },
+        SegwitTestData { address: "bc1qpjult34k9spjfym8hss2jrwjgf0xjf40ze0pp8", script_pubkey: "00140cb9f5c6b62c03249367bc20a90dd2425e6926af" },
+        SegwitTestData { address: "bc1ptmsk7c2yut2xah4pgflpygh2s7fh0cpfkrza9cjj29awapv53mrslgd5cf", script_pubkey: "51205ee16f6144e2d46edea1427e1222ea879377e029b0c5d2e252517aee85948ec7" },
+        SegwitTestData { address: "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y", script_pubkey: "5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6" },
+        SegwitTestData { address: "BC1SW50QGDZ25J", script_pubkey: "6002751e" },
+        SegwitTestData { address: "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs", script_pubkey: "5210751e76e8199196d454941c45d1b3a323" },
+        SegwitTestData { address: "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c", script_pubkey: "5120000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433" },
+        SegwitTestData { address: "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0", script_pubkey: "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798" },
+    ];
+
+    const INVALID_SEGWIT_ADDRESSES: &[&str] = &[
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+        "BC13W508D6QEJXTDG4Y5R3ZARVARY0C5XW7KN40WF2",
+        "bc1rw5uspcuh",
+        "bc10w508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kw5rljs90",
+        "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sL5k7",
+        "bc1zw508d6qejxtdg4y5r3zarvaryvqyzf3du",
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3pjxtptv",
+        "bc1gmk9yu",
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+        "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf",
+        "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL",
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+        "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47",
+        "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+        "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R",
+        "bc1pw5dgrnzv",
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav",
+        "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq",
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf",
+        "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j",
+        "bc1q9zpgru",
+        // Invalid human-readable part.
+        "tc1qw508d6qejxtdg4y5r3zarvary0c5xw7kg3g4ty",
+    ];
+
+    fn script_pubkey(witness: &WitnessProgram) -> String {
+        let op_version = match witness.version {
+            0 => 0,
+            version => 0x50 + version,
+        };
+        let mut script = vec![op_version, witness.program.len() as u8];
+        script.extend_from_slice(&witness.program);
+        hex::encode(script, false)
+    }
+
+    fn hrp_of(address: &str) -> &str {
+        &address[..address.rfind(SEPARATOR).unwrap()]
+    }
+
+    #[test]
+    fn test_segwit_valid() {
+        for test in VALID_SEGWIT_ADDRESSES {
+            let hrp = hrp_of(test.address).to_ascii_lowercase();
+            let witness = decode_segwit(&hrp, test.address).unwrap();
+            assert_eq!(script_pubkey(&witness), test.script_pubkey);
+
+            let encoded = encode_segwit(&hrp, &witness).unwrap();
+            assert_eq!(encoded, test.address.to_ascii_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_segwit_invalid() {
+        for address in INVALID_SEGWIT_ADDRESSES {
+            assert!(
+                decode_segwit("bc", address).is_err() && decode_segwit("tb", address).is_err(),
+                "'{address}' must be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_segwit_encode_invalid() {
+        let invalid = [
+            ("BC", 0, 20),
+            ("bc", 0, 21),
+            ("bc", 17, 32),
+            ("bc", 1, 1),
+            ("bc", 16, 41),
+        ];
+        for (hrp, version, program_len) in invalid {
+            let res = WitnessProgram::new(version, vec![0; program_len])
+                .and_then(|witness| encode_segwit(hrp, &witness));
+            assert!(res.is_err(), "{hrp} {version} {program_len}");
+        }
+    }
+}
@@ -0,0 +1,411 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::float::f16_to_f64;
+use super::*;
+use crate::{EncodingError, EncodingResult};
+
+/// Decodes a single well-formed CBOR item that must take the whole `input`.
+/// See RFC 8949, Appendix C.
+pub fn decode(input: &[u8]) -> EncodingResult<Value> {
+    let mut decoder = Decoder { input };
+    let value = decoder.read_value(0)?;
+    if !decoder.input.is_empty() {
+        return Err(EncodingError::InvalidInput);
+    }
+    Ok(value)
+}
+
+enum Argument {
+    Value(u64),
+    Indefinite,
+}
+
+struct Head {
+    major: u8,
+    /// The "additional information" bits of the initial byte.
+    info: u8,
+    argument: Argument,
+}
+
+struct Decoder<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    /// Reads an item. The "break" stop code is not allowed.
+    fn read_value(&mut self, depth: usize) -> EncodingResult<Value> {
+        self.read_item(depth)?.ok_or(EncodingError::InvalidInput)
+    }
+
+    /// Reads an item or returns `None` if the "break" stop code is read.
+    fn read_item(&mut self, depth: usize) -> EncodingResult<Option<Value>> {
+        let head = self.read_head()?;
+
+        let len = match head.argument {
+            Argument::Value(argument) => argument,
+            Argument::Indefinite => return self.read_indefinite(head.major, depth),
+        };
+
+        let value = match head.major {
+            MAJOR_UNSIGNED => Value::Unsigned(len),
+            MAJOR_NEGATIVE => Value::Negative(len),
+            MAJOR_BYTES => Value::Bytes(self.read_slice(len)?.to_vec()),
+            MAJOR_TEXT => Value::Text(self.read_text(len)?),
+            MAJOR_ARRAY => {
+                let depth = next_depth(depth)?;
+                let len = self.check_items_len(len, 1)?;
+                let items = (0..len)
+                    .map(|_| self.read_value(depth))
+                    .collect::<EncodingResult<_>>()?;
+                Value::Array(items)
+            },
+            MAJOR_MAP => {
+                let depth = next_depth(depth)?;
+                let len = self.check_items_len(len, 2)?;
+                let pairs = (0..len)
+                    .map(|_| Ok((self.read_value(depth)?, self.read_value(depth)?)))
+                    .collect::<EncodingResult<_>>()?;
+                Value::Map(pairs)
+            },
+            MAJOR_TAG => Value::Tag(len, Box::new(self.read_value(next_depth(depth)?)?)),
+            _ => simple_or_float(head.info, len)?,
+        };
+        Ok(Some(value))
+    }
+
+    /// Reads the content of an indefinite-length item up to the "break" stop code.
+    /// Returns `None` if the item is the "break" stop code itself.
+    fn read_indefinite(&mut self, major: u8, depth: usize) -> EncodingResult<Option<Value>> {
+        let value = match major {
+            MAJOR_BYTES => Value::Bytes(self.read_chunks(MAJOR_BYTES)?.concat()),
+            MAJOR_TEXT => {
+                let chunks = self
+                    .read_chunks(MAJOR_TEXT)?
+                    .into_iter()
+                    // Every chunk must be a valid UTF-8 string by itself.
+                    .map(|chunk| {
+                        std::str::from_utf8(chunk).map_err(|_| EncodingError::InvalidInput)
+                    })
+                    .collect::<EncodingResult<Vec<_>>>()?;
+                Value::Text(chunks.concat())
+            },
+            MAJOR_ARRAY => {
+                let depth = next_depth(depth)?;
+                let mut items = Vec::new();
+                while let Some(item) = self.read_item(depth)? {
+                    items.push(item);
+                }
+                Value::IndefiniteArray(items)
+            },
+            MAJOR_MAP => {
+                let depth = next_depth(depth)?;
+                let mut pairs = Vec::new();
+                while let Some(key) = self.read_item(depth)? {
+                    pairs.push((key, self.read_value(depth)?));
+                }
+                Value::IndefiniteMap(pairs)
+            },
+            MAJOR_SIMPLE => return Ok(None),
+            // Integers and tags cannot have an indefinite length.
+            _ => return Err(EncodingError::InvalidInput),
+        };
+        Ok(Some(value))
+    }
+
+    /// Reads definite-length string chunks of the given major type up to the "break" stop code.
+    fn read_chunks(&mut self, major: u8) -> EncodingResult<Vec<&'a [u8]>> {
+        let mut chunks = Vec::new();
+        loop {
+            if self.input.first() == Some(&BREAK) {
+                self.input = &self.input[1..];
+                return Ok(chunks);
+            }
+
+            let head = self.read_head()?;
+            match head.argument {
+                Argument::Value(len) if head.major == major => chunks.push(self.read_slice(len)?),
+                _ => return Err(EncodingError::InvalidInput),
+            }
+        }
+    }
+
+    fn read_head(&mut self) -> EncodingResult<Head> {
+        let initial = self.read_byte()?;
+        let major = initial >> 5;
+        let info = initial & 0x1f;
+
+        let argument = match info {
+            0..=23 => Argument::Value(info as u64),
+            24 => Argument::Value(self.read_byte()? as u64),
+            25 => Argument::Value(u16::from_be_bytes(self.read_array()?) as u64),
+            26 => Argument::Value(u32::from_be_bytes(self.read_array()?) as u64),
+            27 => Argument::Value(u64::from_be_bytes(self.read_array()?)),
+            INDEFINITE => Argument::Indefinite,
+            // 28..=30 are reserved.
+            _ => return Err(EncodingError::InvalidInput),
+        };
+        Ok(Head {
+            major,
+            info,
+            argument,
+        })
+    }
+
+    fn read_text(&mut self, len: u64) -> EncodingResult<String> {
+        let bytes = self.read_slice(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| EncodingError::InvalidInput)
+    }
+
+    fn read_byte(&mut self) -> EncodingResult<u8> {
+        let (byte, rest) = self
+            .input
+            .split_first()
+            .ok_or(EncodingError::InvalidInput)?;
+        self.input = rest;
+        Ok(*byte)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> EncodingResult<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_slice(N as u64)?);
+        Ok(array)
+    }
+
+    fn read_slice(&mut self, len: u64) -> EncodingResult<&'a [u8]> {
+        let len = usize::try_from(len).map_err(|_| EncodingError::InvalidInput)?;
+        if self.input.len() < len {
+            return Err(EncodingError::InvalidInput);
+        }
+        let (slice, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(slice)
+    }
+
+    /// Checks if the rest of the input can contain `len` groups of `group_size` items
+    /// to avoid processing obviously truncated inputs. Every item takes at least one byte.
+    fn check_items_len(&self, len: u64, group_size: u64) -> EncodingResult<u64> {
+        match len.checked_mul(group_size) {
+            Some(min_len) if min_len <= self.input.len() as u64 => Ok(len),
+            _ => Err(EncodingError::InvalidInput),
+        }
+    }
+}
+
+fn simple_or_float(info: u8, argument: u64) -> EncodingResult<Value> {
+    let value = match info {
+        SIMPLE_FALSE => Value::Bool(false),
+        SIMPLE_TRUE => Value::Bool(true),
+        SIMPLE_NULL => Value::Null,
+        SIMPLE_UNDEFINED => Value::Undefined,
+        0..=19 => Value::Simple(info),
+        // Simple values below 32 must be encoded in the initial byte.
+        24 if argument < 32 => return Err(EncodingError::InvalidInput),
+        24 => Value::Simple(argument as u8),
+        25 => Value::Float(f16_to_f64(argument as u16)),
+        26 => Value::Float(f32::from_bits(argument as u32) as f64),
+        27 => Value::Float(f64::from_bits(argument)),
+        _ => return Err(EncodingError::InvalidInput),
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    fn decode_hex(encoded: &str) -> EncodingResult<Value> {
+        decode(&hex::decode(encoded).unwrap())
+    }
+
+    #[test]
+    fn test_cbor_decode_integers() {
+        assert_eq!(decode_hex("00"), Ok(Value::Unsigned(0)));
+        assert_eq!(decode_hex("17"), Ok(Value::Unsigned(23)));
+        assert_eq!(decode_hex("1818"), Ok(Value::Unsigned(24)));
+        assert_eq!(decode_hex("1903e8"), Ok(Value::Unsigned(1000)));
+        assert_eq!(decode_hex("1a000f4240"), Ok(Value::Unsigned(1000000)));
+        assert_eq!(
+            decode_hex("1b000000e8d4a51000"),
+            Ok(Value::Unsigned(1000000000000))
+        );
+        assert_eq!(
+            decode_hex("1bffffffffffffffff"),
+            Ok(Value::Unsigned(u64::MAX))
+        );
+        assert_eq!(decode_hex("20"), Ok(Value::int(-1)));
+        assert_eq!(decode_hex("3863"), Ok(Value::int(-100)));
+        assert_eq!(decode_hex("3903e7"), Ok(Value::int(-1000)));
+        assert_eq!(
+            decode_hex("3bffffffffffffffff"),
+            Ok(Value::Negative(u64::MAX))
+        );
+        // Non-shortest forms are well-formed.
+        assert_eq!(decode_hex("1800"), Ok(Value::Unsigned(0)));
+        assert_eq!(decode_hex("1b0000000000000001"), Ok(Value::Unsigned(1)));
+    }
+
+    #[test]
+    fn test_cbor_decode_strings() {
+        assert_eq!(decode_hex("40"), Ok(Value::Bytes(Vec::new())));
+        assert_eq!(decode_hex("4401020304"), Ok(Value::Bytes(vec![1, 2, 3, 4])));
+        assert_eq!(decode_hex("60"), Ok("".into()));
+        assert_eq!(decode_hex("6449455446"), Ok("IETF".into()));
+        assert_eq!(decode_hex("62c3bc"), Ok("\u{00fc}".into()));
+        assert_eq!(decode_hex("64f0908591"), Ok("\u{10151}".into()));
+        // Indefinite-length strings.
+        assert_eq!(
+            decode_hex("5f42010243030405ff"),
+            Ok(Value::Bytes(vec![1, 2, 3, 4, 5]))
+        );
+        assert_eq!(
+            decode_hex("7f657374726561646d696e67ff"),
+            Ok("streaming".into())
+        );
+        assert_eq!(decode_hex("5fff"), Ok(Value::Bytes(Vec::new())));
+    }
+
+    #[test]
+    fn test_cbor_decode_simple() {
+        assert_eq!(decode_hex("f4"), Ok(Value::Bool(false)));
+        assert_eq!(decode_hex("f5"), Ok(Value::Bool(true)));
+        assert_eq!(decode_hex("f6"), Ok(Value::Null));
+        assert_eq!(decode_hex("f7"), Ok(Value::Undefined));
+        assert_eq!(decode_hex("f0"), Ok(Value::Simple(16)));
+        assert_eq!(decode_hex("e1"), Ok(Value::Simple(1)));
+        assert_eq!(decode_hex("f8ff"), Ok(Value::Simple(255)));
+
+        assert_eq!(decode_hex("f90000"), Ok(Value::Float(0.0)));
+        assert_eq!(decode_hex("f93e00"), Ok(Value::Float(1.5)));
+        assert_eq!(decode_hex("f97bff"), Ok(Value::Float(65504.0)));
+        assert_eq!(decode_hex("fa47c35000"), Ok(Value::Float(100000.0)));
+        assert_eq!(decode_hex("fb3ff199999999999a"), Ok(Value::Float(1.1)));
+        assert_eq!(decode_hex("f9fc00"), Ok(Value::Float(f64::NEG_INFINITY)));
+        assert_eq!(decode_hex("fa7f800000"), Ok(Value::Float(f64::INFINITY)));
+        let nan = decode_hex("fb7ff8000000000000").unwrap();
+        assert!(matches!(nan, Value::Float(value) if value.is_nan()));
+    }
+
+    #[test]
+    fn test_cbor_decode_containers() {
+        assert_eq!(decode_hex("80"), Ok(Value::Array(Vec::new())));
+        assert_eq!(
+            decode_hex("8301820203820405"),
+            Ok(Value::Array(vec![
+                1_u64.into(),
+                Value::Array(vec![2_u64.into(), 3_u64.into()]),
+                Value::Array(vec![4_u64.into(), 5_u64.into()]),
+            ]))
+        );
+        assert_eq!(
+            decode_hex("98190102030405060708090a0b0c0d0e0f101112131415161718181819"),
+            Ok(Value::Array((1..=25).map(Value::Unsigned).collect()))
+        );
+        assert_eq!(decode_hex("a0"), Ok(Value::Map(Vec::new())));
+        assert_eq!(
+            decode_hex("a16161a161626163"),
+            Ok(Value::Map(vec![(
+                "a".into(),
+                Value::Map(vec![("b".into(), "c".into())])
+            )]))
+        );
+        assert_eq!(
+            decode_hex("9f0102ff"),
+            Ok(Value::IndefiniteArray(vec![1_u64.into(), 2_u64.into()]))
+        );
+        assert_eq!(
+            decode_hex("bf01020304ff"),
+            Ok(Value::IndefiniteMap(vec![
+                (1_u64.into(), 2_u64.into()),
+                (3_u64.into(), 4_u64.into()),
+            ]))
+        );
+        assert_eq!(
+            decode_hex("c074323031332d30332d32315432303a30343a30305a"),
+            Ok(Value::Tag(0, Box::new("2013-03-21T20:04:00Z".into())))
+        );
+    }
+
+    #[test]
+    fn test_cbor_decode_round_trip() {
+        for encoded in [
+            "8205a26178186461793831",
+            "d94321191234",
+            "bf61610161629f0203ffff",
+            "826161bf61626163ff",
+            "9f01820203820405ff",
+        ] {
+            let value = decode_hex(encoded).unwrap();
+            assert_eq!(hex::encode(encode(&value).unwrap(), false), encoded);
+        }
+    }
+
+    #[test]
+    fn test_cbor_decode_invalid() {
+        let invalid = [
+            // Empty input.
+            "",
+            // Too long string.
+            "5b99999999999999991234",
+            // Truncated string.
+            "65616263",
+            // Truncated arguments.
+            "18",
+            "1a0001",
+            // Truncated map.
+            "a301020304",
+            "a3010203",
+            // Indefinite-length array without the "break" stop code.
+            "9f0102",
+            // Unexpected "break" stop code.
+            "ff",
+            "8201ff",
+            "bf01ff",
+            // Reserved additional information.
+            "1c",
+            "3d",
+            "fe",
+            // Indefinite-length integers and tags.
+            "1f",
+            "3f",
+            "df00",
+            // Indefinite-length string chunks of a different type or indefinite length.
+            "5f6161ff",
+            "5f5f4100ffff",
+            // Invalid UTF-8.
+            "62c328",
+            // Indefinite-length text with a chunk splitting a UTF-8 character.
+            "7f61c361bcff",
+            // Simple values below 32 encoded in two bytes.
+            "f818",
+            "f814",
+            // Trailing data.
+            "0000",
+            // Array length that cannot fit the input.
+            "9bffffffffffffffff00",
+        ];
+        for input in invalid {
+            assert_eq!(
+                decode_hex(input),
+                Err(EncodingError::InvalidInput),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cbor_decode_max_depth() {
+        let mut nested = vec![0x80];
+        for _ in 0..MAX_DEPTH {
+            nested.insert(0, 0x81);
+        }
+        assert_eq!(decode(&nested), Err(EncodingError::InvalidInput));
+
+        nested.remove(0);
+        decode(&nested).unwrap();
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::Value;
+use crate::hex;
+use std::fmt;
+
+/// Formats the value in the diagnostic notation, see RFC 8949, Section 8.
+/// It's intended for debugging, e.g. `{"a": 1, "b": [_ 2, -3]}` or `24(h'0102')`.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Unsigned(value) => write!(f, "{value}"),
+            Value::Negative(value) => write!(f, "{}", -1 - *value as i128),
+            Value::Bytes(bytes) => write!(f, "h'{}'", hex::encode(bytes, false)),
+            Value::Text(text) => write_text(f, text),
+            Value::Array(items) => write_items(f, "[", items, "]"),
+            Value::IndefiniteArray(items) => write_items(f, "[_ ", items, "]"),
+            Value::Map(pairs) => write_pairs(f, "{", pairs, "}"),
+            Value::IndefiniteMap(pairs) => write_pairs(f, "{_ ", pairs, "}"),
+            Value::Tag(tag, item) => write!(f, "{tag}({item})"),
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Null => write!(f, "null"),
+            Value::Undefined => write!(f, "undefined"),
+            Value::Simple(value) => write!(f, "simple({value})"),
+            Value::Float(value) if value.is_nan() => write!(f, "NaN"),
+            Value::Float(value) if value.is_infinite() && *value > 0.0 => write!(f, "Infinity"),
+            Value::Float(value) if value.is_infinite() => write!(f, "-Infinity"),
+            // `Debug` always prints a fractional part or an exponent, e.g. `1.0` or `1e300`.
+            Value::Float(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
+fn write_text(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for ch in text.chars() {
+        match ch {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            ch if ch.is_control() => write!(f, "\\u{:04x}", ch as u32)?,
+            ch => write!(f, "{ch}")?,
+        }
+    }
+    f.write_str("\"")
+}
+
+fn write_items(
+    f: &mut fmt::Formatter<'_>,
+    open: &str,
+    items: &[Value],
+    close: &str,
+) -> fmt::Result {
+    f.write_str(open)?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{item}")?;
+    }
+    f.write_str(close)
+}
+
+fn write_pairs(
+    f: &mut fmt::Formatter<'_>,
+    open: &str,
+    pairs: &[(Value, Value)],
+    close: &str,
+) -> fmt::Result {
+    f.write_str(open)?;
+    for (i, (key, value)) in pairs.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{key}: {value}")?;
+    }
+    f.write_str(close)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cbor::decode;
+    use crate::hex;
+
+    fn test_diagnostic_impl(encoded: &str, expected: &str) {
+        let value = decode(&hex::decode(encoded).unwrap()).unwrap();
+        assert_eq!(value.to_string(), expected);
+    }
+
+    #[test]
+    fn test_cbor_diagnostic() {
+        test_diagnostic_impl("00", "0");
+        test_diagnostic_impl("1bffffffffffffffff", "18446744073709551615");
+        test_diagnostic_impl("28", "-9");
+        test_diagnostic_impl("3bffffffffffffffff", "-18446744073709551616");
+        test_diagnostic_impl("656162636465", "\"abcde\"");
+        test_diagnostic_impl("62225c", "\"\\\"\\\\\"");
+        test_diagnostic_impl("62c3bc", "\"\u{00fc}\"");
+        test_diagnostic_impl("6101", "\"\\u0001\"");
+        test_diagnostic_impl("456162636465", "h'6162636465'");
+        test_diagnostic_impl("c506", "5(6)");
+        test_diagnostic_impl("d818456449455446", "24(h'6449455446')");
+        test_diagnostic_impl("f4", "false");
+        test_diagnostic_impl("f5", "true");
+        test_diagnostic_impl("f6", "null");
+        test_diagnostic_impl("f7", "undefined");
+        test_diagnostic_impl("e1", "simple(1)");
+        test_diagnostic_impl("f8ff", "simple(255)");
+        test_diagnostic_impl("f93c00", "1.0");
+        test_diagnostic_impl("f9c400", "-4.0");
+        test_diagnostic_impl("fb3ff199999999999a", "1.1");
+        test_diagnostic_impl("fb7e37e43c8800759c", "1e300");
+        test_diagnostic_impl("f97e00", "NaN");
+        test_diagnostic_impl("f97c00", "Infinity");
+        test_diagnostic_impl("f9fc00", "-Infinity");
+    }
+
+    #[test]
+    fn test_cbor_diagnostic_containers() {
+        test_diagnostic_impl("80", "[]");
+        test_diagnostic_impl("83010203", "[1, 2, 3]");
+        test_diagnostic_impl("8301820203820405", "[1, [2, 3], [4, 5]]");
+        test_diagnostic_impl(
+            "98190102030405060708090a0b0c0d0e0f101112131415161718181819",
+            "[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25]",
+        );
+        test_diagnostic_impl("a0", "{}");
+        test_diagnostic_impl("a201020304", "{1: 2, 3: 4}");
+        test_diagnostic_impl("a26161016162820203", "{\"a\": 1, \"b\": [2, 3]}");
+        test_diagnostic_impl("a16161a161626163", "{\"a\": {\"b\": \"c\"}}");
+        test_diagnostic_impl("9f0102ff", "[_ 1, 2]");
+        test_diagnostic_impl("bf01020304ff", "{_ 1: 2, 3: 4}");
+        test_diagnostic_impl("bf61610161629f0203ffff", "{_ \"a\": 1, \"b\": [_ 2, 3]}");
+    }
+}
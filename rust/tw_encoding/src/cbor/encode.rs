@@ -0,0 +1,557 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::float::{f64_to_f16_exact, f64_to_f32_exact};
+use super::*;
+use crate::{EncodingError, EncodingResult};
+
+/// Encodes the `value` preserving the order of map entries and indefinite lengths.
+/// Integers, lengths and floats are always encoded in the shortest form.
+pub fn encode(value: &Value) -> EncodingResult<Vec<u8>> {
+    let mut encoder = Encoder::new();
+    encoder.value(value)?;
+    Ok(encoder.out())
+}
+
+/// Encodes the `value` according to the core deterministic encoding requirements,
+/// see RFC 8949, Section 4.2.1:
+/// * Integers, lengths and floats are encoded in the shortest form.
+/// * Indefinite-length arrays and maps are encoded as definite-length ones.
+/// * Map entries are sorted by the bytewise lexicographic order of their encoded keys.
+///
+/// Returns an error if a map contains duplicate keys.
+pub fn encode_canonical(value: &Value) -> EncodingResult<Vec<u8>> {
+    let mut encoder = Encoder::new();
+    encoder.canonical_value(value)?;
+    Ok(encoder.out())
+}
+
+/// CBOR encoder.
+///
+/// # Example
+///
+/// ```
+/// use tw_encoding::cbor::Encoder;
+///
+/// let mut encoder = Encoder::new();
+/// encoder
+///     .begin_indefinite_array()
+///     .unsigned(1)
+///     .text("a")
+///     .end_indefinite();
+/// assert_eq!(encoder.out(), [0x9f, 0x01, 0x61, 0x61, 0xff]);
+/// ```
+#[derive(Debug, Default)]
+pub struct Encoder {
+    buffer: Vec<u8>,
+    /// The number of indefinite-length items that are not closed yet.
+    open_indefinite: usize,
+}
+
+impl Encoder {
+    pub fn new() -> Encoder {
+        Encoder::default()
+    }
+
+    pub fn unsigned(&mut self, value: u64) -> &mut Self {
+        self.write_head(MAJOR_UNSIGNED, value)
+    }
+
+    /// Writes a negative integer `-1 - value`.
+    pub fn negative(&mut self, value: u64) -> &mut Self {
+        self.write_head(MAJOR_NEGATIVE, value)
+    }
+
+    /// Writes either an unsigned or a negative integer.
+    pub fn int(&mut self, value: i64) -> &mut Self {
+        if value < 0 {
+            self.negative(!value as u64)
+        } else {
+            self.unsigned(value as u64)
+        }
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.write_head(MAJOR_BYTES, bytes.len() as u64);
+        self.buffer.extend_from_slice(bytes);
+        self
+    }
+
+    pub fn text(&mut self, text: &str) -> &mut Self {
+        self.write_head(MAJOR_TEXT, text.len() as u64);
+        self.buffer.extend_from_slice(text.as_bytes());
+        self
+    }
+
+    /// Writes an array header. The following `len` items are the array elements.
+    pub fn array(&mut self, len: usize) -> &mut Self {
+        self.write_head(MAJOR_ARRAY, len as u64)
+    }
+
+    /// Writes a map header. The following `len` pairs of items are the map entries.
+    pub fn map(&mut self, len: usize) -> &mut Self {
+        self.write_head(MAJOR_MAP, len as u64)
+    }
+
+    /// Writes a tag. The following item is the tagged one.
+    pub fn tag(&mut self, tag: u64) -> &mut Self {
+        self.write_head(MAJOR_TAG, tag)
+    }
+
+    pub fn bool(&mut self, value: bool) -> &mut Self {
+        let simple = if value { SIMPLE_TRUE } else { SIMPLE_FALSE };
+        self.write_head(MAJOR_SIMPLE, simple as u64)
+    }
+
+    pub fn null(&mut self) -> &mut Self {
+        self.write_head(MAJOR_SIMPLE, SIMPLE_NULL as u64)
+    }
+
+    pub fn undefined(&mut self) -> &mut Self {
+        self.write_head(MAJOR_SIMPLE, SIMPLE_UNDEFINED as u64)
+    }
+
+    /// Writes a simple value. Returns an error if the `value` is reserved, i.e. within `24..=31`.
+    pub fn simple(&mut self, value: u8) -> EncodingResult<&mut Self> {
+        if (24..32).contains(&value) {
+            return Err(EncodingError::InvalidInput);
+        }
+        Ok(self.write_head(MAJOR_SIMPLE, value as u64))
+    }
+
+    /// Writes a floating-point number in the shortest form that preserves its value.
+    pub fn float(&mut self, value: f64) -> &mut Self {
+        if let Some(half) = f64_to_f16_exact(value) {
+            self.buffer.push((MAJOR_SIMPLE << 5) | 25);
+            self.buffer.extend_from_slice(&half.to_be_bytes());
+        } else if let Some(single) = f64_to_f32_exact(value) {
+            self.buffer.push((MAJOR_SIMPLE << 5) | 26);
+            self.buffer.extend_from_slice(&single.to_be_bytes());
+        } else {
+            self.buffer.push((MAJOR_SIMPLE << 5) | 27);
+            self.buffer.extend_from_slice(&value.to_be_bytes());
+        }
+        self
+    }
+
+    /// Starts an indefinite-length array. Every next item is an array element until
+    /// [`Encoder::end_indefinite`] is called.
+    pub fn begin_indefinite_array(&mut self) -> &mut Self {
+        self.begin_indefinite(MAJOR_ARRAY)
+    }
+
+    /// Starts an indefinite-length map. Every next pair of items is a map entry until
+    /// [`Encoder::end_indefinite`] is called.
+    pub fn begin_indefinite_map(&mut self) -> &mut Self {
+        self.begin_indefinite(MAJOR_MAP)
+    }
+
+    /// Closes the last started indefinite-length item by writing the "break" stop code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no open indefinite-length item.
+    pub fn end_indefinite(&mut self) -> &mut Self {
+        self.open_indefinite = self
+            .open_indefinite
+            .checked_sub(1)
+            .expect("'Encoder::begin_indefinite_*' must be called before 'end_indefinite'");
+        self.buffer.push(BREAK);
+        self
+    }
+
+    /// Writes an already encoded item as is.
+    pub fn raw(&mut self, encoded: &[u8]) -> &mut Self {
+        self.buffer.extend_from_slice(encoded);
+        self
+    }
+
+    /// Writes the `value` preserving the order of map entries and indefinite lengths.
+    /// Returns an error if the items are nested deeper than [`MAX_DEPTH`].
+    pub fn value(&mut self, value: &Value) -> EncodingResult<&mut Self> {
+        self.value_at(value, 0)
+    }
+
+    /// Writes the `value` according to the core deterministic encoding requirements.
+    /// See [`encode_canonical`].
+    pub fn canonical_value(&mut self, value: &Value) -> EncodingResult<&mut Self> {
+        self.canonical_value_at(value, 0)
+    }
+
+    /// Returns the encoded data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some of the indefinite-length items are not closed.
+    pub fn out(self) -> Vec<u8> {
+        assert_eq!(
+            self.open_indefinite, 0,
+            "Every indefinite-length item must be closed"
+        );
+        self.buffer
+    }
+
+    /// Writes the `value` nested into `depth` items.
+    fn value_at(&mut self, value: &Value, depth: usize) -> EncodingResult<&mut Self> {
+        match value {
+            Value::Array(items) => {
+                let depth = next_depth(depth)?;
+                self.array(items.len());
+                for item in items {
+                    self.value_at(item, depth)?;
+                }
+            },
+            Value::IndefiniteArray(items) => {
+                let depth = next_depth(depth)?;
+                self.begin_indefinite_array();
+                for item in items {
+                    self.value_at(item, depth)?;
+                }
+                self.end_indefinite();
+            },
+            Value::Map(pairs) => {
+                let depth = next_depth(depth)?;
+                self.map(pairs.len());
+                for (key, value) in pairs {
+                    self.value_at(key, depth)?.value_at(value, depth)?;
+                }
+            },
+            Value::IndefiniteMap(pairs) => {
+                let depth = next_depth(depth)?;
+                self.begin_indefinite_map();
+                for (key, value) in pairs {
+                    self.value_at(key, depth)?.value_at(value, depth)?;
+                }
+                self.end_indefinite();
+            },
+            Value::Tag(tag, item) => {
+                let depth = next_depth(depth)?;
+                self.tag(*tag).value_at(item, depth)?;
+            },
+            scalar => {
+                self.scalar(scalar)?;
+            },
+        }
+        Ok(self)
+    }
+
+    /// Writes the `value` nested into `depth` items according to the core deterministic encoding requirements.
+    fn canonical_value_at(&mut self, value: &Value, depth: usize) -> EncodingResult<&mut Self> {
+        match value {
+            Value::Array(items) | Value::IndefiniteArray(items) => {
+                let depth = next_depth(depth)?;
+                self.array(items.len());
+                for item in items {
+                    self.canonical_value_at(item, depth)?;
+                }
+            },
+            Value::Map(pairs) | Value::IndefiniteMap(pairs) => {
+                let depth = next_depth(depth)?;
+                let mut entries = pairs
+                    .iter()
+                    .map(|(key, value)| {
+                        let mut key_encoder = Encoder::new();
+                        key_encoder.canonical_value_at(key, depth)?;
+                        Ok((key_encoder.out(), value))
+                    })
+                    .collect::<EncodingResult<Vec<_>>>()?;
+                entries.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+                let has_duplicates = entries.windows(2).any(|pair| pair[0].0 == pair[1].0);
+                if has_duplicates {
+                    return Err(EncodingError::InvalidInput);
+                }
+
+                self.map(entries.len());
+                for (key, value) in entries {
+                    self.raw(&key).canonical_value_at(value, depth)?;
+                }
+            },
+            Value::Tag(tag, item) => {
+                let depth = next_depth(depth)?;
+                self.tag(*tag).canonical_value_at(item, depth)?;
+            },
+            scalar => {
+                self.scalar(scalar)?;
+            },
+        }
+        Ok(self)
+    }
+
+    /// Writes an item that cannot contain other items.
+    fn scalar(&mut self, value: &Value) -> EncodingResult<&mut Self> {
+        match value {
+            Value::Unsigned(value) => Ok(self.unsigned(*value)),
+            Value::Negative(value) => Ok(self.negative(*value)),
+            Value::Bytes(bytes) => Ok(self.bytes(bytes)),
+            Value::Text(text) => Ok(self.text(text)),
+            Value::Bool(value) => Ok(self.bool(*value)),
+            Value::Null => Ok(self.null()),
+            Value::Undefined => Ok(self.undefined()),
+            Value::Simple(value) => self.simple(*value),
+            Value::Float(value) => Ok(self.float(*value)),
+            Value::Array(_)
+            | Value::IndefiniteArray(_)
+            | Value::Map(_)
+            | Value::IndefiniteMap(_)
+            | Value::Tag(_, _) => unreachable!("Nested items are handled by the caller"),
+        }
+    }
+
+    fn begin_indefinite(&mut self, major: u8) -> &mut Self {
+        self.open_indefinite += 1;
+        self.buffer.push((major << 5) | INDEFINITE);
+        self
+    }
+
+    /// Writes the initial byte and the argument in the shortest form.
+    fn write_head(&mut self, major: u8, argument: u64) -> &mut Self {
+        let major = major << 5;
+        match argument {
+            0..=23 => self.buffer.push(major | argument as u8),
+            24..=0xff => {
+                self.buffer.push(major | 24);
+                self.buffer.push(argument as u8);
+            },
+            0x100..=0xffff => {
+                self.buffer.push(major | 25);
+                self.buffer
+                    .extend_from_slice(&(argument as u16).to_be_bytes());
+            },
+            0x10000..=0xffff_ffff => {
+                self.buffer.push(major | 26);
+                self.buffer
+                    .extend_from_slice(&(argument as u32).to_be_bytes());
+            },
+            _ => {
+                self.buffer.push(major | 27);
+                self.buffer.extend_from_slice(&argument.to_be_bytes());
+            },
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    fn test_encode_impl(value: Value, expected: &str) {
+        assert_eq!(hex::encode(encode(&value).unwrap(), false), expected);
+    }
+
+    #[test]
+    fn test_cbor_encode_unsigned() {
+        test_encode_impl(Value::Unsigned(0), "00");
+        test_encode_impl(Value::Unsigned(10), "0a");
+        test_encode_impl(Value::Unsigned(23), "17");
+        test_encode_impl(Value::Unsigned(24), "1818");
+        test_encode_impl(Value::Unsigned(100), "1864");
+        test_encode_impl(Value::Unsigned(0xff), "18ff");
+        test_encode_impl(Value::Unsigned(1000), "1903e8");
+        test_encode_impl(Value::Unsigned(0xffff), "19ffff");
+        test_encode_impl(Value::Unsigned(1000000), "1a000f4240");
+        test_encode_impl(Value::Unsigned(0xffffffff), "1affffffff");
+        test_encode_impl(Value::Unsigned(1000000000000), "1b000000e8d4a51000");
+        test_encode_impl(Value::Unsigned(u64::MAX), "1bffffffffffffffff");
+    }
+
+    #[test]
+    fn test_cbor_encode_negative() {
+        test_encode_impl(Value::int(-1), "20");
+        test_encode_impl(Value::int(-10), "29");
+        test_encode_impl(Value::int(-100), "3863");
+        test_encode_impl(Value::int(-1000), "3903e7");
+        test_encode_impl(Value::int(-0x10001), "3a00010000");
+        test_encode_impl(Value::Negative(u64::MAX), "3bffffffffffffffff");
+    }
+
+    #[test]
+    fn test_cbor_encode_strings() {
+        test_encode_impl(Value::Bytes(Vec::new()), "40");
+        test_encode_impl(Value::Bytes(vec![1, 2, 3, 4]), "4401020304");
+        test_encode_impl("".into(), "60");
+        test_encode_impl("a".into(), "6161");
+        test_encode_impl("IETF".into(), "6449455446");
+        test_encode_impl("\"\\".into(), "62225c");
+        test_encode_impl("\u{00fc}".into(), "62c3bc");
+        test_encode_impl("\u{6c34}".into(), "63e6b0b4");
+
+        let long = encode(&Value::Bytes(vec![0; 258])).unwrap();
+        assert_eq!(long[..3], [0x59, 0x01, 0x02]);
+        assert_eq!(long.len(), 261);
+    }
+
+    #[test]
+    fn test_cbor_encode_simple() {
+        test_encode_impl(Value::Bool(false), "f4");
+        test_encode_impl(Value::Bool(true), "f5");
+        test_encode_impl(Value::Null, "f6");
+        test_encode_impl(Value::Undefined, "f7");
+        test_encode_impl(Value::Simple(16), "f0");
+        test_encode_impl(Value::Simple(255), "f8ff");
+        assert_eq!(encode(&Value::Simple(24)), Err(EncodingError::InvalidInput));
+        assert_eq!(encode(&Value::Simple(31)), Err(EncodingError::InvalidInput));
+    }
+
+    #[test]
+    fn test_cbor_encode_float() {
+        test_encode_impl(Value::Float(0.0), "f90000");
+        test_encode_impl(Value::Float(-0.0), "f98000");
+        test_encode_impl(Value::Float(1.0), "f93c00");
+        test_encode_impl(Value::Float(1.1), "fb3ff199999999999a");
+        test_encode_impl(Value::Float(1.5), "f93e00");
+        test_encode_impl(Value::Float(65504.0), "f97bff");
+        test_encode_impl(Value::Float(100000.0), "fa47c35000");
+        test_encode_impl(Value::Float(3.4028234663852886e+38), "fa7f7fffff");
+        test_encode_impl(Value::Float(1.0e+300), "fb7e37e43c8800759c");
+        test_encode_impl(Value::Float(5.960464477539063e-8), "f90001");
+        test_encode_impl(Value::Float(0.00006103515625), "f90400");
+        test_encode_impl(Value::Float(-4.0), "f9c400");
+        test_encode_impl(Value::Float(-4.1), "fbc010666666666666");
+        test_encode_impl(Value::Float(f64::INFINITY), "f97c00");
+        test_encode_impl(Value::Float(f64::NAN), "f97e00");
+        test_encode_impl(Value::Float(f64::NEG_INFINITY), "f9fc00");
+    }
+
+    #[test]
+    fn test_cbor_encode_containers() {
+        test_encode_impl(Value::Array(Vec::new()), "80");
+        test_encode_impl(
+            Value::Array(vec![1_u64.into(), 2_u64.into(), 3_u64.into()]),
+            "83010203",
+        );
+        test_encode_impl(
+            Value::Array((1..=25).map(Value::Unsigned).collect()),
+            "98190102030405060708090a0b0c0d0e0f101112131415161718181819",
+        );
+        test_encode_impl(Value::Map(Vec::new()), "a0");
+        test_encode_impl(
+            Value::Map(vec![
+                ("a".into(), 1_u64.into()),
+                ("b".into(), Value::Array(vec![2_u64.into(), 3_u64.into()])),
+            ]),
+            "a26161016162820203",
+        );
+        test_encode_impl(
+            Value::Array(vec![
+                5_u64.into(),
+                Value::Map(vec![
+                    ("x".into(), 100_u64.into()),
+                    ("y".into(), Value::int(-50)),
+                ]),
+            ]),
+            "8205a26178186461793831",
+        );
+        test_encode_impl(Value::Tag(5, Box::new(6_u64.into())), "c506");
+        test_encode_impl(
+            Value::Tag(0x4321, Box::new(0x1234_u64.into())),
+            "d94321191234",
+        );
+        test_encode_impl(
+            Value::IndefiniteArray(vec![1_u64.into(), 2_u64.into()]),
+            "9f0102ff",
+        );
+        test_encode_impl(
+            Value::IndefiniteMap(vec![
+                (1_u64.into(), 2_u64.into()),
+                (3_u64.into(), 4_u64.into()),
+            ]),
+            "bf01020304ff",
+        );
+    }
+
+    #[test]
+    fn test_cbor_encode_canonical() {
+        // Keys are sorted by their encoding: 10, 100, -1, "z", "aa", [100], [-1], false.
+        let value = Value::IndefiniteMap(vec![
+            (Value::Bool(false), 8_u64.into()),
+            ("aa".into(), 5_u64.into()),
+            (Value::Array(vec![Value::int(-1)]), 7_u64.into()),
+            (100_u64.into(), 2_u64.into()),
+            ("z".into(), 4_u64.into()),
+            (Value::int(-1), 3_u64.into()),
+            (10_u64.into(), 1_u64.into()),
+            (Value::Array(vec![100_u64.into()]), 6_u64.into()),
+        ]);
+        assert_eq!(
+            hex::encode(encode_canonical(&value).unwrap(), false),
+            "a80a011864022003617a046261610581186406812007f408"
+        );
+
+        let nested = Value::IndefiniteArray(vec![Value::Map(vec![
+            ("b".into(), 2_u64.into()),
+            ("a".into(), 1_u64.into()),
+        ])]);
+        assert_eq!(
+            hex::encode(encode_canonical(&nested).unwrap(), false),
+            "81a2616101616202"
+        );
+
+        let duplicates = Value::Map(vec![("a".into(), 1_u64.into()), ("a".into(), 2_u64.into())]);
+        assert_eq!(
+            encode_canonical(&duplicates),
+            Err(EncodingError::InvalidInput)
+        );
+        // Non-deterministic encoding is allowed to have duplicate keys.
+        encode(&duplicates).unwrap();
+    }
+
+    #[test]
+    fn test_cbor_encode_max_depth() {
+        let mut nested = Value::Array(Vec::new());
+        for _ in 0..MAX_DEPTH {
+            nested = Value::Array(vec![nested]);
+        }
+        assert_eq!(encode(&nested), Err(EncodingError::InvalidInput));
+        assert_eq!(encode_canonical(&nested), Err(EncodingError::InvalidInput));
+
+        // The nesting of map keys is limited as well.
+        let Value::Array(items) = nested else {
+            unreachable!()
+        };
+        let map = Value::Map(vec![(items[0].clone(), Value::Null)]);
+        assert_eq!(encode(&map), Err(EncodingError::InvalidInput));
+        assert_eq!(encode_canonical(&map), Err(EncodingError::InvalidInput));
+
+        let nested = items[0].clone();
+        assert_eq!(decode(&encode(&nested).unwrap()).unwrap(), nested);
+        assert_eq!(decode(&encode_canonical(&nested).unwrap()).unwrap(), nested);
+    }
+
+    #[test]
+    fn test_cbor_encoder() {
+        let mut encoder = Encoder::new();
+        encoder
+            .array(3)
+            .int(-2)
+            .bytes(&[1, 2])
+            .tag(24)
+            .begin_indefinite_map()
+            .text("a")
+            .null()
+            .end_indefinite();
+        assert_eq!(
+            hex::encode(encoder.out(), false),
+            "8321420102d818bf6161f6ff"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cbor_encoder_not_closed() {
+        let mut encoder = Encoder::new();
+        encoder.begin_indefinite_array().unsigned(1);
+        encoder.out();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cbor_encoder_close_not_started() {
+        Encoder::new().unsigned(0).end_indefinite();
+    }
+}
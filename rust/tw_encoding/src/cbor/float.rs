@@ -0,0 +1,127 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! IEEE 754 half-precision conversions that are not provided by the standard library.
+
+/// cbindgen:ignore
+const F16_NAN: u16 = 0x7e00;
+/// cbindgen:ignore
+const F16_INFINITY: u16 = 0x7c00;
+/// cbindgen:ignore
+const F16_SIGN: u16 = 0x8000;
+
+/// Converts a half-precision number to `f64`.
+/// See RFC 8949, Appendix D.
+pub(crate) fn f16_to_f64(half: u16) -> f64 {
+    let exponent = (half >> 10) & 0x1f;
+    let mantissa = (half & 0x3ff) as f64;
+
+    let value = match exponent {
+        0 => mantissa * 2_f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (mantissa + 1024.0) * 2_f64.powi(exponent as i32 - 25),
+    };
+
+    if half & F16_SIGN != 0 {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Converts `value` to a half-precision number if it can be done without losing precision.
+/// NaN is converted to the canonical quiet NaN.
+pub(crate) fn f64_to_f16_exact(value: f64) -> Option<u16> {
+    if value.is_nan() {
+        return Some(F16_NAN);
+    }
+
+    let sign = if value.is_sign_negative() {
+        F16_SIGN
+    } else {
+        0
+    };
+    let abs = value.abs();
+
+    if abs == 0.0 {
+        return Some(sign);
+    }
+    if abs.is_infinite() {
+        return Some(sign | F16_INFINITY);
+    }
+
+    // The smallest positive normal half-precision number is 2^-14.
+    if abs < 2_f64.powi(-14) {
+        let mantissa = abs * 2_f64.powi(24);
+        if mantissa.fract() != 0.0 {
+            return None;
+        }
+        return Some(sign | mantissa as u16);
+    }
+
+    let bits = abs.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i32 - 1023;
+    // Half-precision has 10 mantissa bits against 52 bits of the double-precision.
+    let dropped_bits = bits & ((1 << 42) - 1);
+    if exponent > 15 || dropped_bits != 0 {
+        return None;
+    }
+
+    let mantissa = ((bits >> 42) & 0x3ff) as u16;
+    Some(sign | (((exponent + 15) as u16) << 10) | mantissa)
+}
+
+/// Converts `value` to a single-precision number if it can be done without losing precision.
+pub(crate) fn f64_to_f32_exact(value: f64) -> Option<f32> {
+    let single = value as f32;
+    (single as f64 == value || value.is_nan()).then_some(single)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f16_to_f64() {
+        assert_eq!(f16_to_f64(0x0000), 0.0);
+        assert!(f16_to_f64(0x8000).is_sign_negative());
+        assert_eq!(f16_to_f64(0x3c00), 1.0);
+        assert_eq!(f16_to_f64(0x3e00), 1.5);
+        assert_eq!(f16_to_f64(0x7bff), 65504.0);
+        assert_eq!(f16_to_f64(0x0001), 5.960464477539063e-8);
+        assert_eq!(f16_to_f64(0x0400), 0.00006103515625);
+        assert_eq!(f16_to_f64(0xc400), -4.0);
+        assert_eq!(f16_to_f64(0x7c00), f64::INFINITY);
+        assert_eq!(f16_to_f64(0xfc00), f64::NEG_INFINITY);
+        assert!(f16_to_f64(0x7e00).is_nan());
+    }
+
+    #[test]
+    fn test_f64_to_f16_exact() {
+        for half in [
+            0x0000, 0x8000, 0x3c00, 0x3e00, 0x7bff, 0x0001, 0x03ff, 0x0400, 0xc400, 0x7c00, 0xfc00,
+        ] {
+            assert_eq!(f64_to_f16_exact(f16_to_f64(half)), Some(half), "{half:#x}");
+        }
+        assert_eq!(f64_to_f16_exact(f64::NAN), Some(F16_NAN));
+
+        assert_eq!(f64_to_f16_exact(65505.0), None);
+        assert_eq!(f64_to_f16_exact(65536.0), None);
+        assert_eq!(f64_to_f16_exact(1.1), None);
+        assert_eq!(f64_to_f16_exact(5.960464477539063e-8 / 2.0), None);
+        assert_eq!(f64_to_f16_exact(100000.0), None);
+    }
+
+    #[test]
+    fn test_f64_to_f32_exact() {
+        assert_eq!(f64_to_f32_exact(100000.0), Some(100000.0));
+        assert_eq!(f64_to_f32_exact(3.4028234663852886e+38), Some(f32::MAX));
+        assert_eq!(f64_to_f32_exact(1.1), None);
+        assert_eq!(f64_to_f32_exact(1.0e+300), None);
+        assert!(f64_to_f32_exact(f64::NAN).unwrap().is_nan());
+    }
+}
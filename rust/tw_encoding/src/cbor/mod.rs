@@ -0,0 +1,249 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! Concise Binary Object Representation.
+//! https://www.rfc-editor.org/rfc/rfc8949
+
+mod decode;
+mod diagnostic;
+mod encode;
+mod float;
+
+pub use decode::decode;
+pub use encode::{encode, encode_canonical, Encoder};
+
+use crate::{EncodingError, EncodingResult};
+
+/// The maximum nesting depth of arrays, maps and tags accepted by the decoder and the encoder.
+pub const MAX_DEPTH: usize = 128;
+
+/// cbindgen:ignore
+const MAJOR_UNSIGNED: u8 = 0;
+/// cbindgen:ignore
+const MAJOR_NEGATIVE: u8 = 1;
+/// cbindgen:ignore
+const MAJOR_BYTES: u8 = 2;
+/// cbindgen:ignore
+const MAJOR_TEXT: u8 = 3;
+/// cbindgen:ignore
+const MAJOR_ARRAY: u8 = 4;
+/// cbindgen:ignore
+const MAJOR_MAP: u8 = 5;
+/// cbindgen:ignore
+const MAJOR_TAG: u8 = 6;
+/// cbindgen:ignore
+const MAJOR_SIMPLE: u8 = 7;
+
+/// The additional information value of an indefinite-length item or the "break" stop code.
+/// cbindgen:ignore
+const INDEFINITE: u8 = 31;
+/// cbindgen:ignore
+const BREAK: u8 = (MAJOR_SIMPLE << 5) | INDEFINITE;
+
+/// cbindgen:ignore
+const SIMPLE_FALSE: u8 = 20;
+/// cbindgen:ignore
+const SIMPLE_TRUE: u8 = 21;
+/// cbindgen:ignore
+const SIMPLE_NULL: u8 = 22;
+/// cbindgen:ignore
+const SIMPLE_UNDEFINED: u8 = 23;
+
+/// A CBOR data item.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Value {
+    /// Major type 0.
+    Unsigned(u64),
+    /// Major type 1, the value is `-1 - n`.
+    Negative(u64),
+    /// Major type 2. Indefinite-length byte strings are decoded as concatenated chunks.
+    Bytes(Vec<u8>),
+    /// Major type 3. Indefinite-length text strings are decoded as concatenated chunks.
+    Text(String),
+    /// Major type 4.
+    Array(Vec<Value>),
+    /// Major type 4 with an indefinite length.
+    IndefiniteArray(Vec<Value>),
+    /// Major type 5. Pairs are kept in the encoded order.
+    Map(Vec<(Value, Value)>),
+    /// Major type 5 with an indefinite length.
+    IndefiniteMap(Vec<(Value, Value)>),
+    /// Major type 6.
+    Tag(u64, Box<Value>),
+    /// Major type 7, simple values 20 and 21.
+    Bool(bool),
+    /// Major type 7, simple value 22.
+    Null,
+    /// Major type 7, simple value 23.
+    Undefined,
+    /// Major type 7, other simple values. Values 24..=31 are reserved and cannot be encoded.
+    Simple(u8),
+    /// Major type 7, half, single or double precision floating-point number.
+    Float(f64),
+}
+
+impl Value {
+    /// Creates an integer item of major type 0 or 1.
+    pub fn int(value: i64) -> Value {
+        if value < 0 {
+            Value::Negative(!value as u64)
+        } else {
+            Value::Unsigned(value as u64)
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Unsigned(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer value of major type 0 or 1.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Value::Unsigned(value) => Some(*value as i128),
+            Value::Negative(value) => Some(-1 - *value as i128),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of either a definite or an indefinite-length array.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) | Value::IndefiniteArray(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the pairs of either a definite or an indefinite-length map.
+    pub fn as_map(&self) -> Option<&[(Value, Value)]> {
+        match self {
+            Value::Map(pairs) | Value::IndefiniteMap(pairs) => Some(pairs),
+            _ => None,
+        }
+    }
+
+    /// Returns the tag number and the tagged item.
+    pub fn as_tag(&self) -> Option<(u64, &Value)> {
+        match self {
+            Value::Tag(tag, item) => Some((*tag, item)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the first map entry with the given `key`.
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.as_map()?
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(value: u64) -> Self {
+        Value::Unsigned(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::int(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Value::Text(text.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(bytes: Vec<u8>) -> Self {
+        Value::Bytes(bytes)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+/// Returns the depth of the items nested into an item at the given `depth`.
+fn next_depth(depth: usize) -> EncodingResult<usize> {
+    if depth >= MAX_DEPTH {
+        return Err(EncodingError::InvalidInput);
+    }
+    Ok(depth + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn test_cbor_value_int() {
+        assert_eq!(Value::int(0), Value::Unsigned(0));
+        assert_eq!(Value::int(10), Value::Unsigned(10));
+        assert_eq!(Value::int(-1), Value::Negative(0));
+        assert_eq!(Value::int(-50), Value::Negative(49));
+        assert_eq!(Value::int(i64::MIN), Value::Negative(i64::MAX as u64));
+
+        assert_eq!(Value::Negative(u64::MAX).as_i128(), Some(-(1_i128 << 64)));
+        assert_eq!(Value::Unsigned(u64::MAX).as_i128(), Some(u64::MAX as i128));
+        assert_eq!(Value::Negative(0).as_u64(), None);
+    }
+
+    #[test]
+    fn test_cbor_value_accessors() {
+        // {"fmt": "none", "attStmt": {}, "authData": h'010203'}
+        let encoded =
+            hex::decode("a363666d74646e6f6e656761747453746d74a068617574684461746143010203")
+                .unwrap();
+        let value = decode(&encoded).unwrap();
+
+        assert_eq!(value.get(&"fmt".into()).unwrap().as_text(), Some("none"));
+        assert_eq!(
+            value.get(&"attStmt".into()).unwrap().as_map(),
+            Some(&[][..])
+        );
+        assert_eq!(
+            value.get(&"authData".into()).unwrap().as_bytes(),
+            Some(&[1, 2, 3][..])
+        );
+        assert_eq!(value.get(&"unknown".into()), None);
+        assert_eq!(value.as_array(), None);
+
+        let tagged = decode(&hex::decode("d818456449455446").unwrap()).unwrap();
+        let (tag, item) = tagged.as_tag().unwrap();
+        assert_eq!(tag, 24);
+        assert_eq!(item.as_bytes(), Some(&b"dIETF"[..]));
+    }
+}
@@ -6,7 +6,8 @@
 
 #![allow(clippy::missing_safety_doc)]
 
-use crate::{base32, base58, base64, hex, EncodingError};
+use crate::bech32::WitnessProgram;
+use crate::{base32, base58, base64, bech32, hex, EncodingError};
 use bs58::Alphabet;
 use std::ffi::{c_char, CStr, CString};
 use tw_memory::ffi::c_byte_array::{CByteArray, CByteArrayResult};
@@ -17,6 +18,7 @@ pub enum CEncodingCode {
     Ok = 0,
     InvalidInput = 1,
     InvalidAlphabet = 2,
+    InvalidChecksum = 3,
 }
 
 impl From<EncodingError> for CEncodingCode {
@@ -24,6 +26,7 @@ impl From<EncodingError> for CEncodingCode {
         match error {
             EncodingError::InvalidInput => CEncodingCode::InvalidInput,
             EncodingError::InvalidAlphabet => CEncodingCode::InvalidAlphabet,
+            EncodingError::InvalidChecksum => CEncodingCode::InvalidChecksum,
         }
     }
 }
@@ -41,7 +44,7 @@ impl From<CEncodingCode> for ErrorCode {
 }
 
 #[repr(C)]
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Base58Alphabet {
     Bitcoin = 1,
@@ -57,6 +60,23 @@ impl From<Base58Alphabet> for &Alphabet {
     }
 }
 
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Bech32Variant {
+    Bech32 = 1,
+    Bech32m = 2,
+}
+
+impl From<Bech32Variant> for bech32::Variant {
+    fn from(value: Bech32Variant) -> Self {
+        match value {
+            Bech32Variant::Bech32 => bech32::Variant::Bech32,
+            Bech32Variant::Bech32m => bech32::Variant::Bech32m,
+        }
+    }
+}
+
 /// Encodes the `input` data as base32.
 /// \param input *non-null* byte array.
 /// \param alphabet *optional* C-compatible, nul-terminated string.
@@ -147,6 +167,125 @@ pub unsafe extern "C" fn decode_base58(
         .into()
 }
 
+/// Encodes the `data` bytes as a Bech32 string regrouping them into 5-bit values.
+/// \param hrp *non-null* C-compatible, nul-terminated lowercase human-readable part.
+/// \param data *non-null* byte array.
+/// \param data_len the length of the `data` array.
+/// \param variant checksum variant.
+/// \param max_len the maximum length of the result string, 90 according to BIP173.
+/// \return C-compatible result with a C-compatible, nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn encode_bech32(
+    hrp: *const c_char,
+    data: *const u8,
+    data_len: usize,
+    variant: Bech32Variant,
+    max_len: usize,
+) -> CStrMutResult {
+    let hrp = match CStr::from_ptr(hrp).to_str() {
+        Ok(hrp) => hrp,
+        Err(_) => return CStrMutResult::error(CEncodingCode::InvalidInput),
+    };
+    let data = std::slice::from_raw_parts(data, data_len);
+
+    bech32::encode_with_limit(hrp, &bech32::to_base32(data), variant.into(), max_len)
+        .map(|result| CString::new(result).unwrap().into_raw())
+        .map_err(CEncodingCode::from)
+        .into()
+}
+
+/// Decodes the Bech32 `input` string regrouping the data into bytes.
+/// \param input *non-null* C-compatible, nul-terminated string.
+/// \param hrp *optional* C-compatible, nul-terminated expected human-readable part.
+///             The human-readable part is not checked if `hrp` is null.
+/// \param variant expected checksum variant.
+/// \param max_len the maximum length of the `input` string, 90 according to BIP173.
+/// \return C-compatible result with a C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn decode_bech32(
+    input: *const c_char,
+    hrp: *const c_char,
+    variant: Bech32Variant,
+    max_len: usize,
+) -> CByteArrayResult {
+    let input = match CStr::from_ptr(input).to_str() {
+        Ok(input) => input,
+        Err(_) => return CByteArrayResult::error(CEncodingCode::InvalidInput),
+    };
+    let expected_hrp = match get_optional_str(hrp) {
+        Ok(hrp) => hrp,
+        Err(e) => return CByteArrayResult::error(e),
+    };
+
+    let decoded = match bech32::decode_with_limit(input, max_len) {
+        Ok(decoded) => decoded,
+        Err(e) => return CByteArrayResult::error(CEncodingCode::from(e)),
+    };
+    if expected_hrp.is_some_and(|hrp| hrp != decoded.hrp)
+        || decoded.variant != bech32::Variant::from(variant)
+    {
+        return CByteArrayResult::error(CEncodingCode::InvalidInput);
+    }
+
+    bech32::from_base32(&decoded.data)
+        .map(CByteArray::from)
+        .map_err(CEncodingCode::from)
+        .into()
+}
+
+/// Encodes the witness program as a segwit address.
+/// \param hrp *non-null* C-compatible, nul-terminated lowercase human-readable part, e.g. "bc".
+/// \param witness_version the witness version from 0 to 16.
+/// \param program *non-null* witness program.
+/// \param program_len the length of the `program` array.
+/// \return C-compatible result with a C-compatible, nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn encode_segwit_address(
+    hrp: *const c_char,
+    witness_version: u8,
+    program: *const u8,
+    program_len: usize,
+) -> CStrMutResult {
+    let hrp = match CStr::from_ptr(hrp).to_str() {
+        Ok(hrp) => hrp,
+        Err(_) => return CStrMutResult::error(CEncodingCode::InvalidInput),
+    };
+    let program = std::slice::from_raw_parts(program, program_len);
+
+    WitnessProgram::new(witness_version, program.to_vec())
+        .and_then(|witness| bech32::encode_segwit(hrp, &witness))
+        .map(|result| CString::new(result).unwrap().into_raw())
+        .map_err(CEncodingCode::from)
+        .into()
+}
+
+/// Decodes the segwit `address`.
+/// \param hrp *non-null* C-compatible, nul-terminated expected human-readable part, e.g. "bc".
+/// \param address *non-null* C-compatible, nul-terminated string.
+/// \return C-compatible result with the witness version byte followed by the witness program.
+#[no_mangle]
+pub unsafe extern "C" fn decode_segwit_address(
+    hrp: *const c_char,
+    address: *const c_char,
+) -> CByteArrayResult {
+    let (hrp, address) = match (
+        CStr::from_ptr(hrp).to_str(),
+        CStr::from_ptr(address).to_str(),
+    ) {
+        (Ok(hrp), Ok(address)) => (hrp, address),
+        _ => return CByteArrayResult::error(CEncodingCode::InvalidInput),
+    };
+
+    bech32::decode_segwit(hrp, address)
+        .map(|witness| {
+            let mut res = vec![witness.version];
+            res.extend(witness.program);
+            CByteArray::from(res)
+        })
+        .map_err(CEncodingCode::from)
+        .into()
+}
+
 /// Encodes the `data` data as a padded, base64 string.
 /// \param data *non-null* byte array.
 /// \param len - the length of the `data` array.
@@ -217,3 +356,12 @@ fn get_alphabet(alphabet: *const c_char) -> Result<Option<String>, CEncodingCode
         .map(|alphabet| Some(alphabet.to_string()))
         .map_err(|_| CEncodingCode::InvalidAlphabet)
 }
+
+fn get_optional_str(s: *const c_char) -> Result<Option<&'static str>, CEncodingCode> {
+    if s.is_null() {
+        return Ok(None);
+    }
+    unsafe { CStr::from_ptr(s).to_str() }
+        .map(Some)
+        .map_err(|_| CEncodingCode::InvalidInput)
+}
@@ -7,8 +7,11 @@
 pub mod base32;
 pub mod base58;
 pub mod base64;
+pub mod bech32;
+pub mod cbor;
 pub mod ffi;
 pub mod hex;
+pub mod rlp;
 
 pub type EncodingResult<T> = Result<T, EncodingError>;
 
@@ -16,4 +19,5 @@ pub type EncodingResult<T> = Result<T, EncodingError>;
 pub enum EncodingError {
     InvalidInput,
     InvalidAlphabet,
+    InvalidChecksum,
 }
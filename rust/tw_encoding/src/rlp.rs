@@ -0,0 +1,563 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! Recursive Length Prefix encoding as specified in the Ethereum Yellow Paper, Appendix B.
+//! https://ethereum.org/en/developers/docs/data-structures-and-encoding/rlp/
+
+use crate::{EncodingError, EncodingResult};
+
+/// Strings and lists shorter than this are prefixed with a single byte.
+/// cbindgen:ignore
+const SHORT_LEN_LIMIT: usize = 56;
+/// cbindgen:ignore
+const STRING_OFFSET: u8 = 0x80;
+/// cbindgen:ignore
+const LIST_OFFSET: u8 = 0xc0;
+/// The maximum nesting depth of lists accepted by the decoder.
+pub const MAX_DEPTH: usize = 128;
+
+/// A value that can be appended to an [`RlpStream`].
+pub trait RlpEncode {
+    fn rlp_append(&self, stream: &mut RlpStream);
+}
+
+/// RLP encoder.
+///
+/// # Example
+///
+/// ```
+/// use tw_encoding::rlp::RlpStream;
+///
+/// let mut stream = RlpStream::new();
+/// stream.begin_list().append("cat").append("dog").finalize_list();
+/// assert_eq!(stream.out(), b"\xc8\x83cat\x83dog");
+/// ```
+#[derive(Debug, Default)]
+pub struct RlpStream {
+    buffer: Vec<u8>,
+    /// Start positions of the lists that are not finalized yet.
+    unfinished_lists: Vec<usize>,
+}
+
+impl RlpStream {
+    pub fn new() -> RlpStream {
+        RlpStream::default()
+    }
+
+    /// Appends an encodable value.
+    pub fn append<T: RlpEncode + ?Sized>(&mut self, value: &T) -> &mut Self {
+        value.rlp_append(self);
+        self
+    }
+
+    /// Appends a byte string.
+    pub fn append_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        match bytes {
+            [byte] if *byte < STRING_OFFSET => self.buffer.push(*byte),
+            _ => {
+                write_header(&mut self.buffer, STRING_OFFSET, bytes.len());
+                self.buffer.extend_from_slice(bytes);
+            },
+        }
+        self
+    }
+
+    /// Appends an already RLP encoded item as is.
+    pub fn append_raw(&mut self, encoded: &[u8]) -> &mut Self {
+        self.buffer.extend_from_slice(encoded);
+        self
+    }
+
+    /// Appends every item of the `items` as a list.
+    pub fn append_list<T: RlpEncode>(&mut self, items: &[T]) -> &mut Self {
+        self.begin_list();
+        for item in items {
+            item.rlp_append(self);
+        }
+        self.finalize_list()
+    }
+
+    /// Starts a new list. Every next item is appended to the list until [`RlpStream::finalize_list`] is called.
+    pub fn begin_list(&mut self) -> &mut Self {
+        self.unfinished_lists.push(self.buffer.len());
+        self
+    }
+
+    /// Finalizes the last started list by inserting its header.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no unfinished list.
+    pub fn finalize_list(&mut self) -> &mut Self {
+        let start = self
+            .unfinished_lists
+            .pop()
+            .expect("'RlpStream::begin_list' must be called before 'finalize_list'");
+
+        let mut header = Vec::with_capacity(9);
+        write_header(&mut header, LIST_OFFSET, self.buffer.len() - start);
+        self.buffer.splice(start..start, header);
+        self
+    }
+
+    /// Returns the encoded data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some of the lists are not finalized.
+    pub fn out(self) -> Vec<u8> {
+        assert!(
+            self.unfinished_lists.is_empty(),
+            "Every list must be finalized"
+        );
+        self.buffer
+    }
+}
+
+impl<T: RlpEncode + ?Sized> RlpEncode for &T {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        (**self).rlp_append(stream);
+    }
+}
+
+impl RlpEncode for [u8] {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(self);
+    }
+}
+
+impl RlpEncode for Vec<u8> {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(self);
+    }
+}
+
+impl<const N: usize> RlpEncode for [u8; N] {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(self);
+    }
+}
+
+impl RlpEncode for str {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(self.as_bytes());
+    }
+}
+
+impl RlpEncode for String {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(self.as_bytes());
+    }
+}
+
+/// Integers are encoded as minimal big-endian byte strings, i.e. zero is an empty string.
+macro_rules! impl_rlp_encode_uint {
+    ($($ty:ty),+) => {
+        $(
+            impl RlpEncode for $ty {
+                fn rlp_append(&self, stream: &mut RlpStream) {
+                    let bytes = self.to_be_bytes();
+                    let leading_zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+                    stream.append_bytes(&bytes[leading_zeros..]);
+                }
+            }
+        )+
+    };
+}
+
+impl_rlp_encode_uint!(u8, u16, u32, u64, u128, usize);
+
+impl RlpEncode for RlpItem {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        match self {
+            RlpItem::String(bytes) => {
+                stream.append_bytes(bytes);
+            },
+            RlpItem::List(items) => {
+                stream.append_list(items);
+            },
+        }
+    }
+}
+
+/// Encodes a single value.
+pub fn encode<T: RlpEncode + ?Sized>(value: &T) -> Vec<u8> {
+    let mut stream = RlpStream::new();
+    stream.append(value);
+    stream.out()
+}
+
+/// A decoded RLP item.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RlpItem {
+    String(Vec<u8>),
+    List(Vec<RlpItem>),
+}
+
+impl RlpItem {
+    pub fn as_bytes(&self) -> EncodingResult<&[u8]> {
+        match self {
+            RlpItem::String(bytes) => Ok(bytes),
+            RlpItem::List(_) => Err(EncodingError::InvalidInput),
+        }
+    }
+
+    pub fn as_list(&self) -> EncodingResult<&[RlpItem]> {
+        match self {
+            RlpItem::List(items) => Ok(items),
+            RlpItem::String(_) => Err(EncodingError::InvalidInput),
+        }
+    }
+
+    /// Interprets the item as a canonical integer, i.e. without leading zeros.
+    pub fn as_u64(&self) -> EncodingResult<u64> {
+        let bytes = self.as_uint_bytes()?;
+        if bytes.len() > 8 {
+            return Err(EncodingError::InvalidInput);
+        }
+        Ok(bytes
+            .iter()
+            .fold(0_u64, |num, byte| (num << 8) | *byte as u64))
+    }
+
+    /// Returns the big-endian bytes of a canonical integer, i.e. without leading zeros.
+    /// Zero is an empty string.
+    pub fn as_uint_bytes(&self) -> EncodingResult<&[u8]> {
+        let bytes = self.as_bytes()?;
+        if bytes.first() == Some(&0) {
+            return Err(EncodingError::InvalidInput);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Decodes a single RLP item that must take the whole `input`.
+/// Non-canonical encodings are rejected.
+pub fn decode(input: &[u8]) -> EncodingResult<RlpItem> {
+    let (item, rest) = decode_item(input, 0)?;
+    if !rest.is_empty() {
+        return Err(EncodingError::InvalidInput);
+    }
+    Ok(item)
+}
+
+/// Decodes a single RLP item from the beginning of the `input`.
+/// Returns the item and the rest of the input.
+fn decode_item(input: &[u8], depth: usize) -> EncodingResult<(RlpItem, &[u8])> {
+    let (header, rest) = Header::decode(input)?;
+    let (payload, rest) = split_at_checked(rest, header.payload_len)?;
+
+    if !header.is_list {
+        return Ok((RlpItem::String(payload.to_vec()), rest));
+    }
+
+    if depth >= MAX_DEPTH {
+        return Err(EncodingError::InvalidInput);
+    }
+
+    let mut items = Vec::new();
+    let mut payload = payload;
+    while !payload.is_empty() {
+        let (item, payload_rest) = decode_item(payload, depth + 1)?;
+        items.push(item);
+        payload = payload_rest;
+    }
+    Ok((RlpItem::List(items), rest))
+}
+
+struct Header {
+    is_list: bool,
+    payload_len: usize,
+}
+
+impl Header {
+    /// Decodes an item header and returns the rest of the input starting from the payload.
+    /// A single byte below `0x80` is its own payload.
+    fn decode(input: &[u8]) -> EncodingResult<(Header, &[u8])> {
+        let (prefix, rest) = input.split_first().ok_or(EncodingError::InvalidInput)?;
+
+        match *prefix {
+            0x00..=0x7f => Ok((
+                Header {
+                    is_list: false,
+                    payload_len: 1,
+                },
+                input,
+            )),
+            0x80..=0xb7 => {
+                let payload_len = (prefix - STRING_OFFSET) as usize;
+                // A single byte below `0x80` must be encoded as itself.
+                if payload_len == 1 && rest.first().is_some_and(|byte| *byte < STRING_OFFSET) {
+                    return Err(EncodingError::InvalidInput);
+                }
+                let header = Header {
+                    is_list: false,
+                    payload_len,
+                };
+                Ok((header, rest))
+            },
+            0xb8..=0xbf => {
+                let len_of_len = (prefix - STRING_OFFSET) as usize - (SHORT_LEN_LIMIT - 1);
+                let (payload_len, rest) = decode_long_len(rest, len_of_len)?;
+                let header = Header {
+                    is_list: false,
+                    payload_len,
+                };
+                Ok((header, rest))
+            },
+            0xc0..=0xf7 => {
+                let header = Header {
+                    is_list: true,
+                    payload_len: (prefix - LIST_OFFSET) as usize,
+                };
+                Ok((header, rest))
+            },
+            0xf8..=0xff => {
+                let len_of_len = (prefix - LIST_OFFSET) as usize - (SHORT_LEN_LIMIT - 1);
+                let (payload_len, rest) = decode_long_len(rest, len_of_len)?;
+                let header = Header {
+                    is_list: true,
+                    payload_len,
+                };
+                Ok((header, rest))
+            },
+        }
+    }
+}
+
+/// Decodes a big-endian payload length of a long string or list.
+/// The length must not have leading zeros and must not fit into a short header.
+fn decode_long_len(input: &[u8], len_of_len: usize) -> EncodingResult<(usize, &[u8])> {
+    let (len_bytes, rest) = split_at_checked(input, len_of_len)?;
+    if len_bytes[0] == 0 || len_of_len > std::mem::size_of::<usize>() {
+        return Err(EncodingError::InvalidInput);
+    }
+
+    let len = len_bytes
+        .iter()
+        .fold(0_usize, |len, byte| (len << 8) | *byte as usize);
+    if len < SHORT_LEN_LIMIT {
+        return Err(EncodingError::InvalidInput);
+    }
+    Ok((len, rest))
+}
+
+fn split_at_checked(input: &[u8], mid: usize) -> EncodingResult<(&[u8], &[u8])> {
+    if input.len() < mid {
+        return Err(EncodingError::InvalidInput);
+    }
+    Ok(input.split_at(mid))
+}
+
+/// Writes a string or list header with the given payload length.
+fn write_header(out: &mut Vec<u8>, offset: u8, payload_len: usize) {
+    if payload_len < SHORT_LEN_LIMIT {
+        out.push(offset + payload_len as u8);
+        return;
+    }
+
+    let len_bytes = payload_len.to_be_bytes();
+    let leading_zeros = len_bytes.iter().take_while(|byte| **byte == 0).count();
+    let len_bytes = &len_bytes[leading_zeros..];
+
+    out.push(offset + (SHORT_LEN_LIMIT - 1) as u8 + len_bytes.len() as u8);
+    out.extend_from_slice(len_bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    const LOREM_IPSUM: &str = "Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+
+    fn test_encode_impl<T: RlpEncode + ?Sized>(value: &T, expected: &str) {
+        assert_eq!(hex::encode(encode(value), false), expected);
+    }
+
+    #[test]
+    fn test_rlp_encode_strings() {
+        test_encode_impl("", "80");
+        test_encode_impl("d", "64");
+        test_encode_impl("dog", "83646f67");
+        test_encode_impl(&[0_u8], "00");
+        test_encode_impl(&[0x7f_u8], "7f");
+        test_encode_impl(&[0x80_u8], "8180");
+        test_encode_impl(
+            LOREM_IPSUM,
+            "b8384c6f72656d20697073756d20646f6c6f722073697420616d65742c20636f6e7365637465747572206164697069736963696e6720656c6974",
+        );
+    }
+
+    #[test]
+    fn test_rlp_encode_integers() {
+        test_encode_impl(&0_u64, "80");
+        test_encode_impl(&15_u8, "0f");
+        test_encode_impl(&127_u32, "7f");
+        test_encode_impl(&128_u32, "8180");
+        test_encode_impl(&1024_u16, "820400");
+        test_encode_impl(&0xffffff_u64, "83ffffff");
+        test_encode_impl(&u64::MAX, "88ffffffffffffffff");
+        test_encode_impl(&1_000_000_000_000_000_000_u64, "880de0b6b3a7640000");
+        test_encode_impl(&u128::MAX, "90ffffffffffffffffffffffffffffffff");
+    }
+
+    #[test]
+    fn test_rlp_encode_lists() {
+        let empty: [&str; 0] = [];
+        let mut stream = RlpStream::new();
+        stream.append_list(&empty);
+        assert_eq!(hex::encode(stream.out(), false), "c0");
+
+        let mut stream = RlpStream::new();
+        stream.append_list(&["cat", "dog"]);
+        assert_eq!(hex::encode(stream.out(), false), "c88363617483646f67");
+
+        // The set theoretical representation of three: [ [], [[]], [ [], [[]] ] ]
+        let mut stream = RlpStream::new();
+        stream
+            .begin_list()
+            .begin_list()
+            .finalize_list()
+            .begin_list()
+            .begin_list()
+            .finalize_list()
+            .finalize_list()
+            .begin_list()
+            .begin_list()
+            .finalize_list()
+            .begin_list()
+            .begin_list()
+            .finalize_list()
+            .finalize_list()
+            .finalize_list()
+            .finalize_list();
+        assert_eq!(hex::encode(stream.out(), false), "c7c0c1c0c3c0c1c0");
+
+        let mut stream = RlpStream::new();
+        stream.append_list(&[LOREM_IPSUM]);
+        assert_eq!(
+            hex::encode(stream.out(), false),
+            "f83ab8384c6f72656d20697073756d20646f6c6f722073697420616d65742c20636f6e7365637465747572206164697069736963696e6720656c6974"
+        );
+
+        let mut stream = RlpStream::new();
+        stream
+            .begin_list()
+            .append(&1_u64)
+            .append_raw(&hex::decode("c3010203").unwrap())
+            .finalize_list();
+        assert_eq!(hex::encode(stream.out(), false), "c501c3010203");
+    }
+
+    #[test]
+    fn test_rlp_encode_legacy_transaction() {
+        // Unsigned legacy transaction from EIP-155.
+        let to = hex::decode("3535353535353535353535353535353535353535").unwrap();
+        let mut stream = RlpStream::new();
+        stream
+            .begin_list()
+            .append(&9_u64)
+            .append(&20_000_000_000_u64)
+            .append(&21000_u64)
+            .append(&to)
+            .append(&1_000_000_000_000_000_000_u64)
+            .append("")
+            .append(&1_u64)
+            .append(&0_u64)
+            .append(&0_u64)
+            .finalize_list();
+        assert_eq!(
+            hex::encode(stream.out(), false),
+            "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080"
+        );
+    }
+
+    #[test]
+    fn test_rlp_decode() {
+        let decoded = decode(&hex::decode("c7c0c1c0c3c0c1c0").unwrap()).unwrap();
+        let empty = || RlpItem::List(Vec::new());
+        let expected = RlpItem::List(vec![
+            empty(),
+            RlpItem::List(vec![empty()]),
+            RlpItem::List(vec![empty(), RlpItem::List(vec![empty()])]),
+        ]);
+        assert_eq!(decoded, expected);
+        assert_eq!(encode(&decoded), hex::decode("c7c0c1c0c3c0c1c0").unwrap());
+
+        let decoded = decode(&hex::decode("c88363617483646f67").unwrap()).unwrap();
+        let items = decoded.as_list().unwrap();
+        assert_eq!(items[0].as_bytes().unwrap(), b"cat");
+        assert_eq!(items[1].as_bytes().unwrap(), b"dog");
+
+        let encoded = encode(LOREM_IPSUM);
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded.as_bytes().unwrap(), LOREM_IPSUM.as_bytes());
+
+        let decoded = decode(&hex::decode("880de0b6b3a7640000").unwrap()).unwrap();
+        assert_eq!(decoded.as_u64().unwrap(), 1_000_000_000_000_000_000);
+        assert_eq!(
+            decoded.as_uint_bytes().unwrap(),
+            hex::decode("0de0b6b3a7640000").unwrap()
+        );
+        assert_eq!(decode(&[0x80]).unwrap().as_u64().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_rlp_decode_non_canonical() {
+        let invalid = [
+            // Empty input.
+            "",
+            // A single byte below 0x80 must not be prefixed.
+            "8100",
+            "817f",
+            // Truncated string.
+            "83646f",
+            // Truncated list.
+            "c88363617483646f",
+            // Trailing data.
+            "83646f6700",
+            // Long string header for a short string.
+            "b803646f67",
+            // Leading zero in the length of a long string.
+            "b900384c6f72656d",
+            // Long list header for a short list.
+            "f803c20102",
+            // The nested item exceeds the list payload.
+            "c2820102",
+        ];
+        for input in invalid {
+            let input = hex::decode(input).unwrap();
+            assert_eq!(
+                decode(&input),
+                Err(EncodingError::InvalidInput),
+                "{}",
+                hex::encode(&input, false)
+            );
+        }
+
+        // Integers with leading zeros.
+        let item = decode(&hex::decode("820001").unwrap()).unwrap();
+        item.as_u64().unwrap_err();
+        item.as_uint_bytes().unwrap_err();
+        // Integer overflow.
+        decode(&hex::decode("89010000000000000000").unwrap())
+            .unwrap()
+            .as_u64()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_rlp_decode_max_depth() {
+        let mut nested = vec![0xc0];
+        for _ in 0..MAX_DEPTH {
+            let mut stream = RlpStream::new();
+            stream.begin_list().append_raw(&nested).finalize_list();
+            nested = stream.out();
+        }
+        assert_eq!(decode(&nested), Err(EncodingError::InvalidInput));
+    }
+}
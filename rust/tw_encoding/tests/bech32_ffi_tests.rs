@@ -0,0 +1,105 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::ffi::CString;
+use tw_encoding::ffi::{
+    decode_bech32, decode_segwit_address, encode_bech32, encode_segwit_address, Bech32Variant,
+};
+use tw_encoding::hex;
+
+const COSMOS_ADDRESS: &str = "cosmos1hsk6jryyqjfhp5dhc55tc9jtckygx0eph6dd02";
+const COSMOS_KEY_HASH: &str = "bc2da90c84049370d1b7c528bc164bc588833f21";
+
+#[test]
+fn test_encode_bech32() {
+    let hrp = CString::new("cosmos").unwrap();
+    let data = hex::decode(COSMOS_KEY_HASH).unwrap();
+
+    let result = unsafe {
+        encode_bech32(
+            hrp.as_ptr(),
+            data.as_ptr(),
+            data.len(),
+            Bech32Variant::Bech32,
+            90,
+        )
+        .unwrap()
+    };
+    let result = unsafe { CString::from_raw(result) };
+    assert_eq!(result.to_str().unwrap(), COSMOS_ADDRESS);
+
+    let too_short = unsafe {
+        encode_bech32(
+            hrp.as_ptr(),
+            data.as_ptr(),
+            data.len(),
+            Bech32Variant::Bech32,
+            COSMOS_ADDRESS.len() - 1,
+        )
+    };
+    assert!(too_short.into_result().is_err());
+}
+
+#[test]
+fn test_decode_bech32() {
+    let input = CString::new(COSMOS_ADDRESS).unwrap();
+    let hrp = CString::new("cosmos").unwrap();
+
+    let decoded = unsafe {
+        decode_bech32(input.as_ptr(), hrp.as_ptr(), Bech32Variant::Bech32, 90)
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(hex::encode(decoded, false), COSMOS_KEY_HASH);
+
+    // The human-readable part is not checked.
+    let decoded = unsafe {
+        decode_bech32(input.as_ptr(), std::ptr::null(), Bech32Variant::Bech32, 90)
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(hex::encode(decoded, false), COSMOS_KEY_HASH);
+
+    let other_hrp = CString::new("osmo").unwrap();
+    let res = unsafe {
+        decode_bech32(
+            input.as_ptr(),
+            other_hrp.as_ptr(),
+            Bech32Variant::Bech32,
+            90,
+        )
+    };
+    assert!(res.into_result().is_err());
+
+    let res = unsafe { decode_bech32(input.as_ptr(), hrp.as_ptr(), Bech32Variant::Bech32m, 90) };
+    assert!(res.into_result().is_err());
+}
+
+#[test]
+fn test_segwit_address() {
+    let hrp = CString::new("bc").unwrap();
+    let program =
+        hex::decode("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+    let expected = "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0";
+
+    let result =
+        unsafe { encode_segwit_address(hrp.as_ptr(), 1, program.as_ptr(), program.len()).unwrap() };
+    let result = unsafe { CString::from_raw(result) };
+    assert_eq!(result.to_str().unwrap(), expected);
+
+    let address = CString::new(expected).unwrap();
+    let decoded = unsafe {
+        decode_segwit_address(hrp.as_ptr(), address.as_ptr())
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(decoded[0], 1);
+    assert_eq!(decoded[1..], program);
+
+    // Witness v0 program must be 20 or 32 bytes long.
+    let res = unsafe { encode_segwit_address(hrp.as_ptr(), 0, program.as_ptr(), 21) };
+    assert!(res.into_result().is_err());
+}
@@ -0,0 +1,242 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::abi::contract::{parse_function, Contract};
+use crate::abi::decode::decode_params;
+use crate::abi::param_type::ParamType;
+use crate::abi::token::{NamedToken, Token};
+use crate::abi::{abi_error, AbiResult, WORD_LEN};
+use crate::address::Address;
+use std::borrow::Cow;
+use std::str::FromStr;
+use tw_hash::{H256, U256};
+use tw_proto::EthereumAbi::Proto;
+use Proto::mod_ParamsDecodingInput::OneOfabi as DecodingAbi;
+use Proto::mod_Token::OneOftoken as ProtoTokenValue;
+
+/// Protobuf interface of the ABI module.
+/// Errors are reported through the `error` and `error_message` output fields.
+pub struct AbiEncoder;
+
+impl AbiEncoder {
+    pub fn encode_function_proto(
+        input: Proto::FunctionEncodingInput<'_>,
+    ) -> Proto::FunctionEncodingOutput<'static> {
+        Self::encode_function_impl(&input).unwrap_or_else(|e| Proto::FunctionEncodingOutput {
+            error: e.error,
+            error_message: Cow::Owned(e.message),
+            ..Proto::FunctionEncodingOutput::default()
+        })
+    }
+
+    pub fn decode_contract_call_proto(
+        input: Proto::ContractCallDecodingInput<'_>,
+    ) -> Proto::ContractCallDecodingOutput<'static> {
+        Self::decode_contract_call_impl(&input).unwrap_or_else(|e| {
+            Proto::ContractCallDecodingOutput {
+                error: e.error,
+                error_message: Cow::Owned(e.message),
+                ..Proto::ContractCallDecodingOutput::default()
+            }
+        })
+    }
+
+    pub fn decode_params_proto(
+        input: Proto::ParamsDecodingInput<'_>,
+    ) -> Proto::ParamsDecodingOutput<'static> {
+        Self::decode_params_impl(&input).unwrap_or_else(|e| Proto::ParamsDecodingOutput {
+            error: e.error,
+            error_message: Cow::Owned(e.message),
+            ..Proto::ParamsDecodingOutput::default()
+        })
+    }
+
+    fn encode_function_impl(
+        input: &Proto::FunctionEncodingInput<'_>,
+    ) -> AbiResult<Proto::FunctionEncodingOutput<'static>> {
+        let function = parse_function(&input.function)?;
+        let tokens = input
+            .params
+            .iter()
+            .map(token_from_proto)
+            .collect::<AbiResult<Vec<_>>>()?;
+
+        Ok(Proto::FunctionEncodingOutput {
+            encoded: Cow::Owned(function.encode_input(&tokens)?),
+            function_signature: Cow::Owned(function.signature()),
+            ..Proto::FunctionEncodingOutput::default()
+        })
+    }
+
+    fn decode_contract_call_impl(
+        input: &Proto::ContractCallDecodingInput<'_>,
+    ) -> AbiResult<Proto::ContractCallDecodingOutput<'static>> {
+        let contract = Contract::from_json(&input.abi)?;
+        let (function, params) = contract.decode_call(&input.encoded)?;
+
+        Ok(Proto::ContractCallDecodingOutput {
+            function_signature: Cow::Owned(function.signature()),
+            params: params.iter().map(named_token_to_proto).collect(),
+            ..Proto::ContractCallDecodingOutput::default()
+        })
+    }
+
+    fn decode_params_impl(
+        input: &Proto::ParamsDecodingInput<'_>,
+    ) -> AbiResult<Proto::ParamsDecodingOutput<'static>> {
+        let params = match input.abi {
+            DecodingAbi::function_outputs(ref function) => {
+                parse_function(function)?.decode_output(&input.encoded)?
+            },
+            DecodingAbi::types(ref types) => {
+                let types = types
+                    .types
+                    .iter()
+                    .map(|ty| ParamType::from_str(ty))
+                    .collect::<AbiResult<Vec<_>>>()?;
+                decode_params(&types, &input.encoded)?
+                    .into_iter()
+                    .map(|value| NamedToken {
+                        name: String::default(),
+                        value,
+                    })
+                    .collect()
+            },
+            DecodingAbi::None => return Err(abi_error("Either function or types expected")),
+        };
+
+        Ok(Proto::ParamsDecodingOutput {
+            params: params.iter().map(named_token_to_proto).collect(),
+            ..Proto::ParamsDecodingOutput::default()
+        })
+    }
+}
+
+fn token_from_proto(token: &Proto::Token<'_>) -> AbiResult<Token> {
+    match token.token {
+        ProtoTokenValue::boolean(flag) => Ok(Token::Bool(flag)),
+        ProtoTokenValue::number_int(ref number) => {
+            let bits = number_bits(number, "int")?;
+            if number.value.len() > WORD_LEN {
+                return Err(abi_error("Number is longer than 32 bytes"));
+            }
+            // Sign-extend the number up to 32 bytes.
+            let sign_extension = match number.value.first() {
+                Some(first) if first & 0x80 != 0 => 0xff,
+                _ => 0x00,
+            };
+            let mut value = H256::from([sign_extension; WORD_LEN]);
+            value[WORD_LEN - number.value.len()..].copy_from_slice(&number.value);
+            Ok(Token::Int { bits, value })
+        },
+        ProtoTokenValue::number_uint(ref number) => {
+            let bits = number_bits(number, "uint")?;
+            let value = U256::from_big_endian_slice(&number.value)
+                .map_err(|_| abi_error("Number is longer than 32 bytes"))?;
+            Ok(Token::Uint { bits, value })
+        },
+        ProtoTokenValue::string_value(ref s) => Ok(Token::String(s.to_string())),
+        ProtoTokenValue::address(ref address) => Address::from_str(address).map(Token::Address),
+        ProtoTokenValue::byte_array(ref bytes) => Ok(Token::Bytes(bytes.to_vec())),
+        ProtoTokenValue::byte_array_fix(ref bytes) => {
+            let size = bytes.size as usize;
+            if size == 0 || size > WORD_LEN || bytes.value.len() > size {
+                return Err(abi_error(format!("Invalid bytes{size} value")));
+            }
+            let mut value = bytes.value.to_vec();
+            value.resize(size, 0);
+            Ok(Token::FixedBytes(value))
+        },
+        ProtoTokenValue::array(ref array) => {
+            let (element_type, elements) = array_from_proto(array)?;
+            Ok(Token::Array {
+                element_type,
+                elements,
+            })
+        },
+        ProtoTokenValue::fixed_array(ref array) => {
+            let (element_type, elements) = array_from_proto(array)?;
+            Ok(Token::FixedArray {
+                element_type,
+                elements,
+            })
+        },
+        ProtoTokenValue::tuple(ref tuple) => tuple
+            .params
+            .iter()
+            .map(token_from_proto)
+            .collect::<AbiResult<_>>()
+            .map(Token::Tuple),
+        ProtoTokenValue::None => Err(abi_error("Missing token value")),
+    }
+}
+
+fn number_bits(number: &Proto::NumberNParam, kind: &str) -> AbiResult<usize> {
+    match ParamType::from_str(&format!("{kind}{}", number.bits))? {
+        ParamType::Int(bits) | ParamType::Uint(bits) => Ok(bits),
+        _ => Err(abi_error(format!("Invalid {kind} bits"))),
+    }
+}
+
+fn array_from_proto(array: &Proto::ArrayParam<'_>) -> AbiResult<(ParamType, Vec<Token>)> {
+    let element_type = ParamType::from_str(&array.element_type)?;
+    let elements = array
+        .elements
+        .iter()
+        .map(token_from_proto)
+        .collect::<AbiResult<_>>()?;
+    Ok((element_type, elements))
+}
+
+fn named_token_to_proto(token: &NamedToken) -> Proto::Token<'static> {
+    Proto::Token {
+        name: Cow::Owned(token.name.clone()),
+        ..token_to_proto(&token.value)
+    }
+}
+
+fn token_to_proto(token: &Token) -> Proto::Token<'static> {
+    let value = match token {
+        Token::Address(address) => ProtoTokenValue::address(Cow::Owned(address.to_string())),
+        Token::Bool(flag) => ProtoTokenValue::boolean(*flag),
+        Token::Int { bits, value } => ProtoTokenValue::number_int(Proto::NumberNParam {
+            bits: *bits as u32,
+            value: Cow::Owned(value.into_vec()),
+        }),
+        Token::Uint { bits, value } => ProtoTokenValue::number_uint(Proto::NumberNParam {
+            bits: *bits as u32,
+            value: Cow::Owned(value.to_big_endian_compact()),
+        }),
+        Token::FixedBytes(bytes) => ProtoTokenValue::byte_array_fix(Proto::BytesFixParam {
+            size: bytes.len() as u32,
+            value: Cow::Owned(bytes.clone()),
+        }),
+        Token::Bytes(bytes) => ProtoTokenValue::byte_array(Cow::Owned(bytes.clone())),
+        Token::String(s) => ProtoTokenValue::string_value(Cow::Owned(s.clone())),
+        Token::Array {
+            element_type,
+            elements,
+        } => ProtoTokenValue::array(array_to_proto(element_type, elements)),
+        Token::FixedArray {
+            element_type,
+            elements,
+        } => ProtoTokenValue::fixed_array(array_to_proto(element_type, elements)),
+        Token::Tuple(components) => ProtoTokenValue::tuple(Proto::TupleParam {
+            params: components.iter().map(token_to_proto).collect(),
+        }),
+    };
+    Proto::Token {
+        name: Cow::default(),
+        token: value,
+    }
+}
+
+fn array_to_proto(element_type: &ParamType, elements: &[Token]) -> Proto::ArrayParam<'static> {
+    Proto::ArrayParam {
+        element_type: Cow::Owned(element_type.to_string()),
+        elements: elements.iter().map(token_to_proto).collect(),
+    }
+}
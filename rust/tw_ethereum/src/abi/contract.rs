@@ -0,0 +1,274 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::abi::function::{is_identifier, Event, Function, Param};
+use crate::abi::param_type::ParamType;
+use crate::abi::token::NamedToken;
+use crate::abi::{abi_error, AbiResult, SELECTOR_LEN};
+use crate::{SigningError, SigningErrorType};
+use serde::Deserialize;
+use serde_json::Value as Json;
+use std::str::FromStr;
+
+/// cbindgen:ignore
+const TUPLE_TYPE: &str = "tuple";
+
+/// Functions and events of a contract ABI.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Contract {
+    pub functions: Vec<Function>,
+    pub events: Vec<Event>,
+}
+
+/// A function or event parameter of a JSON ABI.
+#[derive(Deserialize)]
+struct JsonParam {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type")]
+    param_type: String,
+    #[serde(default)]
+    components: Vec<JsonParam>,
+    #[serde(default)]
+    indexed: bool,
+}
+
+/// An entry of a JSON ABI. Constructors, errors, `fallback` and `receive` entries are ignored.
+#[derive(Deserialize)]
+struct JsonEntry {
+    #[serde(rename = "type", default = "default_entry_type")]
+    entry_type: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    inputs: Vec<JsonParam>,
+    #[serde(default)]
+    outputs: Vec<JsonParam>,
+    #[serde(default)]
+    anonymous: bool,
+}
+
+fn default_entry_type() -> String {
+    "function".to_string()
+}
+
+impl Contract {
+    /// Parses a JSON ABI. Entries of the array can be either JSON objects or
+    /// human-readable signatures, e.g. `["function transfer(address to, uint256 amount)"]`.
+    pub fn from_json(json: &str) -> AbiResult<Contract> {
+        let entries: Vec<Json> = serde_json::from_str(json).map_err(|e| {
+            SigningError::new(
+                SigningErrorType::Error_input_parse,
+                format!("Invalid ABI: {e}"),
+            )
+        })?;
+
+        let mut contract = Contract::default();
+        for entry in entries {
+            match entry {
+                Json::String(signature) => contract.add_signature(&signature)?,
+                entry => contract.add_entry(parse_json_entry(entry)?)?,
+            }
+        }
+        Ok(contract)
+    }
+
+    pub fn function_by_selector(&self, selector: &[u8]) -> Option<&Function> {
+        self.functions
+            .iter()
+            .find(|function| function.selector().as_slice() == selector)
+    }
+
+    /// Finds the called function by the selector and decodes its arguments.
+    pub fn decode_call(&self, data: &[u8]) -> AbiResult<(&Function, Vec<NamedToken>)> {
+        let function = data
+            .get(..SELECTOR_LEN)
+            .and_then(|selector| self.function_by_selector(selector))
+            .ok_or_else(|| abi_error("No function found by the selector"))?;
+        let params = function.decode_input(data)?;
+        Ok((function, params))
+    }
+
+    fn add_signature(&mut self, signature: &str) -> AbiResult<()> {
+        let signature = signature.trim();
+        if signature.starts_with("event ") {
+            self.events.push(Event::from_str(signature)?);
+        } else if signature.starts_with("function ") {
+            self.functions.push(Function::from_str(signature)?);
+        }
+        // Other human-readable entries like constructors and errors are ignored.
+        Ok(())
+    }
+
+    fn add_entry(&mut self, entry: JsonEntry) -> AbiResult<()> {
+        match entry.entry_type.as_str() {
+            "function" => self.functions.push(function_from_json(entry)?),
+            "event" => self.events.push(Event {
+                name: checked_name(entry.name)?,
+                inputs: params_from_json(entry.inputs)?,
+                anonymous: entry.anonymous,
+            }),
+            _ => {},
+        }
+        Ok(())
+    }
+}
+
+/// Parses a human-readable signature or a JSON ABI function entry.
+pub fn parse_function(s: &str) -> AbiResult<Function> {
+    if !s.trim_start().starts_with('{') {
+        return Function::from_str(s);
+    }
+
+    let entry: Json = serde_json::from_str(s).map_err(|e| {
+        SigningError::new(
+            SigningErrorType::Error_input_parse,
+            format!("Invalid ABI function: {e}"),
+        )
+    })?;
+    let entry = parse_json_entry(entry)?;
+    if entry.entry_type != "function" {
+        return Err(abi_error("Expected a function ABI"));
+    }
+    function_from_json(entry)
+}
+
+fn parse_json_entry(entry: Json) -> AbiResult<JsonEntry> {
+    serde_json::from_value(entry).map_err(|e| {
+        SigningError::new(
+            SigningErrorType::Error_input_parse,
+            format!("Invalid ABI entry: {e}"),
+        )
+    })
+}
+
+fn function_from_json(entry: JsonEntry) -> AbiResult<Function> {
+    Ok(Function {
+        name: checked_name(entry.name)?,
+        inputs: params_from_json(entry.inputs)?,
+        outputs: params_from_json(entry.outputs)?,
+    })
+}
+
+fn checked_name(name: String) -> AbiResult<String> {
+    if !is_identifier(&name) {
+        return Err(abi_error(format!("Invalid name '{name}'")));
+    }
+    Ok(name)
+}
+
+fn params_from_json(params: Vec<JsonParam>) -> AbiResult<Vec<Param>> {
+    params
+        .into_iter()
+        .map(|param| {
+            Ok(Param {
+                kind: param_type_from_json(&param)?,
+                name: param.name,
+                indexed: param.indexed,
+            })
+        })
+        .collect()
+}
+
+/// Resolves `tuple`, `tuple[]`, `tuple[2][]` etc types using the `components`.
+fn param_type_from_json(param: &JsonParam) -> AbiResult<ParamType> {
+    let Some(array_suffix) = param.param_type.strip_prefix(TUPLE_TYPE) else {
+        return ParamType::from_str(&param.param_type);
+    };
+
+    let components = param
+        .components
+        .iter()
+        .map(param_type_from_json)
+        .collect::<AbiResult<_>>()?;
+    ParamType::from_str(&format!("{}{array_suffix}", ParamType::Tuple(components)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contract_from_json() {
+        let abi = r#"[
+            {
+                "type": "function",
+                "name": "fillOrders",
+                "inputs": [
+                    {
+                        "name": "orders",
+                        "type": "tuple[]",
+                        "components": [
+                            {"name": "maker", "type": "address"},
+                            {"name": "amounts", "type": "uint256[2]"},
+                            {"name": "fee", "type": "tuple", "components": [{"name": "bps", "type": "uint16"}]}
+                        ]
+                    },
+                    {"name": "data", "type": "bytes"}
+                ],
+                "outputs": [{"name": "", "type": "bool"}],
+                "stateMutability": "payable"
+            },
+            {
+                "type": "event",
+                "name": "Transfer",
+                "inputs": [
+                    {"name": "from", "type": "address", "indexed": true},
+                    {"name": "to", "type": "address", "indexed": true},
+                    {"name": "value", "type": "uint256", "indexed": false}
+                ],
+                "anonymous": false
+            },
+            {"type": "constructor", "inputs": []},
+            {"type": "fallback"},
+            "function approve(address spender, uint256 amount) returns (bool)",
+            "error Unauthorized(address)"
+        ]"#;
+        let contract = Contract::from_json(abi).unwrap();
+
+        let signatures: Vec<_> = contract.functions.iter().map(Function::signature).collect();
+        assert_eq!(
+            signatures,
+            [
+                "fillOrders((address,uint256[2],(uint16))[],bytes)",
+                "approve(address,uint256)"
+            ]
+        );
+        assert_eq!(contract.functions[0].outputs.len(), 1);
+        assert_eq!(contract.events.len(), 1);
+        assert!(contract.events[0].inputs[1].indexed);
+    }
+
+    #[test]
+    fn test_contract_from_json_invalid() {
+        for invalid in [
+            "",
+            "{}",
+            r#"[{"type": "function", "name": "f", "inputs": [{"name": "a", "type": "uint7"}]}]"#,
+            r#"[{"type": "function", "name": "f", "inputs": [{"name": "a"}]}]"#,
+            r#"[{"type": "function", "name": "", "inputs": []}]"#,
+            r#"["function f(uint7)"]"#,
+        ] {
+            assert!(Contract::from_json(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_parse_function() {
+        let json = r#"{"name": "transfer", "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint"}]}"#;
+        assert_eq!(
+            parse_function(json).unwrap().signature(),
+            "transfer(address,uint256)"
+        );
+        assert_eq!(
+            parse_function("transfer(address,uint)")
+                .unwrap()
+                .signature(),
+            "transfer(address,uint256)"
+        );
+        parse_function(r#"{"type": "event", "name": "Transfer"}"#).unwrap_err();
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::abi::param_type::ParamType;
+use crate::abi::token::{int_fits, Token};
+use crate::abi::{AbiResult, WORD_LEN};
+use crate::address::Address;
+use crate::{SigningError, SigningErrorType};
+use tw_hash::{H160, H256, U256};
+
+/// Decodes the data as a tuple of the given types.
+/// Values are validated, e.g. `bool` must be 0 or 1, and `uintN` must fit `N` bits.
+pub fn decode_params(types: &[ParamType], data: &[u8]) -> AbiResult<Vec<Token>> {
+    decode_sequence(types.iter(), data)
+}
+
+/// Decodes consecutive values which offsets are relative to the beginning of `data`.
+fn decode_sequence<'a, I>(types: I, data: &[u8]) -> AbiResult<Vec<Token>>
+where
+    I: Iterator<Item = &'a ParamType>,
+{
+    let mut tokens = Vec::new();
+    let mut head_offset = 0;
+    for ty in types {
+        let token = if ty.is_dynamic() {
+            let offset = read_usize(data, head_offset)?;
+            decode_value(ty, data.get(offset..).ok_or_else(out_of_bounds)?)?
+        } else {
+            decode_value(ty, data.get(head_offset..).ok_or_else(out_of_bounds)?)?
+        };
+        tokens.push(token);
+        head_offset = head_offset
+            .checked_add(ty.head_len()?)
+            .ok_or_else(out_of_bounds)?;
+    }
+    Ok(tokens)
+}
+
+/// Decodes a value located at the beginning of `data`.
+fn decode_value(ty: &ParamType, data: &[u8]) -> AbiResult<Token> {
+    match ty {
+        ParamType::Address => {
+            let word = read_word(data, 0)?;
+            let (padding, address) = word.split_at(WORD_LEN - Address::LEN);
+            if !is_zeroed(padding) {
+                return Err(invalid_data("Invalid address"));
+            }
+            let address = H160::try_from(address).expect("address length is checked above");
+            Ok(Token::Address(Address::new(address)))
+        },
+        ParamType::Bool => match U256::from(read_word(data, 0)?) {
+            value if value.is_zero() => Ok(Token::Bool(false)),
+            value if value == U256::from(1_u8) => Ok(Token::Bool(true)),
+            _ => Err(invalid_data("Invalid bool")),
+        },
+        ParamType::Int(bits) => {
+            let value = read_word(data, 0)?;
+            if !int_fits(&value, *bits) {
+                return Err(invalid_data(format!("Number doesn't fit int{bits}")));
+            }
+            Ok(Token::Int { bits: *bits, value })
+        },
+        ParamType::Uint(bits) => {
+            let value = U256::from(read_word(data, 0)?);
+            if value.bits() > *bits {
+                return Err(invalid_data(format!("Number doesn't fit uint{bits}")));
+            }
+            Ok(Token::Uint { bits: *bits, value })
+        },
+        ParamType::FixedBytes(size) => {
+            let word = read_word(data, 0)?;
+            Ok(Token::FixedBytes(word[..*size].to_vec()))
+        },
+        ParamType::Bytes => read_bytes(data).map(|bytes| Token::Bytes(bytes.to_vec())),
+        ParamType::String => {
+            let bytes = read_bytes(data)?;
+            let s = String::from_utf8(bytes.to_vec()).map_err(|_| invalid_data("Invalid UTF-8"))?;
+            Ok(Token::String(s))
+        },
+        ParamType::Array(element_type) => {
+            let len = read_usize(data, 0)?;
+            let elements_data = &data[WORD_LEN..];
+            // Prevent allocating huge vectors for malformed data.
+            if len > elements_data.len() / element_type.head_len()?.max(1) {
+                return Err(out_of_bounds());
+            }
+            let elements = decode_sequence(
+                std::iter::repeat_n(element_type.as_ref(), len),
+                elements_data,
+            )?;
+            Ok(Token::Array {
+                element_type: element_type.as_ref().clone(),
+                elements,
+            })
+        },
+        ParamType::FixedArray(element_type, len) => {
+            // Prevent allocating huge vectors for zero-sized elements, e.g. `()[4294967296]`.
+            if *len > data.len() / element_type.head_len()?.max(1) {
+                return Err(out_of_bounds());
+            }
+            let elements = decode_sequence(std::iter::repeat_n(element_type.as_ref(), *len), data)?;
+            Ok(Token::FixedArray {
+                element_type: element_type.as_ref().clone(),
+                elements,
+            })
+        },
+        ParamType::Tuple(components) => decode_sequence(components.iter(), data).map(Token::Tuple),
+    }
+}
+
+fn read_word(data: &[u8], offset: usize) -> AbiResult<H256> {
+    let end = offset.checked_add(WORD_LEN).ok_or_else(out_of_bounds)?;
+    let word = data.get(offset..end).ok_or_else(out_of_bounds)?;
+    Ok(H256::try_from(word).expect("word length is checked above"))
+}
+
+/// Reads an offset or a length that must not exceed the data length.
+fn read_usize(data: &[u8], offset: usize) -> AbiResult<usize> {
+    let value = U256::from(read_word(data, offset)?);
+    let value = u64::try_from(value).map_err(|_| out_of_bounds())?;
+    if value > data.len() as u64 {
+        return Err(out_of_bounds());
+    }
+    Ok(value as usize)
+}
+
+fn read_bytes(data: &[u8]) -> AbiResult<&[u8]> {
+    let len = read_usize(data, 0)?;
+    data.get(WORD_LEN..WORD_LEN + len).ok_or_else(out_of_bounds)
+}
+
+fn is_zeroed(bytes: &[u8]) -> bool {
+    bytes.iter().all(|byte| *byte == 0)
+}
+
+fn invalid_data<M: Into<String>>(message: M) -> SigningError {
+    SigningError::new(SigningErrorType::Error_input_parse, message)
+}
+
+fn out_of_bounds() -> SigningError {
+    invalid_data("Encoded data is too short")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::encode::encode_tokens;
+    use std::str::FromStr;
+    use tw_encoding::hex;
+
+    fn types(types: &[&str]) -> Vec<ParamType> {
+        types
+            .iter()
+            .map(|ty| ParamType::from_str(ty).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_encode_round_trip() {
+        let types = types(&[
+            "address",
+            "int8",
+            "bytes3",
+            "(string,uint256[2])[]",
+            "bytes[2]",
+        ]);
+        let mut minus_two = H256::default();
+        minus_two.fill(0xff);
+        minus_two[WORD_LEN - 1] = 0xfe;
+
+        let tokens = vec![
+            Token::Address(
+                "0x5322b34c88ed0691971bf52a7047448f0f4efc84"
+                    .parse()
+                    .unwrap(),
+            ),
+            Token::Int {
+                bits: 8,
+                value: minus_two,
+            },
+            Token::FixedBytes(vec![1, 2, 3]),
+            Token::Array {
+                element_type: ParamType::from_str("(string,uint256[2])").unwrap(),
+                elements: vec![Token::Tuple(vec![
+                    Token::String("Hello".to_string()),
+                    Token::FixedArray {
+                        element_type: ParamType::Uint(256),
+                        elements: vec![
+                            Token::u256(U256::from(1_u8)),
+                            Token::u256(U256::from(2_u8)),
+                        ],
+                    },
+                ])],
+            },
+            Token::FixedArray {
+                element_type: ParamType::Bytes,
+                elements: vec![Token::Bytes(vec![0xab; 33]), Token::Bytes(Vec::new())],
+            },
+        ];
+        let encoded = encode_tokens(&tokens);
+        assert_eq!(decode_params(&types, &encoded).unwrap(), tokens);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let word = |s: &str| format!("{s:0>64}");
+        let tests = [
+            ("bool", word("2")),
+            ("uint8", word("100")),
+            ("int8", word("80")),
+            ("address", word("10000000000000000000000000000000000000000")),
+            ("uint256", "00".to_string()),
+            // The offset exceeds the data length.
+            ("bytes", word("40")),
+            // The length exceeds the data length.
+            ("bytes", word("20") + &word("20")),
+            ("uint256[]", word("20") + &word("ffffffff")),
+            (
+                "string",
+                word("20") + &word("1") + &format!("{:0<64}", "ff"),
+            ),
+        ];
+        for (ty, data) in tests {
+            let data = hex::decode(&data).unwrap();
+            let err = decode_params(&types(&[ty]), &data).unwrap_err();
+            assert_eq!(err.error, SigningErrorType::Error_input_parse, "{ty}");
+        }
+    }
+
+    #[test]
+    fn test_decode_fixed_array_too_large() {
+        let word = |s: &str| format!("{s:0>64}");
+        let tests = [
+            (
+                "uint256[4294967296][4294967296][]",
+                word("20") + &word("1") + &word("0"),
+                SigningErrorType::Error_invalid_params,
+            ),
+            (
+                "()[4294967296]",
+                word("0"),
+                SigningErrorType::Error_input_parse,
+            ),
+        ];
+        for (ty, data, error) in tests {
+            let data = hex::decode(&data).unwrap();
+            let err = decode_params(&types(&[ty]), &data).unwrap_err();
+            assert_eq!(err.error, error, "{ty}");
+        }
+    }
+}
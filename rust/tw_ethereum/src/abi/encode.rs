@@ -0,0 +1,176 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::abi::token::Token;
+use crate::abi::WORD_LEN;
+use crate::address::Address;
+use tw_hash::{H256, U256};
+
+/// Encodes the tokens as a tuple, i.e. static values and offsets of dynamic values go first
+/// followed by the dynamic values.
+/// Please note the tokens are expected to be type checked already.
+pub fn encode_tokens(tokens: &[Token]) -> Vec<u8> {
+    let encoded: Vec<_> = tokens
+        .iter()
+        .map(|token| (token.param_type().is_dynamic(), encode_token(token)))
+        .collect();
+    // A static value is encoded in place, a dynamic one is referenced by its offset.
+    let head_len: usize = encoded
+        .iter()
+        .map(|(dynamic, value)| if *dynamic { WORD_LEN } else { value.len() })
+        .sum();
+
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+    for (dynamic, value) in encoded {
+        if dynamic {
+            head.extend_from_slice(usize_word(head_len + tail.len()).as_slice());
+            tail.extend_from_slice(&value);
+        } else {
+            head.extend_from_slice(&value);
+        }
+    }
+
+    head.extend_from_slice(&tail);
+    head
+}
+
+fn encode_token(token: &Token) -> Vec<u8> {
+    match token {
+        Token::Address(address) => address_word(address).into_vec(),
+        Token::Bool(flag) => U256::from(*flag as u8).to_big_endian().into_vec(),
+        Token::Int { value, .. } => value.into_vec(),
+        Token::Uint { value, .. } => value.to_big_endian().into_vec(),
+        Token::FixedBytes(bytes) => right_padded(bytes),
+        Token::Bytes(bytes) => encode_bytes(bytes),
+        Token::String(s) => encode_bytes(s.as_bytes()),
+        Token::Array { elements, .. } => {
+            let mut encoded = usize_word(elements.len()).into_vec();
+            encoded.extend_from_slice(&encode_tokens(elements));
+            encoded
+        },
+        Token::FixedArray { elements, .. } => encode_tokens(elements),
+        Token::Tuple(components) => encode_tokens(components),
+    }
+}
+
+fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = usize_word(bytes.len()).into_vec();
+    encoded.extend_from_slice(&right_padded(bytes));
+    encoded
+}
+
+/// Pads the data with zeros up to a multiple of the word length.
+fn right_padded(bytes: &[u8]) -> Vec<u8> {
+    let mut padded = bytes.to_vec();
+    let padding = (WORD_LEN - bytes.len() % WORD_LEN) % WORD_LEN;
+    padded.resize(bytes.len() + padding, 0);
+    padded
+}
+
+fn address_word(address: &Address) -> H256 {
+    let mut word = H256::default();
+    word[WORD_LEN - Address::LEN..].copy_from_slice(address.as_slice());
+    word
+}
+
+fn usize_word(value: usize) -> H256 {
+    U256::from(value as u64).to_big_endian()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::param_type::ParamType;
+    use tw_encoding::hex;
+
+    fn uint(value: u64) -> Token {
+        Token::u256(U256::from(value))
+    }
+
+    #[test]
+    fn test_encode_static() {
+        let encoded = encode_tokens(&[uint(69), Token::Bool(true)]);
+        assert_eq!(
+            hex::encode(encoded, false),
+            "00000000000000000000000000000000000000000000000000000000000000450000000000000000000000000000000000000000000000000000000000000001"
+        );
+    }
+
+    /// The `sam(bytes,bool,uint256[])` example from the Solidity ABI specification.
+    #[test]
+    fn test_encode_dynamic() {
+        let encoded = encode_tokens(&[
+            Token::Bytes(b"dave".to_vec()),
+            Token::Bool(true),
+            Token::Array {
+                element_type: ParamType::Uint(256),
+                elements: vec![uint(1), uint(2), uint(3)],
+            },
+        ]);
+        assert_eq!(
+            hex::encode(encoded, false),
+            "0000000000000000000000000000000000000000000000000000000000000060\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             00000000000000000000000000000000000000000000000000000000000000a0\
+             0000000000000000000000000000000000000000000000000000000000000004\
+             6461766500000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000003"
+        );
+    }
+
+    /// The `g(uint256[][],string[])` example from the Solidity ABI specification.
+    #[test]
+    fn test_encode_nested_arrays() {
+        let uint_array = |elements: Vec<Token>| Token::Array {
+            element_type: ParamType::Uint(256),
+            elements,
+        };
+        let encoded = encode_tokens(&[
+            Token::Array {
+                element_type: ParamType::Array(Box::new(ParamType::Uint(256))),
+                elements: vec![
+                    uint_array(vec![uint(1), uint(2)]),
+                    uint_array(vec![uint(3)]),
+                ],
+            },
+            Token::Array {
+                element_type: ParamType::String,
+                elements: vec![
+                    Token::String("one".to_string()),
+                    Token::String("two".to_string()),
+                    Token::String("three".to_string()),
+                ],
+            },
+        ]);
+        assert_eq!(
+            hex::encode(encoded, false),
+            "0000000000000000000000000000000000000000000000000000000000000040\
+             0000000000000000000000000000000000000000000000000000000000000140\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000040\
+             00000000000000000000000000000000000000000000000000000000000000a0\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             0000000000000000000000000000000000000000000000000000000000000060\
+             00000000000000000000000000000000000000000000000000000000000000a0\
+             00000000000000000000000000000000000000000000000000000000000000e0\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             6f6e650000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             74776f0000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000005\
+             7468726565000000000000000000000000000000000000000000000000000000"
+        );
+    }
+}
@@ -0,0 +1,395 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::abi::decode::decode_params;
+use crate::abi::encode::encode_tokens;
+use crate::abi::param_type::{split_top_level, ParamType};
+use crate::abi::token::{NamedToken, Token};
+use crate::abi::{abi_error, AbiResult, SELECTOR_LEN};
+use crate::SigningError;
+use std::str::FromStr;
+use tw_hash::sha3::keccak256;
+use tw_hash::H256;
+
+/// cbindgen:ignore
+const DATA_LOCATIONS: [&str; 3] = ["memory", "calldata", "storage"];
+/// cbindgen:ignore
+const FUNCTION_MODIFIERS: [&str; 6] = [
+    "view",
+    "pure",
+    "payable",
+    "nonpayable",
+    "external",
+    "public",
+];
+
+pub type Selector = [u8; SELECTOR_LEN];
+
+/// A function or event parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub kind: ParamType,
+    /// Whether the event parameter is stored in the log topics. Always false for functions.
+    pub indexed: bool,
+}
+
+/// A contract function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<Param>,
+    pub outputs: Vec<Param>,
+}
+
+/// A contract event.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    pub name: String,
+    pub inputs: Vec<Param>,
+    pub anonymous: bool,
+}
+
+impl Function {
+    /// Returns the canonical signature, e.g. `transfer(address,uint256)`.
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.inputs)
+    }
+
+    /// Returns the first 4 bytes of the signature hash.
+    pub fn selector(&self) -> Selector {
+        let hash = keccak256(self.signature().as_bytes());
+        let mut selector = Selector::default();
+        selector.copy_from_slice(&hash[..SELECTOR_LEN]);
+        selector
+    }
+
+    /// Encodes the function call, i.e. the selector followed by the encoded arguments.
+    pub fn encode_input(&self, tokens: &[Token]) -> AbiResult<Vec<u8>> {
+        if tokens.len() != self.inputs.len() {
+            return Err(abi_error(format!(
+                "Expected {} arguments, found {}",
+                self.inputs.len(),
+                tokens.len()
+            )));
+        }
+        for (token, param) in tokens.iter().zip(self.inputs.iter()) {
+            token.type_check(&param.kind)?;
+        }
+
+        let mut encoded = self.selector().to_vec();
+        encoded.extend_from_slice(&encode_tokens(tokens));
+        Ok(encoded)
+    }
+
+    /// Decodes the function call arguments. `data` must start with the function selector.
+    pub fn decode_input(&self, data: &[u8]) -> AbiResult<Vec<NamedToken>> {
+        match data.split_first_chunk::<SELECTOR_LEN>() {
+            Some((selector, encoded)) if *selector == self.selector() => {
+                decode_named(&self.inputs, encoded)
+            },
+            _ => Err(abi_error("Function selector mismatch")),
+        }
+    }
+
+    /// Decodes the values returned by the function.
+    pub fn decode_output(&self, data: &[u8]) -> AbiResult<Vec<NamedToken>> {
+        decode_named(&self.outputs, data)
+    }
+}
+
+/// Parses a human-readable function signature, e.g. `transfer(address,uint256)`
+/// or `function balanceOf(address owner) external view returns (uint256 balance)`.
+impl FromStr for Function {
+    type Err = SigningError;
+
+    fn from_str(s: &str) -> AbiResult<Self> {
+        let parsed = parse_signature(s, "function")?;
+        if parsed.inputs.iter().any(|param| param.indexed) {
+            return Err(abi_error("Function parameters cannot be indexed"));
+        }
+        if parsed
+            .modifiers
+            .iter()
+            .any(|modifier| !FUNCTION_MODIFIERS.contains(modifier))
+        {
+            return Err(abi_error(format!("Invalid function signature '{s}'")));
+        }
+        Ok(Function {
+            name: parsed.name.to_string(),
+            inputs: parsed.inputs,
+            outputs: parsed.outputs.unwrap_or_default(),
+        })
+    }
+}
+
+impl Event {
+    /// Returns the canonical signature, e.g. `Transfer(address,address,uint256)`.
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.inputs)
+    }
+
+    /// Returns the first log topic of a non-anonymous event, i.e. the signature hash.
+    pub fn topic(&self) -> H256 {
+        let hash = keccak256(self.signature().as_bytes());
+        H256::try_from(hash.as_slice()).expect("keccak256 hash is 32 bytes long")
+    }
+}
+
+/// Parses a human-readable event signature, e.g. `Transfer(address,address,uint256)`
+/// or `event Transfer(address indexed from, address indexed to, uint256 value)`.
+impl FromStr for Event {
+    type Err = SigningError;
+
+    fn from_str(s: &str) -> AbiResult<Self> {
+        let parsed = parse_signature(s, "event")?;
+        let anonymous = match parsed.modifiers.as_slice() {
+            [] => false,
+            ["anonymous"] => true,
+            _ => return Err(abi_error(format!("Invalid event signature '{s}'"))),
+        };
+        if parsed.outputs.is_some() {
+            return Err(abi_error("Events cannot return values"));
+        }
+        Ok(Event {
+            name: parsed.name.to_string(),
+            inputs: parsed.inputs,
+            anonymous,
+        })
+    }
+}
+
+fn signature(name: &str, params: &[Param]) -> String {
+    let types: Vec<_> = params.iter().map(|param| param.kind.to_string()).collect();
+    format!("{name}({})", types.join(","))
+}
+
+fn decode_named(params: &[Param], data: &[u8]) -> AbiResult<Vec<NamedToken>> {
+    let types: Vec<_> = params.iter().map(|param| param.kind.clone()).collect();
+    let tokens = decode_params(&types, data)?;
+    Ok(params
+        .iter()
+        .zip(tokens)
+        .map(|(param, value)| NamedToken {
+            name: param.name.clone(),
+            value,
+        })
+        .collect())
+}
+
+struct ParsedSignature<'a> {
+    name: &'a str,
+    inputs: Vec<Param>,
+    modifiers: Vec<&'a str>,
+    outputs: Option<Vec<Param>>,
+}
+
+/// Parses `[keyword] name(params) [modifiers] [returns (params)]`.
+fn parse_signature<'a>(s: &'a str, keyword: &str) -> AbiResult<ParsedSignature<'a>> {
+    let invalid = || abi_error(format!("Invalid signature '{s}'"));
+
+    let s = s.trim();
+    let s = s
+        .strip_prefix(keyword)
+        .filter(|rest| rest.starts_with(char::is_whitespace))
+        .unwrap_or(s)
+        .trim_start();
+
+    let open = s.find('(').ok_or_else(invalid)?;
+    let name = s[..open].trim();
+    if !is_identifier(name) {
+        return Err(invalid());
+    }
+    let (inputs, rest) = parse_params(&s[open..]).ok_or_else(invalid)?;
+
+    let (modifiers, outputs) = match rest.split_once("returns") {
+        Some((modifiers, outputs)) => {
+            let (outputs, rest) = parse_params(outputs.trim_start()).ok_or_else(invalid)?;
+            if !rest.trim().is_empty() {
+                return Err(invalid());
+            }
+            (modifiers, Some(outputs))
+        },
+        None => (rest, None),
+    };
+
+    Ok(ParsedSignature {
+        name,
+        inputs: parse_param_list(inputs)?,
+        modifiers: modifiers.split_whitespace().collect(),
+        outputs: outputs.map(parse_param_list).transpose()?,
+    })
+}
+
+/// Splits a parenthesized parameter list from the rest of the string.
+fn parse_params(s: &str) -> Option<(&str, &str)> {
+    let inner = s.strip_prefix('(')?;
+    let mut depth = 1_usize;
+    for (i, ch) in inner.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((&inner[..i], &inner[i + 1..]));
+                }
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Parses a comma separated list of `type [indexed] [location] [name]` parameters.
+fn parse_param_list(s: &str) -> AbiResult<Vec<Param>> {
+    if s.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    split_top_level(s)
+        .ok_or_else(|| abi_error(format!("Invalid parameters '{s}'")))?
+        .into_iter()
+        .map(parse_param)
+        .collect()
+}
+
+fn parse_param(s: &str) -> AbiResult<Param> {
+    let invalid = || abi_error(format!("Invalid parameter '{s}'"));
+
+    // A tuple type may contain whitespaces, so it ends with the last closing parenthesis
+    // followed by optional array suffixes.
+    let s = s.trim();
+    let type_end = match s.rfind(')') {
+        Some(close) => {
+            let suffix_len = s[close + 1..]
+                .find(|ch: char| ch != '[' && ch != ']' && !ch.is_ascii_digit())
+                .unwrap_or(s.len() - close - 1);
+            close + 1 + suffix_len
+        },
+        None => s.find(char::is_whitespace).unwrap_or(s.len()),
+    };
+    let kind = parse_param_type(&s[..type_end])?;
+
+    let mut words = s[type_end..].split_whitespace().peekable();
+    let indexed = words.next_if_eq(&"indexed").is_some();
+    words.next_if(|word| DATA_LOCATIONS.contains(word));
+    let name = words.next().unwrap_or_default();
+    if words.next().is_some() || !(name.is_empty() || is_identifier(name)) {
+        return Err(invalid());
+    }
+
+    Ok(Param {
+        name: name.to_string(),
+        kind,
+        indexed,
+    })
+}
+
+/// Parses a type where tuple components may be named, e.g. `(address maker, bytes data)[]`.
+fn parse_param_type(s: &str) -> AbiResult<ParamType> {
+    let tuple = s
+        .strip_prefix("tuple")
+        .filter(|rest| rest.starts_with('('))
+        .or_else(|| s.starts_with('(').then_some(s));
+    let Some(tuple) = tuple else {
+        return ParamType::from_str(s);
+    };
+
+    let (components, array_suffix) =
+        parse_params(tuple).ok_or_else(|| abi_error(format!("Invalid type '{s}'")))?;
+    let components = parse_param_list(components)?
+        .into_iter()
+        .map(|param| param.kind)
+        .collect();
+    ParamType::from_str(&format!("{}{array_suffix}", ParamType::Tuple(components)))
+}
+
+pub(crate) fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' || first == '$' => {},
+        _ => return false,
+    }
+    chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    #[test]
+    fn test_function_from_str() {
+        let tests = [
+            ("transfer(address,uint256)", "transfer(address,uint256)", 0),
+            (
+                "function transfer(address to, uint amount) external returns (bool success)",
+                "transfer(address,uint256)",
+                1,
+            ),
+            (
+                "function fill((address maker, bytes data)[] memory orders, uint256[2] calldata limits) payable",
+                "fill((address,bytes)[],uint256[2])",
+                0,
+            ),
+            ("function name() view returns (string)", "name()", 1),
+            ("functionName(uint8)", "functionName(uint8)", 0),
+        ];
+        for (s, signature, outputs) in tests {
+            let function = Function::from_str(s).unwrap();
+            assert_eq!(function.signature(), signature, "{s}");
+            assert_eq!(function.outputs.len(), outputs, "{s}");
+        }
+
+        let function = Function::from_str("function transfer(address to, uint amount)").unwrap();
+        assert_eq!(function.inputs[0].name, "to");
+        assert_eq!(function.inputs[1].name, "amount");
+        assert_eq!(hex::encode(function.selector(), false), "a9059cbb");
+    }
+
+    #[test]
+    fn test_function_from_str_invalid() {
+        for invalid in [
+            "",
+            "transfer",
+            "transfer(address",
+            "transfer(address,)",
+            "transfer(addr)",
+            "1transfer(address)",
+            "transfer(address indexed to)",
+            "transfer(address to extra)",
+            "transfer(address) returns",
+            "transfer(address) returns (bool) view",
+            "transfer(address) constant",
+        ] {
+            assert!(Function::from_str(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_event_from_str() {
+        let event = Event::from_str(
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        )
+        .unwrap();
+        assert_eq!(event.signature(), "Transfer(address,address,uint256)");
+        assert!(event.inputs[0].indexed);
+        assert!(!event.inputs[2].indexed);
+        assert!(!event.anonymous);
+        assert_eq!(
+            hex::encode(event.topic(), false),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+
+        let event = Event::from_str("Approval(address,address,uint256) anonymous").unwrap();
+        assert!(event.anonymous);
+        assert_eq!(
+            hex::encode(event.topic(), false),
+            "8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"
+        );
+
+        Event::from_str("Transfer(address) returns (bool)").unwrap_err();
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! [Contract ABI](https://docs.soliditylang.org/en/latest/abi-spec.html) encoding and decoding.
+//!
+//! Functions and events are parsed from human-readable signatures or a JSON ABI.
+//! Values are represented as a [`token::Token`] tree.
+
+use crate::{SigningError, SigningErrorType, SigningResult};
+
+pub mod abi_encoder;
+pub mod contract;
+pub mod decode;
+pub mod encode;
+pub mod function;
+pub mod param_type;
+pub mod token;
+
+/// cbindgen:ignore
+pub const WORD_LEN: usize = 32;
+/// cbindgen:ignore
+pub const SELECTOR_LEN: usize = 4;
+
+pub type AbiResult<T> = SigningResult<T>;
+
+/// Returns an error of an invalid ABI, type or value.
+pub(crate) fn abi_error<M: Into<String>>(message: M) -> SigningError {
+    SigningError::new(SigningErrorType::Error_invalid_params, message)
+}
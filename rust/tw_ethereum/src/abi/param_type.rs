@@ -0,0 +1,327 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::abi::{abi_error, AbiResult, WORD_LEN};
+use crate::SigningError;
+use std::fmt;
+use std::str::FromStr;
+
+/// cbindgen:ignore
+const MAX_BITS: usize = 256;
+/// The maximum nesting depth of arrays and tuples accepted by the parser.
+/// cbindgen:ignore
+pub const MAX_DEPTH: usize = 128;
+
+/// An ABI type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParamType {
+    Address,
+    Bool,
+    Int(usize),
+    Uint(usize),
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+    Tuple(Vec<ParamType>),
+}
+
+impl ParamType {
+    /// Whether the value is encoded in the tail of the enclosing tuple.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::FixedArray(element, len) => *len > 0 && element.is_dynamic(),
+            ParamType::Tuple(components) => components.iter().any(ParamType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Parses an elementary type, i.e. not an array or a tuple.
+    /// Returns `None` if `s` is not the name of an elementary type,
+    /// and an error if it is, but the size is invalid, e.g. `uint7`.
+    pub(crate) fn parse_elementary(s: &str) -> Option<AbiResult<ParamType>> {
+        let invalid = || abi_error(format!("Invalid type '{s}'"));
+
+        let ty = match s {
+            "address" => ParamType::Address,
+            "bool" => ParamType::Bool,
+            "string" => ParamType::String,
+            "bytes" => ParamType::Bytes,
+            "uint" => ParamType::Uint(MAX_BITS),
+            "int" => ParamType::Int(MAX_BITS),
+            _ => {
+                if let Some(size) = parse_size(s, "bytes") {
+                    if size == 0 || size > WORD_LEN {
+                        return Some(Err(invalid()));
+                    }
+                    ParamType::FixedBytes(size)
+                } else if let Some(bits) = parse_size(s, "uint") {
+                    if !is_valid_bits(bits) {
+                        return Some(Err(invalid()));
+                    }
+                    ParamType::Uint(bits)
+                } else if let Some(bits) = parse_size(s, "int") {
+                    if !is_valid_bits(bits) {
+                        return Some(Err(invalid()));
+                    }
+                    ParamType::Int(bits)
+                } else {
+                    return None;
+                }
+            },
+        };
+        Some(Ok(ty))
+    }
+
+    /// Returns the number of bytes the value takes in the head of the enclosing tuple.
+    /// Returns an error if the length of a fixed-size array makes it overflow `usize`.
+    pub fn head_len(&self) -> AbiResult<usize> {
+        if self.is_dynamic() {
+            return Ok(WORD_LEN);
+        }
+        let too_large = || abi_error(format!("Type '{self}' is too large"));
+        match self {
+            ParamType::FixedArray(element, len) => {
+                element.head_len()?.checked_mul(*len).ok_or_else(too_large)
+            },
+            ParamType::Tuple(components) => {
+                components.iter().try_fold(0_usize, |total, component| {
+                    total
+                        .checked_add(component.head_len()?)
+                        .ok_or_else(too_large)
+                })
+            },
+            _ => Ok(WORD_LEN),
+        }
+    }
+}
+
+/// Parses a type as it is written in a human-readable signature, e.g. `uint`, `bytes32[]`,
+/// `(address,uint256)[2]` or `tuple(address,uint256)`.
+impl FromStr for ParamType {
+    type Err = SigningError;
+
+    fn from_str(s: &str) -> AbiResult<Self> {
+        parse_type(s, 0)
+    }
+}
+
+/// Parses a type nested into `depth` arrays and tuples.
+/// Returns an error if the type is nested deeper than [`MAX_DEPTH`].
+fn parse_type(s: &str, depth: usize) -> AbiResult<ParamType> {
+    let s = s.trim();
+    let invalid = || abi_error(format!("Invalid type '{s}'"));
+    let too_deep = || abi_error("Type is nested too deeply");
+
+    // Array suffixes are stripped iteratively starting from the outermost one.
+    let mut element = s;
+    let mut lengths = Vec::new();
+    while let Some(array) = element.strip_suffix(']') {
+        if depth + lengths.len() >= MAX_DEPTH {
+            return Err(too_deep());
+        }
+        let (inner, len) = array.rsplit_once('[').ok_or_else(invalid)?;
+        let len = match len.trim() {
+            "" => None,
+            len => Some(len.parse().map_err(|_| invalid())?),
+        };
+        lengths.push(len);
+        element = inner.trim();
+    }
+    let depth = depth + lengths.len();
+
+    let mut ty = if let Some(components) = element
+        .strip_prefix("tuple(")
+        .or_else(|| element.strip_prefix('('))
+    {
+        if depth >= MAX_DEPTH {
+            return Err(too_deep());
+        }
+        let components = components.strip_suffix(')').ok_or_else(invalid)?;
+        if components.trim().is_empty() {
+            ParamType::Tuple(Vec::new())
+        } else {
+            let components = split_top_level(components)
+                .ok_or_else(invalid)?
+                .into_iter()
+                .map(|component| parse_type(component, depth + 1))
+                .collect::<AbiResult<_>>()?;
+            ParamType::Tuple(components)
+        }
+    } else {
+        ParamType::parse_elementary(element).unwrap_or_else(|| Err(invalid()))?
+    };
+
+    for len in lengths.into_iter().rev() {
+        ty = match len {
+            None => ParamType::Array(Box::new(ty)),
+            Some(len) => ParamType::FixedArray(Box::new(ty), len),
+        };
+    }
+    Ok(ty)
+}
+
+/// Formats the canonical type used to compute selectors, e.g. `(address,uint256)[]`.
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamType::Address => write!(f, "address"),
+            ParamType::Bool => write!(f, "bool"),
+            ParamType::Int(bits) => write!(f, "int{bits}"),
+            ParamType::Uint(bits) => write!(f, "uint{bits}"),
+            ParamType::FixedBytes(size) => write!(f, "bytes{size}"),
+            ParamType::Bytes => write!(f, "bytes"),
+            ParamType::String => write!(f, "string"),
+            ParamType::Array(element) => write!(f, "{element}[]"),
+            ParamType::FixedArray(element, len) => write!(f, "{element}[{len}]"),
+            ParamType::Tuple(components) => {
+                write!(f, "(")?;
+                for (i, component) in components.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{component}")?;
+                }
+                write!(f, ")")
+            },
+        }
+    }
+}
+
+/// Splits a comma separated list ignoring the commas within parentheses.
+/// Returns `None` if the parentheses are unbalanced.
+pub(crate) fn split_top_level(s: &str) -> Option<Vec<&str>> {
+    let mut items = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                items.push(&s[start..i]);
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    items.push(&s[start..]);
+    Some(items)
+}
+
+/// Parses the size suffix of `bytesN`, `uintN` or `intN` types.
+fn parse_size(s: &str, prefix: &str) -> Option<usize> {
+    let digits = s.strip_prefix(prefix)?;
+    if digits.is_empty() || !digits.bytes().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+fn is_valid_bits(bits: usize) -> bool {
+    bits > 0 && bits <= MAX_BITS && bits.is_multiple_of(8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_param_type_canonical() {
+        let tests = [
+            ("address", "address"),
+            ("uint", "uint256"),
+            ("int", "int256"),
+            ("uint8", "uint8"),
+            ("bytes", "bytes"),
+            ("bytes32", "bytes32"),
+            ("string[]", "string[]"),
+            ("uint[2][]", "uint256[2][]"),
+            ("tuple(address, uint)", "(address,uint256)"),
+            ("(address,(bytes,bool)[])[3]", "(address,(bytes,bool)[])[3]"),
+            ("()", "()"),
+        ];
+        for (s, expected) in tests {
+            assert_eq!(ParamType::from_str(s).unwrap().to_string(), expected, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_param_type_invalid() {
+        for invalid in [
+            "",
+            "uint7",
+            "uint264",
+            "int0",
+            "bytes0",
+            "bytes33",
+            "fixed128x18",
+            "uint[",
+            "uint]",
+            "uint[x]",
+            "(uint",
+            "(uint))",
+            "Person",
+        ] {
+            assert!(ParamType::from_str(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_param_type_max_depth() {
+        let array = format!("uint256{}", "[]".repeat(MAX_DEPTH));
+        ParamType::from_str(&array).unwrap();
+        let tuple = format!("{}uint256{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+        ParamType::from_str(&tuple).unwrap();
+
+        for too_deep in [
+            format!("uint256{}", "[]".repeat(MAX_DEPTH + 1)),
+            format!("uint256{}", "[]".repeat(200_000)),
+            format!("({})[2]{}", "uint256", "[]".repeat(MAX_DEPTH - 1)),
+            format!(
+                "{}uint256{}",
+                "(".repeat(MAX_DEPTH + 1),
+                ")".repeat(MAX_DEPTH + 1)
+            ),
+        ] {
+            assert!(ParamType::from_str(&too_deep).is_err());
+        }
+    }
+
+    #[test]
+    fn test_param_type_dynamic() {
+        let tests = [
+            ("uint256", false, 32),
+            ("bytes", true, 32),
+            ("uint256[]", true, 32),
+            ("uint256[3]", false, 96),
+            ("string[3]", true, 32),
+            ("(address,uint256[2])", false, 96),
+            ("(address,bytes)", true, 32),
+        ];
+        for (s, dynamic, head_len) in tests {
+            let ty = ParamType::from_str(s).unwrap();
+            assert_eq!(ty.is_dynamic(), dynamic, "{s}");
+            assert_eq!(ty.head_len().unwrap(), head_len, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_param_type_head_len_overflow() {
+        for s in [
+            "uint256[4294967296][4294967296]",
+            "(uint256[576460752303423488],uint256[576460752303423488])",
+        ] {
+            let ty = ParamType::from_str(s).unwrap();
+            assert!(ty.head_len().is_err(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::abi::param_type::ParamType;
+use crate::abi::{abi_error, AbiResult, WORD_LEN};
+use crate::address::Address;
+use tw_hash::{H256, U256};
+
+/// A value of an ABI type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    Address(Address),
+    Bool(bool),
+    /// A signed number stored as a sign-extended 256 bit two's complement word.
+    Int {
+        bits: usize,
+        value: H256,
+    },
+    Uint {
+        bits: usize,
+        value: U256,
+    },
+    /// A `bytesN` value where `N` is the length of the data.
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    Array {
+        element_type: ParamType,
+        elements: Vec<Token>,
+    },
+    FixedArray {
+        element_type: ParamType,
+        elements: Vec<Token>,
+    },
+    Tuple(Vec<Token>),
+}
+
+/// A token with the parameter name declared by the ABI.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NamedToken {
+    pub name: String,
+    pub value: Token,
+}
+
+impl Token {
+    /// Returns an `uint256` token.
+    pub fn u256(value: U256) -> Token {
+        Token::Uint { bits: 256, value }
+    }
+
+    pub fn param_type(&self) -> ParamType {
+        match self {
+            Token::Address(_) => ParamType::Address,
+            Token::Bool(_) => ParamType::Bool,
+            Token::Int { bits, .. } => ParamType::Int(*bits),
+            Token::Uint { bits, .. } => ParamType::Uint(*bits),
+            Token::FixedBytes(bytes) => ParamType::FixedBytes(bytes.len()),
+            Token::Bytes(_) => ParamType::Bytes,
+            Token::String(_) => ParamType::String,
+            Token::Array { element_type, .. } => ParamType::Array(Box::new(element_type.clone())),
+            Token::FixedArray {
+                element_type,
+                elements,
+            } => ParamType::FixedArray(Box::new(element_type.clone()), elements.len()),
+            Token::Tuple(components) => {
+                ParamType::Tuple(components.iter().map(Token::param_type).collect())
+            },
+        }
+    }
+
+    /// Checks if the token is a valid value of the `expected` type.
+    pub fn type_check(&self, expected: &ParamType) -> AbiResult<()> {
+        let mismatch = || {
+            abi_error(format!(
+                "Expected a value of '{expected}' type, found '{}'",
+                self.param_type()
+            ))
+        };
+
+        match (self, expected) {
+            (Token::Address(_), ParamType::Address)
+            | (Token::Bool(_), ParamType::Bool)
+            | (Token::Bytes(_), ParamType::Bytes)
+            | (Token::String(_), ParamType::String) => Ok(()),
+            (Token::Int { bits, value }, ParamType::Int(expected_bits))
+                if bits == expected_bits =>
+            {
+                if int_fits(value, *bits) {
+                    Ok(())
+                } else {
+                    Err(abi_error(format!("Number doesn't fit int{bits}")))
+                }
+            },
+            (Token::Uint { bits, value }, ParamType::Uint(expected_bits))
+                if bits == expected_bits =>
+            {
+                if value.bits() <= *bits {
+                    Ok(())
+                } else {
+                    Err(abi_error(format!("Number doesn't fit uint{bits}")))
+                }
+            },
+            (Token::FixedBytes(bytes), ParamType::FixedBytes(size)) if bytes.len() == *size => {
+                Ok(())
+            },
+            (
+                Token::Array {
+                    element_type,
+                    elements,
+                },
+                ParamType::Array(expected_element),
+            ) if element_type == expected_element.as_ref() => elements
+                .iter()
+                .try_for_each(|element| element.type_check(element_type)),
+            (
+                Token::FixedArray {
+                    element_type,
+                    elements,
+                },
+                ParamType::FixedArray(expected_element, len),
+            ) if element_type == expected_element.as_ref() && elements.len() == *len => elements
+                .iter()
+                .try_for_each(|element| element.type_check(element_type)),
+            (Token::Tuple(components), ParamType::Tuple(expected_components))
+                if components.len() == expected_components.len() =>
+            {
+                components
+                    .iter()
+                    .zip(expected_components)
+                    .try_for_each(|(component, expected)| component.type_check(expected))
+            },
+            _ => Err(mismatch()),
+        }
+    }
+}
+
+/// Checks if the sign-extended `value` fits `bits`.
+pub(crate) fn int_fits(value: &H256, bits: usize) -> bool {
+    let value_len = bits / 8;
+    let sign_extension = if value[WORD_LEN - value_len] & 0x80 == 0 {
+        0x00
+    } else {
+        0xff
+    };
+    value[..WORD_LEN - value_len]
+        .iter()
+        .all(|byte| *byte == sign_extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_int_fits() {
+        let mut minus_one = H256::default();
+        minus_one.fill(0xff);
+        assert!(int_fits(&minus_one, 8));
+
+        let mut min_int8 = minus_one;
+        min_int8[WORD_LEN - 1] = 0x80;
+        assert!(int_fits(&min_int8, 8));
+
+        // -129
+        let mut value = minus_one;
+        value[WORD_LEN - 1] = 0x7f;
+        assert!(!int_fits(&value, 8));
+        assert!(int_fits(&value, 16));
+
+        // 128
+        let value = U256::from(128_u64).to_big_endian();
+        assert!(!int_fits(&value, 8));
+        assert!(int_fits(&value, 16));
+    }
+
+    #[test]
+    fn test_type_check() {
+        let ty = ParamType::from_str("(uint8,bytes2[])").unwrap();
+        let token = Token::Tuple(vec![
+            Token::Uint {
+                bits: 8,
+                value: U256::from(255_u64),
+            },
+            Token::Array {
+                element_type: ParamType::FixedBytes(2),
+                elements: vec![Token::FixedBytes(vec![1, 2])],
+            },
+        ]);
+        token.type_check(&ty).unwrap();
+        assert_eq!(token.param_type(), ty);
+
+        let overflow = Token::Uint {
+            bits: 8,
+            value: U256::from(256_u64),
+        };
+        overflow.type_check(&ParamType::Uint(8)).unwrap_err();
+        Token::u256(U256::zero())
+            .type_check(&ParamType::Uint(8))
+            .unwrap_err();
+
+        let wrong_element = Token::Array {
+            element_type: ParamType::FixedBytes(2),
+            elements: vec![Token::FixedBytes(vec![1])],
+        };
+        wrong_element
+            .type_check(&ParamType::from_str("bytes2[]").unwrap())
+            .unwrap_err();
+    }
+}
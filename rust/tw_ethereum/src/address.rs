@@ -0,0 +1,169 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::{SigningError, SigningErrorType, SigningResult};
+use std::fmt;
+use std::str::FromStr;
+use tw_encoding::hex;
+use tw_encoding::rlp::{RlpEncode, RlpStream};
+use tw_hash::sha3::keccak256;
+use tw_hash::H160;
+use tw_keypair::secp256k1::PublicKey;
+
+/// cbindgen:ignore
+const ADDRESS_PREFIX: &str = "0x";
+/// Ronin addresses are Ethereum addresses with a different prefix.
+/// cbindgen:ignore
+const RONIN_PREFIX: &str = "ronin:";
+
+/// Represents an Ethereum address.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Address(H160);
+
+impl Address {
+    pub const LEN: usize = 20;
+
+    pub fn new(bytes: H160) -> Address {
+        Address(bytes)
+    }
+
+    /// Derives an address from the last 20 bytes of the public key `keccak256` hash.
+    pub fn with_public_key(public: &PublicKey) -> Address {
+        // Skip the `0x04` tag of the uncompressed public key.
+        let hash = keccak256(&public.uncompressed()[1..]);
+        let bytes = H160::try_from(&hash[hash.len() - Address::LEN..])
+            .expect("keccak256 hash is longer than an address");
+        Address(bytes)
+    }
+
+    pub fn bytes(&self) -> H160 {
+        self.0
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    /// Returns the [EIP-55](https://eips.ethereum.org/EIPS/eip-55) mixed-case checksum representation.
+    pub fn to_checksum_string(&self) -> String {
+        let lowercase = hex::encode(self.0, false);
+        let hash = keccak256(lowercase.as_bytes());
+
+        let checksummed: String = lowercase
+            .chars()
+            .enumerate()
+            .map(|(i, ch)| {
+                let nibble = if i % 2 == 0 {
+                    hash[i / 2] >> 4
+                } else {
+                    hash[i / 2] & 0x0f
+                };
+                if nibble >= 8 {
+                    ch.to_ascii_uppercase()
+                } else {
+                    ch
+                }
+            })
+            .collect();
+        format!("{ADDRESS_PREFIX}{checksummed}")
+    }
+}
+
+/// Parses a `0x` or `ronin:` prefixed hex address.
+/// Please note the checksum is not verified, so lowercase addresses are accepted too.
+impl FromStr for Address {
+    type Err = SigningError;
+
+    fn from_str(s: &str) -> SigningResult<Self> {
+        let invalid =
+            || SigningError::new(SigningErrorType::Error_invalid_address, "Invalid address");
+
+        let hex_str = s
+            .strip_prefix(ADDRESS_PREFIX)
+            .or_else(|| s.strip_prefix(RONIN_PREFIX))
+            .ok_or_else(invalid)?;
+        if hex_str.len() != Address::LEN * 2 {
+            return Err(invalid());
+        }
+        let bytes = hex::decode(hex_str).map_err(|_| invalid())?;
+        H160::try_from(bytes.as_slice())
+            .map(Address)
+            .map_err(|_| invalid())
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_checksum_string())
+    }
+}
+
+impl RlpEncode for Address {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(self.as_slice());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_keypair::secp256k1::PrivateKey;
+
+    #[test]
+    fn test_address_checksum() {
+        for checksummed in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let lowercase = checksummed.to_lowercase();
+            let address: Address = lowercase.parse().unwrap();
+            assert_eq!(address.to_string(), checksummed);
+        }
+    }
+
+    #[test]
+    fn test_address_from_str() {
+        let address: Address = "ronin:5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            address.to_string(),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+
+        for invalid in [
+            "",
+            "0x",
+            "0xdeadbeef",
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAedd",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg",
+        ] {
+            let err = Address::from_str(invalid).unwrap_err();
+            assert_eq!(
+                err.error,
+                SigningErrorType::Error_invalid_address,
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_address_with_public_key() {
+        let private = PrivateKey::try_from(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let address = Address::with_public_key(&private.public());
+        assert_eq!(
+            address.to_string(),
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+    }
+}
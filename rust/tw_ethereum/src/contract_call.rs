@@ -0,0 +1,124 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! Call data of the token standard functions that can be requested by `SigningInput` directly.
+
+use crate::abi::encode::encode_tokens;
+use crate::abi::token::Token;
+use crate::abi::SELECTOR_LEN;
+use crate::address::Address;
+use tw_hash::sha3::keccak256;
+use tw_hash::U256;
+
+/// ERC-20 `transfer(address,uint256)`.
+pub fn erc20_transfer(to: Address, amount: U256) -> Vec<u8> {
+    function_call(
+        "transfer(address,uint256)",
+        &[Token::Address(to), Token::u256(amount)],
+    )
+}
+
+/// ERC-20 `approve(address,uint256)`.
+pub fn erc20_approve(spender: Address, amount: U256) -> Vec<u8> {
+    function_call(
+        "approve(address,uint256)",
+        &[Token::Address(spender), Token::u256(amount)],
+    )
+}
+
+/// ERC-721 `transferFrom(address,address,uint256)`.
+pub fn erc721_transfer_from(from: Address, to: Address, token_id: U256) -> Vec<u8> {
+    function_call(
+        "transferFrom(address,address,uint256)",
+        &[
+            Token::Address(from),
+            Token::Address(to),
+            Token::u256(token_id),
+        ],
+    )
+}
+
+/// ERC-1155 `safeTransferFrom(address,address,uint256,uint256,bytes)`.
+pub fn erc1155_safe_transfer_from(
+    from: Address,
+    to: Address,
+    token_id: U256,
+    value: U256,
+    data: &[u8],
+) -> Vec<u8> {
+    function_call(
+        "safeTransferFrom(address,address,uint256,uint256,bytes)",
+        &[
+            Token::Address(from),
+            Token::Address(to),
+            Token::u256(token_id),
+            Token::u256(value),
+            Token::Bytes(data.to_vec()),
+        ],
+    )
+}
+
+/// Encodes a function call. The `tokens` must match the canonical `signature`.
+fn function_call(signature: &str, tokens: &[Token]) -> Vec<u8> {
+    let mut call = keccak256(signature.as_bytes());
+    call.truncate(SELECTOR_LEN);
+    call.extend_from_slice(&encode_tokens(tokens));
+    call
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::WORD_LEN;
+    use tw_encoding::hex;
+
+    fn address(s: &str) -> Address {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_erc20_calls() {
+        let to = address("0x5322b34c88ed0691971bf52a7047448f0f4efc84");
+        let amount = U256::from(2_000_000_000_000_000_000_u64);
+
+        assert_eq!(
+            hex::encode(erc20_transfer(to, amount), false),
+            "a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000"
+        );
+        assert_eq!(
+            hex::encode(erc20_approve(to, amount), false),
+            "095ea7b30000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000"
+        );
+    }
+
+    #[test]
+    fn test_erc721_transfer_from() {
+        let from = address("0x718046867b5b1782379a14eA4fc0c9b724DA94Fc");
+        let to = address("0x5322b34c88ed0691971bf52a7047448f0f4efc84");
+
+        assert_eq!(
+            hex::encode(erc721_transfer_from(from, to, U256::from(0x23c47ee5_u64)), false),
+            "23b872dd000000000000000000000000718046867b5b1782379a14ea4fc0c9b724da94fc0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000000000000023c47ee5"
+        );
+    }
+
+    #[test]
+    fn test_erc1155_safe_transfer_from() {
+        let from = address("0x718046867b5b1782379a14eA4fc0c9b724DA94Fc");
+        let to = address("0x5322b34c88ed0691971bf52a7047448f0f4efc84");
+        let token_id = U256::from(0x23c47ee5_u64);
+        let value = U256::from(2_000_000_000_000_000_000_u64);
+
+        let call = erc1155_safe_transfer_from(from, to, token_id, value, &[1, 2, 3, 4]);
+        assert_eq!(
+            hex::encode(call, false),
+            "f242432a000000000000000000000000718046867b5b1782379a14ea4fc0c9b724da94fc0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000000000000023c47ee50000000000000000000000000000000000000000000000001bc16d674ec8000000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000040102030400000000000000000000000000000000000000000000000000000000"
+        );
+
+        let call = erc1155_safe_transfer_from(from, to, token_id, value, &[]);
+        assert_eq!(call.len(), SELECTOR_LEN + 6 * WORD_LEN);
+    }
+}
@@ -0,0 +1,408 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::address::Address;
+use crate::eip712::property_type::PropertyType;
+use crate::{SigningError, SigningErrorType, SigningResult};
+use serde::Deserialize;
+use serde_json::{Map as JsonMap, Value as Json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use tw_encoding::hex;
+use tw_hash::sha3::keccak256;
+use tw_hash::{H256, U256};
+
+/// A member of a struct declared in the `types` section.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Property {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub property_type: String,
+}
+
+/// Struct types declared in the `types` section, indexed by their names.
+pub type Types = BTreeMap<String, Vec<Property>>;
+
+/// Encodes struct values according to the [EIP-712](https://eips.ethereum.org/EIPS/eip-712)
+/// `eth_signTypedData_v4` rules.
+///
+/// For compatibility with the existing wallets, a missing (or `null`) struct member
+/// is encoded as its type default value, and a missing struct as 32 zero bytes.
+pub struct Encoder<'a> {
+    types: &'a Types,
+}
+
+impl<'a> Encoder<'a> {
+    pub fn new(types: &'a Types) -> Encoder<'a> {
+        Encoder { types }
+    }
+
+    /// Returns `encodeType` of the struct, i.e. its signature followed by the signatures
+    /// of all the referenced structs sorted by name, e.g.
+    /// `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+    pub fn encode_type(&self, type_name: &str) -> SigningResult<String> {
+        let mut dependencies = BTreeSet::new();
+        self.collect_dependencies(type_name, &mut dependencies)?;
+        dependencies.remove(type_name);
+
+        let mut encoded = String::new();
+        for name in std::iter::once(type_name).chain(dependencies.iter().map(String::as_str)) {
+            let members: Vec<_> = self
+                .properties(name)?
+                .iter()
+                .map(|property| format!("{} {}", property.property_type, property.name))
+                .collect();
+            encoded.push_str(&format!("{name}({})", members.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    /// Returns `typeHash = keccak256(encodeType(type))`.
+    pub fn type_hash(&self, type_name: &str) -> SigningResult<H256> {
+        let encoded = self.encode_type(type_name)?;
+        Ok(keccak256_h256(encoded.as_bytes()))
+    }
+
+    /// Returns `hashStruct(s) = keccak256(typeHash ‖ encodeData(s))`.
+    pub fn hash_struct(
+        &self,
+        type_name: &str,
+        value: &JsonMap<String, Json>,
+    ) -> SigningResult<H256> {
+        let mut encoded = self.type_hash(type_name)?.into_vec();
+        for property in self.properties(type_name)? {
+            let property_type = PropertyType::from_str(&property.property_type)?;
+            let word = self
+                .encode_value(&property_type, value.get(&property.name))
+                .map_err(|e| {
+                    SigningError::new(e.error, format!("{}: {}", property.name, e.message))
+                })?;
+            encoded.extend_from_slice(word.as_slice());
+        }
+        Ok(keccak256_h256(&encoded))
+    }
+
+    /// Encodes a struct member value as a 32 byte word.
+    fn encode_value(
+        &self,
+        property_type: &PropertyType,
+        value: Option<&Json>,
+    ) -> SigningResult<H256> {
+        let value = value.filter(|value| !value.is_null());
+
+        match property_type {
+            PropertyType::Custom(type_name) => match value {
+                Some(Json::Object(object)) => self.hash_struct(type_name, object),
+                Some(_) => Err(invalid_value("Expected an object")),
+                None => Ok(H256::default()),
+            },
+            PropertyType::Array(element_type, len) => {
+                let elements = match value {
+                    Some(Json::Array(elements)) => elements.as_slice(),
+                    Some(_) => return Err(invalid_value("Expected an array")),
+                    None => &[],
+                };
+                if let Some(len) = len {
+                    if value.is_some() && elements.len() != *len {
+                        return Err(invalid_value(format!("Expected {len} array elements")));
+                    }
+                }
+
+                let mut encoded = Vec::with_capacity(elements.len() * H256::len());
+                for element in elements {
+                    let word = self.encode_value(element_type, Some(element))?;
+                    encoded.extend_from_slice(word.as_slice());
+                }
+                Ok(keccak256_h256(&encoded))
+            },
+            PropertyType::String => match value {
+                Some(Json::String(s)) => Ok(keccak256_h256(s.as_bytes())),
+                Some(_) => Err(invalid_value("Expected a string")),
+                None => Ok(keccak256_h256(&[])),
+            },
+            PropertyType::Bytes => {
+                let bytes = value.map(parse_hex_bytes).transpose()?.unwrap_or_default();
+                Ok(keccak256_h256(&bytes))
+            },
+            PropertyType::FixedBytes(size) => {
+                let bytes = value.map(parse_hex_bytes).transpose()?.unwrap_or_default();
+                if bytes.len() > *size {
+                    return Err(invalid_value(format!("Expected at most {size} bytes")));
+                }
+                // Fixed-size byte arrays are right-padded.
+                let mut word = H256::default();
+                word[..bytes.len()].copy_from_slice(&bytes);
+                Ok(word)
+            },
+            PropertyType::Address => {
+                let Some(value) = value else {
+                    return Ok(H256::default());
+                };
+                let bytes = parse_hex_bytes(value)?;
+                if bytes.len() != Address::LEN {
+                    return Err(invalid_value("Invalid address"));
+                }
+                let mut word = H256::default();
+                word[H256::len() - Address::LEN..].copy_from_slice(&bytes);
+                Ok(word)
+            },
+            PropertyType::Bool => {
+                let flag = match value {
+                    Some(Json::Bool(flag)) => *flag,
+                    Some(Json::String(s)) if s == "true" || s == "1" => true,
+                    Some(Json::String(s)) if s == "false" || s == "0" => false,
+                    Some(_) => return Err(invalid_value("Expected a boolean")),
+                    None => false,
+                };
+                Ok(U256::from(flag as u8).to_big_endian())
+            },
+            PropertyType::Uint(bits) => {
+                let Some(value) = value else {
+                    return Ok(H256::default());
+                };
+                let (negative, number) = parse_number(value)?;
+                if negative || number.bits() > *bits {
+                    return Err(invalid_value(format!("Number doesn't fit uint{bits}")));
+                }
+                Ok(number.to_big_endian())
+            },
+            PropertyType::Int(bits) => {
+                let Some(value) = value else {
+                    return Ok(H256::default());
+                };
+                let (negative, magnitude) = parse_number(value)?;
+                let out_of_range = || invalid_value(format!("Number doesn't fit int{bits}"));
+                if !negative || magnitude.is_zero() {
+                    if magnitude.bits() >= *bits {
+                        return Err(out_of_range());
+                    }
+                    return Ok(magnitude.to_big_endian());
+                }
+
+                // The minimum value is `-2^(bits-1)`, so `magnitude - 1` must fit `bits - 1` bits.
+                let decremented = magnitude
+                    .checked_sub(U256::from(1_u8))
+                    .ok_or_else(out_of_range)?;
+                if decremented.bits() >= *bits {
+                    return Err(out_of_range());
+                }
+                // Two's complement: `2^256 - magnitude = U256::MAX - (magnitude - 1)`.
+                let complement = U256::MAX
+                    .checked_sub(decremented)
+                    .ok_or_else(out_of_range)?;
+                Ok(complement.to_big_endian())
+            },
+        }
+    }
+
+    /// Collects the struct itself and all the structs it references recursively.
+    fn collect_dependencies(
+        &self,
+        type_name: &str,
+        found: &mut BTreeSet<String>,
+    ) -> SigningResult<()> {
+        if !found.insert(type_name.to_string()) {
+            return Ok(());
+        }
+        for property in self.properties(type_name)? {
+            let property_type = PropertyType::from_str(&property.property_type)?;
+            if let PropertyType::Custom(name) = property_type.base_type() {
+                self.collect_dependencies(name, found)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn properties(&self, type_name: &str) -> SigningResult<&'a [Property]> {
+        self.types.get(type_name).map(Vec::as_slice).ok_or_else(|| {
+            SigningError::new(
+                SigningErrorType::Error_invalid_params,
+                format!("Unknown type '{type_name}'"),
+            )
+        })
+    }
+}
+
+fn invalid_value<M: Into<String>>(message: M) -> SigningError {
+    SigningError::new(SigningErrorType::Error_invalid_params, message)
+}
+
+fn keccak256_h256(data: &[u8]) -> H256 {
+    H256::try_from(keccak256(data).as_slice()).expect("keccak256 hash is 32 bytes long")
+}
+
+/// Parses a hex string with an optional `0x` prefix.
+/// An odd number of digits is left-padded with zero, e.g. `0x0` is parsed as `[0x00]`.
+fn parse_hex_bytes(value: &Json) -> SigningResult<Vec<u8>> {
+    let Json::String(s) = value else {
+        return Err(invalid_value("Expected a hex string"));
+    };
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    let decoded = if digits.len().is_multiple_of(2) {
+        hex::decode(digits)
+    } else {
+        hex::decode(&format!("0{digits}"))
+    };
+    decoded.map_err(|_| invalid_value("Invalid hex string"))
+}
+
+/// Parses a JSON number, a decimal or a `0x` prefixed hex string.
+/// Returns whether the number is negative and its magnitude.
+fn parse_number(value: &Json) -> SigningResult<(bool, U256)> {
+    let invalid = || invalid_value("Invalid number");
+
+    match value {
+        Json::Number(number) => {
+            if let Some(unsigned) = number.as_u64() {
+                Ok((false, U256::from(unsigned)))
+            } else if let Some(signed) = number.as_i64() {
+                Ok((signed < 0, U256::from(signed.unsigned_abs())))
+            } else {
+                // Floating point numbers are not allowed.
+                Err(invalid())
+            }
+        },
+        Json::String(s) => {
+            let (negative, digits) = match s.strip_prefix('-') {
+                Some(digits) => (true, digits),
+                None => (false, s.as_str()),
+            };
+            let magnitude = if digits.starts_with("0x") {
+                U256::from_hex_str(digits)
+            } else {
+                U256::from_dec_str(digits)
+            };
+            magnitude
+                .map(|magnitude| (negative, magnitude))
+                .map_err(|_| invalid())
+        },
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn encode(property_type: &str, value: Json) -> SigningResult<String> {
+        let types = Types::default();
+        let property_type = PropertyType::from_str(property_type).unwrap();
+        Encoder::new(&types)
+            .encode_value(&property_type, Some(&value))
+            .map(|word| hex::encode(word, false))
+    }
+
+    #[test]
+    fn test_encode_atomic_values() {
+        let tests = [
+            (
+                "uint8",
+                json!(13),
+                "000000000000000000000000000000000000000000000000000000000000000d",
+            ),
+            (
+                "uint256",
+                json!("1234567"),
+                "000000000000000000000000000000000000000000000000000000000012d687",
+            ),
+            (
+                "uint256",
+                json!("0x12d687"),
+                "000000000000000000000000000000000000000000000000000000000012d687",
+            ),
+            (
+                "int64",
+                json!(1234567890123456789_i64),
+                "000000000000000000000000000000000000000000000000112210f47de98115",
+            ),
+            (
+                "int8",
+                json!(-1),
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+            (
+                "int8",
+                json!("-128"),
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff80",
+            ),
+            (
+                "int256",
+                json!("-0x1"),
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+            (
+                "bool",
+                json!(true),
+                "0000000000000000000000000000000000000000000000000000000000000001",
+            ),
+            (
+                "bool",
+                json!("0"),
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            ),
+            (
+                "address",
+                json!("CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+                "000000000000000000000000cd2a3d9f938e13cd947ec05abc7fe734df8dd826",
+            ),
+            (
+                "bytes4",
+                json!("0x12345678"),
+                "1234567800000000000000000000000000000000000000000000000000000000",
+            ),
+            (
+                "bytes32",
+                json!("0x0"),
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            ),
+            (
+                "string",
+                json!("Hello"),
+                "06b3dfaec148fb1bb2b066f10ec285e7c9bf402ab32aa78a5d38e34566810cd2",
+            ),
+            (
+                "bytes",
+                json!("0x"),
+                "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            ),
+        ];
+        for (property_type, value, expected) in tests {
+            assert_eq!(
+                encode(property_type, value.clone()).unwrap(),
+                expected,
+                "{property_type}: {value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_invalid_values() {
+        let tests = [
+            ("uint8", json!(256)),
+            ("uint8", json!(-1)),
+            ("uint256", json!(1.5)),
+            ("uint256", json!("abc")),
+            ("int8", json!(128)),
+            ("int8", json!("-129")),
+            ("bool", json!("yes")),
+            ("address", json!("0xdeadbeef")),
+            ("bytes2", json!("0x123456")),
+            ("bytes", json!("0xzz")),
+            ("string", json!(1)),
+            ("uint8[2]", json!([1])),
+            ("uint8[]", json!(1)),
+        ];
+        for (property_type, value) in tests {
+            let err = encode(property_type, value.clone()).unwrap_err();
+            assert_eq!(
+                err.error,
+                SigningErrorType::Error_invalid_params,
+                "{property_type}: {value}"
+            );
+        }
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! [EIP-712](https://eips.ethereum.org/EIPS/eip-712) typed structured data hashing and signing
+//! compatible with `eth_signTypedData_v4`.
+
+use crate::eip712::encoder::{Encoder, Types};
+use crate::{SigningError, SigningErrorType, SigningResult};
+use serde::Deserialize;
+use serde_json::{Map as JsonMap, Value as Json};
+use tw_hash::sha3::keccak256;
+use tw_hash::{H256, H520};
+use tw_keypair::secp256k1::PrivateKey;
+use tw_keypair::traits::SigningKeyTrait;
+
+pub mod encoder;
+pub mod property_type;
+
+/// cbindgen:ignore
+const DOMAIN_TYPE: &str = "EIP712Domain";
+/// cbindgen:ignore
+const DIGEST_PREFIX: [u8; 2] = [0x19, 0x01];
+/// `v` of a typed data signature is `27 + recovery_id`.
+/// cbindgen:ignore
+const V_OFFSET: u8 = 27;
+
+/// A typed data document as it is passed to `eth_signTypedData_v4`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: Types,
+    pub primary_type: String,
+    pub domain: JsonMap<String, Json>,
+    pub message: JsonMap<String, Json>,
+}
+
+impl TypedData {
+    pub fn from_json(json: &str) -> SigningResult<TypedData> {
+        serde_json::from_str(json).map_err(|e| {
+            SigningError::new(
+                SigningErrorType::Error_input_parse,
+                format!("Invalid typed data: {e}"),
+            )
+        })
+    }
+
+    /// Returns `hashStruct(EIP712Domain, domain)`.
+    pub fn domain_separator(&self) -> SigningResult<H256> {
+        Encoder::new(&self.types).hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    /// Returns `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+    /// The message hash is omitted if the primary type is `EIP712Domain` itself.
+    pub fn hash(&self) -> SigningResult<H256> {
+        let mut encoded = DIGEST_PREFIX.to_vec();
+        encoded.extend_from_slice(self.domain_separator()?.as_slice());
+        if self.primary_type != DOMAIN_TYPE {
+            let message_hash =
+                Encoder::new(&self.types).hash_struct(&self.primary_type, &self.message)?;
+            encoded.extend_from_slice(message_hash.as_slice());
+        }
+
+        let digest = keccak256(&encoded);
+        Ok(H256::try_from(digest.as_slice()).expect("keccak256 hash is 32 bytes long"))
+    }
+
+    /// Signs the typed data hash.
+    /// Returns the 65 byte `r ‖ s ‖ v` signature where `v` is 27 or 28.
+    pub fn sign(&self, private: &PrivateKey) -> SigningResult<H520> {
+        let hash = self.hash()?;
+        let signature = private
+            .sign(hash)
+            .map_err(|_| SigningError::new(SigningErrorType::Error_signing, "Error signing"))?;
+
+        let mut bytes = signature.to_bytes();
+        bytes[H520::len() - 1] += V_OFFSET;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    const MAIL_JSON: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "Person": [
+                {"name": "name", "type": "string"},
+                {"name": "wallet", "type": "address"}
+            ],
+            "Mail": [
+                {"name": "from", "type": "Person"},
+                {"name": "to", "type": "Person"},
+                {"name": "contents", "type": "string"}
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+            "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    #[test]
+    fn test_encode_type() {
+        let typed_data = TypedData::from_json(MAIL_JSON).unwrap();
+        let encoder = Encoder::new(&typed_data.types);
+
+        assert_eq!(
+            encoder.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            hex::encode(encoder.type_hash("Mail").unwrap(), false),
+            "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+        );
+    }
+
+    #[test]
+    fn test_hash_struct() {
+        let typed_data = TypedData::from_json(MAIL_JSON).unwrap();
+        let encoder = Encoder::new(&typed_data.types);
+
+        assert_eq!(
+            hex::encode(typed_data.domain_separator().unwrap(), false),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex::encode(
+                encoder.hash_struct("Mail", &typed_data.message).unwrap(),
+                false
+            ),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            hex::encode(typed_data.hash().unwrap(), false),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn test_sign() {
+        let typed_data = TypedData::from_json(MAIL_JSON).unwrap();
+        let private = PrivateKey::try_from(keccak256(b"cow").as_slice()).unwrap();
+
+        assert_eq!(
+            hex::encode(typed_data.sign(&private).unwrap(), false),
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c"
+        );
+    }
+}
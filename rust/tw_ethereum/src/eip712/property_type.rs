@@ -0,0 +1,169 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::abi::param_type::ParamType;
+use crate::{SigningError, SigningErrorType, SigningResult};
+use std::str::FromStr;
+
+/// The maximum number of array dimensions of a type, e.g. `uint256[][2]` has two.
+/// cbindgen:ignore
+pub const MAX_ARRAY_DIMENSIONS: usize = 128;
+
+/// A type of a struct member as it is declared in the `types` section of a typed data document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyType {
+    Address,
+    Bool,
+    String,
+    Bytes,
+    FixedBytes(usize),
+    Uint(usize),
+    Int(usize),
+    /// `T[]` if the length is `None`, `T[n]` otherwise.
+    Array(Box<PropertyType>, Option<usize>),
+    /// A reference to a struct declared in the `types` section.
+    Custom(String),
+}
+
+impl PropertyType {
+    /// Returns the element type of a (possibly nested) array, or the type itself otherwise.
+    pub fn base_type(&self) -> &PropertyType {
+        match self {
+            PropertyType::Array(element, _) => element.base_type(),
+            other => other,
+        }
+    }
+}
+
+impl FromStr for PropertyType {
+    type Err = SigningError;
+
+    fn from_str(s: &str) -> SigningResult<Self> {
+        let invalid = || {
+            SigningError::new(
+                SigningErrorType::Error_invalid_params,
+                format!("Invalid type '{s}'"),
+            )
+        };
+
+        // Array suffixes are stripped iteratively starting from the outermost one.
+        let mut element = s;
+        let mut lengths = Vec::new();
+        while let Some(array) = element.strip_suffix(']') {
+            if lengths.len() >= MAX_ARRAY_DIMENSIONS {
+                return Err(SigningError::new(
+                    SigningErrorType::Error_invalid_params,
+                    "Too many array dimensions",
+                ));
+            }
+            let (inner, len) = array.rsplit_once('[').ok_or_else(invalid)?;
+            let len = match len {
+                "" => None,
+                len => Some(len.parse::<usize>().map_err(|_| invalid())?),
+            };
+            lengths.push(len);
+            element = inner;
+        }
+
+        // Elementary types are parsed as ABI types, any other identifier refers to a struct.
+        let mut ty = match ParamType::parse_elementary(element) {
+            Some(param_type) => match param_type? {
+                ParamType::Address => PropertyType::Address,
+                ParamType::Bool => PropertyType::Bool,
+                ParamType::String => PropertyType::String,
+                ParamType::Bytes => PropertyType::Bytes,
+                ParamType::FixedBytes(size) => PropertyType::FixedBytes(size),
+                ParamType::Uint(bits) => PropertyType::Uint(bits),
+                ParamType::Int(bits) => PropertyType::Int(bits),
+                _ => return Err(invalid()),
+            },
+            None if is_identifier(element) => PropertyType::Custom(element.to_string()),
+            None => return Err(invalid()),
+        };
+        for len in lengths.into_iter().rev() {
+            ty = PropertyType::Array(Box::new(ty), len);
+        }
+        Ok(ty)
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' || first == '$' => {},
+        _ => return false,
+    }
+    chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_property_type() {
+        let array = |element, len| PropertyType::Array(Box::new(element), len);
+
+        let tests = [
+            ("address", PropertyType::Address),
+            ("bool", PropertyType::Bool),
+            ("string", PropertyType::String),
+            ("bytes", PropertyType::Bytes),
+            ("bytes1", PropertyType::FixedBytes(1)),
+            ("bytes32", PropertyType::FixedBytes(32)),
+            ("uint", PropertyType::Uint(256)),
+            ("uint8", PropertyType::Uint(8)),
+            ("int", PropertyType::Int(256)),
+            ("int128", PropertyType::Int(128)),
+            ("Person", PropertyType::Custom("Person".to_string())),
+            ("address[]", array(PropertyType::Address, None)),
+            ("uint256[3]", array(PropertyType::Uint(256), Some(3))),
+            (
+                "Person[2][]",
+                array(
+                    array(PropertyType::Custom("Person".to_string()), Some(2)),
+                    None,
+                ),
+            ),
+        ];
+        for (s, expected) in tests {
+            assert_eq!(PropertyType::from_str(s).unwrap(), expected, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_parse_property_type_invalid() {
+        for invalid in [
+            "", "bytes0", "bytes33", "uint7", "uint264", "int0", "uint[", "uint]", "uint[x]",
+            "uint[-1]", "1Person", "Per son",
+        ] {
+            let err = PropertyType::from_str(invalid).unwrap_err();
+            assert_eq!(
+                err.error,
+                SigningErrorType::Error_invalid_params,
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_property_type_max_dimensions() {
+        let ty = format!("uint256{}", "[]".repeat(MAX_ARRAY_DIMENSIONS));
+        PropertyType::from_str(&ty).unwrap();
+
+        for too_deep in [MAX_ARRAY_DIMENSIONS + 1, 200_000] {
+            let ty = format!("uint256{}", "[]".repeat(too_deep));
+            let err = PropertyType::from_str(&ty).unwrap_err();
+            assert_eq!(err.error, SigningErrorType::Error_invalid_params);
+        }
+    }
+
+    #[test]
+    fn test_base_type() {
+        let ty = PropertyType::from_str("Person[2][]").unwrap();
+        assert_eq!(ty.base_type(), &PropertyType::Custom("Person".to_string()));
+    }
+}
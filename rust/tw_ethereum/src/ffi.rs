@@ -0,0 +1,231 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+#![allow(clippy::missing_safety_doc)]
+
+use crate::abi::abi_encoder::AbiEncoder;
+use crate::abi::function::{Event, Function};
+use crate::eip712::TypedData;
+use crate::rlp::RlpEncoder;
+use crate::signer::Signer;
+use crate::{SigningError, SigningErrorType, SigningResult};
+use std::borrow::Cow;
+use std::ffi::{c_char, CStr};
+use std::str::FromStr;
+use tw_keypair::secp256k1::PrivateKey;
+use tw_memory::ffi::c_byte_array::{CByteArray, CByteArrayResult};
+use tw_memory::ffi::c_byte_array_ref::CByteArrayRef;
+use tw_memory::ffi::c_result::ErrorCode;
+use tw_proto::Ethereum::Proto;
+use tw_proto::EthereumAbi::Proto as AbiProto;
+use tw_proto::EthereumRlp::Proto as RlpProto;
+
+impl From<SigningError> for ErrorCode {
+    fn from(error: SigningError) -> Self {
+        error.error as ErrorCode
+    }
+}
+
+/// Builds and signs an Ethereum transaction.
+/// \param input *non-null* byte array of a serialized `Ethereum::Proto::SigningInput` message.
+/// \param input_len length of the input byte array.
+/// \return a serialized `Ethereum::Proto::SigningOutput` message.
+#[no_mangle]
+pub unsafe extern "C" fn ethereum_sign(input: *const u8, input_len: usize) -> CByteArray {
+    let input = CByteArrayRef::new(input, input_len)
+        .as_slice()
+        .unwrap_or_default();
+
+    let output = match tw_proto::deserialize::<Proto::SigningInput>(input) {
+        Ok(input) => Signer::sign_proto(input),
+        Err(_) => Proto::SigningOutput {
+            error: SigningErrorType::Error_input_parse,
+            error_message: Cow::Borrowed("Error deserializing 'SigningInput'"),
+            ..Proto::SigningOutput::default()
+        },
+    };
+    tw_proto::serialize(&output)
+        .map(CByteArray::from)
+        .unwrap_or_default()
+}
+
+/// RLP encodes an item or a list of items.
+/// \param input *non-null* byte array of a serialized `EthereumRlp::Proto::EncodingInput` message.
+/// \param input_len length of the input byte array.
+/// \return a serialized `EthereumRlp::Proto::EncodingOutput` message.
+#[no_mangle]
+pub unsafe extern "C" fn ethereum_rlp_encode(input: *const u8, input_len: usize) -> CByteArray {
+    let input = CByteArrayRef::new(input, input_len)
+        .as_slice()
+        .unwrap_or_default();
+
+    let output = match tw_proto::deserialize::<RlpProto::EncodingInput>(input) {
+        Ok(input) => RlpEncoder::encode_proto(input),
+        Err(_) => RlpProto::EncodingOutput {
+            error: SigningErrorType::Error_input_parse,
+            error_message: Cow::Borrowed("Error deserializing 'EncodingInput'"),
+            ..RlpProto::EncodingOutput::default()
+        },
+    };
+    tw_proto::serialize(&output)
+        .map(CByteArray::from)
+        .unwrap_or_default()
+}
+
+/// Computes the EIP-712 hash of a typed data document as in `eth_signTypedData_v4`.
+/// \param json *non-null* C-compatible, nul-terminated string of the typed data JSON.
+/// \return C-compatible result with the 32 byte hash.
+///          The error code is one of `Common::Proto::SigningError` values.
+#[no_mangle]
+pub unsafe extern "C" fn ethereum_eip712_hash(json: *const c_char) -> CByteArrayResult {
+    parse_typed_data(json)
+        .and_then(|typed_data| typed_data.hash())
+        .map(|hash| CByteArray::from(hash.into_vec()))
+        .into()
+}
+
+/// Signs the EIP-712 hash of a typed data document as in `eth_signTypedData_v4`.
+/// \param json *non-null* C-compatible, nul-terminated string of the typed data JSON.
+/// \param private_key *non-null* byte array of a secp256k1 private key.
+/// \param private_key_len length of the private key byte array.
+/// \return C-compatible result with the 65 byte `r ‖ s ‖ v` signature where `v` is 27 or 28.
+///          The error code is one of `Common::Proto::SigningError` values.
+#[no_mangle]
+pub unsafe extern "C" fn ethereum_eip712_sign(
+    json: *const c_char,
+    private_key: *const u8,
+    private_key_len: usize,
+) -> CByteArrayResult {
+    let private_key = CByteArrayRef::new(private_key, private_key_len)
+        .as_slice()
+        .unwrap_or_default();
+    let Ok(private) = PrivateKey::try_from(private_key) else {
+        return CByteArrayResult::error(SigningErrorType::Error_invalid_private_key as ErrorCode);
+    };
+
+    parse_typed_data(json)
+        .and_then(|typed_data| typed_data.sign(&private))
+        .map(|signature| CByteArray::from(signature.into_vec()))
+        .into()
+}
+
+unsafe fn parse_typed_data(json: *const c_char) -> SigningResult<TypedData> {
+    parse_c_str(json).and_then(TypedData::from_json)
+}
+
+/// Encodes a contract function call.
+/// \param input *non-null* byte array of a serialized `EthereumAbi::Proto::FunctionEncodingInput` message.
+/// \param input_len length of the input byte array.
+/// \return a serialized `EthereumAbi::Proto::FunctionEncodingOutput` message.
+#[no_mangle]
+pub unsafe extern "C" fn ethereum_abi_encode_function(
+    input: *const u8,
+    input_len: usize,
+) -> CByteArray {
+    let input = CByteArrayRef::new(input, input_len)
+        .as_slice()
+        .unwrap_or_default();
+
+    let output = match tw_proto::deserialize::<AbiProto::FunctionEncodingInput>(input) {
+        Ok(input) => AbiEncoder::encode_function_proto(input),
+        Err(_) => AbiProto::FunctionEncodingOutput {
+            error: SigningErrorType::Error_input_parse,
+            error_message: Cow::Borrowed("Error deserializing 'FunctionEncodingInput'"),
+            ..AbiProto::FunctionEncodingOutput::default()
+        },
+    };
+    tw_proto::serialize(&output)
+        .map(CByteArray::from)
+        .unwrap_or_default()
+}
+
+/// Decodes a contract function call using a JSON ABI.
+/// \param input *non-null* byte array of a serialized `EthereumAbi::Proto::ContractCallDecodingInput` message.
+/// \param input_len length of the input byte array.
+/// \return a serialized `EthereumAbi::Proto::ContractCallDecodingOutput` message.
+#[no_mangle]
+pub unsafe extern "C" fn ethereum_abi_decode_contract_call(
+    input: *const u8,
+    input_len: usize,
+) -> CByteArray {
+    let input = CByteArrayRef::new(input, input_len)
+        .as_slice()
+        .unwrap_or_default();
+
+    let output = match tw_proto::deserialize::<AbiProto::ContractCallDecodingInput>(input) {
+        Ok(input) => AbiEncoder::decode_contract_call_proto(input),
+        Err(_) => AbiProto::ContractCallDecodingOutput {
+            error: SigningErrorType::Error_input_parse,
+            error_message: Cow::Borrowed("Error deserializing 'ContractCallDecodingInput'"),
+            ..AbiProto::ContractCallDecodingOutput::default()
+        },
+    };
+    tw_proto::serialize(&output)
+        .map(CByteArray::from)
+        .unwrap_or_default()
+}
+
+/// Decodes function return values or parameters of the given types.
+/// \param input *non-null* byte array of a serialized `EthereumAbi::Proto::ParamsDecodingInput` message.
+/// \param input_len length of the input byte array.
+/// \return a serialized `EthereumAbi::Proto::ParamsDecodingOutput` message.
+#[no_mangle]
+pub unsafe extern "C" fn ethereum_abi_decode_params(
+    input: *const u8,
+    input_len: usize,
+) -> CByteArray {
+    let input = CByteArrayRef::new(input, input_len)
+        .as_slice()
+        .unwrap_or_default();
+
+    let output = match tw_proto::deserialize::<AbiProto::ParamsDecodingInput>(input) {
+        Ok(input) => AbiEncoder::decode_params_proto(input),
+        Err(_) => AbiProto::ParamsDecodingOutput {
+            error: SigningErrorType::Error_input_parse,
+            error_message: Cow::Borrowed("Error deserializing 'ParamsDecodingInput'"),
+            ..AbiProto::ParamsDecodingOutput::default()
+        },
+    };
+    tw_proto::serialize(&output)
+        .map(CByteArray::from)
+        .unwrap_or_default()
+}
+
+/// Computes the 4 byte selector of a function.
+/// \param signature *non-null* C-compatible, nul-terminated human-readable function signature,
+///                  e.g. `transfer(address,uint256)` or `function transfer(address to, uint amount)`.
+/// \return C-compatible result with the 4 byte selector.
+///          The error code is one of `Common::Proto::SigningError` values.
+#[no_mangle]
+pub unsafe extern "C" fn ethereum_abi_function_selector(
+    signature: *const c_char,
+) -> CByteArrayResult {
+    parse_c_str(signature)
+        .and_then(Function::from_str)
+        .map(|function| CByteArray::from(function.selector().to_vec()))
+        .into()
+}
+
+/// Computes the topic of an event, i.e. the 32 byte hash of its signature.
+/// \param signature *non-null* C-compatible, nul-terminated human-readable event signature,
+///                  e.g. `Transfer(address,address,uint256)`.
+/// \return C-compatible result with the 32 byte topic.
+///          The error code is one of `Common::Proto::SigningError` values.
+#[no_mangle]
+pub unsafe extern "C" fn ethereum_abi_event_topic(signature: *const c_char) -> CByteArrayResult {
+    parse_c_str(signature)
+        .and_then(Event::from_str)
+        .map(|event| CByteArray::from(event.topic().into_vec()))
+        .into()
+}
+
+unsafe fn parse_c_str<'a>(s: *const c_char) -> SigningResult<&'a str> {
+    let invalid = || SigningError::new(SigningErrorType::Error_input_parse, "Invalid string");
+    if s.is_null() {
+        return Err(invalid());
+    }
+    CStr::from_ptr(s).to_str().map_err(|_| invalid())
+}
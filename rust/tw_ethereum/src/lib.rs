@@ -0,0 +1,42 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! `tw_ethereum` crate builds and signs Ethereum transactions:
+//! legacy (EIP-155), access list (EIP-2930), dynamic fee (EIP-1559)
+//! and blob-carrying (EIP-4844) ones.
+//! It also hashes and signs EIP-712 typed structured data, encodes and decodes contract ABI values,
+//! and RLP encodes items described by `EthereumRlp.proto` messages.
+
+pub mod abi;
+pub mod address;
+pub mod contract_call;
+pub mod eip712;
+pub mod ffi;
+pub mod rlp;
+pub mod signer;
+pub mod transaction;
+
+mod tx_builder;
+
+pub use tw_proto::Common::Proto::SigningError as SigningErrorType;
+
+pub type SigningResult<T> = Result<T, SigningError>;
+
+/// An error that is reported through the `error` and `error_message` fields of `SigningOutput`.
+#[derive(Debug, PartialEq)]
+pub struct SigningError {
+    pub error: SigningErrorType,
+    pub message: String,
+}
+
+impl SigningError {
+    pub fn new<M: Into<String>>(error: SigningErrorType, message: M) -> SigningError {
+        SigningError {
+            error,
+            message: message.into(),
+        }
+    }
+}
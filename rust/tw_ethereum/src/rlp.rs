@@ -0,0 +1,92 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::address::Address;
+use crate::tx_builder::parse_u256;
+use crate::{SigningError, SigningErrorType, SigningResult};
+use std::borrow::Cow;
+use tw_encoding::hex;
+use tw_encoding::rlp::RlpStream;
+use tw_hash::H160;
+use tw_proto::EthereumRlp::Proto;
+use tw_proto::EthereumRlp::Proto::mod_RlpItem::OneOfitem as Item;
+
+pub struct RlpEncoder;
+
+impl RlpEncoder {
+    /// RLP encodes an item or a list of items.
+    /// The error is reported through the `error` and `error_message` output fields.
+    pub fn encode_proto(input: Proto::EncodingInput<'_>) -> Proto::EncodingOutput<'static> {
+        Self::encode_impl(&input).unwrap_or_else(|e| Proto::EncodingOutput {
+            error: e.error,
+            error_message: Cow::Owned(e.message),
+            ..Proto::EncodingOutput::default()
+        })
+    }
+
+    fn encode_impl(
+        input: &Proto::EncodingInput<'_>,
+    ) -> SigningResult<Proto::EncodingOutput<'static>> {
+        let item = input.item.as_ref().ok_or_else(|| {
+            SigningError::new(SigningErrorType::Error_invalid_params, "No item to encode")
+        })?;
+
+        let mut stream = RlpStream::new();
+        Self::append_item(&mut stream, item)?;
+        Ok(Proto::EncodingOutput {
+            encoded: Cow::Owned(stream.out()),
+            ..Proto::EncodingOutput::default()
+        })
+    }
+
+    fn append_item(stream: &mut RlpStream, item: &Proto::RlpItem<'_>) -> SigningResult<()> {
+        match item.item {
+            Item::string_item(ref string) => {
+                stream.append(string.as_ref());
+            },
+            Item::number_u64(num) => {
+                stream.append(&num);
+            },
+            Item::number_u256(ref num) => {
+                stream.append(&parse_u256(num, "number_u256")?);
+            },
+            Item::address(ref address) => {
+                stream.append(&parse_address(address)?);
+            },
+            Item::data(ref data) => {
+                stream.append(data.as_ref());
+            },
+            Item::list(ref list) => {
+                stream.begin_list();
+                for item in list.items.iter() {
+                    Self::append_item(stream, item)?;
+                }
+                stream.finalize_list();
+            },
+            Item::raw_encoded(ref raw) => {
+                stream.append_raw(raw);
+            },
+            Item::None => {
+                return Err(SigningError::new(
+                    SigningErrorType::Error_invalid_params,
+                    "No item to encode",
+                ));
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Parses a hex address with an optional `0x` prefix.
+fn parse_address(address: &str) -> SigningResult<Address> {
+    hex::decode(address)
+        .ok()
+        .and_then(|bytes| H160::try_from(bytes.as_slice()).ok())
+        .map(Address::new)
+        .ok_or_else(|| {
+            SigningError::new(SigningErrorType::Error_invalid_address, "Invalid address")
+        })
+}
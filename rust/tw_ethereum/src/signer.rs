@@ -0,0 +1,64 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::transaction::SignatureVrs;
+use crate::tx_builder::{build_transaction, parse_u256};
+use crate::{SigningError, SigningErrorType, SigningResult};
+use std::borrow::Cow;
+use tw_hash::U256;
+use tw_keypair::secp256k1::PrivateKey;
+use tw_keypair::traits::SigningKeyTrait;
+use tw_proto::Ethereum::Proto;
+
+pub struct Signer;
+
+impl Signer {
+    /// Builds and signs a transaction.
+    /// The error is reported through the `error` and `error_message` output fields.
+    pub fn sign_proto(input: Proto::SigningInput<'_>) -> Proto::SigningOutput<'static> {
+        Self::sign_impl(&input).unwrap_or_else(|e| Proto::SigningOutput {
+            error: e.error,
+            error_message: Cow::Owned(e.message),
+            ..Proto::SigningOutput::default()
+        })
+    }
+
+    fn sign_impl(input: &Proto::SigningInput<'_>) -> SigningResult<Proto::SigningOutput<'static>> {
+        let chain_id = parse_u256(&input.chain_id, "chain_id")?;
+        let private = PrivateKey::try_from(input.private_key.as_ref()).map_err(|_| {
+            SigningError::new(
+                SigningErrorType::Error_invalid_private_key,
+                "Invalid private key",
+            )
+        })?;
+
+        let tx = build_transaction(input)?;
+        let signature = private
+            .sign(tx.pre_hash(chain_id))
+            .map_err(|_| SigningError::new(SigningErrorType::Error_signing, "Error signing"))?;
+
+        let signature = SignatureVrs {
+            v: tx.signature_v(signature.v(), chain_id)?,
+            r: U256::from(signature.r()),
+            s: U256::from(signature.s()),
+        };
+        let encoded = tx.encode_signed(&signature, chain_id);
+
+        // `v` is at least one byte long, `r` and `s` are 32 bytes long.
+        let mut v = signature.v.to_big_endian_compact();
+        if v.is_empty() {
+            v.push(0);
+        }
+        Ok(Proto::SigningOutput {
+            encoded: Cow::Owned(encoded),
+            v: Cow::Owned(v),
+            r: Cow::Owned(signature.r.to_big_endian().into_vec()),
+            s: Cow::Owned(signature.s.to_big_endian().into_vec()),
+            data: Cow::Owned(tx.payload().to_vec()),
+            ..Proto::SigningOutput::default()
+        })
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::address::Address;
+use tw_encoding::rlp::{RlpEncode, RlpStream};
+use tw_hash::H256;
+
+/// An item of the [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) access list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Access {
+    pub address: Address,
+    pub storage_keys: Vec<H256>,
+}
+
+/// Encoded as `[address, [storage_key, ...]]`.
+impl RlpEncode for Access {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.begin_list().append(&self.address).begin_list();
+        for key in self.storage_keys.iter() {
+            stream.append_bytes(key.as_slice());
+        }
+        stream.finalize_list().finalize_list();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::{hex, rlp};
+
+    #[test]
+    fn test_encode_access_list() {
+        let access_list = vec![
+            Access {
+                address: "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae"
+                    .parse()
+                    .unwrap(),
+                storage_keys: vec![
+                    "0x0000000000000000000000000000000000000000000000000000000000000003".into(),
+                    "0x0000000000000000000000000000000000000000000000000000000000000007".into(),
+                ],
+            },
+            Access {
+                address: "0xbb9bc244d798123fde783fcc1c72d3bb8c189413"
+                    .parse()
+                    .unwrap(),
+                storage_keys: Vec::new(),
+            },
+        ];
+
+        let mut stream = rlp::RlpStream::new();
+        stream.append_list(&access_list);
+        assert_eq!(
+            hex::encode(stream.out(), false),
+            "f872f85994de0b295669a9fd93d5f28d9ec85e40f4cb697baef842a00000000000000000000000000000000000000000000000000000000000000003a00000000000000000000000000000000000000000000000000000000000000007d694bb9bc244d798123fde783fcc1c72d3bb8c189413c0"
+        );
+    }
+}
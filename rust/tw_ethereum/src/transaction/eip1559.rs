@@ -0,0 +1,68 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::address::Address;
+use crate::transaction::{
+    append_signature, append_to, envelope, Access, SignatureVrs, UnsignedTransaction,
+};
+use tw_encoding::rlp::RlpStream;
+use tw_hash::U256;
+
+/// cbindgen:ignore
+const EIP1559_TX_TYPE: u8 = 0x02;
+
+/// Dynamic fee transaction, see [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559).
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionEip1559 {
+    pub nonce: U256,
+    pub max_inclusion_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: U256,
+    /// `None` for a contract creation.
+    pub to: Option<Address>,
+    pub amount: U256,
+    pub payload: Vec<u8>,
+    pub access_list: Vec<Access>,
+}
+
+impl TransactionEip1559 {
+    fn append_fields(&self, stream: &mut RlpStream, chain_id: U256) {
+        stream
+            .append(&chain_id)
+            .append(&self.nonce)
+            .append(&self.max_inclusion_fee_per_gas)
+            .append(&self.max_fee_per_gas)
+            .append(&self.gas_limit);
+        append_to(stream, &self.to);
+        stream
+            .append(&self.amount)
+            .append(&self.payload)
+            .append_list(&self.access_list);
+    }
+}
+
+impl UnsignedTransaction for TransactionEip1559 {
+    fn encode_unsigned(&self, chain_id: U256) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        self.append_fields(&mut stream, chain_id);
+        stream.finalize_list();
+        envelope(EIP1559_TX_TYPE, stream)
+    }
+
+    fn encode_signed(&self, signature: &SignatureVrs, chain_id: U256) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        self.append_fields(&mut stream, chain_id);
+        append_signature(&mut stream, signature);
+        stream.finalize_list();
+        envelope(EIP1559_TX_TYPE, stream)
+    }
+
+    fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
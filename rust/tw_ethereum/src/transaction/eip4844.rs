@@ -0,0 +1,76 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::address::Address;
+use crate::transaction::{append_signature, envelope, Access, SignatureVrs, UnsignedTransaction};
+use tw_encoding::rlp::RlpStream;
+use tw_hash::{H256, U256};
+
+/// cbindgen:ignore
+const EIP4844_TX_TYPE: u8 = 0x03;
+/// The first byte of a blob versioned hash, i.e. `0x01 || sha256(kzg_commitment)[1..]`.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// Blob-carrying transaction, see [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844).
+/// This is the execution payload only, the network wrapper with blobs, commitments and proofs is not supported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionEip4844 {
+    pub nonce: U256,
+    pub max_inclusion_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: U256,
+    /// Blob transactions cannot create contracts.
+    pub to: Address,
+    pub amount: U256,
+    pub payload: Vec<u8>,
+    pub access_list: Vec<Access>,
+    pub max_fee_per_blob_gas: U256,
+    pub blob_versioned_hashes: Vec<H256>,
+}
+
+impl TransactionEip4844 {
+    fn append_fields(&self, stream: &mut RlpStream, chain_id: U256) {
+        stream
+            .append(&chain_id)
+            .append(&self.nonce)
+            .append(&self.max_inclusion_fee_per_gas)
+            .append(&self.max_fee_per_gas)
+            .append(&self.gas_limit)
+            .append(&self.to)
+            .append(&self.amount)
+            .append(&self.payload)
+            .append_list(&self.access_list)
+            .append(&self.max_fee_per_blob_gas)
+            .begin_list();
+        for hash in self.blob_versioned_hashes.iter() {
+            stream.append_bytes(hash.as_slice());
+        }
+        stream.finalize_list();
+    }
+}
+
+impl UnsignedTransaction for TransactionEip4844 {
+    fn encode_unsigned(&self, chain_id: U256) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        self.append_fields(&mut stream, chain_id);
+        stream.finalize_list();
+        envelope(EIP4844_TX_TYPE, stream)
+    }
+
+    fn encode_signed(&self, signature: &SignatureVrs, chain_id: U256) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        self.append_fields(&mut stream, chain_id);
+        append_signature(&mut stream, signature);
+        stream.finalize_list();
+        envelope(EIP4844_TX_TYPE, stream)
+    }
+
+    fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
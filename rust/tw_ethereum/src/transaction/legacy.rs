@@ -0,0 +1,182 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::address::Address;
+use crate::transaction::{append_signature, append_to, SignatureVrs, UnsignedTransaction};
+use crate::{SigningError, SigningErrorType, SigningResult};
+use tw_encoding::rlp::RlpStream;
+use tw_hash::U256;
+
+/// `v = 27 + recovery_id` for transactions without a chain ID.
+/// cbindgen:ignore
+const V_OFFSET: u64 = 27;
+/// `v = 35 + chain_id * 2 + recovery_id` for transactions with a chain ID.
+/// cbindgen:ignore
+const V_EIP155_OFFSET: u64 = 35;
+
+/// Legacy non-typed transaction.
+/// If the chain ID is not zero, it's signed with the [EIP-155](https://eips.ethereum.org/EIPS/eip-155) replay protection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionLegacy {
+    pub nonce: U256,
+    pub gas_price: U256,
+    pub gas_limit: U256,
+    /// `None` for a contract creation.
+    pub to: Option<Address>,
+    pub amount: U256,
+    pub payload: Vec<u8>,
+}
+
+impl TransactionLegacy {
+    fn append_fields(&self, stream: &mut RlpStream) {
+        stream
+            .append(&self.nonce)
+            .append(&self.gas_price)
+            .append(&self.gas_limit);
+        append_to(stream, &self.to);
+        stream.append(&self.amount).append(&self.payload);
+    }
+}
+
+impl UnsignedTransaction for TransactionLegacy {
+    fn encode_unsigned(&self, chain_id: U256) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        self.append_fields(&mut stream);
+        if !chain_id.is_zero() {
+            stream.append(&chain_id).append(&0_u8).append(&0_u8);
+        }
+        stream.finalize_list();
+        stream.out()
+    }
+
+    fn encode_signed(&self, signature: &SignatureVrs, _chain_id: U256) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        self.append_fields(&mut stream);
+        append_signature(&mut stream, signature);
+        stream.finalize_list();
+        stream.out()
+    }
+
+    fn signature_v(&self, recovery_id: u8, chain_id: U256) -> SigningResult<U256> {
+        if chain_id.is_zero() {
+            return Ok(U256::from(V_OFFSET + recovery_id as u64));
+        }
+
+        chain_id
+            .checked_mul(U256::from(2_u8))
+            .and_then(|v| v.checked_add(U256::from(V_EIP155_OFFSET + recovery_id as u64)))
+            .ok_or_else(|| {
+                SigningError::new(
+                    SigningErrorType::Error_invalid_params,
+                    "Chain ID is too big",
+                )
+            })
+    }
+
+    fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    /// The example from EIP-155.
+    fn eip155_transaction() -> TransactionLegacy {
+        TransactionLegacy {
+            nonce: U256::from(9_u8),
+            gas_price: U256::from(20_000_000_000_u64),
+            gas_limit: U256::from(21000_u64),
+            to: Some(
+                "0x3535353535353535353535353535353535353535"
+                    .parse()
+                    .unwrap(),
+            ),
+            amount: U256::from(1_000_000_000_000_000_000_u64),
+            payload: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_legacy_encode_unsigned() {
+        let tx = eip155_transaction();
+        assert_eq!(
+            hex::encode(tx.encode_unsigned(U256::from(1_u8)), false),
+            "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080"
+        );
+        assert_eq!(
+            hex::encode(tx.pre_hash(U256::from(1_u8)), false),
+            "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+        // No replay protection.
+        assert_eq!(
+            hex::encode(tx.encode_unsigned(U256::zero()), false),
+            "e9098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080"
+        );
+    }
+
+    #[test]
+    fn test_legacy_encode_dummy_signature() {
+        let tx = TransactionLegacy {
+            nonce: U256::zero(),
+            gas_price: U256::from(42_000_000_000_u64),
+            gas_limit: U256::from(78009_u64),
+            to: Some("0x6b175474e89094c44da98b954eedeac495271d0f".parse().unwrap()),
+            amount: U256::zero(),
+            payload: hex::decode("a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000").unwrap(),
+        };
+        let chain_id = U256::from(0x34_u8);
+        let signature = SignatureVrs {
+            v: U256::zero(),
+            r: U256::zero(),
+            s: U256::zero(),
+        };
+
+        assert_eq!(
+            hex::encode(tx.pre_hash(chain_id), false),
+            "b3525019dc367d3ecac48905f9a95ff3550c25a24823db765f92cae2dec7ebfd"
+        );
+        assert_eq!(
+            hex::encode(tx.encode_signed(&signature, chain_id), false),
+            "f86a808509c7652400830130b9946b175474e89094c44da98b954eedeac495271d0f80b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000808080"
+        );
+    }
+
+    #[test]
+    fn test_legacy_signature_v() {
+        let tx = eip155_transaction();
+        assert_eq!(tx.signature_v(0, U256::zero()).unwrap(), U256::from(27_u8));
+        assert_eq!(
+            tx.signature_v(1, U256::from(1_u8)).unwrap(),
+            U256::from(38_u8)
+        );
+        assert_eq!(
+            tx.signature_v(0, U256::from(56_u8)).unwrap(),
+            U256::from(147_u8)
+        );
+        assert_eq!(
+            tx.signature_v(0, U256::MAX).unwrap_err().error,
+            SigningErrorType::Error_invalid_params
+        );
+    }
+
+    #[test]
+    fn test_legacy_contract_creation() {
+        let tx = TransactionLegacy {
+            to: None,
+            payload: vec![0x60, 0x80],
+            ..eip155_transaction()
+        };
+        assert_eq!(
+            hex::encode(tx.encode_unsigned(U256::zero()), false),
+            "d7098504a817c80082520880880de0b6b3a7640000826080"
+        );
+    }
+}
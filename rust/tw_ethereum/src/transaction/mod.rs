@@ -0,0 +1,76 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::address::Address;
+use crate::SigningResult;
+use tw_encoding::rlp::RlpStream;
+use tw_hash::sha3::keccak256;
+use tw_hash::{H256, U256};
+
+mod access_list;
+mod eip1559;
+mod eip2930;
+mod eip4844;
+mod legacy;
+
+pub use access_list::Access;
+pub use eip1559::TransactionEip1559;
+pub use eip2930::TransactionEip2930;
+pub use eip4844::{TransactionEip4844, VERSIONED_HASH_VERSION_KZG};
+pub use legacy::TransactionLegacy;
+
+/// An ECDSA signature with the `v` value prepared according to the transaction type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignatureVrs {
+    pub v: U256,
+    pub r: U256,
+    pub s: U256,
+}
+
+pub trait UnsignedTransaction {
+    /// Returns the transaction encoded for signing.
+    fn encode_unsigned(&self, chain_id: U256) -> Vec<u8>;
+
+    /// Returns the signed transaction encoded for broadcasting.
+    fn encode_signed(&self, signature: &SignatureVrs, chain_id: U256) -> Vec<u8>;
+
+    /// Returns the `v` signature value for the given `recovery_id`.
+    /// Typed transactions keep the y-parity as is.
+    fn signature_v(&self, recovery_id: u8, _chain_id: U256) -> SigningResult<U256> {
+        Ok(U256::from(recovery_id))
+    }
+
+    /// Returns the transaction call data.
+    fn payload(&self) -> &[u8];
+
+    /// Returns the hash to be signed.
+    fn pre_hash(&self, chain_id: U256) -> H256 {
+        let hash = keccak256(&self.encode_unsigned(chain_id));
+        H256::try_from(hash.as_slice()).expect("keccak256 hash must be 32 bytes")
+    }
+}
+
+/// Appends the recipient address or an empty string for a contract creation.
+fn append_to(stream: &mut RlpStream, to: &Option<Address>) {
+    match to {
+        Some(address) => stream.append(address),
+        None => stream.append_bytes(&[]),
+    };
+}
+
+fn append_signature(stream: &mut RlpStream, signature: &SignatureVrs) {
+    stream
+        .append(&signature.v)
+        .append(&signature.r)
+        .append(&signature.s);
+}
+
+/// Prefixes the encoded transaction with its type, see [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718).
+fn envelope(tx_type: u8, stream: RlpStream) -> Vec<u8> {
+    let mut encoded = vec![tx_type];
+    encoded.extend_from_slice(&stream.out());
+    encoded
+}
@@ -0,0 +1,220 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::address::Address;
+use crate::contract_call;
+use crate::transaction::{
+    Access, TransactionEip1559, TransactionEip2930, TransactionEip4844, TransactionLegacy,
+    UnsignedTransaction, VERSIONED_HASH_VERSION_KZG,
+};
+use crate::{SigningError, SigningErrorType, SigningResult};
+use tw_hash::{H256, U256};
+use tw_proto::Ethereum::Proto;
+
+/// The recipient, value and call data of a transaction.
+struct TransactionCall {
+    to: Option<Address>,
+    amount: U256,
+    payload: Vec<u8>,
+}
+
+/// Builds a transaction of the `tx_mode` type from the given `SigningInput`.
+pub(crate) fn build_transaction(
+    input: &Proto::SigningInput,
+) -> SigningResult<Box<dyn UnsignedTransaction>> {
+    let TransactionCall {
+        to,
+        amount,
+        payload,
+    } = build_call(input)?;
+    let nonce = parse_u256(&input.nonce, "nonce")?;
+    let gas_limit = parse_u256(&input.gas_limit, "gas_limit")?;
+
+    let tx: Box<dyn UnsignedTransaction> = match input.tx_mode {
+        Proto::TransactionMode::Legacy => Box::new(TransactionLegacy {
+            nonce,
+            gas_price: parse_u256(&input.gas_price, "gas_price")?,
+            gas_limit,
+            to,
+            amount,
+            payload,
+        }),
+        Proto::TransactionMode::AccessList => Box::new(TransactionEip2930 {
+            nonce,
+            gas_price: parse_u256(&input.gas_price, "gas_price")?,
+            gas_limit,
+            to,
+            amount,
+            payload,
+            access_list: build_access_list(&input.access_list)?,
+        }),
+        Proto::TransactionMode::Enveloped => Box::new(TransactionEip1559 {
+            nonce,
+            max_inclusion_fee_per_gas: parse_u256(
+                &input.max_inclusion_fee_per_gas,
+                "max_inclusion_fee_per_gas",
+            )?,
+            max_fee_per_gas: parse_u256(&input.max_fee_per_gas, "max_fee_per_gas")?,
+            gas_limit,
+            to,
+            amount,
+            payload,
+            access_list: build_access_list(&input.access_list)?,
+        }),
+        Proto::TransactionMode::Blob => Box::new(TransactionEip4844 {
+            nonce,
+            max_inclusion_fee_per_gas: parse_u256(
+                &input.max_inclusion_fee_per_gas,
+                "max_inclusion_fee_per_gas",
+            )?,
+            max_fee_per_gas: parse_u256(&input.max_fee_per_gas, "max_fee_per_gas")?,
+            gas_limit,
+            to: to.ok_or_else(|| {
+                SigningError::new(
+                    SigningErrorType::Error_invalid_address,
+                    "Blob transaction cannot create a contract",
+                )
+            })?,
+            amount,
+            payload,
+            access_list: build_access_list(&input.access_list)?,
+            max_fee_per_blob_gas: parse_u256(&input.max_fee_per_blob_gas, "max_fee_per_blob_gas")?,
+            blob_versioned_hashes: build_blob_versioned_hashes(&input.blob_versioned_hashes)?,
+        }),
+        Proto::TransactionMode::UserOp => {
+            return Err(SigningError::new(
+                SigningErrorType::Error_invalid_params,
+                "ERC-4337 user operations are not supported",
+            ))
+        },
+    };
+    Ok(tx)
+}
+
+fn build_call(input: &Proto::SigningInput) -> SigningResult<TransactionCall> {
+    use Proto::mod_Transaction::OneOftransaction_oneof as Tx;
+
+    let transaction = input.transaction.as_ref().map(|tx| &tx.transaction_oneof);
+    match transaction {
+        Some(Tx::transfer(transfer)) => Ok(TransactionCall {
+            to: parse_optional_address(&input.to_address)?,
+            amount: parse_u256(&transfer.amount, "amount")?,
+            payload: transfer.data.to_vec(),
+        }),
+        Some(Tx::contract_generic(generic)) => Ok(TransactionCall {
+            to: parse_optional_address(&input.to_address)?,
+            amount: parse_u256(&generic.amount, "amount")?,
+            payload: generic.data.to_vec(),
+        }),
+        Some(Tx::erc20_transfer(transfer)) => token_call(
+            input,
+            contract_call::erc20_transfer(
+                transfer.to.parse()?,
+                parse_u256(&transfer.amount, "amount")?,
+            ),
+        ),
+        Some(Tx::erc20_approve(approve)) => token_call(
+            input,
+            contract_call::erc20_approve(
+                approve.spender.parse()?,
+                parse_u256(&approve.amount, "amount")?,
+            ),
+        ),
+        Some(Tx::erc721_transfer(transfer)) => token_call(
+            input,
+            contract_call::erc721_transfer_from(
+                transfer.from.parse()?,
+                transfer.to.parse()?,
+                parse_u256(&transfer.token_id, "token_id")?,
+            ),
+        ),
+        Some(Tx::erc1155_transfer(transfer)) => token_call(
+            input,
+            contract_call::erc1155_safe_transfer_from(
+                transfer.from.parse()?,
+                transfer.to.parse()?,
+                parse_u256(&transfer.token_id, "token_id")?,
+                parse_u256(&transfer.value, "value")?,
+                &transfer.data,
+            ),
+        ),
+        Some(Tx::None) | None => Err(SigningError::new(
+            SigningErrorType::Error_invalid_params,
+            "No transaction specified",
+        )),
+    }
+}
+
+/// Token calls are sent to the token contract (`to_address`) with zero value.
+fn token_call(input: &Proto::SigningInput, payload: Vec<u8>) -> SigningResult<TransactionCall> {
+    Ok(TransactionCall {
+        to: Some(input.to_address.parse()?),
+        amount: U256::zero(),
+        payload,
+    })
+}
+
+fn build_access_list(access_list: &[Proto::Access]) -> SigningResult<Vec<Access>> {
+    access_list
+        .iter()
+        .map(|access| {
+            let storage_keys = access
+                .stored_keys
+                .iter()
+                .map(|key| parse_h256(key, "Invalid access list storage key"))
+                .collect::<SigningResult<Vec<_>>>()?;
+            Ok(Access {
+                address: access.address.parse()?,
+                storage_keys,
+            })
+        })
+        .collect()
+}
+
+fn build_blob_versioned_hashes<T: AsRef<[u8]>>(hashes: &[T]) -> SigningResult<Vec<H256>> {
+    if hashes.is_empty() {
+        return Err(SigningError::new(
+            SigningErrorType::Error_invalid_params,
+            "Blob transaction must have at least one blob versioned hash",
+        ));
+    }
+    hashes
+        .iter()
+        .map(|hash| {
+            let hash = parse_h256(hash.as_ref(), "Invalid blob versioned hash")?;
+            if hash[0] != VERSIONED_HASH_VERSION_KZG {
+                return Err(SigningError::new(
+                    SigningErrorType::Error_invalid_params,
+                    "Unsupported blob versioned hash version",
+                ));
+            }
+            Ok(hash)
+        })
+        .collect()
+}
+
+/// An empty `to_address` means a contract creation.
+fn parse_optional_address(address: &str) -> SigningResult<Option<Address>> {
+    if address.is_empty() {
+        return Ok(None);
+    }
+    address.parse().map(Some)
+}
+
+/// Parses a big-endian number. Empty bytes are treated as zero.
+pub(crate) fn parse_u256(bytes: &[u8], field: &str) -> SigningResult<U256> {
+    U256::from_big_endian_slice(bytes).map_err(|_| {
+        SigningError::new(
+            SigningErrorType::Error_invalid_params,
+            format!("'{field}' must be at most 32 bytes"),
+        )
+    })
+}
+
+fn parse_h256(bytes: &[u8], message: &'static str) -> SigningResult<H256> {
+    H256::try_from(bytes)
+        .map_err(|_| SigningError::new(SigningErrorType::Error_invalid_params, message))
+}
@@ -0,0 +1,141 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::borrow::Cow;
+use std::ffi::CString;
+use tw_encoding::hex;
+use tw_ethereum::ffi::{
+    ethereum_abi_decode_contract_call, ethereum_abi_decode_params, ethereum_abi_encode_function,
+    ethereum_abi_event_topic, ethereum_abi_function_selector,
+};
+use tw_proto::Common::Proto::SigningError;
+use tw_proto::EthereumAbi::Proto;
+use Proto::mod_ParamsDecodingInput::OneOfabi as DecodingAbi;
+use Proto::mod_Token::OneOftoken as TokenValue;
+
+#[test]
+fn test_ethereum_abi_encode_function() {
+    let input = Proto::FunctionEncodingInput {
+        function: Cow::Borrowed("approve(address,uint256)"),
+        params: vec![
+            Proto::Token {
+                name: Cow::default(),
+                token: TokenValue::address(Cow::Borrowed(
+                    "0x5322b34c88ed0691971bf52a7047448f0f4efc84",
+                )),
+            },
+            Proto::Token {
+                name: Cow::default(),
+                token: TokenValue::number_uint(Proto::NumberNParam {
+                    bits: 256,
+                    value: Cow::Owned(hex::decode("1bc16d674ec80000").unwrap()),
+                }),
+            },
+        ],
+    };
+    let input = tw_proto::serialize(&input).unwrap();
+
+    let output = unsafe { ethereum_abi_encode_function(input.as_ptr(), input.len()).into_vec() };
+    let output: Proto::FunctionEncodingOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "095ea7b30000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000"
+    );
+}
+
+#[test]
+fn test_ethereum_abi_decode_contract_call() {
+    let input = Proto::ContractCallDecodingInput {
+        encoded: Cow::Owned(hex::decode("a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000").unwrap()),
+        abi: Cow::Borrowed(r#"["function transfer(address to, uint256 amount)"]"#),
+    };
+    let input = tw_proto::serialize(&input).unwrap();
+
+    let output =
+        unsafe { ethereum_abi_decode_contract_call(input.as_ptr(), input.len()).into_vec() };
+    let output: Proto::ContractCallDecodingOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.function_signature, "transfer(address,uint256)");
+    let names: Vec<_> = output
+        .params
+        .iter()
+        .map(|param| param.name.as_ref())
+        .collect();
+    assert_eq!(names, ["to", "amount"]);
+}
+
+#[test]
+fn test_ethereum_abi_decode_params() {
+    let input = Proto::ParamsDecodingInput {
+        encoded: Cow::Owned(
+            hex::decode("0000000000000000000000000000000000000000000000000000000000000000")
+                .unwrap(),
+        ),
+        abi: DecodingAbi::types(Proto::ParamTypes {
+            types: vec![Cow::Borrowed("bool")],
+        }),
+    };
+    let input = tw_proto::serialize(&input).unwrap();
+
+    let output = unsafe { ethereum_abi_decode_params(input.as_ptr(), input.len()).into_vec() };
+    let output: Proto::ParamsDecodingOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.params.len(), 1);
+    assert_eq!(output.params[0].token, TokenValue::boolean(false));
+}
+
+#[test]
+fn test_ethereum_abi_invalid_input() {
+    let input = [0xff_u8; 3];
+
+    let output = unsafe { ethereum_abi_encode_function(input.as_ptr(), input.len()).into_vec() };
+    let output: Proto::FunctionEncodingOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::Error_input_parse);
+
+    let output =
+        unsafe { ethereum_abi_decode_contract_call(input.as_ptr(), input.len()).into_vec() };
+    let output: Proto::ContractCallDecodingOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::Error_input_parse);
+
+    let output = unsafe { ethereum_abi_decode_params(input.as_ptr(), input.len()).into_vec() };
+    let output: Proto::ParamsDecodingOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::Error_input_parse);
+}
+
+#[test]
+fn test_ethereum_abi_function_selector() {
+    let signature = CString::new("transfer(address,uint256)").unwrap();
+    let selector = unsafe {
+        ethereum_abi_function_selector(signature.as_ptr())
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(hex::encode(selector, false), "a9059cbb");
+
+    let invalid = CString::new("transfer(address,uint7)").unwrap();
+    let res = unsafe { ethereum_abi_function_selector(invalid.as_ptr()) };
+    assert_eq!(res.code, SigningError::Error_invalid_params as i32);
+
+    let res = unsafe { ethereum_abi_function_selector(std::ptr::null()) };
+    assert_eq!(res.code, SigningError::Error_input_parse as i32);
+}
+
+#[test]
+fn test_ethereum_abi_event_topic() {
+    let signature =
+        CString::new("Approval(address indexed owner, address indexed spender, uint256 value)")
+            .unwrap();
+    let topic = unsafe {
+        ethereum_abi_event_topic(signature.as_ptr())
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(
+        hex::encode(topic, false),
+        "8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"
+    );
+}
@@ -0,0 +1,263 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::borrow::Cow;
+use std::str::FromStr;
+use tw_encoding::hex;
+use tw_ethereum::abi::abi_encoder::AbiEncoder;
+use tw_ethereum::abi::contract::Contract;
+use tw_ethereum::abi::function::{Event, Function};
+use tw_ethereum::abi::token::Token;
+use tw_proto::Common::Proto::SigningError;
+use tw_proto::EthereumAbi::Proto;
+use Proto::mod_ParamsDecodingInput::OneOfabi as DecodingAbi;
+use Proto::mod_Token::OneOftoken as TokenValue;
+
+const ERC20_ABI: &str = r#"[
+    {
+        "type": "function",
+        "name": "transfer",
+        "inputs": [
+            {"name": "to", "type": "address"},
+            {"name": "amount", "type": "uint256"}
+        ],
+        "outputs": [{"name": "", "type": "bool"}]
+    },
+    "function approve(address spender, uint256 amount) returns (bool)",
+    "event Transfer(address indexed from, address indexed to, uint256 value)"
+]"#;
+
+const TRANSFER_CALL: &str = "a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000";
+
+fn uint(bits: u32, value: &str) -> TokenValue<'static> {
+    TokenValue::number_uint(Proto::NumberNParam {
+        bits,
+        value: Cow::Owned(hex::decode(value).unwrap()),
+    })
+}
+
+fn token(value: TokenValue<'static>) -> Proto::Token<'static> {
+    Proto::Token {
+        name: Cow::default(),
+        token: value,
+    }
+}
+
+#[test]
+fn test_encode_function_erc20_transfer() {
+    let input = Proto::FunctionEncodingInput {
+        function: Cow::Borrowed("function transfer(address to, uint amount)"),
+        params: vec![
+            token(TokenValue::address(Cow::Borrowed(
+                "0x5322b34c88ed0691971bf52a7047448f0f4efc84",
+            ))),
+            token(uint(256, "1bc16d674ec80000")),
+        ],
+    };
+
+    let output = AbiEncoder::encode_function_proto(input);
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.function_signature, "transfer(address,uint256)");
+    assert_eq!(hex::encode(output.encoded, false), TRANSFER_CALL);
+}
+
+#[test]
+fn test_encode_function_dynamic() {
+    // The example from the Solidity ABI specification.
+    let input = Proto::FunctionEncodingInput {
+        function: Cow::Borrowed("f(uint,uint32[],bytes10,bytes)"),
+        params: vec![
+            token(uint(256, "0123")),
+            token(TokenValue::array(Proto::ArrayParam {
+                element_type: Cow::Borrowed("uint32"),
+                elements: vec![token(uint(32, "0456")), token(uint(32, "0789"))],
+            })),
+            token(TokenValue::byte_array_fix(Proto::BytesFixParam {
+                size: 10,
+                value: Cow::Borrowed(b"1234567890"),
+            })),
+            token(TokenValue::byte_array(Cow::Borrowed(b"Hello, world!"))),
+        ],
+    };
+
+    let output = AbiEncoder::encode_function_proto(input);
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        concat!(
+            "8be65246",
+            "0000000000000000000000000000000000000000000000000000000000000123",
+            "0000000000000000000000000000000000000000000000000000000000000080",
+            "3132333435363738393000000000000000000000000000000000000000000000",
+            "00000000000000000000000000000000000000000000000000000000000000e0",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000456",
+            "0000000000000000000000000000000000000000000000000000000000000789",
+            "000000000000000000000000000000000000000000000000000000000000000d",
+            "48656c6c6f2c20776f726c642100000000000000000000000000000000000000",
+        )
+    );
+}
+
+#[test]
+fn test_encode_function_invalid() {
+    let tests = [
+        // Missing argument.
+        ("transfer(address,uint256)", vec![token(uint(256, "01"))]),
+        // Type mismatch.
+        (
+            "transfer(address,uint256)",
+            vec![token(uint(256, "01")), token(uint(256, "01"))],
+        ),
+        // The value doesn't fit the type.
+        ("f(uint8)", vec![token(uint(8, "0100"))]),
+        // Invalid signature.
+        ("f(uint7)", vec![token(uint(8, "01"))]),
+    ];
+    for (function, params) in tests {
+        let input = Proto::FunctionEncodingInput {
+            function: Cow::Borrowed(function),
+            params,
+        };
+        let output = AbiEncoder::encode_function_proto(input);
+        assert_eq!(
+            output.error,
+            SigningError::Error_invalid_params,
+            "{function}"
+        );
+        assert!(output.encoded.is_empty());
+    }
+}
+
+#[test]
+fn test_decode_contract_call() {
+    let input = Proto::ContractCallDecodingInput {
+        encoded: Cow::Owned(hex::decode(TRANSFER_CALL).unwrap()),
+        abi: Cow::Borrowed(ERC20_ABI),
+    };
+
+    let output = AbiEncoder::decode_contract_call_proto(input);
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.function_signature, "transfer(address,uint256)");
+    assert_eq!(output.params.len(), 2);
+
+    assert_eq!(output.params[0].name, "to");
+    match output.params[0].token {
+        TokenValue::address(ref address) => assert_eq!(
+            address.to_lowercase(),
+            "0x5322b34c88ed0691971bf52a7047448f0f4efc84"
+        ),
+        ref other => panic!("Unexpected token: {other:?}"),
+    }
+    assert_eq!(output.params[1].name, "amount");
+    assert_eq!(output.params[1].token, uint(256, "1bc16d674ec80000"));
+}
+
+#[test]
+fn test_decode_contract_call_unknown_function() {
+    let input = Proto::ContractCallDecodingInput {
+        encoded: Cow::Owned(hex::decode(&TRANSFER_CALL.replacen("a9", "b9", 1)).unwrap()),
+        abi: Cow::Borrowed(ERC20_ABI),
+    };
+    let output = AbiEncoder::decode_contract_call_proto(input);
+    assert_eq!(output.error, SigningError::Error_invalid_params);
+    assert!(output.params.is_empty());
+
+    let input = Proto::ContractCallDecodingInput {
+        encoded: Cow::Owned(hex::decode(TRANSFER_CALL).unwrap()),
+        abi: Cow::Borrowed("NOT_A_JSON"),
+    };
+    let output = AbiEncoder::decode_contract_call_proto(input);
+    assert_eq!(output.error, SigningError::Error_input_parse);
+}
+
+#[test]
+fn test_decode_params_types() {
+    let encoded = concat!(
+        "00000000000000000000000000000000000000000000000000000000000000ff",
+        "0000000000000000000000000000000000000000000000000000000000000040",
+        "0000000000000000000000000000000000000000000000000000000000000005",
+        "48656c6c6f000000000000000000000000000000000000000000000000000000",
+    );
+    let input = Proto::ParamsDecodingInput {
+        encoded: Cow::Owned(hex::decode(encoded).unwrap()),
+        abi: DecodingAbi::types(Proto::ParamTypes {
+            types: vec![Cow::Borrowed("int16"), Cow::Borrowed("string")],
+        }),
+    };
+
+    let output = AbiEncoder::decode_params_proto(input);
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(
+        output.params,
+        vec![
+            token(TokenValue::number_int(Proto::NumberNParam {
+                bits: 16,
+                value: Cow::Owned(hex::decode(&encoded[..64]).unwrap()),
+            })),
+            token(TokenValue::string_value(Cow::Borrowed("Hello"))),
+        ]
+    );
+}
+
+#[test]
+fn test_decode_params_function_outputs() {
+    let input = Proto::ParamsDecodingInput {
+        encoded: Cow::Owned(
+            hex::decode("0000000000000000000000000000000000000000000000000000000000000001")
+                .unwrap(),
+        ),
+        abi: DecodingAbi::function_outputs(Cow::Borrowed(
+            "function transfer(address to, uint256 amount) returns (bool success)",
+        )),
+    };
+
+    let output = AbiEncoder::decode_params_proto(input);
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(
+        output.params,
+        vec![Proto::Token {
+            name: Cow::Borrowed("success"),
+            token: TokenValue::boolean(true),
+        }]
+    );
+
+    let output = AbiEncoder::decode_params_proto(Proto::ParamsDecodingInput::default());
+    assert_eq!(output.error, SigningError::Error_invalid_params);
+}
+
+#[test]
+fn test_contract_decode_call_tokens() {
+    let contract = Contract::from_json(ERC20_ABI).unwrap();
+    assert_eq!(contract.functions.len(), 2);
+    assert_eq!(contract.events.len(), 1);
+
+    let (function, params) = contract
+        .decode_call(&hex::decode(TRANSFER_CALL).unwrap())
+        .unwrap();
+    assert_eq!(function.name, "transfer");
+    let tokens: Vec<_> = params.into_iter().map(|param| param.value).collect();
+    assert_eq!(
+        function.encode_input(&tokens).unwrap(),
+        hex::decode(TRANSFER_CALL).unwrap()
+    );
+    assert!(matches!(tokens[1], Token::Uint { bits: 256, .. }));
+}
+
+#[test]
+fn test_selector_and_topic() {
+    let function =
+        Function::from_str("function balanceOf(address owner) view returns (uint256)").unwrap();
+    assert_eq!(hex::encode(function.selector(), false), "70a08231");
+
+    let event =
+        Event::from_str("event Transfer(address indexed from, address indexed to, uint256 value)")
+            .unwrap();
+    assert_eq!(
+        hex::encode(event.topic(), false),
+        "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+    );
+}
@@ -0,0 +1,93 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::ffi::CString;
+use tw_encoding::hex;
+use tw_ethereum::ffi::{ethereum_eip712_hash, ethereum_eip712_sign};
+use tw_proto::Common::Proto::SigningError;
+
+const PERSON_JSON: &str = r#"{
+    "types": {
+        "EIP712Domain": [
+            {"name": "name", "type": "string"},
+            {"name": "version", "type": "string"},
+            {"name": "chainId", "type": "uint256"},
+            {"name": "verifyingContract", "type": "address"}
+        ],
+        "Person": [
+            {"name": "name", "type": "string"},
+            {"name": "wallet", "type": "address"}
+        ]
+    },
+    "primaryType": "Person",
+    "domain": {
+        "name": "Ether Person",
+        "version": "1",
+        "chainId": 0,
+        "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+    },
+    "message": {
+        "name": "Cow",
+        "wallet": "CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+    }
+}"#;
+
+const PRIVATE_KEY: &str = "03a9ca895dca1623c7dfd69693f7b4111f5d819d2e145536e0b03c136025a25d";
+
+#[test]
+fn test_ethereum_eip712_sign() {
+    let json = CString::new(PERSON_JSON).unwrap();
+    let private_key = hex::decode(PRIVATE_KEY).unwrap();
+
+    let signature = unsafe {
+        ethereum_eip712_sign(json.as_ptr(), private_key.as_ptr(), private_key.len())
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(
+        hex::encode(signature, false),
+        "446434e4c34d6b7456e5f07a1b994b88bf85c057234c68d1e10c936b1c85706c4e19147c0ac3a983bc2d56ebfd7146f8b62bcea6114900fe8e7d7351f44bf3761c"
+    );
+}
+
+#[test]
+fn test_ethereum_eip712_hash() {
+    let json = CString::new(PERSON_JSON.replace(r#""chainId": 0"#, r#""chainId": 1"#)).unwrap();
+
+    let hash = unsafe { ethereum_eip712_hash(json.as_ptr()).unwrap().into_vec() };
+    assert_eq!(
+        hex::encode(hash, false),
+        "0b4bb85394b9ebb1c2425e283c9e734a9a7a832622e97c998f77e1c7a3f01a20"
+    );
+}
+
+#[test]
+fn test_ethereum_eip712_sign_invalid() {
+    let json = CString::new(PERSON_JSON).unwrap();
+    let private_key = hex::decode(PRIVATE_KEY).unwrap();
+
+    let zero_key = [0_u8; 32];
+    let res = unsafe { ethereum_eip712_sign(json.as_ptr(), zero_key.as_ptr(), zero_key.len()) };
+    assert_eq!(res.code, SigningError::Error_invalid_private_key as i32);
+
+    let invalid_json = CString::new("NOT_A_JSON").unwrap();
+    let res = unsafe {
+        ethereum_eip712_sign(
+            invalid_json.as_ptr(),
+            private_key.as_ptr(),
+            private_key.len(),
+        )
+    };
+    assert_eq!(res.code, SigningError::Error_input_parse as i32);
+
+    let res = unsafe { ethereum_eip712_hash(std::ptr::null()) };
+    assert_eq!(res.code, SigningError::Error_input_parse as i32);
+
+    let unknown_type =
+        CString::new(PERSON_JSON.replace(r#""type": "address""#, r#""type": "Wallet""#)).unwrap();
+    let res = unsafe { ethereum_eip712_hash(unknown_type.as_ptr()) };
+    assert_eq!(res.code, SigningError::Error_invalid_params as i32);
+}
@@ -0,0 +1,302 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use tw_encoding::hex;
+use tw_ethereum::eip712::TypedData;
+use tw_ethereum::SigningErrorType;
+use tw_hash::sha3::keccak256;
+use tw_keypair::secp256k1::PrivateKey;
+
+const EIP712_CRYPTOFIGHTS: &str = include_str!("eip712_cryptofights.json");
+const EIP712_EMPTY_ARRAY: &str = include_str!("eip712_emptyArray.json");
+const EIP712_EMPTY_STRING: &str = include_str!("eip712_emptyString.json");
+const EIP712_RARIBLE: &str = include_str!("eip712_rarible.json");
+const EIP712_SNAPSHOT_V4: &str = include_str!("eip712_snapshot_v4.json");
+const EIP712_WALLETCONNECT: &str = include_str!("eip712_walletconnect.json");
+
+/// 0x60c2A43Cc69658eC4b02a65A07623D7192166F4e
+const PRIVATE_KEY_OIL_TIMES_12: &str =
+    "b0f20d59451a2fac1be6d458e036adfa5d83ebd4c21f9a76de3c4a3a65671eba";
+
+fn private_key_of(seed: &str) -> PrivateKey {
+    PrivateKey::try_from(keccak256(seed.as_bytes()).as_slice()).unwrap()
+}
+
+fn test_typed_data(
+    json: &str,
+    private: &PrivateKey,
+    expected_hash: &str,
+    expected_signature: &str,
+) {
+    let typed_data = TypedData::from_json(json).unwrap();
+    assert_eq!(
+        hex::encode(typed_data.hash().unwrap(), false),
+        expected_hash
+    );
+    assert_eq!(
+        hex::encode(typed_data.sign(private).unwrap(), false),
+        expected_signature
+    );
+}
+
+#[test]
+fn test_eip712_arrays_of_structs() {
+    let json = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "Person": [
+                {"name": "name", "type": "string"},
+                {"name": "wallets", "type": "address[]"}
+            ],
+            "Mail": [
+                {"name": "from", "type": "Person"},
+                {"name": "to", "type": "Person[]"},
+                {"name": "contents", "type": "string"}
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {
+                "name": "Cow",
+                "wallets": [
+                    "CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+                    "DeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"
+                ]
+            },
+            "to": [
+                {
+                    "name": "Bob",
+                    "wallets": [
+                        "bBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB",
+                        "B0BdaBea57B0BDABeA57b0bdABEA57b0BDabEa57",
+                        "B0B0b0b0b0b0B000000000000000000000000000"
+                    ]
+                }
+            ],
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+    test_typed_data(
+        json,
+        &private_key_of("cow"),
+        "a85c2e2b118698e88db68a8105b794a8cc7cec074e89ef991cb4f5f533819cc2",
+        "65cbd956f2fae28a601bebc9b906cea0191744bd4c4247bcd27cd08f8eb6b71c78efdf7a31dc9abee78f492292721f362d296cf86b4538e07b51303b67f749061b",
+    );
+}
+
+#[test]
+fn test_eip712_recursive_struct() {
+    // Missing `mother` and `father` structs are encoded as zero words.
+    let json = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "Person": [
+                {"name": "name", "type": "string"},
+                {"name": "mother", "type": "Person"},
+                {"name": "father", "type": "Person"}
+            ]
+        },
+        "primaryType": "Person",
+        "domain": {
+            "name": "Family Tree",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "name": "Jon",
+            "mother": {
+                "name": "Lyanna",
+                "father": {"name": "Rickard"}
+            },
+            "father": {
+                "name": "Rhaegar",
+                "father": {"name": "Aeris II"}
+            }
+        }
+    }"#;
+    test_typed_data(
+        json,
+        &private_key_of("dragon"),
+        "807773b9faa9879d4971b43856c4d60c2da15c6f8c062bd9d33afefb756de19c",
+        "f2ec61e636ff7bb3ac8bc2a4cc2c8b8f635dd1b2ec8094c963128b358e79c85c5ca6dd637ed7e80f0436fe8fce39c0e5f2082c9517fe677cc2917dcd6c84ba881c",
+    );
+}
+
+#[test]
+fn test_eip712_fixed_size_arrays() {
+    let json = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "chainId", "type": "uint256"}
+            ],
+            "Board": [
+                {"name": "cells", "type": "int8[3][2]"},
+                {"name": "owners", "type": "address[2]"}
+            ]
+        },
+        "primaryType": "Board",
+        "domain": {"name": "Tic Tac Toe", "chainId": "0x1"},
+        "message": {
+            "cells": [[1, -1, 0], ["-1", "1", "0"]],
+            "owners": [
+                "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+                "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+            ]
+        }
+    }"#;
+    let typed_data = TypedData::from_json(json).unwrap();
+    assert_eq!(
+        hex::encode(typed_data.hash().unwrap(), false),
+        "23cc412aadfba94b69f875da8906fcf1f861045f96b5ba63fc80343617cebf88"
+    );
+
+    // A fixed-size array must have the declared number of elements.
+    let json = json.replace(r#"["-1", "1", "0"]"#, r#"["-1", "1"]"#);
+    let err = TypedData::from_json(&json).unwrap().hash().unwrap_err();
+    assert_eq!(err.error, SigningErrorType::Error_invalid_params);
+}
+
+#[test]
+fn test_eip712_domain_primary_type() {
+    let json = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ]
+        },
+        "primaryType": "EIP712Domain",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {}
+    }"#;
+    let typed_data = TypedData::from_json(json).unwrap();
+    assert_eq!(
+        hex::encode(typed_data.hash().unwrap(), false),
+        hex::encode(
+            keccak256(
+                &hex::decode(
+                    "1901f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+                )
+                .unwrap()
+            ),
+            false
+        )
+    );
+}
+
+#[test]
+fn test_eip712_empty_string() {
+    test_typed_data(
+        EIP712_EMPTY_STRING,
+        &PrivateKey::try_from(PRIVATE_KEY_OIL_TIMES_12).unwrap(),
+        "bc9d33285c5e42b00571f5deaf9636d2e498a6fa50e0d1be81095bded070117a",
+        "5df6cb46d874bc0acc519695f393008a837ca9d2e316836b669b8f0de767363854cc0bcc0ad657f9222f7e7be3fbe0ec4a8edb9385c39d578dfac8d38727af121c",
+    );
+}
+
+#[test]
+fn test_eip712_empty_array() {
+    test_typed_data(
+        EIP712_EMPTY_ARRAY,
+        &PrivateKey::try_from(PRIVATE_KEY_OIL_TIMES_12).unwrap(),
+        "9f1a1bc718e966d683c544aef6fd0b73c85a1d6244af9b64bb8f4a6fa6716086",
+        "de47efd592493f7189d44f071424ecb24b50d80750d3bd2bb6fc80451c13a52f202b8a2be1ef3c466853e8cd5275a6af15b11e7e1cc0ae4a7e249bc9bad591eb1c",
+    );
+}
+
+#[test]
+fn test_eip712_walletconnect() {
+    test_typed_data(
+        EIP712_WALLETCONNECT,
+        &PrivateKey::try_from(PRIVATE_KEY_OIL_TIMES_12).unwrap(),
+        "abc79f527273b9e7bca1b3f1ac6ad1a8431fa6dc34ece900deabcd6969856b5e",
+        "e9c1ce1307593c378c7e38e8aa00dfb42b5a1ce543b59a138a12f29bd7fea75c3fe71ef91c37abea29fe14b5f0de805f924af19d71bcef09e74aef2f0ccdf52a1c",
+    );
+}
+
+#[test]
+fn test_eip712_cryptofights() {
+    test_typed_data(
+        EIP712_CRYPTOFIGHTS,
+        &PrivateKey::try_from(PRIVATE_KEY_OIL_TIMES_12).unwrap(),
+        "db12328a6d193965801548e1174936c3aa7adbe1b54b3535a3c905bd4966467c",
+        "9e26bdf0d113a72805acb1c2c8b0734d264290fd1cfbdf5e6502ae65a2f2bd8311512c15ad0833fd457ae5dd59c3bcb3d03f35b3d33c1c5a575852163db423691b",
+    );
+}
+
+#[test]
+fn test_eip712_rarible() {
+    test_typed_data(
+        EIP712_RARIBLE,
+        &private_key_of("cow"),
+        "df0200de55c05eb55af2597012767ea3af653d68000be49580f8e05acd91d366",
+        "9e6155c62a55d3dc6034973d93821dace5a0c66bfbd8413ad29205c2fb079e843ca5906f24b82672304302a0e42e5dc090acc800060bad51fb81cc4469f699301b",
+    );
+}
+
+#[test]
+fn test_eip712_snapshot() {
+    test_typed_data(
+        EIP712_SNAPSHOT_V4,
+        &PrivateKey::try_from(PRIVATE_KEY_OIL_TIMES_12).unwrap(),
+        "f558d08ad4a7651dbc9ec028cfcb4a8e6878a249073ef4fa694f85ee95f61c0f",
+        "9da563ffcafe9fa8809540ebcc4bcf8bbc26874e192f430432e06547593e8681164808603aca259775bdf511124b58651f1b3ce9ccbcd5a8d63df02e2359bb8b1b",
+    );
+}
+
+#[test]
+fn test_eip712_invalid_document() {
+    for json in [
+        "NOT_A_JSON",
+        "",
+        "0",
+        "[]",
+        "{}",
+        r#"{"domain": {}, "message": {}, "types": {}}"#,
+        r#"{"primaryType": [], "domain": {}, "message": {}, "types": {}}"#,
+        r#"{"primaryType": "v1", "message": {}, "types": {}}"#,
+        r#"{"primaryType": "v1", "domain": "vDomain", "message": {}, "types": {}}"#,
+        r#"{"primaryType": "v1", "domain": {}, "message": "v2", "types": {}}"#,
+        r#"{"primaryType": "v1", "domain": {}, "message": {}, "types": "vTypes"}"#,
+    ] {
+        let err = TypedData::from_json(json).unwrap_err();
+        assert_eq!(err.error, SigningErrorType::Error_input_parse, "{json}");
+    }
+
+    for json in [
+        r#"{"primaryType": "v1", "domain": {}, "message": {}, "types": {}}"#,
+        r#"{"primaryType": "v1", "domain": {}, "message": {}, "types": {"EIP712Domain": [{"name": "param", "type": "type"}]}}"#,
+        r#"{"primaryType": "v1", "domain": {"param": "val"}, "message": {}, "types": {"EIP712Domain": [{"name": "param", "type": "string"}]}}"#,
+    ] {
+        let err = TypedData::from_json(json).unwrap().hash().unwrap_err();
+        assert_eq!(err.error, SigningErrorType::Error_invalid_params, "{json}");
+    }
+}
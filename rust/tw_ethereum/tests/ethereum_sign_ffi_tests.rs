@@ -0,0 +1,61 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::borrow::Cow;
+use tw_encoding::hex;
+use tw_ethereum::ffi::ethereum_sign;
+use tw_proto::Common::Proto::SigningError;
+use tw_proto::Ethereum::Proto;
+use tw_proto::Ethereum::Proto::mod_Transaction::{self as tx, OneOftransaction_oneof as Tx};
+
+fn ethereum_sign_impl(input: &[u8]) -> Vec<u8> {
+    unsafe { ethereum_sign(input.as_ptr(), input.len()).into_vec() }
+}
+
+#[test]
+fn test_ethereum_sign() {
+    // The example from EIP-155.
+    let input = Proto::SigningInput {
+        chain_id: Cow::Owned(vec![1]),
+        nonce: Cow::Owned(vec![9]),
+        gas_price: Cow::Owned(hex::decode("04a817c800").unwrap()),
+        gas_limit: Cow::Owned(hex::decode("5208").unwrap()),
+        to_address: Cow::Borrowed("0x3535353535353535353535353535353535353535"),
+        private_key: Cow::Owned(
+            hex::decode("4646464646464646464646464646464646464646464646464646464646464646")
+                .unwrap(),
+        ),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Tx::transfer(tx::Transfer {
+                amount: Cow::Owned(hex::decode("0de0b6b3a7640000").unwrap()),
+                data: Cow::default(),
+            }),
+        }),
+        ..Proto::SigningInput::default()
+    };
+    let input = tw_proto::serialize(&input).unwrap();
+
+    let output = ethereum_sign_impl(&input);
+    let output: Proto::SigningOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+    );
+}
+
+#[test]
+fn test_ethereum_sign_invalid_input() {
+    let output = ethereum_sign_impl(&[0xff, 0xff]);
+    let output: Proto::SigningOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::Error_input_parse);
+    assert!(output.encoded.is_empty());
+
+    // An empty input is deserialized as a default `SigningInput` without a private key.
+    let output = unsafe { ethereum_sign(std::ptr::null(), 0).into_vec() };
+    let output: Proto::SigningOutput = tw_proto::deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningError::Error_invalid_private_key);
+}
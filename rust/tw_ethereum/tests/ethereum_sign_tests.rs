@@ -0,0 +1,395 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::borrow::Cow;
+use tw_encoding::hex;
+use tw_ethereum::signer::Signer;
+use tw_ethereum::SigningErrorType;
+use tw_hash::U256;
+use tw_proto::Ethereum::Proto;
+use tw_proto::Ethereum::Proto::mod_Transaction::{self as tx, OneOftransaction_oneof as Tx};
+
+const ERC20_PRIVATE_KEY: &str = "608dcb1742bb3fb7aec002074e3420e4fab7d00cced79ccdac53ed5b27138151";
+const DAI_CONTRACT: &str = "0x6b175474e89094c44da98b954eedeac495271d0f";
+const TOKEN_CONTRACT: &str = "0x4e45e92ed38f885d39a733c14f1817217a89d425";
+const FROM_ADDRESS: &str = "0x718046867b5b1782379a14eA4fc0c9b724DA94Fc";
+const TO_ADDRESS: &str = "0x5322b34c88ed0691971bf52a7047448f0f4efc84";
+
+fn u256(value: u64) -> Cow<'static, [u8]> {
+    Cow::Owned(U256::from(value).to_big_endian_compact())
+}
+
+fn bytes(hex: &str) -> Cow<'static, [u8]> {
+    Cow::Owned(hex::decode(hex).unwrap())
+}
+
+fn transaction(transaction_oneof: Tx<'static>) -> Option<Proto::Transaction<'static>> {
+    Some(Proto::Transaction { transaction_oneof })
+}
+
+/// Legacy transaction with a DAI token call, see the `SignERC20*` tests.
+fn erc20_legacy_input(transaction_oneof: Tx<'static>) -> Proto::SigningInput<'static> {
+    Proto::SigningInput {
+        chain_id: u256(1),
+        nonce: u256(0),
+        gas_price: u256(42_000_000_000),
+        gas_limit: u256(78009),
+        to_address: Cow::Borrowed(DAI_CONTRACT),
+        private_key: bytes(ERC20_PRIVATE_KEY),
+        transaction: transaction(transaction_oneof),
+        ..Proto::SigningInput::default()
+    }
+}
+
+/// The same as [`erc20_legacy_input`] but EIP-1559.
+fn erc20_eip1559_input(transaction_oneof: Tx<'static>) -> Proto::SigningInput<'static> {
+    Proto::SigningInput {
+        tx_mode: Proto::TransactionMode::Enveloped,
+        gas_price: Cow::default(),
+        max_inclusion_fee_per_gas: u256(2_000_000_000),
+        max_fee_per_gas: u256(3_000_000_000),
+        ..erc20_legacy_input(transaction_oneof)
+    }
+}
+
+fn erc20_transfer() -> Tx<'static> {
+    Tx::erc20_transfer(tx::ERC20Transfer {
+        to: Cow::Borrowed(TO_ADDRESS),
+        amount: u256(2_000_000_000_000_000_000),
+    })
+}
+
+fn erc20_approve() -> Tx<'static> {
+    Tx::erc20_approve(tx::ERC20Approve {
+        spender: Cow::Borrowed(TO_ADDRESS),
+        amount: u256(2_000_000_000_000_000_000),
+    })
+}
+
+fn erc721_transfer() -> Tx<'static> {
+    Tx::erc721_transfer(tx::ERC721Transfer {
+        from: Cow::Borrowed(FROM_ADDRESS),
+        to: Cow::Borrowed(TO_ADDRESS),
+        token_id: bytes("23c47ee5"),
+    })
+}
+
+fn sign(input: Proto::SigningInput<'_>) -> Proto::SigningOutput<'static> {
+    let output = Signer::sign_proto(input);
+    assert_eq!(
+        output.error,
+        SigningErrorType::OK,
+        "{}",
+        output.error_message
+    );
+    output
+}
+
+#[test]
+fn test_sign_legacy_eip155_example() {
+    let input = Proto::SigningInput {
+        chain_id: u256(1),
+        nonce: u256(9),
+        gas_price: u256(20_000_000_000),
+        gas_limit: u256(21000),
+        to_address: Cow::Borrowed("0x3535353535353535353535353535353535353535"),
+        private_key: bytes("4646464646464646464646464646464646464646464646464646464646464646"),
+        transaction: transaction(Tx::transfer(tx::Transfer {
+            amount: u256(1_000_000_000_000_000_000),
+            data: Cow::default(),
+        })),
+        ..Proto::SigningInput::default()
+    };
+
+    let output = sign(input);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+    );
+    assert_eq!(hex::encode(output.v, false), "25");
+    assert_eq!(
+        U256::from_big_endian_slice(&output.r).unwrap().to_string(),
+        "18515461264373351373200002665853028612451056578545711640558177340181847433846"
+    );
+    assert_eq!(
+        U256::from_big_endian_slice(&output.s).unwrap().to_string(),
+        "46948507304638947509940763649030358759909902576025900602547168820602576006531"
+    );
+    assert!(output.data.is_empty());
+}
+
+#[test]
+fn test_sign_legacy_erc20_transfer() {
+    // https://etherscan.io/tx/0x199a7829fc5149e49b452c2cab76d8fa5a9682fee6e4891b8acb697ac142513e
+    let expected = "f8aa808509c7652400830130b9946b175474e89094c44da98b954eedeac495271d0f80b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec8000025a0724c62ad4fbf47346b02de06e603e013f26f26b56fdc0be7ba3d6273401d98cea0032131cae15da7ddcda66963e8bef51ca0d9962bfef0547d3f02597a4a58c931";
+    let payload = "a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000";
+
+    let output = sign(erc20_legacy_input(erc20_transfer()));
+    assert_eq!(hex::encode(output.encoded, false), expected);
+    assert_eq!(hex::encode(output.data, false), payload);
+
+    // The same call data as a generic contract call.
+    let output = sign(erc20_legacy_input(Tx::contract_generic(
+        tx::ContractGeneric {
+            amount: Cow::default(),
+            data: bytes(payload),
+        },
+    )));
+    assert_eq!(hex::encode(output.encoded, false), expected);
+}
+
+#[test]
+fn test_sign_legacy_erc20_approve() {
+    let output = sign(erc20_legacy_input(erc20_approve()));
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "f8aa808509c7652400830130b9946b175474e89094c44da98b954eedeac495271d0f80b844095ea7b30000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec8000025a0d8136d66da1e0ba8c7208d5c4f143167f54b89a0fe2e23440653bcca28b34dc1a049222a79339f1a9e4641cb4ad805c49c225ae704299ffc10627bf41c035c464a"
+    );
+}
+
+#[test]
+fn test_sign_legacy_erc721_transfer() {
+    let input = Proto::SigningInput {
+        to_address: Cow::Borrowed(TOKEN_CONTRACT),
+        ..erc20_legacy_input(erc721_transfer())
+    };
+
+    let output = sign(input);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "f8ca808509c7652400830130b9944e45e92ed38f885d39a733c14f1817217a89d42580b86423b872dd000000000000000000000000718046867b5b1782379a14ea4fc0c9b724da94fc0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000000000000023c47ee526a0d38440a4dc140a4100d301eb49fcc35b64439e27d1d8dd9b55823dca04e6e659a03b5f56a57feabc3406f123d6f8198cd7d7e2ced7e2d58d375f076952ecd9ce88"
+    );
+}
+
+#[test]
+fn test_sign_legacy_erc1155_transfer() {
+    let transfer = Tx::erc1155_transfer(tx::ERC1155Transfer {
+        from: Cow::Borrowed(FROM_ADDRESS),
+        to: Cow::Borrowed(TO_ADDRESS),
+        token_id: bytes("23c47ee5"),
+        value: u256(2_000_000_000_000_000_000),
+        data: bytes("01020304"),
+    });
+    let input = Proto::SigningInput {
+        to_address: Cow::Borrowed(TOKEN_CONTRACT),
+        ..erc20_legacy_input(transfer)
+    };
+
+    let output = sign(input);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "f9014a808509c7652400830130b9944e45e92ed38f885d39a733c14f1817217a89d42580b8e4f242432a000000000000000000000000718046867b5b1782379a14ea4fc0c9b724da94fc0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000000000000023c47ee50000000000000000000000000000000000000000000000001bc16d674ec8000000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000004010203040000000000000000000000000000000000000000000000000000000026a010315488201ac801ce346bffd1570de147615462d7e7db3cf08cf558465c6b79a06643943b24593bc3904a9fda63bb169881730994c973ab80f07d66a698064573"
+    );
+}
+
+#[test]
+fn test_sign_eip1559_transfer() {
+    let input = Proto::SigningInput {
+        chain_id: u256(3),
+        nonce: u256(6),
+        tx_mode: Proto::TransactionMode::Enveloped,
+        gas_limit: u256(21100),
+        max_inclusion_fee_per_gas: u256(2_000_000_000),
+        max_fee_per_gas: u256(3_000_000_000),
+        to_address: Cow::Borrowed("0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7"),
+        private_key: bytes("4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904"),
+        transaction: transaction(Tx::transfer(tx::Transfer {
+            amount: u256(543_210_987_654_321),
+            data: Cow::default(),
+        })),
+        ..Proto::SigningInput::default()
+    };
+
+    // https://ropsten.etherscan.io/tx/0x14429509307efebfdaa05227d84c147450d168c68539351fbc01ed87c916ab2e
+    let output = sign(input);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "02f8710306847735940084b2d05e0082526c94b9f5771c27664bf2282d98e09d7f50cec7cb01a78701ee0c29f50cb180c080a092c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c64a06487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e58"
+    );
+    assert_eq!(hex::encode(output.v, false), "00");
+    assert_eq!(
+        hex::encode(output.r, false),
+        "92c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c64"
+    );
+    assert_eq!(
+        hex::encode(output.s, false),
+        "6487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e58"
+    );
+    assert!(output.data.is_empty());
+}
+
+#[test]
+fn test_sign_eip1559_erc20_transfer() {
+    let output = sign(erc20_eip1559_input(erc20_transfer()));
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "02f8b00180847735940084b2d05e00830130b9946b175474e89094c44da98b954eedeac495271d0f80b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000c080a0adfcfdf98d4ed35a8967a0c1d78b42adb7c5d831cf5a3272654ec8f8bcd7be2ea011641e065684f6aa476f4fd250aa46cd0b44eccdb0a6e1650d658d1998684cdf"
+    );
+}
+
+#[test]
+fn test_sign_eip1559_erc20_approve() {
+    let output = sign(erc20_eip1559_input(erc20_approve()));
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "02f8b00180847735940084b2d05e00830130b9946b175474e89094c44da98b954eedeac495271d0f80b844095ea7b30000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000c080a05a43dda3dc193480ee532a5ed67ba8fbd2e3afb9eee218f4fb955b415d592925a01300e5b5f51c8cd5bf80f018cea3fb347fae589e65355068ac44ffc996313c60"
+    );
+}
+
+fn dai_access_list() -> Vec<Proto::Access<'static>> {
+    vec![Proto::Access {
+        address: Cow::Borrowed(DAI_CONTRACT),
+        stored_keys: vec![
+            bytes("0000000000000000000000000000000000000000000000000000000000000003"),
+            bytes("0000000000000000000000000000000000000000000000000000000000000007"),
+        ],
+    }]
+}
+
+/// Expected values are generated by `alloy-consensus`.
+#[test]
+fn test_sign_eip2930_erc20_transfer() {
+    let input = Proto::SigningInput {
+        tx_mode: Proto::TransactionMode::AccessList,
+        access_list: dai_access_list(),
+        ..erc20_legacy_input(erc20_transfer())
+    };
+
+    let output = sign(input);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "01f9010801808509c7652400830130b9946b175474e89094c44da98b954eedeac495271d0f80b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000f85bf859946b175474e89094c44da98b954eedeac495271d0ff842a00000000000000000000000000000000000000000000000000000000000000003a0000000000000000000000000000000000000000000000000000000000000000780a068c1ffa215bce55c5ab1cc353c2f3c72e038ab483028d1ffbcaef9f0e5f5c5b8a00a4af9e3028689be8d0ce9795668fcc7c78ca7753cc3de20d6819c4f8ba8e761"
+    );
+    assert_eq!(hex::encode(output.v, false), "00");
+}
+
+/// Expected values are generated by `alloy-consensus`.
+#[test]
+fn test_sign_eip1559_with_access_list() {
+    let input = Proto::SigningInput {
+        access_list: dai_access_list(),
+        ..erc20_eip1559_input(erc20_transfer())
+    };
+
+    let output = sign(input);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "02f9010c0180847735940084b2d05e00830130b9946b175474e89094c44da98b954eedeac495271d0f80b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000f85bf859946b175474e89094c44da98b954eedeac495271d0ff842a00000000000000000000000000000000000000000000000000000000000000003a0000000000000000000000000000000000000000000000000000000000000000701a0d171b1d7961c96b3da1c568f27be3fbd6ef2f7e8389a8bce169ba180d7c4e812a0390a9b7423e7eb64044ff59ffba7ca8a5dcdcadb664604ce0a3b175ee84a66b3"
+    );
+    assert_eq!(hex::encode(output.v, false), "01");
+}
+
+fn blob_input() -> Proto::SigningInput<'static> {
+    Proto::SigningInput {
+        chain_id: u256(1),
+        nonce: u256(5),
+        tx_mode: Proto::TransactionMode::Blob,
+        gas_limit: u256(21000),
+        max_inclusion_fee_per_gas: u256(2_000_000_000),
+        max_fee_per_gas: u256(3_000_000_000),
+        max_fee_per_blob_gas: u256(1_000_000_000),
+        blob_versioned_hashes: vec![
+            bytes("0101010101010101010101010101010101010101010101010101010101010101"),
+            bytes("01b0a4cdd5f55589f5c5b4d46c76704bb6ce95c0a8c09f77f197a57808dded28"),
+        ],
+        to_address: Cow::Borrowed(TO_ADDRESS),
+        private_key: bytes(ERC20_PRIVATE_KEY),
+        transaction: transaction(Tx::transfer(tx::Transfer {
+            amount: u256(1_000_000_000_000_000),
+            data: Cow::default(),
+        })),
+        ..Proto::SigningInput::default()
+    }
+}
+
+/// Expected values are generated by `alloy-consensus`.
+#[test]
+fn test_sign_eip4844_transfer() {
+    let output = sign(blob_input());
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "03f8ba0105847735940084b2d05e00825208945322b34c88ed0691971bf52a7047448f0f4efc8487038d7ea4c6800080c0843b9aca00f842a00101010101010101010101010101010101010101010101010101010101010101a001b0a4cdd5f55589f5c5b4d46c76704bb6ce95c0a8c09f77f197a57808dded2801a084ed2dbe146a8a6beffe4fa2f9104d5dc35895602aeb4adb6afabd99da716b3ca0712eb3d2bf4413d41f093bf93afc031b250b1cdfdcaa9368b5b0c9ae62b73a7c"
+    );
+}
+
+fn test_sign_error_impl(input: Proto::SigningInput<'_>, expected: SigningErrorType) {
+    let output = Signer::sign_proto(input);
+    assert_eq!(output.error, expected, "{}", output.error_message);
+    assert!(!output.error_message.is_empty());
+    assert!(output.encoded.is_empty());
+}
+
+#[test]
+fn test_sign_invalid_input() {
+    let invalid_to = Tx::erc20_transfer(tx::ERC20Transfer {
+        to: Cow::Borrowed("0xdeadbeef"),
+        amount: u256(1),
+    });
+    test_sign_error_impl(
+        erc20_legacy_input(invalid_to),
+        SigningErrorType::Error_invalid_address,
+    );
+
+    let input = Proto::SigningInput {
+        to_address: Cow::Borrowed("0xdeadbeef"),
+        ..erc20_legacy_input(erc20_transfer())
+    };
+    test_sign_error_impl(input, SigningErrorType::Error_invalid_address);
+
+    let input = Proto::SigningInput {
+        private_key: Cow::default(),
+        ..erc20_legacy_input(erc20_transfer())
+    };
+    test_sign_error_impl(input, SigningErrorType::Error_invalid_private_key);
+
+    let input = Proto::SigningInput {
+        transaction: None,
+        ..erc20_legacy_input(erc20_transfer())
+    };
+    test_sign_error_impl(input, SigningErrorType::Error_invalid_params);
+
+    let input = Proto::SigningInput {
+        gas_price: Cow::Owned(vec![1; 33]),
+        ..erc20_legacy_input(erc20_transfer())
+    };
+    test_sign_error_impl(input, SigningErrorType::Error_invalid_params);
+
+    let input = Proto::SigningInput {
+        tx_mode: Proto::TransactionMode::UserOp,
+        ..erc20_legacy_input(erc20_transfer())
+    };
+    test_sign_error_impl(input, SigningErrorType::Error_invalid_params);
+}
+
+#[test]
+fn test_sign_eip4844_invalid_input() {
+    let input = Proto::SigningInput {
+        blob_versioned_hashes: Vec::new(),
+        ..blob_input()
+    };
+    test_sign_error_impl(input, SigningErrorType::Error_invalid_params);
+
+    let input = Proto::SigningInput {
+        blob_versioned_hashes: vec![bytes(
+            "0201010101010101010101010101010101010101010101010101010101010101",
+        )],
+        ..blob_input()
+    };
+    test_sign_error_impl(input, SigningErrorType::Error_invalid_params);
+
+    let input = Proto::SigningInput {
+        blob_versioned_hashes: vec![bytes("0101")],
+        ..blob_input()
+    };
+    test_sign_error_impl(input, SigningErrorType::Error_invalid_params);
+
+    // Blob transactions cannot create contracts.
+    let input = Proto::SigningInput {
+        to_address: Cow::default(),
+        ..blob_input()
+    };
+    test_sign_error_impl(input, SigningErrorType::Error_invalid_address);
+}
@@ -0,0 +1,98 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::borrow::Cow;
+use tw_encoding::hex;
+use tw_ethereum::ffi::ethereum_rlp_encode;
+use tw_proto::Common::Proto::SigningError;
+use tw_proto::EthereumRlp::Proto::{
+    mod_RlpItem::OneOfitem as Item, EncodingInput, EncodingOutput, RlpItem, RlpList,
+};
+
+fn item(item: Item<'static>) -> RlpItem<'static> {
+    RlpItem { item }
+}
+
+fn encode_rlp_impl(input: &EncodingInput) -> EncodingOutput<'static> {
+    let input = tw_proto::serialize(input).unwrap();
+    let output = unsafe { ethereum_rlp_encode(input.as_ptr(), input.len()).into_vec() };
+    let output: EncodingOutput = tw_proto::deserialize(&output).unwrap();
+    EncodingOutput {
+        encoded: Cow::Owned(output.encoded.to_vec()),
+        error: output.error,
+        error_message: Cow::Owned(output.error_message.to_string()),
+    }
+}
+
+#[test]
+fn test_encode_rlp_legacy_transaction() {
+    // Unsigned legacy transaction from EIP-155.
+    let wei = hex::decode("0de0b6b3a7640000").unwrap();
+    let items = vec![
+        item(Item::number_u64(9)),
+        item(Item::number_u64(20_000_000_000)),
+        item(Item::number_u64(21000)),
+        item(Item::address(Cow::Borrowed(
+            "0x3535353535353535353535353535353535353535",
+        ))),
+        item(Item::number_u256(Cow::Owned(wei))),
+        item(Item::data(Cow::Borrowed(&[]))),
+        item(Item::number_u64(1)),
+        item(Item::number_u64(0)),
+        item(Item::number_u64(0)),
+    ];
+    let input = EncodingInput {
+        item: Some(item(Item::list(RlpList { items }))),
+    };
+
+    let output = encode_rlp_impl(&input);
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(
+        hex::encode(output.encoded, false),
+        "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080"
+    );
+}
+
+#[test]
+fn test_encode_rlp_nested_list() {
+    let nested = RlpList {
+        items: vec![
+            item(Item::string_item(Cow::Borrowed("dog"))),
+            item(Item::raw_encoded(Cow::Borrowed(&[0xc0]))),
+        ],
+    };
+    let input = EncodingInput {
+        item: Some(item(Item::list(RlpList {
+            items: vec![
+                item(Item::string_item(Cow::Borrowed("cat"))),
+                item(Item::list(nested)),
+            ],
+        }))),
+    };
+
+    let output = encode_rlp_impl(&input);
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(hex::encode(output.encoded, false), "ca83636174c583646f67c0");
+}
+
+#[test]
+fn test_encode_rlp_invalid_address() {
+    let input = EncodingInput {
+        item: Some(item(Item::address(Cow::Borrowed("0x3535")))),
+    };
+
+    let output = encode_rlp_impl(&input);
+    assert_eq!(output.error, SigningError::Error_invalid_address);
+    assert!(output.encoded.is_empty());
+    assert!(!output.error_message.is_empty());
+}
+
+#[test]
+fn test_encode_rlp_no_item() {
+    let output = encode_rlp_impl(&EncodingInput::default());
+    assert_eq!(output.error, SigningError::Error_invalid_params);
+    assert!(output.encoded.is_empty());
+}
@@ -4,7 +4,11 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
+use crate::hasher::Hasher;
 use blake_hash::{Blake256, Digest};
+use digest::consts::{U32, U64};
+use digest::core_api::BlockSizeUser;
+use digest::{FixedOutput, HashMarker, Output, OutputSizeUser};
 
 pub fn blake_256(input: &[u8]) -> Vec<u8> {
     let mut hasher = Blake256::new();
@@ -12,3 +16,56 @@ pub fn blake_256(input: &[u8]) -> Vec<u8> {
     let result = &hasher.finalize()[..];
     result.to_vec()
 }
+
+/// Computes the double Blake-256 hash: `blake_256(blake_256(input))`.
+pub fn blake_256d(input: &[u8]) -> Vec<u8> {
+    blake_256(&blake_256(input))
+}
+
+/// Blake-256 incremental hasher.
+/// Please note `blake-hash` is based on `digest 0.9`, so [`DigestHasher`](crate::hasher::DigestHasher) can't be used.
+#[derive(Default)]
+pub struct Blake256Hasher {
+    digest: Blake256,
+}
+
+impl Hasher for Blake256Hasher {
+    fn output_len(&self) -> usize {
+        Blake256::output_size()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(&mut self.digest, data);
+    }
+
+    fn finalize(&mut self) -> Vec<u8> {
+        self.digest.finalize_reset().to_vec()
+    }
+
+    fn reset(&mut self) {
+        Digest::reset(&mut self.digest);
+    }
+}
+
+/// `digest 0.10` implementation that allows to use Blake-256 with [`hmac`](crate::hmac).
+impl HashMarker for Blake256Hasher {}
+
+impl OutputSizeUser for Blake256Hasher {
+    type OutputSize = U32;
+}
+
+impl BlockSizeUser for Blake256Hasher {
+    type BlockSize = U64;
+}
+
+impl digest::Update for Blake256Hasher {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(&mut self.digest, data);
+    }
+}
+
+impl FixedOutput for Blake256Hasher {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&Digest::finalize(self.digest));
+    }
+}
@@ -4,12 +4,19 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
+use crate::hasher::Hasher;
 use crate::Error;
-use blake2b_ref::Blake2bBuilder;
+use blake2b_ref::{Blake2b, Blake2bBuilder};
 use std::ops::RangeInclusive;
 
 const OUTPUT_HASH_LEN_RANGE: RangeInclusive<usize> = 1..=64;
 const PERSONAL_INPUT_MAX_LEN: usize = 16;
+const KEY_MAX_LEN: usize = 64;
+const SALT_MAX_LEN: usize = 16;
+
+const BLAKE2S_OUTPUT_HASH_LEN_RANGE: RangeInclusive<usize> = 1..=32;
+const BLAKE2S_KEY_MAX_LEN: usize = 32;
+const BLAKE2S_256_LEN: usize = 32;
 
 pub fn blake2_b(input: &[u8], hash_size: usize) -> Result<Vec<u8>, Error> {
     if !OUTPUT_HASH_LEN_RANGE.contains(&hash_size) {
@@ -44,9 +51,132 @@ pub fn blake2_b_personal(
     Ok(output)
 }
 
+/// Computes the keyed BLAKE2B hash (MAC mode) of the `input` byte array.
+/// The `key` must be at most 64 bytes long, an empty key falls back to [`blake2_b`].
+pub fn blake2_b_keyed(input: &[u8], hash_size: usize, key: &[u8]) -> Result<Vec<u8>, Error> {
+    blake2_b_salted(input, hash_size, key, &[], &[])
+}
+
+/// Computes the BLAKE2B hash of the `input` byte array with all the optional parameters.
+/// Each of `key`, `salt` (up to 16 bytes) and `personal_input` (up to 16 bytes) may be empty.
+pub fn blake2_b_salted(
+    input: &[u8],
+    hash_size: usize,
+    key: &[u8],
+    salt: &[u8],
+    personal_input: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if !OUTPUT_HASH_LEN_RANGE.contains(&hash_size) {
+        return Err(Error::InvalidHashLength);
+    }
+    if key.len() > KEY_MAX_LEN {
+        return Err(Error::InvalidKeyLength);
+    }
+    if salt.len() > SALT_MAX_LEN {
+        return Err(Error::InvalidSaltLength);
+    }
+    if personal_input.len() > PERSONAL_INPUT_MAX_LEN {
+        return Err(Error::InvalidArgument);
+    }
+
+    let mut output: Vec<u8> = vec![0; hash_size];
+    let mut blake2b = Blake2bBuilder::new(hash_size)
+        .key(key)
+        .salt(salt)
+        .personal(personal_input)
+        .build();
+    blake2b.update(input);
+    blake2b.finalize(&mut output);
+    Ok(output)
+}
+
+/// Computes the BLAKE2S-256 hash of the `input` byte array.
+pub fn blake2_s_256(input: &[u8]) -> Vec<u8> {
+    blake2s_simd::Params::new()
+        .hash_length(BLAKE2S_256_LEN)
+        .hash(input)
+        .as_bytes()
+        .to_vec()
+}
+
+/// Computes the keyed BLAKE2S hash (MAC mode) of the `input` byte array.
+/// The `key` must be at most 32 bytes long.
+pub fn blake2_s_keyed(input: &[u8], hash_size: usize, key: &[u8]) -> Result<Vec<u8>, Error> {
+    if !BLAKE2S_OUTPUT_HASH_LEN_RANGE.contains(&hash_size) {
+        return Err(Error::InvalidHashLength);
+    }
+    if key.len() > BLAKE2S_KEY_MAX_LEN {
+        return Err(Error::InvalidKeyLength);
+    }
+
+    let hash = blake2s_simd::Params::new()
+        .hash_length(hash_size)
+        .key(key)
+        .hash(input);
+    Ok(hash.as_bytes().to_vec())
+}
+
+/// BLAKE2B incremental hasher.
+pub struct Blake2bHasher {
+    hash_size: usize,
+    personal: Vec<u8>,
+    state: Blake2b,
+}
+
+impl Blake2bHasher {
+    /// Creates a BLAKE2B hasher with the given output `hash_size`.
+    pub fn new(hash_size: usize) -> Result<Blake2bHasher, Error> {
+        Self::with_personal(hash_size, &[])
+    }
+
+    /// Creates a personalized BLAKE2B hasher with the given output `hash_size`.
+    pub fn with_personal(hash_size: usize, personal: &[u8]) -> Result<Blake2bHasher, Error> {
+        if !OUTPUT_HASH_LEN_RANGE.contains(&hash_size) {
+            return Err(Error::InvalidHashLength);
+        }
+        if personal.len() > PERSONAL_INPUT_MAX_LEN {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(Blake2bHasher {
+            hash_size,
+            personal: personal.to_vec(),
+            state: Self::initial_state(hash_size, personal),
+        })
+    }
+
+    fn initial_state(hash_size: usize, personal: &[u8]) -> Blake2b {
+        Blake2bBuilder::new(hash_size).personal(personal).build()
+    }
+}
+
+impl Hasher for Blake2bHasher {
+    fn output_len(&self) -> usize {
+        self.hash_size
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.state.update(data);
+    }
+
+    fn finalize(&mut self) -> Vec<u8> {
+        let initial = Self::initial_state(self.hash_size, &self.personal);
+        let state = std::mem::replace(&mut self.state, initial);
+
+        let mut output = vec![0; self.hash_size];
+        state.finalize(&mut output);
+        output
+    }
+
+    fn reset(&mut self) {
+        self.state = Self::initial_state(self.hash_size, &self.personal);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tw_encoding::hex;
 
     #[test]
     fn test_blake2_b_invalid_hash_size() {
@@ -67,4 +197,135 @@ mod tests {
         let personal_data = b"MyApp Files Hash ...";
         blake2_b_personal(input, 64, personal_data).unwrap_err();
     }
+
+    #[test]
+    fn test_blake2_b_personal_hasher() {
+        let personal_data = b"MyApp Files Hash";
+        let mut hasher = Blake2bHasher::with_personal(32, personal_data).unwrap();
+        hasher.update(b"Hello ");
+        hasher.update(b"world");
+
+        let expected = blake2_b_personal(b"Hello world", 32, personal_data).unwrap();
+        assert_eq!(hasher.finalize(), expected);
+    }
+
+    #[test]
+    fn test_blake2_b_hasher_invalid_params() {
+        assert!(Blake2bHasher::new(65).is_err());
+        assert!(Blake2bHasher::with_personal(64, b"MyApp Files Hash ...").is_err());
+    }
+
+    #[test]
+    fn test_blake2_b_keyed() {
+        // https://github.com/BLAKE2/BLAKE2/blob/master/testvectors/blake2b-kat.txt
+        let key: Vec<u8> = (0..64).collect();
+        let actual = blake2_b_keyed(&[], 64, &key).unwrap();
+        assert_eq!(hex::encode(actual, false), "10ebb67700b1868efb4417987acf4690ae9d972fb7a590c2f02871799aaa4786b5e996e8f0f4eb981fc214b005f42d2ff4233499391653df7aefcbc13fc51568");
+
+        let actual = blake2_b_keyed(b"Hello world", 32, b"secret key").unwrap();
+        assert_eq!(
+            hex::encode(actual, false),
+            "2246b5018dd885a773ea8afab5cc34b8e9b07127530833bea248529ffa2b28a6"
+        );
+
+        let unkeyed = blake2_b_keyed(b"Hello world", 32, &[]).unwrap();
+        assert_eq!(unkeyed, blake2_b(b"Hello world", 32).unwrap());
+    }
+
+    #[test]
+    fn test_blake2_b_salted() {
+        let actual = blake2_b_salted(
+            b"Hello world",
+            32,
+            b"secret key",
+            b"0123456789abcdef",
+            b"MyApp Files Hash",
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(actual, false),
+            "2cf0028ee50b7c209d7b193000b247f8351b889ba6f4d54bdf499e5fdf2ee91e"
+        );
+
+        // A short salt is padded with zeros.
+        let actual = blake2_b_salted(b"Hello world", 32, &[], b"salt", &[]).unwrap();
+        assert_eq!(
+            hex::encode(actual, false),
+            "69dad955db550c4de35843ae122a1f229dd4d953f9d920ef087d8226507308f3"
+        );
+
+        let personal = blake2_b_salted(b"Hello world", 32, &[], &[], b"MyApp Files Hash").unwrap();
+        assert_eq!(
+            personal,
+            blake2_b_personal(b"Hello world", 32, b"MyApp Files Hash").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_blake2_b_salted_invalid_params() {
+        let input = b"Hello world";
+        assert!(matches!(
+            blake2_b_salted(input, 0, &[], &[], &[]),
+            Err(Error::InvalidHashLength)
+        ));
+        assert!(matches!(
+            blake2_b_keyed(input, 32, &[0; 65]),
+            Err(Error::InvalidKeyLength)
+        ));
+        assert!(matches!(
+            blake2_b_salted(input, 32, &[], &[0; 17], &[]),
+            Err(Error::InvalidSaltLength)
+        ));
+        assert!(matches!(
+            blake2_b_salted(input, 32, &[], &[], &[0; 17]),
+            Err(Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    fn test_blake2_s_256() {
+        let tests = [
+            (
+                "",
+                "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9",
+            ),
+            (
+                "abc",
+                "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+            ),
+            (
+                "Hello world",
+                "619a15b0f4dd21ef4bd626a9146af64561caf1325b21bccf755e4d7fbc31a65f",
+            ),
+        ];
+        for (input, expected) in tests {
+            assert_eq!(hex::encode(blake2_s_256(input.as_bytes()), false), expected);
+        }
+    }
+
+    #[test]
+    fn test_blake2_s_keyed() {
+        // https://github.com/BLAKE2/BLAKE2/blob/master/testvectors/blake2s-kat.txt
+        let key: Vec<u8> = (0..32).collect();
+        let actual = blake2_s_keyed(&[], 32, &key).unwrap();
+        assert_eq!(
+            hex::encode(actual, false),
+            "48a8997da407876b3d79c0d92325ad3b89cbb754d86ab71aee047ad345fd2c49"
+        );
+
+        let actual = blake2_s_keyed(b"Hello world", 16, b"secret key").unwrap();
+        assert_eq!(
+            hex::encode(actual, false),
+            "6d4f4dc0be56d28e8106bbc6eaa621f1"
+        );
+
+        assert!(matches!(
+            blake2_s_keyed(b"Hello world", 33, &[]),
+            Err(Error::InvalidHashLength)
+        ));
+        assert!(matches!(
+            blake2_s_keyed(b"Hello world", 32, &[0; 33]),
+            Err(Error::InvalidKeyLength)
+        ));
+    }
 }
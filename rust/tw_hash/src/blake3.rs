@@ -0,0 +1,91 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::Error;
+
+pub const BLAKE3_OUTPUT_LEN: usize = ::blake3::OUT_LEN;
+pub const BLAKE3_KEY_LEN: usize = ::blake3::KEY_LEN;
+
+/// Computes the BLAKE3 hash of the `input` byte array.
+pub fn blake3(input: &[u8]) -> Vec<u8> {
+    ::blake3::hash(input).as_bytes().to_vec()
+}
+
+/// Computes the keyed BLAKE3 hash (MAC mode) of the `input` byte array.
+/// The `key` must be exactly 32 bytes long.
+pub fn blake3_keyed(input: &[u8], key: &[u8]) -> Result<Vec<u8>, Error> {
+    let key: &[u8; BLAKE3_KEY_LEN] = key.try_into().map_err(|_| Error::InvalidKeyLength)?;
+    Ok(::blake3::keyed_hash(key, input).as_bytes().to_vec())
+}
+
+/// Computes the BLAKE3 extendable output (XOF) of `output_len` bytes.
+/// The first 32 bytes of the output are equal to [`blake3`].
+pub fn blake3_xof(input: &[u8], output_len: usize) -> Result<Vec<u8>, Error> {
+    if output_len == 0 {
+        return Err(Error::InvalidHashLength);
+    }
+
+    let mut hasher = ::blake3::Hasher::new();
+    hasher.update(input);
+    let mut output = vec![0; output_len];
+    hasher.finalize_xof().fill(&mut output);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    const TEST_KEY: &[u8; BLAKE3_KEY_LEN] = b"whats the Elvish word for friend";
+
+    #[test]
+    fn test_blake3() {
+        let tests = [
+            (
+                "",
+                "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            ),
+            (
+                "abc",
+                "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+            ),
+        ];
+        for (input, expected) in tests {
+            assert_eq!(hex::encode(blake3(input.as_bytes()), false), expected);
+        }
+    }
+
+    #[test]
+    fn test_blake3_keyed() {
+        // https://github.com/BLAKE3-team/BLAKE3/blob/master/test_vectors/test_vectors.json
+        let actual = blake3_keyed(&[], TEST_KEY).unwrap();
+        assert_eq!(
+            hex::encode(actual, false),
+            "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26"
+        );
+
+        assert!(matches!(
+            blake3_keyed(b"abc", &TEST_KEY[1..]),
+            Err(Error::InvalidKeyLength)
+        ));
+    }
+
+    #[test]
+    fn test_blake3_xof() {
+        // https://github.com/BLAKE3-team/BLAKE3/blob/master/test_vectors/test_vectors.json
+        let actual = blake3_xof(&[], 64).unwrap();
+        assert_eq!(hex::encode(&actual, false), "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262e00f03e7b69af26b7faaf09fcd333050338ddfe085b8cc869ca98b206c08243a");
+
+        let short = blake3_xof(b"abc", 16).unwrap();
+        assert_eq!(short[..], blake3(b"abc")[..16]);
+
+        assert!(matches!(
+            blake3_xof(b"abc", 0),
+            Err(Error::InvalidHashLength)
+        ));
+    }
+}
@@ -6,9 +6,20 @@
 
 #![allow(clippy::missing_safety_doc)]
 
-use crate::{blake, blake2, groestl, hmac, ripemd, sha1, sha2, sha3, Error};
+use crate::hasher::{Hasher, HasherKind};
+use crate::kdf::argon2::{argon2id, Argon2Params};
+use crate::kdf::scrypt::{scrypt, ScryptParams};
+use crate::kdf::{hkdf, pbkdf2};
+use crate::merkle::{MerkleHash, MerklePairing, MerkleProof, MerkleTree};
+use crate::{blake, blake2, blake3, groestl, hmac, ripemd, sha1, sha2, sha3, xxhash, Error, H256};
+use std::ffi::{c_char, CStr, CString};
+use tw_encoding::base58;
+use tw_encoding::ffi::{Base58Alphabet, CEncodingCode};
 use tw_memory::ffi::c_byte_array::{CByteArray, CByteArrayResult};
+use tw_memory::ffi::c_byte_array_ref::CByteArrayRef;
 use tw_memory::ffi::c_result::ErrorCode;
+use tw_memory::ffi::RawPtrTrait;
+use tw_misc::{try_or_else, try_or_false};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -16,6 +27,8 @@ pub enum CHashingCode {
     Ok = 0,
     InvalidHashLength = 1,
     InvalidArgument = 2,
+    InvalidKeyLength = 3,
+    InvalidSaltLength = 4,
 }
 
 impl From<Error> for CHashingCode {
@@ -23,6 +36,8 @@ impl From<Error> for CHashingCode {
         match e {
             Error::FromHexError(_) | Error::InvalidArgument => CHashingCode::InvalidArgument,
             Error::InvalidHashLength => CHashingCode::InvalidHashLength,
+            Error::InvalidKeyLength => CHashingCode::InvalidKeyLength,
+            Error::InvalidSaltLength => CHashingCode::InvalidSaltLength,
         }
     }
 }
@@ -84,6 +99,150 @@ pub unsafe extern "C" fn blake2_b_personal(
         .into()
 }
 
+/// Computes the keyed BLAKE2B hash (MAC mode) of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \param hash_size the size of the output hash.
+/// \param key *optional* byte array, at most 64 bytes.
+/// \param key_len the length of the `key` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn blake2_b_keyed(
+    input: *const u8,
+    input_len: usize,
+    hash_size: usize,
+    key: *const u8,
+    key_len: usize,
+) -> CByteArrayResult {
+    let input = std::slice::from_raw_parts(input, input_len);
+    let key = optional_input(key, key_len);
+    blake2::blake2_b_keyed(input, hash_size, key)
+        .map(CByteArray::from)
+        .map_err(CHashingCode::from)
+        .into()
+}
+
+/// Computes the BLAKE2B hash of the `input` byte array with an optional key, salt and personalization.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \param hash_size the size of the output hash.
+/// \param key *optional* byte array, at most 64 bytes.
+/// \param key_len the length of the `key` array.
+/// \param salt *optional* byte array, at most 16 bytes.
+/// \param salt_len the length of the `salt` array.
+/// \param personal_input *optional* byte array, at most 16 bytes.
+/// \param personal_len the length of the `personal_input` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn blake2_b_salted(
+    input: *const u8,
+    input_len: usize,
+    hash_size: usize,
+    key: *const u8,
+    key_len: usize,
+    salt: *const u8,
+    salt_len: usize,
+    personal_input: *const u8,
+    personal_len: usize,
+) -> CByteArrayResult {
+    let input = std::slice::from_raw_parts(input, input_len);
+    let key = optional_input(key, key_len);
+    let salt = optional_input(salt, salt_len);
+    let personal = optional_input(personal_input, personal_len);
+    blake2::blake2_b_salted(input, hash_size, key, salt, personal)
+        .map(CByteArray::from)
+        .map_err(CHashingCode::from)
+        .into()
+}
+
+/// Computes the BLAKE2S-256 hash of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn blake2_s_256(input: *const u8, input_len: usize) -> CByteArray {
+    let input = std::slice::from_raw_parts(input, input_len);
+    blake2::blake2_s_256(input).into()
+}
+
+/// Computes the keyed BLAKE2S hash (MAC mode) of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \param hash_size the size of the output hash.
+/// \param key *optional* byte array, at most 32 bytes.
+/// \param key_len the length of the `key` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn blake2_s_keyed(
+    input: *const u8,
+    input_len: usize,
+    hash_size: usize,
+    key: *const u8,
+    key_len: usize,
+) -> CByteArrayResult {
+    let input = std::slice::from_raw_parts(input, input_len);
+    let key = optional_input(key, key_len);
+    blake2::blake2_s_keyed(input, hash_size, key)
+        .map(CByteArray::from)
+        .map_err(CHashingCode::from)
+        .into()
+}
+
+/// Computes the BLAKE3 hash of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn blake3(input: *const u8, input_len: usize) -> CByteArray {
+    let input = std::slice::from_raw_parts(input, input_len);
+    blake3::blake3(input).into()
+}
+
+/// Computes the keyed BLAKE3 hash (MAC mode) of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \param key *non-null* byte array, exactly 32 bytes.
+/// \param key_len the length of the `key` array.
+/// \return C-compatible result with a C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn blake3_keyed(
+    input: *const u8,
+    input_len: usize,
+    key: *const u8,
+    key_len: usize,
+) -> CByteArrayResult {
+    let input = std::slice::from_raw_parts(input, input_len);
+    let key = std::slice::from_raw_parts(key, key_len);
+    blake3::blake3_keyed(input, key)
+        .map(CByteArray::from)
+        .map_err(CHashingCode::from)
+        .into()
+}
+
+/// Computes the BLAKE3 extendable output (XOF) of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \param output_len the number of output bytes.
+/// \return C-compatible result with a C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn blake3_xof(
+    input: *const u8,
+    input_len: usize,
+    output_len: usize,
+) -> CByteArrayResult {
+    let input = std::slice::from_raw_parts(input, input_len);
+    blake3::blake3_xof(input, output_len)
+        .map(CByteArray::from)
+        .map_err(CHashingCode::from)
+        .into()
+}
+
+/// Returns the slice or an empty slice if the `data` is null.
+unsafe fn optional_input(data: *const u8, len: usize) -> &'static [u8] {
+    CByteArrayRef::new(data, len).as_slice().unwrap_or_default()
+}
+
 /// Computes the Groestl-512 hash of the `input` byte array.
 /// \param input *non-null* byte array.
 /// \param input_len the length of the `input` array.
@@ -112,6 +271,51 @@ pub unsafe extern "C" fn hmac__sha256(
     hmac::hmac_sha256(key, input).into()
 }
 
+/// Hash-based Message Authentication Code (HMAC) using the SHA-512 hash function.
+/// \param key *non-null* byte array.
+/// \param key_len the length of the `key` array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn hmac__sha512(
+    key: *const u8,
+    key_len: usize,
+    input: *const u8,
+    input_len: usize,
+) -> CByteArray {
+    let key = std::slice::from_raw_parts(key, key_len);
+    let input = std::slice::from_raw_parts(input, input_len);
+    hmac::hmac_sha512(key, input).into()
+}
+
+/// Hash-based Message Authentication Code (HMAC) using the given hash function.
+/// \param kind the hash function kind, for example, SHA-1.
+/// \param key *non-null* byte array of any length.
+/// \param key_len the length of the `key` array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \return C-compatible result with a C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn tw_hmac(
+    kind: u32,
+    key: *const u8,
+    key_len: usize,
+    input: *const u8,
+    input_len: usize,
+) -> CByteArrayResult {
+    let kind = match HasherKind::from_raw(kind) {
+        Some(kind) => kind,
+        None => return CByteArrayResult::error(CHashingCode::InvalidArgument),
+    };
+    let key = CByteArrayRef::new(key, key_len).as_slice();
+    let input = CByteArrayRef::new(input, input_len).as_slice();
+    match (key, input) {
+        (Some(key), Some(input)) => CByteArrayResult::ok(hmac::hmac(kind, key, input).into()),
+        _ => CByteArrayResult::error(CHashingCode::InvalidArgument),
+    }
+}
+
 /// Computes the RIPEMD-160 hash of the `input` byte array.
 /// \param input *non-null* byte array.
 /// \param input_len the length of the `input` array.
@@ -201,3 +405,462 @@ pub unsafe extern "C" fn sha3__512(input: *const u8, input_len: usize) -> CByteA
     let input = std::slice::from_raw_parts(input, input_len);
     sha3::sha3_512(input).into()
 }
+
+/// Computes the 64-bit Twox hash of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn twox_64(input: *const u8, input_len: usize) -> CByteArray {
+    let input = std::slice::from_raw_parts(input, input_len);
+    xxhash::twox_64(input).into()
+}
+
+/// Computes the 128-bit Twox hash of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn twox_128(input: *const u8, input_len: usize) -> CByteArray {
+    let input = std::slice::from_raw_parts(input, input_len);
+    xxhash::twox_128(input).into()
+}
+
+/// Computes the 256-bit Twox hash of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn twox_256(input: *const u8, input_len: usize) -> CByteArray {
+    let input = std::slice::from_raw_parts(input, input_len);
+    xxhash::twox_256(input).into()
+}
+
+/// Computes the Substrate `Twox64Concat` storage key of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn twox_64_concat(input: *const u8, input_len: usize) -> CByteArray {
+    let input = std::slice::from_raw_parts(input, input_len);
+    xxhash::twox_64_concat(input).into()
+}
+
+/// Computes the Substrate `Blake2_128Concat` storage key of the `input` byte array.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \return C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn blake2_128_concat(input: *const u8, input_len: usize) -> CByteArray {
+    let input = std::slice::from_raw_parts(input, input_len);
+    xxhash::blake2_128_concat(input).into()
+}
+
+/// Returns the slice or [`CHashingCode::InvalidArgument`] if the `data` is null.
+unsafe fn kdf_input(data: *const u8, len: usize) -> Result<&'static [u8], CHashingCode> {
+    CByteArrayRef::new(data, len)
+        .as_slice()
+        .ok_or(CHashingCode::InvalidArgument)
+}
+
+/// Derives a key using PBKDF2-HMAC-SHA256.
+/// \param password *non-null* byte array.
+/// \param password_len the length of the `password` array.
+/// \param salt *non-null* byte array.
+/// \param salt_len the length of the `salt` array.
+/// \param rounds the number of iterations. Must be greater than 0.
+/// \param output_len the length of the derived key.
+/// \return C-compatible result with a C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn kdf_pbkdf2_hmac_sha256(
+    password: *const u8,
+    password_len: usize,
+    salt: *const u8,
+    salt_len: usize,
+    rounds: u32,
+    output_len: usize,
+) -> CByteArrayResult {
+    let derive = || -> Result<Vec<u8>, CHashingCode> {
+        let password = kdf_input(password, password_len)?;
+        let salt = kdf_input(salt, salt_len)?;
+        Ok(pbkdf2::pbkdf2_hmac_sha256(
+            password, salt, rounds, output_len,
+        )?)
+    };
+    derive().map(CByteArray::from).into()
+}
+
+/// Derives a key using PBKDF2-HMAC-SHA512.
+/// \param password *non-null* byte array.
+/// \param password_len the length of the `password` array.
+/// \param salt *non-null* byte array.
+/// \param salt_len the length of the `salt` array.
+/// \param rounds the number of iterations. Must be greater than 0.
+/// \param output_len the length of the derived key.
+/// \return C-compatible result with a C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn kdf_pbkdf2_hmac_sha512(
+    password: *const u8,
+    password_len: usize,
+    salt: *const u8,
+    salt_len: usize,
+    rounds: u32,
+    output_len: usize,
+) -> CByteArrayResult {
+    let derive = || -> Result<Vec<u8>, CHashingCode> {
+        let password = kdf_input(password, password_len)?;
+        let salt = kdf_input(salt, salt_len)?;
+        Ok(pbkdf2::pbkdf2_hmac_sha512(
+            password, salt, rounds, output_len,
+        )?)
+    };
+    derive().map(CByteArray::from).into()
+}
+
+/// Derives a key using scrypt with the default memory limit (512 MiB).
+/// \param password *non-null* byte array.
+/// \param password_len the length of the `password` array.
+/// \param salt *non-null* byte array.
+/// \param salt_len the length of the `salt` array.
+/// \param n CPU/memory cost parameter. Must be a power of 2 greater than 1.
+/// \param r block size parameter.
+/// \param p parallelization parameter.
+/// \param output_len the length of the derived key.
+/// \return C-compatible result with a C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn kdf_scrypt(
+    password: *const u8,
+    password_len: usize,
+    salt: *const u8,
+    salt_len: usize,
+    n: u64,
+    r: u32,
+    p: u32,
+    output_len: usize,
+) -> CByteArrayResult {
+    let derive = || -> Result<Vec<u8>, CHashingCode> {
+        let password = kdf_input(password, password_len)?;
+        let salt = kdf_input(salt, salt_len)?;
+        let params = ScryptParams {
+            n,
+            r,
+            p,
+            output_len,
+        };
+        Ok(scrypt(password, salt, &params)?)
+    };
+    derive().map(CByteArray::from).into()
+}
+
+/// Derives a key using HKDF-SHA256.
+/// \param ikm *non-null* byte array, the input keying material.
+/// \param ikm_len the length of the `ikm` array.
+/// \param salt *non-null* byte array.
+/// \param salt_len the length of the `salt` array.
+/// \param info *non-null* byte array, the context and application specific information.
+/// \param info_len the length of the `info` array.
+/// \param output_len the length of the derived key. Must not exceed `255 * 32`.
+/// \return C-compatible result with a C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn kdf_hkdf_sha256(
+    ikm: *const u8,
+    ikm_len: usize,
+    salt: *const u8,
+    salt_len: usize,
+    info: *const u8,
+    info_len: usize,
+    output_len: usize,
+) -> CByteArrayResult {
+    let derive = || -> Result<Vec<u8>, CHashingCode> {
+        let ikm = kdf_input(ikm, ikm_len)?;
+        let salt = kdf_input(salt, salt_len)?;
+        let info = kdf_input(info, info_len)?;
+        Ok(hkdf::hkdf_sha256(ikm, salt, info, output_len)?)
+    };
+    derive().map(CByteArray::from).into()
+}
+
+/// Derives a key using HKDF-SHA512.
+/// \param ikm *non-null* byte array, the input keying material.
+/// \param ikm_len the length of the `ikm` array.
+/// \param salt *non-null* byte array.
+/// \param salt_len the length of the `salt` array.
+/// \param info *non-null* byte array, the context and application specific information.
+/// \param info_len the length of the `info` array.
+/// \param output_len the length of the derived key. Must not exceed `255 * 64`.
+/// \return C-compatible result with a C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn kdf_hkdf_sha512(
+    ikm: *const u8,
+    ikm_len: usize,
+    salt: *const u8,
+    salt_len: usize,
+    info: *const u8,
+    info_len: usize,
+    output_len: usize,
+) -> CByteArrayResult {
+    let derive = || -> Result<Vec<u8>, CHashingCode> {
+        let ikm = kdf_input(ikm, ikm_len)?;
+        let salt = kdf_input(salt, salt_len)?;
+        let info = kdf_input(info, info_len)?;
+        Ok(hkdf::hkdf_sha512(ikm, salt, info, output_len)?)
+    };
+    derive().map(CByteArray::from).into()
+}
+
+/// Derives a key using Argon2id (version 0x13) with the default memory limit (512 MiB).
+/// \param password *non-null* byte array.
+/// \param password_len the length of the `password` array.
+/// \param salt *non-null* byte array, at least 8 bytes.
+/// \param salt_len the length of the `salt` array.
+/// \param memory_cost memory size in KiB. Must be at least `8 * parallelism` and not exceed the limit.
+/// \param iterations the number of passes.
+/// \param parallelism the degree of parallelism.
+/// \param output_len the length of the derived key. Must be at least 4.
+/// \return C-compatible result with a C-compatible byte array.
+#[no_mangle]
+pub unsafe extern "C" fn kdf_argon2id(
+    password: *const u8,
+    password_len: usize,
+    salt: *const u8,
+    salt_len: usize,
+    memory_cost: u32,
+    iterations: u32,
+    parallelism: u32,
+    output_len: usize,
+) -> CByteArrayResult {
+    let derive = || -> Result<Vec<u8>, CHashingCode> {
+        let password = kdf_input(password, password_len)?;
+        let salt = kdf_input(salt, salt_len)?;
+        let params = Argon2Params {
+            memory_cost,
+            iterations,
+            parallelism,
+            output_len,
+        };
+        Ok(argon2id(password, salt, &params)?)
+    };
+    derive().map(CByteArray::from).into()
+}
+
+/// The hash function used to calculate a Base58Check checksum.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Base58ChecksumHasher {
+    /// Double SHA256, used by Bitcoin and most of its forks, Ripple.
+    Sha256d = 1,
+    /// Double Blake256, used by Decred.
+    Blake256d = 2,
+    /// Double Groestl512, used by Groestlcoin.
+    Groestl512d = 3,
+}
+
+impl Base58ChecksumHasher {
+    fn checksum_hasher(self) -> base58::ChecksumHasher {
+        match self {
+            Base58ChecksumHasher::Sha256d => sha2::sha256d,
+            Base58ChecksumHasher::Blake256d => blake::blake_256d,
+            Base58ChecksumHasher::Groestl512d => groestl::groestl_512d,
+        }
+    }
+}
+
+/// Encodes the `input` data as base58 with a 4-byte checksum appended.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \param alphabet alphabet type.
+/// \param hasher checksum hash function.
+/// \return *non-null* C-compatible, nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn encode_base58_check(
+    input: *const u8,
+    input_len: usize,
+    alphabet: Base58Alphabet,
+    hasher: Base58ChecksumHasher,
+) -> *mut c_char {
+    let input = unsafe { std::slice::from_raw_parts(input, input_len) };
+    CString::new(base58::encode_check(
+        input,
+        alphabet.into(),
+        hasher.checksum_hasher(),
+    ))
+    .unwrap()
+    .into_raw()
+}
+
+/// Decodes the base58 `input` string and validates its 4-byte checksum.
+/// \param input *non-null* C-compatible, nul-terminated string.
+/// \param alphabet alphabet type.
+/// \param hasher checksum hash function.
+/// \return C-compatible result with a C-compatible byte array without the checksum.
+#[no_mangle]
+pub unsafe extern "C" fn decode_base58_check(
+    input: *const c_char,
+    alphabet: Base58Alphabet,
+    hasher: Base58ChecksumHasher,
+) -> CByteArrayResult {
+    let input = match CStr::from_ptr(input).to_str() {
+        Ok(input) => input,
+        Err(_) => return CByteArrayResult::error(CEncodingCode::InvalidInput),
+    };
+
+    base58::decode_check(input, alphabet.into(), hasher.checksum_hasher())
+        .map(CByteArray::from)
+        .map_err(CEncodingCode::from)
+        .into()
+}
+
+pub struct TWHasher(Box<dyn Hasher>);
+
+impl RawPtrTrait for TWHasher {}
+
+/// Creates an incremental hasher of the given kind.
+/// \param kind the hasher kind, for example, SHA-256.
+/// \note Should be deleted with \tw_hasher_delete.
+/// \return Nullable pointer to the hasher. Null if the `kind` is not supported.
+#[no_mangle]
+pub unsafe extern "C" fn tw_hasher_create(kind: u32) -> *mut TWHasher {
+    let kind = try_or_else!(HasherKind::from_raw(kind), std::ptr::null_mut);
+    TWHasher(kind.hasher()).into_ptr()
+}
+
+/// Deletes the given hasher.
+/// \param hasher *non-null* pointer to the hasher.
+#[no_mangle]
+pub unsafe extern "C" fn tw_hasher_delete(hasher: *mut TWHasher) {
+    // Take the ownership back to rust and drop the owner.
+    let _ = TWHasher::from_ptr(hasher);
+}
+
+/// Processes the next chunk of data.
+/// \param hasher *non-null* pointer to the hasher.
+/// \param input *non-null* byte array.
+/// \param input_len the length of the `input` array.
+/// \return true if the chunk has been processed, false otherwise.
+#[no_mangle]
+pub unsafe extern "C" fn tw_hasher_update(
+    hasher: *mut TWHasher,
+    input: *const u8,
+    input_len: usize,
+) -> bool {
+    let hasher = try_or_false!(TWHasher::from_ptr_as_mut(hasher));
+    let input = try_or_false!(CByteArrayRef::new(input, input_len).as_slice());
+    hasher.0.update(input);
+    true
+}
+
+/// Returns the hash of the processed data and resets the hasher to its initial state.
+/// \param hasher *non-null* pointer to the hasher.
+/// \return C-compatible byte array. Empty if the `hasher` is null.
+#[no_mangle]
+pub unsafe extern "C" fn tw_hasher_finalize(hasher: *mut TWHasher) -> CByteArray {
+    let hasher = try_or_else!(TWHasher::from_ptr_as_mut(hasher), CByteArray::default);
+    hasher.0.finalize().into()
+}
+
+/// Resets the hasher to its initial state discarding the processed data.
+/// \param hasher *non-null* pointer to the hasher.
+#[no_mangle]
+pub unsafe extern "C" fn tw_hasher_reset(hasher: *mut TWHasher) {
+    if let Some(hasher) = TWHasher::from_ptr_as_mut(hasher) {
+        hasher.0.reset();
+    }
+}
+
+/// Parses the merkle tree parameters and builds the tree over the concatenated 32-byte `leaves`.
+unsafe fn merkle_tree(
+    hash: u32,
+    pairing: u32,
+    leaves: *const u8,
+    leaves_len: usize,
+) -> Result<MerkleTree, CHashingCode> {
+    let hash = MerkleHash::from_raw(hash).ok_or(CHashingCode::InvalidArgument)?;
+    let pairing = MerklePairing::from_raw(pairing).ok_or(CHashingCode::InvalidArgument)?;
+    let leaves = CByteArrayRef::new(leaves, leaves_len)
+        .as_slice()
+        .ok_or(CHashingCode::InvalidArgument)?;
+    if !leaves.len().is_multiple_of(H256::len()) {
+        return Err(CHashingCode::InvalidHashLength);
+    }
+
+    let leaves = leaves
+        .chunks(H256::len())
+        .map(H256::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(MerkleTree::new(leaves, hash, pairing)?)
+}
+
+/// Computes the merkle root of the given leaves.
+/// \param hash the hash function used to combine nodes, see `MerkleHash`.
+/// \param pairing the rule used to pair nodes, see `MerklePairing`.
+/// \param leaves *non-null* byte array of concatenated 32-byte leaf hashes.
+/// \param leaves_len the length of the `leaves` array.
+/// \return C-compatible 32-byte array.
+#[no_mangle]
+pub unsafe extern "C" fn tw_merkle_root(
+    hash: u32,
+    pairing: u32,
+    leaves: *const u8,
+    leaves_len: usize,
+) -> CByteArrayResult {
+    merkle_tree(hash, pairing, leaves, leaves_len)
+        .map(|tree| CByteArray::from(tree.root().into_vec()))
+        .into()
+}
+
+/// Computes an inclusion proof of the leaf at the given `index`.
+/// \param hash the hash function used to combine nodes, see `MerkleHash`.
+/// \param pairing the rule used to pair nodes, see `MerklePairing`.
+/// \param leaves *non-null* byte array of concatenated 32-byte leaf hashes.
+/// \param leaves_len the length of the `leaves` array.
+/// \param index the leaf index.
+/// \return C-compatible byte array of concatenated 32-byte sibling hashes.
+#[no_mangle]
+pub unsafe extern "C" fn tw_merkle_proof(
+    hash: u32,
+    pairing: u32,
+    leaves: *const u8,
+    leaves_len: usize,
+    index: usize,
+) -> CByteArrayResult {
+    let proof = || -> Result<Vec<u8>, CHashingCode> {
+        let tree = merkle_tree(hash, pairing, leaves, leaves_len)?;
+        Ok(tree.proof(index)?.to_bytes())
+    };
+    proof().map(CByteArray::from).into()
+}
+
+/// Verifies an inclusion proof of the `leaf` into the tree with the given `root`.
+/// \param hash the hash function used to combine nodes, see `MerkleHash`.
+/// \param pairing the rule used to pair nodes, see `MerklePairing`.
+/// \param leaf *non-null* 32-byte leaf hash.
+/// \param leaf_len the length of the `leaf` array.
+/// \param index the leaf index.
+/// \param proof *non-null* byte array of concatenated 32-byte sibling hashes.
+/// \param proof_len the length of the `proof` array.
+/// \param root *non-null* 32-byte merkle root.
+/// \param root_len the length of the `root` array.
+/// \return true if the proof is valid, false otherwise.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn tw_merkle_verify_proof(
+    hash: u32,
+    pairing: u32,
+    leaf: *const u8,
+    leaf_len: usize,
+    index: usize,
+    proof: *const u8,
+    proof_len: usize,
+    root: *const u8,
+    root_len: usize,
+) -> bool {
+    let hash = try_or_false!(MerkleHash::from_raw(hash));
+    let pairing = try_or_false!(MerklePairing::from_raw(pairing));
+    let leaf = try_or_false!(CByteArrayRef::new(leaf, leaf_len).as_slice());
+    let leaf = try_or_false!(H256::try_from(leaf));
+    let proof = try_or_false!(CByteArrayRef::new(proof, proof_len).as_slice());
+    let proof = try_or_false!(MerkleProof::from_bytes(index, proof));
+    let root = try_or_false!(CByteArrayRef::new(root, root_len).as_slice());
+    let root = try_or_false!(H256::try_from(root));
+    proof.verify(&leaf, &root, hash, pairing)
+}
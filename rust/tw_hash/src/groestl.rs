@@ -5,8 +5,16 @@
 // file LICENSE at the root of the source code distribution tree.
 
 use crate::hash_wrapper::hasher;
+use crate::hasher::DigestHasher;
 use groestl::Groestl512;
 
+pub type Groestl512Hasher = DigestHasher<Groestl512>;
+
 pub fn groestl_512(input: &[u8]) -> Vec<u8> {
     hasher::<Groestl512>(input)
 }
+
+/// Computes the double Groestl-512 hash: `groestl_512(groestl_512(input))`.
+pub fn groestl_512d(input: &[u8]) -> Vec<u8> {
+    groestl_512(&groestl_512(input))
+}
@@ -0,0 +1,189 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! Incremental hashing API that allows to hash data chunk by chunk.
+
+use crate::blake::Blake256Hasher;
+use crate::blake2::Blake2bHasher;
+use crate::groestl::Groestl512Hasher;
+use crate::ripemd::Ripemd160Hasher;
+use crate::sha1::Sha1Hasher;
+use crate::sha2::{Sha256Hasher, Sha512Hasher, Sha512_256Hasher};
+use crate::sha3::{Keccak256Hasher, Keccak512Hasher, Sha3_256Hasher, Sha3_512Hasher};
+use digest::{Digest, FixedOutputReset};
+
+/// An incremental hasher.
+pub trait Hasher {
+    /// Returns the number of bytes in the output hash.
+    fn output_len(&self) -> usize;
+
+    /// Processes the given `data` chunk.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the hash of the processed data and resets the hasher to its initial state.
+    fn finalize(&mut self) -> Vec<u8>;
+
+    /// Resets the hasher to its initial state discarding the processed data.
+    fn reset(&mut self);
+}
+
+/// A [`Hasher`] implementation over any [`Digest`] that supports resetting.
+#[derive(Default)]
+pub struct DigestHasher<D> {
+    digest: D,
+}
+
+impl<D: Digest + FixedOutputReset> Hasher for DigestHasher<D> {
+    fn output_len(&self) -> usize {
+        <D as Digest>::output_size()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(&mut self.digest, data);
+    }
+
+    fn finalize(&mut self) -> Vec<u8> {
+        Digest::finalize_reset(&mut self.digest).to_vec()
+    }
+
+    fn reset(&mut self) {
+        Digest::reset(&mut self.digest);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HasherKind {
+    Sha1 = 0,
+    Sha256 = 1,
+    Sha512 = 2,
+    Sha512_256 = 3,
+    Keccak256 = 4,
+    Keccak512 = 5,
+    Sha3_256 = 6,
+    Sha3_512 = 7,
+    Ripemd160 = 8,
+    Blake256 = 9,
+    Blake2b256 = 10,
+    Blake2b512 = 11,
+    Groestl512 = 12,
+}
+
+impl HasherKind {
+    /// Returns `None` if the given hasher kind is not supported.
+    pub fn from_raw(kind: u32) -> Option<HasherKind> {
+        match kind {
+            0 => Some(HasherKind::Sha1),
+            1 => Some(HasherKind::Sha256),
+            2 => Some(HasherKind::Sha512),
+            3 => Some(HasherKind::Sha512_256),
+            4 => Some(HasherKind::Keccak256),
+            5 => Some(HasherKind::Keccak512),
+            6 => Some(HasherKind::Sha3_256),
+            7 => Some(HasherKind::Sha3_512),
+            8 => Some(HasherKind::Ripemd160),
+            9 => Some(HasherKind::Blake256),
+            10 => Some(HasherKind::Blake2b256),
+            11 => Some(HasherKind::Blake2b512),
+            12 => Some(HasherKind::Groestl512),
+            _ => None,
+        }
+    }
+
+    /// Creates a new hasher of the kind.
+    pub fn hasher(self) -> Box<dyn Hasher> {
+        match self {
+            HasherKind::Sha1 => Box::<Sha1Hasher>::default(),
+            HasherKind::Sha256 => Box::<Sha256Hasher>::default(),
+            HasherKind::Sha512 => Box::<Sha512Hasher>::default(),
+            HasherKind::Sha512_256 => Box::<Sha512_256Hasher>::default(),
+            HasherKind::Keccak256 => Box::<Keccak256Hasher>::default(),
+            HasherKind::Keccak512 => Box::<Keccak512Hasher>::default(),
+            HasherKind::Sha3_256 => Box::<Sha3_256Hasher>::default(),
+            HasherKind::Sha3_512 => Box::<Sha3_512Hasher>::default(),
+            HasherKind::Ripemd160 => Box::<Ripemd160Hasher>::default(),
+            HasherKind::Blake256 => Box::<Blake256Hasher>::default(),
+            HasherKind::Blake2b256 => {
+                Box::new(Blake2bHasher::new(32).expect("Expected a valid blake2b hash size"))
+            },
+            HasherKind::Blake2b512 => {
+                Box::new(Blake2bHasher::new(64).expect("Expected a valid blake2b hash size"))
+            },
+            HasherKind::Groestl512 => Box::<Groestl512Hasher>::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blake, blake2, groestl, ripemd, sha1, sha2, sha3};
+
+    type OneShotFn = fn(&[u8]) -> Vec<u8>;
+
+    fn blake2_b_256(input: &[u8]) -> Vec<u8> {
+        blake2::blake2_b(input, 32).unwrap()
+    }
+
+    fn blake2_b_512(input: &[u8]) -> Vec<u8> {
+        blake2::blake2_b(input, 64).unwrap()
+    }
+
+    const HASHERS: [(HasherKind, OneShotFn); 13] = [
+        (HasherKind::Sha1, sha1::sha1),
+        (HasherKind::Sha256, sha2::sha256),
+        (HasherKind::Sha512, sha2::sha512),
+        (HasherKind::Sha512_256, sha2::sha512_256),
+        (HasherKind::Keccak256, sha3::keccak256),
+        (HasherKind::Keccak512, sha3::keccak512),
+        (HasherKind::Sha3_256, sha3::sha3_256),
+        (HasherKind::Sha3_512, sha3::sha3_512),
+        (HasherKind::Ripemd160, ripemd::ripemd_160),
+        (HasherKind::Blake256, blake::blake_256),
+        (HasherKind::Blake2b256, blake2_b_256),
+        (HasherKind::Blake2b512, blake2_b_512),
+        (HasherKind::Groestl512, groestl::groestl_512),
+    ];
+
+    #[test]
+    fn test_hasher_chunks() {
+        let input = b"The quick brown fox jumps over the lazy dog";
+        for (kind, one_shot) in HASHERS {
+            let expected = one_shot(input);
+
+            let mut hasher = kind.hasher();
+            for chunk in input.chunks(5) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), expected, "{kind:?}");
+            assert_eq!(hasher.output_len(), expected.len(), "{kind:?}");
+
+            // The hasher must be reset after `finalize`.
+            hasher.update(input);
+            assert_eq!(hasher.finalize(), expected, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_hasher_reset() {
+        let input = b"Hello world";
+        for (kind, one_shot) in HASHERS {
+            let mut hasher = kind.hasher();
+            hasher.update(b"garbage");
+            hasher.reset();
+            hasher.update(input);
+            assert_eq!(hasher.finalize(), one_shot(input), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_hasher_kind_from_raw() {
+        for (kind, _) in HASHERS {
+            assert_eq!(HasherKind::from_raw(kind as u32), Some(kind));
+        }
+        assert_eq!(HasherKind::from_raw(13), None);
+    }
+}
@@ -4,15 +4,165 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
-use hmac::{Hmac, Mac};
-use sha2::Sha256;
+//! Hash-based Message Authentication Code ([RFC 2104](https://datatracker.ietf.org/doc/html/rfc2104))
+//! over any [`HasherKind`] of the crate.
 
-type HmacSha256 = Hmac<Sha256>;
+use crate::blake::Blake256Hasher;
+use crate::hasher::HasherKind;
+use blake2::{Blake2b, Blake2b512};
+use digest::consts::U32;
+use groestl::Groestl512;
+use hmac::{Hmac, Mac, SimpleHmac};
+use ripemd::Ripemd160;
+use sha1::Sha1;
+use sha2::{Sha256, Sha512, Sha512_256};
+use sha3::{Keccak256, Keccak512, Sha3_256, Sha3_512};
+
+/// Computes HMAC of the `input` using the given hash function.
+/// The `key` can be of any size: keys longer than the hash block size are hashed first.
+pub fn hmac(kind: HasherKind, key: &[u8], input: &[u8]) -> Vec<u8> {
+    match kind {
+        HasherKind::Sha1 => mac::<Hmac<Sha1>>(key, input),
+        HasherKind::Sha256 => hmac_sha256(key, input),
+        HasherKind::Sha512 => hmac_sha512(key, input),
+        HasherKind::Sha512_256 => mac::<Hmac<Sha512_256>>(key, input),
+        HasherKind::Keccak256 => mac::<Hmac<Keccak256>>(key, input),
+        HasherKind::Keccak512 => mac::<Hmac<Keccak512>>(key, input),
+        HasherKind::Sha3_256 => mac::<Hmac<Sha3_256>>(key, input),
+        HasherKind::Sha3_512 => mac::<Hmac<Sha3_512>>(key, input),
+        HasherKind::Ripemd160 => mac::<Hmac<Ripemd160>>(key, input),
+        HasherKind::Blake256 => mac::<SimpleHmac<Blake256Hasher>>(key, input),
+        HasherKind::Blake2b256 => mac::<SimpleHmac<Blake2b<U32>>>(key, input),
+        HasherKind::Blake2b512 => mac::<SimpleHmac<Blake2b512>>(key, input),
+        HasherKind::Groestl512 => mac::<SimpleHmac<Groestl512>>(key, input),
+    }
+}
 
 pub fn hmac_sha256(key: &[u8], input: &[u8]) -> Vec<u8> {
-    let mut mac = HmacSha256::new_from_slice(key).unwrap();
-    mac.update(input);
-    let res = mac.finalize();
-    let code_bytes = res.into_bytes();
-    code_bytes.to_vec()
+    mac::<Hmac<Sha256>>(key, input)
+}
+
+pub fn hmac_sha512(key: &[u8], input: &[u8]) -> Vec<u8> {
+    mac::<Hmac<Sha512>>(key, input)
+}
+
+fn mac<M: Mac + hmac::digest::KeyInit>(key: &[u8], input: &[u8]) -> Vec<u8> {
+    // HMAC takes keys of any size, so the key initialization never fails.
+    let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    Mac::update(&mut mac, input);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    const IPAD: u8 = 0x36;
+    const OPAD: u8 = 0x5c;
+
+    /// Computes HMAC as it is defined in RFC 2104 using the incremental hasher of the given kind.
+    fn hmac_reference(kind: HasherKind, block_size: usize, key: &[u8], input: &[u8]) -> Vec<u8> {
+        let mut hasher = kind.hasher();
+
+        let mut block_key = vec![0; block_size];
+        if key.len() > block_size {
+            hasher.update(key);
+            let key_hash = hasher.finalize();
+            block_key[..key_hash.len()].copy_from_slice(&key_hash);
+        } else {
+            block_key[..key.len()].copy_from_slice(key);
+        }
+
+        let inner_key: Vec<_> = block_key.iter().map(|byte| byte ^ IPAD).collect();
+        hasher.update(&inner_key);
+        hasher.update(input);
+        let inner_hash = hasher.finalize();
+
+        let outer_key: Vec<_> = block_key.iter().map(|byte| byte ^ OPAD).collect();
+        hasher.update(&outer_key);
+        hasher.update(&inner_hash);
+        hasher.finalize()
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc4231() {
+        // https://datatracker.ietf.org/doc/html/rfc4231#section-4.2
+        let key = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        assert_eq!(
+            hex::encode(hmac_sha256(&key, b"Hi There"), false),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha512_rfc4231() {
+        // https://datatracker.ietf.org/doc/html/rfc4231#section-4.2
+        let key = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        assert_eq!(
+            hex::encode(hmac_sha512(&key, b"Hi There"), false),
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"
+        );
+
+        // Test with a key larger than the block size.
+        // https://datatracker.ietf.org/doc/html/rfc4231#section-4.7
+        let key = [0xaa; 131];
+        let input = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        assert_eq!(
+            hex::encode(hmac_sha512(&key, input), false),
+            "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha1_rfc2202() {
+        // https://datatracker.ietf.org/doc/html/rfc2202#section-3
+        assert_eq!(
+            hex::encode(
+                hmac(HasherKind::Sha1, b"Jefe", b"what do ya want for nothing?"),
+                false
+            ),
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+        );
+    }
+
+    #[test]
+    fn test_hmac_matches_reference() {
+        let short_key = b"key";
+        let long_key = [0x42; 200];
+        let input = b"The quick brown fox jumps over the lazy dog";
+
+        for key in [short_key.as_slice(), long_key.as_slice(), &[]] {
+            // The hash functions with their block sizes.
+            let tests = [
+                (HasherKind::Sha1, 64),
+                (HasherKind::Sha256, 64),
+                (HasherKind::Sha512, 128),
+                (HasherKind::Sha512_256, 128),
+                (HasherKind::Keccak256, 136),
+                (HasherKind::Keccak512, 72),
+                (HasherKind::Sha3_256, 136),
+                (HasherKind::Sha3_512, 72),
+                (HasherKind::Ripemd160, 64),
+                (HasherKind::Blake256, 64),
+                (HasherKind::Blake2b256, 128),
+                (HasherKind::Blake2b512, 128),
+                (HasherKind::Groestl512, 128),
+            ];
+            for (kind, block_size) in tests {
+                let expected = hmac_reference(kind, block_size, key, input);
+                assert_eq!(hmac(kind, key, input), expected, "{kind:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_hmac_blake2b() {
+        // Generated with Python: `hmac.new(b"key", msg, hashlib.blake2b).hexdigest()`.
+        let input = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            hex::encode(hmac(HasherKind::Blake2b512, b"key", input), false),
+            "92294f92c0dfb9b00ec9ae8bd94d7e7d8a036b885a499f149dfe2fd2199394aaaf6b8894a1730cccb2cd050f9bcf5062a38b51b0dab33207f8ef35ae2c9df51b"
+        );
+    }
 }
@@ -0,0 +1,187 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::{Error, Result};
+use argon2::{Algorithm, Argon2, AssociatedData, ParamsBuilder, Version};
+
+/// The default limit of memory in KiB that can be allocated by [`argon2id`].
+/// It's enough for the second recommended option of RFC 9106 (64 MiB).
+pub const ARGON2_DEFAULT_MEMORY_LIMIT: u32 = 512 * 1024;
+
+/// [Argon2id](https://datatracker.ietf.org/doc/html/rfc9106) cost parameters.
+#[derive(Clone, Copy, Debug)]
+pub struct Argon2Params {
+    /// Memory size in KiB. Must be at least `8 * parallelism`.
+    pub memory_cost: u32,
+    /// The number of passes.
+    pub iterations: u32,
+    /// The degree of parallelism (the number of lanes).
+    pub parallelism: u32,
+    /// The number of bytes in the derived key. Must be at least 4.
+    pub output_len: usize,
+}
+
+/// Derives a key using Argon2id (version 0x13) with the [`ARGON2_DEFAULT_MEMORY_LIMIT`] memory limit.
+/// Returns an error if the cost parameters are invalid or the `salt` is shorter than 8 bytes.
+pub fn argon2id(password: &[u8], salt: &[u8], params: &Argon2Params) -> Result<Vec<u8>> {
+    argon2id_with_memory_limit(password, salt, params, ARGON2_DEFAULT_MEMORY_LIMIT)
+}
+
+/// Derives a key using Argon2id (version 0x13).
+/// Returns an error if the cost parameters are invalid, `memory_cost` exceeds `memory_limit` KiB
+/// or the `salt` is shorter than 8 bytes.
+pub fn argon2id_with_memory_limit(
+    password: &[u8],
+    salt: &[u8],
+    params: &Argon2Params,
+    memory_limit: u32,
+) -> Result<Vec<u8>> {
+    derive_key(password, salt, &[], &[], params, memory_limit)
+}
+
+/// Derives a key using Argon2id (version 0x13) with the optional `secret` key
+/// and `associated_data` (up to 32 bytes), and the [`ARGON2_DEFAULT_MEMORY_LIMIT`] memory limit.
+pub fn argon2id_keyed(
+    password: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    associated_data: &[u8],
+    params: &Argon2Params,
+) -> Result<Vec<u8>> {
+    derive_key(
+        password,
+        salt,
+        secret,
+        associated_data,
+        params,
+        ARGON2_DEFAULT_MEMORY_LIMIT,
+    )
+}
+
+fn derive_key(
+    password: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    associated_data: &[u8],
+    params: &Argon2Params,
+    memory_limit: u32,
+) -> Result<Vec<u8>> {
+    if params.output_len < argon2::Params::MIN_OUTPUT_LEN {
+        return Err(Error::InvalidHashLength);
+    }
+    if params.memory_cost > memory_limit {
+        return Err(Error::InvalidArgument);
+    }
+
+    let associated_data =
+        AssociatedData::new(associated_data).map_err(|_| Error::InvalidArgument)?;
+    let argon2_params = ParamsBuilder::new()
+        .m_cost(params.memory_cost)
+        .t_cost(params.iterations)
+        .p_cost(params.parallelism)
+        .data(associated_data)
+        .output_len(params.output_len)
+        .build()
+        .map_err(|_| Error::InvalidArgument)?;
+
+    let argon2 =
+        Argon2::new_with_secret(secret, Algorithm::Argon2id, Version::V0x13, argon2_params)
+            .map_err(|_| Error::InvalidArgument)?;
+
+    let mut res = vec![0; params.output_len];
+    argon2
+        .hash_password_into(password, salt, &mut res)
+        .map_err(|_| Error::InvalidArgument)?;
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    #[test]
+    fn test_argon2id_rfc9106() {
+        // https://datatracker.ietf.org/doc/html/rfc9106#section-5.3
+        let params = Argon2Params {
+            memory_cost: 32,
+            iterations: 3,
+            parallelism: 4,
+            output_len: 32,
+        };
+        let actual =
+            argon2id_keyed(&[0x01; 32], &[0x02; 16], &[0x03; 8], &[0x04; 12], &params).unwrap();
+        assert_eq!(
+            hex::encode(actual, false),
+            "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659"
+        );
+    }
+
+    #[test]
+    fn test_argon2id_invalid_params() {
+        let valid = Argon2Params {
+            memory_cost: 32,
+            iterations: 3,
+            parallelism: 4,
+            output_len: 32,
+        };
+        assert!(argon2id(b"password", b"somesalt", &valid).is_ok());
+
+        // Salt is too short.
+        assert!(argon2id(b"password", b"salt", &valid).is_err());
+
+        let tests = [
+            Argon2Params {
+                memory_cost: 31,
+                ..valid
+            },
+            Argon2Params {
+                iterations: 0,
+                ..valid
+            },
+            Argon2Params {
+                parallelism: 0,
+                ..valid
+            },
+            Argon2Params {
+                output_len: 3,
+                ..valid
+            },
+        ];
+        for params in tests {
+            assert!(
+                argon2id(b"password", b"somesalt", &params).is_err(),
+                "{params:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_argon2id_memory_limit() {
+        let params = Argon2Params {
+            memory_cost: 64 * 1024,
+            iterations: 1,
+            parallelism: 4,
+            output_len: 32,
+        };
+        assert!(params.memory_cost <= ARGON2_DEFAULT_MEMORY_LIMIT);
+
+        // About 4 TiB must not be allocated.
+        let params = Argon2Params {
+            memory_cost: u32::MAX,
+            ..params
+        };
+        assert!(argon2id(b"password", b"somesalt", &params).is_err());
+        assert!(argon2id_keyed(b"password", b"somesalt", &[], &[], &params).is_err());
+
+        let params = Argon2Params {
+            memory_cost: 64,
+            ..params
+        };
+        assert!(argon2id_with_memory_limit(b"password", b"somesalt", &params, 64).is_ok());
+        assert!(argon2id_with_memory_limit(b"password", b"somesalt", &params, 63).is_err());
+    }
+}
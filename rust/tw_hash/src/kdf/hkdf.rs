@@ -0,0 +1,84 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::{Error, Result};
+use hkdf::Hkdf;
+use sha2::{Sha256, Sha512};
+
+/// Derives a key of `output_len` bytes using [HKDF](https://datatracker.ietf.org/doc/html/rfc5869) with SHA-256.
+/// Returns an error if `output_len` is zero or greater than `255 * 32`.
+pub fn hkdf_sha256(ikm: &[u8], salt: &[u8], info: &[u8], output_len: usize) -> Result<Vec<u8>> {
+    if output_len == 0 {
+        return Err(Error::InvalidHashLength);
+    }
+    let mut res = vec![0; output_len];
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand(info, &mut res)
+        .map_err(|_| Error::InvalidHashLength)?;
+    Ok(res)
+}
+
+/// Derives a key of `output_len` bytes using [HKDF](https://datatracker.ietf.org/doc/html/rfc5869) with SHA-512.
+/// Returns an error if `output_len` is zero or greater than `255 * 64`.
+pub fn hkdf_sha512(ikm: &[u8], salt: &[u8], info: &[u8], output_len: usize) -> Result<Vec<u8>> {
+    if output_len == 0 {
+        return Err(Error::InvalidHashLength);
+    }
+    let mut res = vec![0; output_len];
+    Hkdf::<Sha512>::new(Some(salt), ikm)
+        .expand(info, &mut res)
+        .map_err(|_| Error::InvalidHashLength)?;
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    #[test]
+    fn test_hkdf_sha256() {
+        // https://datatracker.ietf.org/doc/html/rfc5869#appendix-A.1
+        let ikm = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let actual = hkdf_sha256(&ikm, &salt, &info, 42).unwrap();
+        assert_eq!(
+            hex::encode(actual, false),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        );
+    }
+
+    #[test]
+    fn test_hkdf_sha256_empty_salt_info() {
+        // https://datatracker.ietf.org/doc/html/rfc5869#appendix-A.3
+        let ikm = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let actual = hkdf_sha256(&ikm, &[], &[], 42).unwrap();
+        assert_eq!(
+            hex::encode(actual, false),
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"
+        );
+    }
+
+    #[test]
+    fn test_hkdf_sha512() {
+        let ikm = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let actual = hkdf_sha512(&ikm, &salt, &info, 42).unwrap();
+        assert_eq!(
+            hex::encode(actual, false),
+            "832390086cda71fb47625bb5ceb168e4c8e26a1a16ed34d9fc7fe92c1481579338da362cb8d9f925d7cb"
+        );
+    }
+
+    #[test]
+    fn test_hkdf_invalid_output_len() {
+        assert!(hkdf_sha256(b"ikm", b"salt", b"info", 0).is_err());
+        assert!(hkdf_sha256(b"ikm", b"salt", b"info", 255 * 32 + 1).is_err());
+        assert!(hkdf_sha512(b"ikm", b"salt", b"info", 255 * 64 + 1).is_err());
+    }
+}
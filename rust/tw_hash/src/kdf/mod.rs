@@ -0,0 +1,12 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! Key derivation functions.
+
+pub mod argon2;
+pub mod hkdf;
+pub mod pbkdf2;
+pub mod scrypt;
@@ -0,0 +1,70 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::{Error, Result};
+use pbkdf2::pbkdf2_hmac;
+use sha2::{Sha256, Sha512};
+
+/// Derives a key of `output_len` bytes using PBKDF2-HMAC-SHA256.
+/// Returns an error if `rounds` or `output_len` is zero.
+pub fn pbkdf2_hmac_sha256(
+    password: &[u8],
+    salt: &[u8],
+    rounds: u32,
+    output_len: usize,
+) -> Result<Vec<u8>> {
+    let mut res = allocate_output(rounds, output_len)?;
+    pbkdf2_hmac::<Sha256>(password, salt, rounds, &mut res);
+    Ok(res)
+}
+
+/// Derives a key of `output_len` bytes using PBKDF2-HMAC-SHA512.
+/// Returns an error if `rounds` or `output_len` is zero.
+pub fn pbkdf2_hmac_sha512(
+    password: &[u8],
+    salt: &[u8],
+    rounds: u32,
+    output_len: usize,
+) -> Result<Vec<u8>> {
+    let mut res = allocate_output(rounds, output_len)?;
+    pbkdf2_hmac::<Sha512>(password, salt, rounds, &mut res);
+    Ok(res)
+}
+
+fn allocate_output(rounds: u32, output_len: usize) -> Result<Vec<u8>> {
+    if rounds == 0 {
+        return Err(Error::InvalidArgument);
+    }
+    if output_len == 0 {
+        return Err(Error::InvalidHashLength);
+    }
+    Ok(vec![0; output_len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    #[test]
+    fn test_pbkdf2_hmac_sha256() {
+        // https://datatracker.ietf.org/doc/html/rfc7914#section-11
+        let actual = pbkdf2_hmac_sha256(b"passwd", b"salt", 1, 64).unwrap();
+        assert_eq!(hex::encode(actual, false), "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783");
+    }
+
+    #[test]
+    fn test_pbkdf2_hmac_sha512() {
+        let actual = pbkdf2_hmac_sha512(b"password", b"salt", 1, 64).unwrap();
+        assert_eq!(hex::encode(actual, false), "867f70cf1ade02cff3752599a3a53dc4af34c7a669815ae5d513554e1c8cf252c02d470a285a0501bad999bfe943c08f050235d7d68b1da55e63f73b60a57fce");
+    }
+
+    #[test]
+    fn test_pbkdf2_invalid_params() {
+        assert!(pbkdf2_hmac_sha256(b"passwd", b"salt", 0, 64).is_err());
+        assert!(pbkdf2_hmac_sha512(b"passwd", b"salt", 1, 0).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::{Error, Result};
+
+/// The default limit of memory that can be allocated by [`scrypt`].
+/// It's enough to decrypt a Web3 keystore with the standard `n = 2^18, r = 8` parameters.
+pub const SCRYPT_DEFAULT_MEMORY_LIMIT: usize = 512 * 1024 * 1024;
+
+/// [scrypt](https://datatracker.ietf.org/doc/html/rfc7914) cost parameters.
+#[derive(Clone, Copy, Debug)]
+pub struct ScryptParams {
+    /// CPU/memory cost parameter. Must be a power of 2 greater than 1.
+    pub n: u64,
+    /// Block size parameter.
+    pub r: u32,
+    /// Parallelization parameter.
+    pub p: u32,
+    /// The number of bytes in the derived key.
+    pub output_len: usize,
+}
+
+impl ScryptParams {
+    /// Returns the number of bytes allocated by the algorithm: `128 * r * (n + p)`.
+    pub fn memory_required(&self) -> Option<usize> {
+        let n = usize::try_from(self.n).ok()?;
+        let p = usize::try_from(self.p).ok()?;
+        128_usize
+            .checked_mul(self.r as usize)?
+            .checked_mul(n.checked_add(p)?)
+    }
+
+    fn validate(&self, memory_limit: usize) -> Result<scrypt::Params> {
+        if self.n < 2 || !self.n.is_power_of_two() {
+            return Err(Error::InvalidArgument);
+        }
+        if self.output_len == 0 {
+            return Err(Error::InvalidHashLength);
+        }
+        match self.memory_required() {
+            Some(memory) if memory <= memory_limit => (),
+            _ => return Err(Error::InvalidArgument),
+        }
+
+        let log_n = self.n.trailing_zeros() as u8;
+        scrypt::Params::new(log_n, self.r, self.p, self.output_len)
+            .map_err(|_| Error::InvalidArgument)
+    }
+}
+
+/// Derives a key using scrypt with the [`SCRYPT_DEFAULT_MEMORY_LIMIT`] memory limit.
+pub fn scrypt(password: &[u8], salt: &[u8], params: &ScryptParams) -> Result<Vec<u8>> {
+    scrypt_with_memory_limit(password, salt, params, SCRYPT_DEFAULT_MEMORY_LIMIT)
+}
+
+/// Derives a key using scrypt.
+/// Returns an error if the cost parameters are invalid or require more than `memory_limit` bytes.
+pub fn scrypt_with_memory_limit(
+    password: &[u8],
+    salt: &[u8],
+    params: &ScryptParams,
+    memory_limit: usize,
+) -> Result<Vec<u8>> {
+    let scrypt_params = params.validate(memory_limit)?;
+
+    let mut res = vec![0; params.output_len];
+    scrypt::scrypt(password, salt, &scrypt_params, &mut res)
+        .map_err(|_| Error::InvalidHashLength)?;
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    #[test]
+    fn test_scrypt() {
+        // https://datatracker.ietf.org/doc/html/rfc7914#section-12
+        let params = ScryptParams {
+            n: 16,
+            r: 1,
+            p: 1,
+            output_len: 64,
+        };
+        let actual = scrypt(b"", b"", &params).unwrap();
+        assert_eq!(hex::encode(actual, false), "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906");
+
+        let params = ScryptParams {
+            n: 1024,
+            r: 8,
+            p: 16,
+            output_len: 64,
+        };
+        let actual = scrypt(b"password", b"NaCl", &params).unwrap();
+        assert_eq!(hex::encode(actual, false), "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640");
+    }
+
+    #[test]
+    fn test_scrypt_invalid_params() {
+        let valid = ScryptParams {
+            n: 16,
+            r: 1,
+            p: 1,
+            output_len: 64,
+        };
+
+        for n in [0, 1, 15] {
+            let params = ScryptParams { n, ..valid };
+            assert!(scrypt(b"", b"", &params).is_err(), "n={n}");
+        }
+        assert!(scrypt(b"", b"", &ScryptParams { r: 0, ..valid }).is_err());
+        assert!(scrypt(b"", b"", &ScryptParams { p: 0, ..valid }).is_err());
+        assert!(scrypt(
+            b"",
+            b"",
+            &ScryptParams {
+                output_len: 0,
+                ..valid
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_scrypt_memory_limit() {
+        // The standard Web3 keystore parameters fit the default limit.
+        let keystore = ScryptParams {
+            n: 1 << 18,
+            r: 8,
+            p: 1,
+            output_len: 32,
+        };
+        assert!(keystore.memory_required().unwrap() <= SCRYPT_DEFAULT_MEMORY_LIMIT);
+
+        let params = ScryptParams {
+            n: 1 << 20,
+            ..keystore
+        };
+        assert!(scrypt(b"password", b"salt", &params).is_err());
+
+        let params = ScryptParams {
+            n: 16,
+            r: 1,
+            p: 1,
+            output_len: 64,
+        };
+        assert!(scrypt_with_memory_limit(b"", b"", &params, 1024).is_err());
+    }
+}
@@ -6,18 +6,25 @@
 
 pub mod blake;
 pub mod blake2;
+pub mod blake3;
 pub mod ffi;
 pub mod groestl;
+pub mod hasher;
 pub mod hmac;
+pub mod kdf;
+pub mod merkle;
 pub mod ripemd;
 pub mod sha1;
 pub mod sha2;
 pub mod sha3;
+pub mod xxhash;
 
 mod hash_array;
 mod hash_wrapper;
+mod u256;
 
-pub use hash_array::{concat, H256, H264, H512, H520};
+pub use hash_array::{concat, H160, H256, H264, H512, H520};
+pub use u256::U256;
 
 use tw_encoding::hex::FromHexError;
 
@@ -28,6 +35,8 @@ pub enum Error {
     FromHexError(FromHexError),
     InvalidHashLength,
     InvalidArgument,
+    InvalidKeyLength,
+    InvalidSaltLength,
 }
 
 impl From<FromHexError> for Error {
@@ -0,0 +1,430 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! Binary Merkle trees over 32-byte leaf hashes with inclusion proofs.
+//!
+//! Leaves are expected to be hashes already (e.g. Bitcoin transaction IDs in the internal byte order,
+//! or `keccak256` of the encoded airdrop entries), so they are not hashed once again.
+
+use crate::blake2::blake2_b;
+use crate::sha2::sha256d;
+use crate::sha3::keccak256;
+use crate::{concat, Error, Result, H256, H512};
+
+/// A hash function that is used to combine two child nodes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MerkleHash {
+    Sha256d = 0,
+    Keccak256 = 1,
+    Blake2b256 = 2,
+}
+
+impl MerkleHash {
+    /// Returns `None` if the given hash kind is not supported.
+    pub fn from_raw(hash: u32) -> Option<MerkleHash> {
+        match hash {
+            0 => Some(MerkleHash::Sha256d),
+            1 => Some(MerkleHash::Keccak256),
+            2 => Some(MerkleHash::Blake2b256),
+            _ => None,
+        }
+    }
+
+    /// Computes `hash(left || right)`.
+    pub fn hash_pair(self, left: &H256, right: &H256) -> H256 {
+        let data: H512 = concat(*left, *right);
+        let hash = match self {
+            MerkleHash::Sha256d => sha256d(data.as_slice()),
+            MerkleHash::Keccak256 => keccak256(data.as_slice()),
+            MerkleHash::Blake2b256 => {
+                blake2_b(data.as_slice(), H256::len()).expect("Expected a valid blake2b hash size")
+            },
+        };
+        H256::try_from(hash.as_slice()).expect("Expected a 32 byte hash")
+    }
+}
+
+/// A rule that defines how the child nodes are paired.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MerklePairing {
+    /// Nodes are hashed in order, and the last node of an odd level is paired with itself.
+    /// This is how Bitcoin computes block merkle roots.
+    DuplicateLast = 0,
+    /// Each pair is sorted before hashing, and the last node of an odd level is promoted as is.
+    /// This is how OpenZeppelin `MerkleProof` compatible trees are built.
+    SortedPairs = 1,
+}
+
+impl MerklePairing {
+    /// Returns `None` if the given pairing rule is not supported.
+    pub fn from_raw(pairing: u32) -> Option<MerklePairing> {
+        match pairing {
+            0 => Some(MerklePairing::DuplicateLast),
+            1 => Some(MerklePairing::SortedPairs),
+            _ => None,
+        }
+    }
+}
+
+/// A merkle tree with all the intermediate levels.
+#[derive(Clone, Debug)]
+pub struct MerkleTree {
+    hash: MerkleHash,
+    pairing: MerklePairing,
+    /// The first level consists of the leaves, the last one consists of the root only.
+    levels: Vec<Vec<H256>>,
+}
+
+impl MerkleTree {
+    /// Builds a merkle tree over the given `leaves`.
+    /// Returns an error if there are no leaves.
+    pub fn new(leaves: Vec<H256>, hash: MerkleHash, pairing: MerklePairing) -> Result<MerkleTree> {
+        if leaves.is_empty() {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut levels = vec![leaves];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => combine(hash, pairing, left, right),
+                    [single] => match pairing {
+                        MerklePairing::DuplicateLast => combine(hash, pairing, single, single),
+                        MerklePairing::SortedPairs => *single,
+                    },
+                    _ => unreachable!("`chunks(2)` yields one or two nodes"),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Ok(MerkleTree {
+            hash,
+            pairing,
+            levels,
+        })
+    }
+
+    /// Returns the merkle root.
+    pub fn root(&self) -> H256 {
+        self.levels
+            .last()
+            .and_then(|root_level| root_level.first())
+            .copied()
+            .expect("There is at least one leaf")
+    }
+
+    /// Returns the number of leaves.
+    pub fn leaves_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Returns an inclusion proof of the leaf at the given `index`.
+    pub fn proof(&self, index: usize) -> Result<MerkleProof> {
+        if index >= self.leaves_count() {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut siblings = Vec::with_capacity(self.levels.len());
+        let mut node_index = index;
+        // Skip the root level.
+        for level in &self.levels[..self.levels.len() - 1] {
+            match level.get(node_index ^ 1) {
+                Some(sibling) => siblings.push(*sibling),
+                // The last node of an odd level.
+                None if self.pairing == MerklePairing::DuplicateLast => {
+                    siblings.push(level[node_index])
+                },
+                None => (),
+            }
+            node_index /= 2;
+        }
+
+        Ok(MerkleProof { index, siblings })
+    }
+
+    pub fn hash(&self) -> MerkleHash {
+        self.hash
+    }
+
+    pub fn pairing(&self) -> MerklePairing {
+        self.pairing
+    }
+}
+
+/// An inclusion proof of a leaf.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerkleProof {
+    /// The leaf index. It defines the order of the nodes when [`MerklePairing::DuplicateLast`] is used.
+    pub index: usize,
+    /// Sibling nodes from the leaf level up to the root.
+    pub siblings: Vec<H256>,
+}
+
+impl MerkleProof {
+    /// Parses the proof from the concatenated sibling hashes.
+    pub fn from_bytes(index: usize, bytes: &[u8]) -> Result<MerkleProof> {
+        if !bytes.len().is_multiple_of(H256::len()) {
+            return Err(Error::InvalidHashLength);
+        }
+
+        let siblings = bytes
+            .chunks(H256::len())
+            .map(H256::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(MerkleProof { index, siblings })
+    }
+
+    /// Returns the concatenated sibling hashes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.siblings
+            .iter()
+            .flat_map(|sibling| sibling.as_slice())
+            .copied()
+            .collect()
+    }
+
+    /// Computes the merkle root from the given `leaf` and the proof.
+    /// Returns `None` if the proof index is out of the tree bounds.
+    pub fn compute_root(
+        &self,
+        leaf: &H256,
+        hash: MerkleHash,
+        pairing: MerklePairing,
+    ) -> Option<H256> {
+        let mut node = *leaf;
+        let mut node_index = self.index;
+        for sibling in &self.siblings {
+            node = if node_index.is_multiple_of(2) {
+                combine(hash, pairing, &node, sibling)
+            } else {
+                combine(hash, pairing, sibling, &node)
+            };
+            node_index /= 2;
+        }
+
+        // The order of the nodes does not matter for sorted pairs.
+        if pairing == MerklePairing::DuplicateLast && node_index != 0 {
+            return None;
+        }
+        Some(node)
+    }
+
+    /// Checks if the `leaf` is included into the tree with the given `root`.
+    pub fn verify(
+        &self,
+        leaf: &H256,
+        root: &H256,
+        hash: MerkleHash,
+        pairing: MerklePairing,
+    ) -> bool {
+        self.compute_root(leaf, hash, pairing).as_ref() == Some(root)
+    }
+}
+
+fn combine(hash: MerkleHash, pairing: MerklePairing, left: &H256, right: &H256) -> H256 {
+    match pairing {
+        MerklePairing::SortedPairs if right.as_slice() < left.as_slice() => {
+            hash.hash_pair(right, left)
+        },
+        _ => hash.hash_pair(left, right),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reversed(hex: &'static str) -> H256 {
+        let mut hash = H256::from(hex);
+        hash.reverse();
+        hash
+    }
+
+    #[test]
+    fn test_bitcoin_block_merkle_root() {
+        // Bitcoin block #100000.
+        let txids = vec![
+            reversed("8c14f0db3df150123e6f3dbbf30f8b955a8249b62ac1d1ff16284aefa3d06d87"),
+            reversed("fff2525b8931402dd09222c50775608f75787bd2b87e56995a7bdd30f79702c4"),
+            reversed("6359f0868171b1d194cbee1af2f16ea598ae8fad666d9b012c8ed2b79a236ec4"),
+            reversed("e9a66845e05d5abc0ad04ec80f774a7e585c6e8db975962d069a522137b80c1d"),
+        ];
+        let expected = reversed("f3e94742aca4b5ef85488dc37c06c3282295ffec960994b2c0d5ac2a25a95766");
+
+        let tree = MerkleTree::new(
+            txids.clone(),
+            MerkleHash::Sha256d,
+            MerklePairing::DuplicateLast,
+        )
+        .unwrap();
+        assert_eq!(tree.root(), expected);
+
+        for (index, txid) in txids.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert_eq!(proof.siblings.len(), 2);
+            assert!(proof.verify(
+                txid,
+                &expected,
+                MerkleHash::Sha256d,
+                MerklePairing::DuplicateLast
+            ));
+        }
+
+        // The order of nodes matters.
+        let mut proof = tree.proof(0).unwrap();
+        proof.index = 1;
+        assert!(!proof.verify(
+            &txids[0],
+            &expected,
+            MerkleHash::Sha256d,
+            MerklePairing::DuplicateLast
+        ));
+        // The index is out of bounds.
+        proof.index = 4;
+        assert_eq!(
+            proof.compute_root(&txids[0], MerkleHash::Sha256d, MerklePairing::DuplicateLast),
+            None
+        );
+    }
+
+    #[test]
+    fn test_duplicate_last_odd_leaves() {
+        let leaves = vec![
+            H256::from([1; 32]),
+            H256::from([2; 32]),
+            H256::from([3; 32]),
+        ];
+        let tree = MerkleTree::new(
+            leaves.clone(),
+            MerkleHash::Sha256d,
+            MerklePairing::DuplicateLast,
+        )
+        .unwrap();
+
+        let hash = MerkleHash::Sha256d;
+        let expected = hash.hash_pair(
+            &hash.hash_pair(&leaves[0], &leaves[1]),
+            &hash.hash_pair(&leaves[2], &leaves[2]),
+        );
+        assert_eq!(tree.root(), expected);
+
+        let proof = tree.proof(2).unwrap();
+        assert_eq!(proof.siblings[0], leaves[2]);
+        assert!(proof.verify(&leaves[2], &expected, hash, MerklePairing::DuplicateLast));
+    }
+
+    #[test]
+    fn test_sorted_pairs() {
+        let hash = MerkleHash::Keccak256;
+        let leaves: Vec<H256> = (1..=5)
+            .map(|i| H256::try_from(keccak256(&[i]).as_slice()).unwrap())
+            .collect();
+        let tree = MerkleTree::new(leaves.clone(), hash, MerklePairing::SortedPairs).unwrap();
+
+        let sorted_pair = |a: &H256, b: &H256| {
+            if a.as_slice() <= b.as_slice() {
+                hash.hash_pair(a, b)
+            } else {
+                hash.hash_pair(b, a)
+            }
+        };
+        let left = sorted_pair(
+            &sorted_pair(&leaves[0], &leaves[1]),
+            &sorted_pair(&leaves[2], &leaves[3]),
+        );
+        // The fifth leaf is promoted up to the root level.
+        let expected = sorted_pair(&left, &leaves[4]);
+        assert_eq!(tree.root(), expected);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert!(proof.verify(leaf, &expected, hash, MerklePairing::SortedPairs));
+        }
+        assert_eq!(tree.proof(4).unwrap().siblings, vec![left]);
+
+        let proof = tree.proof(0).unwrap();
+        assert!(!proof.verify(&leaves[1], &expected, hash, MerklePairing::SortedPairs));
+    }
+
+    #[test]
+    fn test_blake2b_tree() {
+        let leaves = vec![H256::from([1; 32]), H256::from([2; 32])];
+        let tree = MerkleTree::new(
+            leaves.clone(),
+            MerkleHash::Blake2b256,
+            MerklePairing::DuplicateLast,
+        )
+        .unwrap();
+
+        let mut data = [1; 64];
+        data[32..].fill(2);
+        let expected = H256::try_from(blake2_b(&data, 32).unwrap().as_slice()).unwrap();
+        assert_eq!(tree.root(), expected);
+    }
+
+    #[test]
+    fn test_single_leaf() {
+        let leaf = H256::from([7; 32]);
+        let tree = MerkleTree::new(
+            vec![leaf],
+            MerkleHash::Sha256d,
+            MerklePairing::DuplicateLast,
+        )
+        .unwrap();
+        assert_eq!(tree.root(), leaf);
+
+        let proof = tree.proof(0).unwrap();
+        assert!(proof.siblings.is_empty());
+        assert!(proof.verify(
+            &leaf,
+            &leaf,
+            MerkleHash::Sha256d,
+            MerklePairing::DuplicateLast
+        ));
+    }
+
+    #[test]
+    fn test_proof_bytes() {
+        let leaves = vec![
+            H256::from([1; 32]),
+            H256::from([2; 32]),
+            H256::from([3; 32]),
+        ];
+        let tree =
+            MerkleTree::new(leaves, MerkleHash::Keccak256, MerklePairing::SortedPairs).unwrap();
+
+        let proof = tree.proof(1).unwrap();
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(MerkleProof::from_bytes(1, &bytes).unwrap(), proof);
+        assert!(MerkleProof::from_bytes(1, &bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        assert!(MerkleTree::new(
+            Vec::new(),
+            MerkleHash::Sha256d,
+            MerklePairing::DuplicateLast
+        )
+        .is_err());
+
+        let tree = MerkleTree::new(
+            vec![H256::from([1; 32])],
+            MerkleHash::Sha256d,
+            MerklePairing::DuplicateLast,
+        )
+        .unwrap();
+        assert!(tree.proof(1).is_err());
+
+        assert_eq!(MerkleHash::from_raw(3), None);
+        assert_eq!(MerklePairing::from_raw(2), None);
+    }
+}
@@ -5,8 +5,11 @@
 // file LICENSE at the root of the source code distribution tree.
 
 use crate::hash_wrapper::hasher;
+use crate::hasher::DigestHasher;
 use ripemd::Ripemd160;
 
+pub type Ripemd160Hasher = DigestHasher<Ripemd160>;
+
 pub fn ripemd_160(input: &[u8]) -> Vec<u8> {
     hasher::<Ripemd160>(input)
 }
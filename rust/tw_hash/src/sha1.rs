@@ -5,8 +5,11 @@
 // file LICENSE at the root of the source code distribution tree.
 
 use crate::hash_wrapper::hasher;
+use crate::hasher::DigestHasher;
 use sha1::Sha1;
 
+pub type Sha1Hasher = DigestHasher<Sha1>;
+
 pub fn sha1(input: &[u8]) -> Vec<u8> {
     hasher::<Sha1>(input)
 }
@@ -5,8 +5,13 @@
 // file LICENSE at the root of the source code distribution tree.
 
 use crate::hash_wrapper::hasher;
+use crate::hasher::DigestHasher;
 use sha2::{Sha256, Sha512, Sha512_256};
 
+pub type Sha256Hasher = DigestHasher<Sha256>;
+pub type Sha512Hasher = DigestHasher<Sha512>;
+pub type Sha512_256Hasher = DigestHasher<Sha512_256>;
+
 pub fn sha256(input: &[u8]) -> Vec<u8> {
     hasher::<Sha256>(input)
 }
@@ -18,3 +23,19 @@ pub fn sha512(input: &[u8]) -> Vec<u8> {
 pub fn sha512_256(input: &[u8]) -> Vec<u8> {
     hasher::<Sha512_256>(input)
 }
+
+/// Computes the double SHA-256 hash: `sha256(sha256(input))`.
+pub fn sha256d(input: &[u8]) -> Vec<u8> {
+    sha256(&sha256(input))
+}
+
+/// Computes a [BIP340](https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki#design) tagged hash:
+/// `sha256(sha256(tag) || sha256(tag) || input)`.
+pub fn sha256_tagged(tag: &[u8], input: &[u8]) -> Vec<u8> {
+    let tag_hash = sha256(tag);
+    let mut data = Vec::with_capacity(tag_hash.len() * 2 + input.len());
+    data.extend_from_slice(&tag_hash);
+    data.extend_from_slice(&tag_hash);
+    data.extend_from_slice(input);
+    sha256(&data)
+}
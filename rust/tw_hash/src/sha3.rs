@@ -5,8 +5,14 @@
 // file LICENSE at the root of the source code distribution tree.
 
 use crate::hash_wrapper::hasher;
+use crate::hasher::DigestHasher;
 use sha3::{Keccak256, Keccak512, Sha3_256, Sha3_512};
 
+pub type Keccak256Hasher = DigestHasher<Keccak256>;
+pub type Keccak512Hasher = DigestHasher<Keccak512>;
+pub type Sha3_256Hasher = DigestHasher<Sha3_256>;
+pub type Sha3_512Hasher = DigestHasher<Sha3_512>;
+
 pub fn keccak256(input: &[u8]) -> Vec<u8> {
     hasher::<Keccak256>(input)
 }
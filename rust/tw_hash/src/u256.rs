@@ -0,0 +1,372 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::{Error, H256};
+use std::fmt;
+use std::str::FromStr;
+use tw_encoding::rlp::{RlpEncode, RlpStream};
+
+/// Lints are triggered by the `construct_uint!` macro expansion.
+#[allow(clippy::assign_op_pattern, clippy::manual_div_ceil)]
+mod inner {
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
+}
+
+/// 256-bit unsigned integer.
+/// All arithmetic operations are checked, i.e. return `None` on overflow.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct U256(inner::U256);
+
+impl U256 {
+    pub const LEN: usize = 32;
+
+    pub const ZERO: U256 = U256(inner::U256::zero());
+    pub const MAX: U256 = U256(inner::U256::MAX);
+
+    pub fn zero() -> U256 {
+        U256::ZERO
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Returns the number of significant bits.
+    pub fn bits(&self) -> usize {
+        self.0.bits()
+    }
+
+    pub fn checked_add(self, rhs: U256) -> Option<U256> {
+        self.0.checked_add(rhs.0).map(U256)
+    }
+
+    pub fn checked_sub(self, rhs: U256) -> Option<U256> {
+        self.0.checked_sub(rhs.0).map(U256)
+    }
+
+    pub fn checked_mul(self, rhs: U256) -> Option<U256> {
+        self.0.checked_mul(rhs.0).map(U256)
+    }
+
+    /// Returns `None` if `rhs` is zero.
+    pub fn checked_div(self, rhs: U256) -> Option<U256> {
+        self.0.checked_div(rhs.0).map(U256)
+    }
+
+    /// Returns `None` if `rhs` is zero.
+    pub fn checked_rem(self, rhs: U256) -> Option<U256> {
+        self.0.checked_rem(rhs.0).map(U256)
+    }
+
+    /// Parses a big-endian number of at most 32 bytes. Leading zeros are allowed.
+    pub fn from_big_endian_slice(bytes: &[u8]) -> Result<U256, Error> {
+        if bytes.len() > U256::LEN {
+            return Err(Error::InvalidHashLength);
+        }
+        Ok(U256(inner::U256::from_big_endian(bytes)))
+    }
+
+    /// Parses a little-endian number of at most 32 bytes. Trailing zeros are allowed.
+    pub fn from_little_endian_slice(bytes: &[u8]) -> Result<U256, Error> {
+        if bytes.len() > U256::LEN {
+            return Err(Error::InvalidHashLength);
+        }
+        Ok(U256(inner::U256::from_little_endian(bytes)))
+    }
+
+    /// Returns the 32-byte big-endian representation.
+    pub fn to_big_endian(&self) -> H256 {
+        let mut res = H256::new();
+        self.0.to_big_endian(res.as_mut_slice());
+        res
+    }
+
+    /// Returns the 32-byte little-endian representation.
+    pub fn to_little_endian(&self) -> H256 {
+        let mut res = H256::new();
+        self.0.to_little_endian(res.as_mut_slice());
+        res
+    }
+
+    /// Returns the big-endian representation without leading zeros.
+    /// Zero is represented as an empty array.
+    pub fn to_big_endian_compact(&self) -> Vec<u8> {
+        let bytes = self.to_big_endian();
+        let leading_zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+        bytes[leading_zeros..].to_vec()
+    }
+
+    /// Parses a decimal string, e.g. "1000000000000000000".
+    pub fn from_dec_str(s: &str) -> Result<U256, Error> {
+        if s.is_empty() {
+            return Err(Error::InvalidArgument);
+        }
+        inner::U256::from_dec_str(s)
+            .map(U256)
+            .map_err(|_| Error::InvalidArgument)
+    }
+
+    /// Parses a hex string with an optional `0x` prefix, e.g. "0xde0b6b3a7640000".
+    /// The number of hex digits may be odd.
+    pub fn from_hex_str(s: &str) -> Result<U256, Error> {
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        if digits.is_empty() {
+            return Err(Error::InvalidArgument);
+        }
+        inner::U256::from_str_radix(digits, 16)
+            .map(U256)
+            .map_err(|_| Error::InvalidArgument)
+    }
+
+    /// Returns a `0x` prefixed hex string without leading zeros, e.g. "0x0" or "0xde0b6b3a7640000".
+    pub fn to_hex_string(&self) -> String {
+        format!("{:#x}", self.0)
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($ty:ty),+) => {
+        $(
+            impl From<$ty> for U256 {
+                fn from(num: $ty) -> Self {
+                    U256(inner::U256::from(num))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_primitive!(u8, u16, u32, u64, u128);
+
+impl TryFrom<U256> for u64 {
+    type Error = Error;
+
+    fn try_from(num: U256) -> Result<Self, Self::Error> {
+        u64::try_from(num.0).map_err(|_| Error::InvalidArgument)
+    }
+}
+
+impl TryFrom<U256> for u128 {
+    type Error = Error;
+
+    fn try_from(num: U256) -> Result<Self, Self::Error> {
+        u128::try_from(num.0).map_err(|_| Error::InvalidArgument)
+    }
+}
+
+/// Interprets the hash as a big-endian number.
+impl From<H256> for U256 {
+    fn from(hash: H256) -> Self {
+        U256(inner::U256::from_big_endian(hash.as_slice()))
+    }
+}
+
+/// Returns the big-endian representation.
+impl From<U256> for H256 {
+    fn from(num: U256) -> Self {
+        num.to_big_endian()
+    }
+}
+
+/// Parses a decimal string.
+impl FromStr for U256 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        U256::from_dec_str(s)
+    }
+}
+
+/// Formats the number as a decimal string.
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+/// Encodes the number as a minimal big-endian byte string, i.e. zero is an empty string.
+impl RlpEncode for U256 {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(&self.to_big_endian_compact());
+    }
+}
+
+#[cfg(feature = "serde")]
+mod impl_serde {
+    use super::U256;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes the number from a decimal string.
+    impl<'de> Deserialize<'de> for U256 {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let num = String::deserialize(deserializer)?;
+            U256::from_dec_str(&num).map_err(|e| Error::custom(format!("{e:?}")))
+        }
+    }
+
+    /// Serializes the number as a decimal string.
+    impl Serialize for U256 {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.to_string().serialize(serializer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::{hex, rlp};
+
+    const ONE_ETHER: &str = "1000000000000000000";
+
+    #[test]
+    fn test_u256_checked_arithmetic() {
+        let a = U256::from(7_u64);
+        let b = U256::from(3_u64);
+        assert_eq!(a.checked_add(b), Some(U256::from(10_u64)));
+        assert_eq!(a.checked_sub(b), Some(U256::from(4_u64)));
+        assert_eq!(a.checked_mul(b), Some(U256::from(21_u64)));
+        assert_eq!(a.checked_div(b), Some(U256::from(2_u64)));
+        assert_eq!(a.checked_rem(b), Some(U256::from(1_u64)));
+
+        assert_eq!(b.checked_sub(a), None);
+        assert_eq!(a.checked_div(U256::zero()), None);
+        assert_eq!(U256::MAX.checked_add(U256::from(1_u8)), None);
+        assert_eq!(U256::MAX.checked_mul(U256::from(2_u8)), None);
+    }
+
+    #[test]
+    fn test_u256_fee_math() {
+        // 21000 gas * 20 Gwei.
+        let gas_limit = U256::from(21_000_u64);
+        let gas_price = U256::from_dec_str("20000000000").unwrap();
+        let fee = gas_limit.checked_mul(gas_price).unwrap();
+        assert_eq!(fee.to_string(), "420000000000000");
+
+        let balance = U256::from_dec_str(ONE_ETHER).unwrap();
+        let rest = balance.checked_sub(fee).unwrap();
+        assert_eq!(rest.to_string(), "999580000000000000");
+    }
+
+    #[test]
+    fn test_u256_strings() {
+        let num = U256::from_dec_str(ONE_ETHER).unwrap();
+        assert_eq!(num, U256::from_hex_str("0xde0b6b3a7640000").unwrap());
+        assert_eq!(num, U256::from_hex_str("0de0b6b3a7640000").unwrap());
+        assert_eq!(num, ONE_ETHER.parse().unwrap());
+        assert_eq!(num.to_string(), ONE_ETHER);
+        assert_eq!(num.to_hex_string(), "0xde0b6b3a7640000");
+        assert_eq!(format!("{num:x}"), "de0b6b3a7640000");
+        assert_eq!(U256::zero().to_hex_string(), "0x0");
+
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(U256::MAX.to_string(), max);
+        assert_eq!(U256::from_dec_str(max).unwrap(), U256::MAX);
+    }
+
+    #[test]
+    fn test_u256_strings_invalid() {
+        U256::from_dec_str("").unwrap_err();
+        U256::from_dec_str("-1").unwrap_err();
+        U256::from_dec_str("0x10").unwrap_err();
+        // U256::MAX + 1
+        U256::from_dec_str(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936",
+        )
+        .unwrap_err();
+
+        U256::from_hex_str("").unwrap_err();
+        U256::from_hex_str("0x").unwrap_err();
+        U256::from_hex_str("0xzz").unwrap_err();
+        U256::from_hex_str(&"f".repeat(65)).unwrap_err();
+    }
+
+    #[test]
+    fn test_u256_bytes() {
+        let num = U256::from(0x0102_u64);
+
+        let be = num.to_big_endian();
+        assert_eq!(be[30..], [1, 2]);
+        assert_eq!(U256::from_big_endian_slice(be.as_slice()).unwrap(), num);
+        assert_eq!(U256::from_big_endian_slice(&[1, 2]).unwrap(), num);
+
+        let le = num.to_little_endian();
+        assert_eq!(le[..2], [2, 1]);
+        assert_eq!(U256::from_little_endian_slice(le.as_slice()).unwrap(), num);
+        assert_eq!(U256::from_little_endian_slice(&[2, 1]).unwrap(), num);
+
+        assert_eq!(num.to_big_endian_compact(), vec![1, 2]);
+        assert!(U256::zero().to_big_endian_compact().is_empty());
+
+        assert!(matches!(
+            U256::from_big_endian_slice(&[0; 33]),
+            Err(Error::InvalidHashLength)
+        ));
+        assert!(matches!(
+            U256::from_little_endian_slice(&[0; 33]),
+            Err(Error::InvalidHashLength)
+        ));
+    }
+
+    #[test]
+    fn test_u256_h256() {
+        let hash = H256::from("0000000000000000000000000000000000000000000000000de0b6b3a7640000");
+        let num = U256::from(hash);
+        assert_eq!(num.to_string(), ONE_ETHER);
+        assert_eq!(H256::from(num), hash);
+    }
+
+    #[test]
+    fn test_u256_primitives() {
+        let num = U256::from(u64::MAX);
+        assert_eq!(u64::try_from(num).unwrap(), u64::MAX);
+
+        let num = num.checked_add(U256::from(1_u8)).unwrap();
+        u64::try_from(num).unwrap_err();
+        assert_eq!(u128::try_from(num).unwrap(), u64::MAX as u128 + 1);
+        u128::try_from(U256::MAX).unwrap_err();
+    }
+
+    #[test]
+    fn test_u256_rlp() {
+        let rlp = |num: U256| hex::encode(rlp::encode(&num), false);
+        assert_eq!(rlp(U256::zero()), "80");
+        assert_eq!(rlp(U256::from(15_u8)), "0f");
+        assert_eq!(
+            rlp(U256::from_dec_str(ONE_ETHER).unwrap()),
+            "880de0b6b3a7640000"
+        );
+        assert_eq!(
+            rlp(U256::MAX),
+            "a0ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        );
+    }
+
+    #[test]
+    fn test_u256_serde() {
+        let num: U256 = serde_json::from_str(&format!("\"{ONE_ETHER}\"")).unwrap();
+        assert_eq!(num.to_string(), ONE_ETHER);
+        assert_eq!(
+            serde_json::to_string(&num).unwrap(),
+            format!("\"{ONE_ETHER}\"")
+        );
+    }
+}
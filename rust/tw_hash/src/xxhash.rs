@@ -0,0 +1,123 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! Substrate storage hashers based on the 64-bit [xxHash](https://github.com/Cyan4973/xxHash) algorithm.
+//! Ported from `sp_core::hashing` and `frame_support::StorageHasher`.
+
+use crate::blake2::blake2_b;
+use std::hash::Hasher;
+use twox_hash::XxHash64;
+
+const BLAKE2_128_LEN: usize = 16;
+
+/// Computes the 64-bit xxHash of the `input` with the given `seed`.
+pub fn xxhash64(input: &[u8], seed: u64) -> u64 {
+    let mut hasher = XxHash64::with_seed(seed);
+    hasher.write(input);
+    hasher.finish()
+}
+
+/// Computes `xxhash64` with each seed in `0..N / 8` and concatenates the little-endian results.
+fn twox<const N: usize>(input: &[u8]) -> Vec<u8> {
+    (0..(N / 8) as u64)
+        .flat_map(|seed| xxhash64(input, seed).to_le_bytes())
+        .collect()
+}
+
+/// Computes the 64-bit Twox hash (8 bytes).
+pub fn twox_64(input: &[u8]) -> Vec<u8> {
+    twox::<8>(input)
+}
+
+/// Computes the 128-bit Twox hash (16 bytes).
+pub fn twox_128(input: &[u8]) -> Vec<u8> {
+    twox::<16>(input)
+}
+
+/// Computes the 256-bit Twox hash (32 bytes).
+pub fn twox_256(input: &[u8]) -> Vec<u8> {
+    twox::<32>(input)
+}
+
+/// Returns `twox_64(input) || input` that is used as a transparent storage map key.
+pub fn twox_64_concat(input: &[u8]) -> Vec<u8> {
+    let mut res = twox_64(input);
+    res.extend_from_slice(input);
+    res
+}
+
+/// Returns `blake2_128(input) || input` that is used as a transparent storage map key.
+pub fn blake2_128_concat(input: &[u8]) -> Vec<u8> {
+    let mut res = blake2_b(input, BLAKE2_128_LEN).expect("Expected a valid blake2b hash size");
+    res.extend_from_slice(input);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    #[test]
+    fn test_xxhash64() {
+        assert_eq!(xxhash64(b"", 0), 0xef46db3751d8e999);
+        assert_eq!(hex::encode(twox_64(b""), false), "99e9d85137db46ef");
+    }
+
+    #[test]
+    fn test_twox_128() {
+        let tests = [
+            ("Sudo", "5c0d1176a568c1f92944340dbfed9e9c"),
+            ("Key", "530ebca703c85910e7164cb7d1c9e47b"),
+            ("System", "26aa394eea5630e07c48ae0c9558cef7"),
+            ("Account", "b99d880ec681799c0cf30e8886371da9"),
+        ];
+        for (input, expected) in tests {
+            assert_eq!(hex::encode(twox_128(input.as_bytes()), false), expected);
+        }
+    }
+
+    #[test]
+    fn test_twox_256() {
+        let tests = [
+            (
+                "",
+                "99e9d85137db46ef4bbea33613baafd56f963c64b1f3685a4eb4abd67ff6203a",
+            ),
+            (
+                "abc",
+                "990977adf52cbc440889329981caa9bef7da5770b2b8a05303b75d95360dd62b",
+            ),
+            (
+                "Sudo",
+                "5c0d1176a568c1f92944340dbfed9e9c17f4f8868e154c17fe31e7bc731be322",
+            ),
+            (
+                "hello worldhello worldhello worldhello worldhello world",
+                "6c3c32b148d54316d62dd21424c3475f55f304b25be51d1d93d9de07cef645c8",
+            ),
+        ];
+        for (input, expected) in tests {
+            assert_eq!(hex::encode(twox_256(input.as_bytes()), false), expected);
+        }
+    }
+
+    #[test]
+    fn test_storage_map_keys() {
+        // `System.Account` storage key of the `//Alice` development account.
+        let alice = hex::decode("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")
+            .unwrap();
+        let mut key = twox_128(b"System");
+        key.extend(twox_128(b"Account"));
+        key.extend(blake2_128_concat(&alice));
+        assert_eq!(hex::encode(key, false), "26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9de1e86a9a8c739864cf3cc5ec2bea59fd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+
+        let index = 7u32.to_le_bytes();
+        let actual = twox_64_concat(&index);
+        assert_eq!(actual[..8], twox_64(&index));
+        assert_eq!(actual[8..], index);
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::ffi::CString;
+use tw_encoding::ffi::{Base58Alphabet, CEncodingCode};
+use tw_encoding::hex;
+use tw_hash::ffi::{decode_base58_check, encode_base58_check, Base58ChecksumHasher};
+
+fn test_base58_check_impl(
+    hex_data: &str,
+    expected: &str,
+    alphabet: Base58Alphabet,
+    hasher: Base58ChecksumHasher,
+) {
+    let data = hex::decode(hex_data).unwrap();
+
+    let result_ptr = unsafe { encode_base58_check(data.as_ptr(), data.len(), alphabet, hasher) };
+    let result = unsafe { CString::from_raw(result_ptr) };
+    assert_eq!(result.to_str().unwrap(), expected);
+
+    let input = CString::new(expected).unwrap();
+    let decoded = unsafe {
+        decode_base58_check(input.as_ptr(), alphabet, hasher)
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(decoded, data);
+}
+
+fn test_base58_check_error_impl(
+    input: &str,
+    alphabet: Base58Alphabet,
+    hasher: Base58ChecksumHasher,
+    expected: CEncodingCode,
+) {
+    let input = CString::new(input).unwrap();
+    let res = unsafe { decode_base58_check(input.as_ptr(), alphabet, hasher) };
+    assert_eq!(res.into_result().unwrap_err(), expected as i32);
+}
+
+#[test]
+fn test_base58_check_bitcoin() {
+    test_base58_check_impl(
+        "00769bdff96a02f9135a1d19b749db6a78fe07dc90",
+        "1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcx",
+        Base58Alphabet::Bitcoin,
+        Base58ChecksumHasher::Sha256d,
+    );
+}
+
+#[test]
+fn test_base58_check_ripple() {
+    test_base58_check_impl(
+        "002decab42ca805119a9ba2ff305c9afa12f0b86a1",
+        "rnBFvgZphmN39GWzUJeUitaP22Fr9be75H",
+        Base58Alphabet::Ripple,
+        Base58ChecksumHasher::Sha256d,
+    );
+}
+
+#[test]
+fn test_base58_check_decred() {
+    test_base58_check_impl(
+        "073fe280cb6e66b96679aec288b1fbdbd4db08077a1b",
+        "DsmcYVbP1Nmag2H4AS17UTvmWXmGeA7nLDx",
+        Base58Alphabet::Bitcoin,
+        Base58ChecksumHasher::Blake256d,
+    );
+    test_base58_check_error_impl(
+        "DsmcYVbP1Nmag2H4AS17UTvmWXmGeA7nLDx",
+        Base58Alphabet::Bitcoin,
+        Base58ChecksumHasher::Sha256d,
+        CEncodingCode::InvalidChecksum,
+    );
+}
+
+#[test]
+fn test_base58_check_groestlcoin() {
+    test_base58_check_impl(
+        "2498af0aaca388a7e1024f505c033626d908e3b54a",
+        "Fj62rBJi8LvbmWu2jzkaUX1NFXLEqDLoZM",
+        Base58Alphabet::Bitcoin,
+        Base58ChecksumHasher::Groestl512d,
+    );
+    test_base58_check_impl(
+        "050055b0c94df477ee6b9f75185dfc9aa8ce2e52e4",
+        "31inaRqambLsd9D7Ke4USZmGEVd3PHkh7P",
+        Base58Alphabet::Bitcoin,
+        Base58ChecksumHasher::Groestl512d,
+    );
+    test_base58_check_error_impl(
+        "Fj62rBJi8LvbmWu2jzkaUX1NFXLEqDLoZM",
+        Base58Alphabet::Bitcoin,
+        Base58ChecksumHasher::Sha256d,
+        CEncodingCode::InvalidChecksum,
+    );
+}
+
+#[test]
+fn test_base58_check_invalid() {
+    // The last character of a valid address is changed.
+    test_base58_check_error_impl(
+        "1Bp9U1ogV3A14FMvKbRJms7ctyso4Z4Tcy",
+        Base58Alphabet::Bitcoin,
+        Base58ChecksumHasher::Sha256d,
+        CEncodingCode::InvalidChecksum,
+    );
+    // Too short to contain a checksum.
+    test_base58_check_error_impl(
+        "111",
+        Base58Alphabet::Bitcoin,
+        Base58ChecksumHasher::Sha256d,
+        CEncodingCode::InvalidInput,
+    );
+    // Invalid character.
+    test_base58_check_error_impl(
+        "0OIl",
+        Base58Alphabet::Bitcoin,
+        Base58ChecksumHasher::Sha256d,
+        CEncodingCode::InvalidInput,
+    );
+}
@@ -7,9 +7,16 @@
 use tw_encoding::hex;
 use tw_encoding::hex::FromHexError;
 use tw_hash::ffi::{
-    blake2_b, blake2_b_personal, blake_256, groestl_512, hmac__sha256, keccak256, keccak512,
-    ripemd_160, sha1, sha256, sha3__256, sha3__512, sha512, sha512_256, CHashingCode,
+    blake2_128_concat, blake2_b, blake2_b_keyed, blake2_b_personal, blake2_b_salted, blake2_s_256,
+    blake2_s_keyed, blake3, blake3_keyed, blake3_xof, blake_256, groestl_512, hmac__sha256,
+    hmac__sha512, kdf_argon2id, kdf_hkdf_sha256, kdf_hkdf_sha512, kdf_pbkdf2_hmac_sha256,
+    kdf_pbkdf2_hmac_sha512, kdf_scrypt, keccak256, keccak512, ripemd_160, sha1, sha256, sha3__256,
+    sha3__512, sha512, sha512_256, tw_hasher_create, tw_hasher_delete, tw_hasher_finalize,
+    tw_hasher_reset, tw_hasher_update, tw_hmac, tw_merkle_proof, tw_merkle_root,
+    tw_merkle_verify_proof, twox_128, twox_256, twox_64, twox_64_concat, CHashingCode,
 };
+use tw_hash::hasher::HasherKind;
+use tw_hash::merkle::{MerkleHash, MerklePairing};
 use tw_hash::Error;
 use tw_memory::ffi::c_byte_array::CByteArray;
 use tw_memory::ffi::c_result::ErrorCode;
@@ -55,6 +62,141 @@ fn test_blake2b_personal() {
     assert_eq!(hex::encode(actual, false), expected);
 }
 
+#[test]
+fn test_blake2b_keyed() {
+    let key = b"secret key";
+    let content = b"Hello world";
+    let actual = unsafe {
+        blake2_b_keyed(content.as_ptr(), content.len(), 32, key.as_ptr(), key.len())
+            .unwrap()
+            .into_vec()
+    };
+    let expected = "2246b5018dd885a773ea8afab5cc34b8e9b07127530833bea248529ffa2b28a6";
+    assert_eq!(hex::encode(actual, false), expected);
+
+    let too_long_key = [0; 65];
+    let err = unsafe {
+        blake2_b_keyed(
+            content.as_ptr(),
+            content.len(),
+            32,
+            too_long_key.as_ptr(),
+            too_long_key.len(),
+        )
+        .into_result()
+        .unwrap_err()
+    };
+    assert_eq!(err, CHashingCode::InvalidKeyLength as ErrorCode);
+}
+
+#[test]
+fn test_blake2b_salted() {
+    let key = b"secret key";
+    let salt = b"0123456789abcdef";
+    let personal_data = b"MyApp Files Hash";
+    let content = b"Hello world";
+    let actual = unsafe {
+        blake2_b_salted(
+            content.as_ptr(),
+            content.len(),
+            32,
+            key.as_ptr(),
+            key.len(),
+            salt.as_ptr(),
+            salt.len(),
+            personal_data.as_ptr(),
+            personal_data.len(),
+        )
+        .unwrap()
+        .into_vec()
+    };
+    let expected = "2cf0028ee50b7c209d7b193000b247f8351b889ba6f4d54bdf499e5fdf2ee91e";
+    assert_eq!(hex::encode(actual, false), expected);
+
+    let too_long_salt = [0; 17];
+    let err = unsafe {
+        blake2_b_salted(
+            content.as_ptr(),
+            content.len(),
+            32,
+            std::ptr::null(),
+            0,
+            too_long_salt.as_ptr(),
+            too_long_salt.len(),
+            std::ptr::null(),
+            0,
+        )
+        .into_result()
+        .unwrap_err()
+    };
+    assert_eq!(err, CHashingCode::InvalidSaltLength as ErrorCode);
+}
+
+#[test]
+fn test_blake2s() {
+    test_hash_helper(
+        blake2_s_256,
+        b"abc",
+        "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+    );
+
+    let key = b"secret key";
+    let content = b"Hello world";
+    let actual = unsafe {
+        blake2_s_keyed(content.as_ptr(), content.len(), 16, key.as_ptr(), key.len())
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(
+        hex::encode(actual, false),
+        "6d4f4dc0be56d28e8106bbc6eaa621f1"
+    );
+
+    let err = unsafe {
+        blake2_s_keyed(content.as_ptr(), content.len(), 64, std::ptr::null(), 0)
+            .into_result()
+            .unwrap_err()
+    };
+    assert_eq!(err, CHashingCode::InvalidHashLength as ErrorCode);
+}
+
+#[test]
+fn test_blake3() {
+    test_hash_helper(
+        blake3,
+        b"abc",
+        "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+    );
+
+    let key = b"whats the Elvish word for friend";
+    let actual = unsafe {
+        blake3_keyed(b"".as_ptr(), 0, key.as_ptr(), key.len())
+            .unwrap()
+            .into_vec()
+    };
+    let expected = "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26";
+    assert_eq!(hex::encode(actual, false), expected);
+
+    let err = unsafe {
+        blake3_keyed(b"".as_ptr(), 0, key.as_ptr(), 16)
+            .into_result()
+            .unwrap_err()
+    };
+    assert_eq!(err, CHashingCode::InvalidKeyLength as ErrorCode);
+
+    let content = b"abc";
+    let actual = unsafe {
+        blake3_xof(content.as_ptr(), content.len(), 48)
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(actual.len(), 48);
+    assert_eq!(
+        hex::encode(&actual[..32], false),
+        "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+    );
+}
+
 #[test]
 fn test_blake_256() {
     test_hash_helper(
@@ -89,6 +231,54 @@ fn test_hmac_sha256() {
     assert_eq!(hex::encode(actual, false), expected);
 }
 
+#[test]
+fn test_hmac_sha512() {
+    let key = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+    let data = b"Hi There";
+    let actual =
+        unsafe { hmac__sha512(key.as_ptr(), key.len(), data.as_ptr(), data.len()).into_vec() };
+    let expected = "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854";
+    assert_eq!(hex::encode(actual, false), expected);
+}
+
+#[test]
+fn test_tw_hmac() {
+    let (key, data) = (b"Jefe", b"what do ya want for nothing?");
+    let actual = unsafe {
+        tw_hmac(
+            HasherKind::Sha1 as u32,
+            key.as_ptr(),
+            key.len(),
+            data.as_ptr(),
+            data.len(),
+        )
+    };
+    let actual = unsafe { actual.unwrap().into_vec() };
+    assert_eq!(
+        hex::encode(actual, false),
+        "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+    );
+
+    // Unknown hash kind.
+    let actual = unsafe { tw_hmac(100, key.as_ptr(), key.len(), data.as_ptr(), data.len()) };
+    assert_eq!(
+        actual.into_result().unwrap_err(),
+        CHashingCode::InvalidArgument as ErrorCode
+    );
+
+    // Null key.
+    let actual = unsafe {
+        tw_hmac(
+            HasherKind::Sha256 as u32,
+            std::ptr::null(),
+            0,
+            data.as_ptr(),
+            data.len(),
+        )
+    };
+    assert!(actual.is_err());
+}
+
 #[test]
 fn test_ripemd160() {
     test_hash_helper(
@@ -216,11 +406,36 @@ fn test_sha3_512() {
     );
 }
 
+#[test]
+fn test_twox() {
+    test_hash_helper(twox_64, b"", "99e9d85137db46ef");
+    test_hash_helper(twox_128, b"System", "26aa394eea5630e07c48ae0c9558cef7");
+    test_hash_helper(twox_128, b"Balances", "c2261276cc9d1f8598ea4b6a74b15c2f");
+
+    let actual = unsafe { twox_256(b"Sudo".as_ptr(), 4).into_vec() };
+    assert_eq!(
+        hex::encode(&actual[..16], false),
+        "5c0d1176a568c1f92944340dbfed9e9c"
+    );
+}
+
+#[test]
+fn test_substrate_storage_map_keys() {
+    test_hash_helper(
+        blake2_128_concat,
+        &hex::decode("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d").unwrap(),
+        "de1e86a9a8c739864cf3cc5ec2bea59fd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+    );
+    test_hash_helper(twox_64_concat, b"", "99e9d85137db46ef");
+}
+
 #[test]
 fn test_c_hashing_error_convert() {
     assert_eq!(ErrorCode::from(CHashingCode::Ok), 0);
     assert_eq!(ErrorCode::from(CHashingCode::InvalidHashLength), 1);
     assert_eq!(ErrorCode::from(CHashingCode::InvalidArgument), 2);
+    assert_eq!(ErrorCode::from(CHashingCode::InvalidKeyLength), 3);
+    assert_eq!(ErrorCode::from(CHashingCode::InvalidSaltLength), 4);
 
     assert_eq!(
         CHashingCode::InvalidArgument,
@@ -231,4 +446,341 @@ fn test_c_hashing_error_convert() {
         CHashingCode::InvalidHashLength,
         Error::InvalidHashLength.into(),
     );
+    assert_eq!(
+        CHashingCode::InvalidKeyLength,
+        Error::InvalidKeyLength.into()
+    );
+    assert_eq!(
+        CHashingCode::InvalidSaltLength,
+        Error::InvalidSaltLength.into()
+    );
+}
+
+#[test]
+fn test_tw_hasher() {
+    let hasher = unsafe { tw_hasher_create(HasherKind::Sha256 as u32) };
+    assert!(!hasher.is_null());
+
+    for chunk in [b"hello".as_slice(), b" ", b"world"] {
+        assert!(unsafe { tw_hasher_update(hasher, chunk.as_ptr(), chunk.len()) });
+    }
+    let actual = unsafe { tw_hasher_finalize(hasher).into_vec() };
+    assert_eq!(
+        hex::encode(actual, false),
+        "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+    );
+
+    // The hasher is reset after finalizing, and `tw_hasher_reset` discards the processed data.
+    let garbage = b"garbage";
+    assert!(unsafe { tw_hasher_update(hasher, garbage.as_ptr(), garbage.len()) });
+    unsafe { tw_hasher_reset(hasher) };
+    let actual = unsafe { tw_hasher_finalize(hasher).into_vec() };
+    assert_eq!(
+        hex::encode(actual, false),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+
+    // Null data is not allowed.
+    assert!(!unsafe { tw_hasher_update(hasher, std::ptr::null(), 0) });
+
+    unsafe { tw_hasher_delete(hasher) };
+}
+
+#[test]
+fn test_tw_hasher_invalid() {
+    assert!(unsafe { tw_hasher_create(100) }.is_null());
+    assert!(unsafe { tw_hasher_finalize(std::ptr::null_mut()).into_vec() }.is_empty());
+    assert!(!unsafe { tw_hasher_update(std::ptr::null_mut(), b"a".as_ptr(), 1) });
+}
+
+#[test]
+fn test_kdf_pbkdf2() {
+    let (password, salt) = (b"passwd", b"salt");
+    let actual = unsafe {
+        kdf_pbkdf2_hmac_sha256(
+            password.as_ptr(),
+            password.len(),
+            salt.as_ptr(),
+            salt.len(),
+            1,
+            64,
+        )
+    };
+    assert_eq!(hex::encode(unsafe { actual.unwrap().into_vec() }, false), "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783");
+
+    let (password, salt) = (b"password", b"salt");
+    let actual = unsafe {
+        kdf_pbkdf2_hmac_sha512(
+            password.as_ptr(),
+            password.len(),
+            salt.as_ptr(),
+            salt.len(),
+            1,
+            64,
+        )
+    };
+    assert_eq!(hex::encode(unsafe { actual.unwrap().into_vec() }, false), "867f70cf1ade02cff3752599a3a53dc4af34c7a669815ae5d513554e1c8cf252c02d470a285a0501bad999bfe943c08f050235d7d68b1da55e63f73b60a57fce");
+
+    // Zero rounds.
+    let actual = unsafe {
+        kdf_pbkdf2_hmac_sha256(
+            password.as_ptr(),
+            password.len(),
+            salt.as_ptr(),
+            salt.len(),
+            0,
+            64,
+        )
+    };
+    assert_eq!(
+        actual.into_result().unwrap_err(),
+        CHashingCode::InvalidArgument as ErrorCode
+    );
+}
+
+#[test]
+fn test_kdf_scrypt() {
+    let (password, salt) = (b"password", b"NaCl");
+    let actual = unsafe {
+        kdf_scrypt(
+            password.as_ptr(),
+            password.len(),
+            salt.as_ptr(),
+            salt.len(),
+            1024,
+            8,
+            16,
+            64,
+        )
+    };
+    assert_eq!(hex::encode(unsafe { actual.unwrap().into_vec() }, false), "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640");
+
+    // `n` is not a power of 2.
+    let actual = unsafe {
+        kdf_scrypt(
+            password.as_ptr(),
+            password.len(),
+            salt.as_ptr(),
+            salt.len(),
+            1000,
+            8,
+            16,
+            64,
+        )
+    };
+    assert!(actual.is_err());
+
+    // Null salt.
+    let actual = unsafe {
+        kdf_scrypt(
+            password.as_ptr(),
+            password.len(),
+            std::ptr::null(),
+            0,
+            1024,
+            8,
+            16,
+            64,
+        )
+    };
+    assert_eq!(
+        actual.into_result().unwrap_err(),
+        CHashingCode::InvalidArgument as ErrorCode
+    );
+}
+
+#[test]
+fn test_kdf_hkdf() {
+    let ikm = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+    let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+    let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+
+    let actual = unsafe {
+        kdf_hkdf_sha256(
+            ikm.as_ptr(),
+            ikm.len(),
+            salt.as_ptr(),
+            salt.len(),
+            info.as_ptr(),
+            info.len(),
+            42,
+        )
+    };
+    assert_eq!(
+        hex::encode(unsafe { actual.unwrap().into_vec() }, false),
+        "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+    );
+
+    let actual = unsafe {
+        kdf_hkdf_sha512(
+            ikm.as_ptr(),
+            ikm.len(),
+            salt.as_ptr(),
+            salt.len(),
+            info.as_ptr(),
+            info.len(),
+            42,
+        )
+    };
+    assert_eq!(
+        hex::encode(unsafe { actual.unwrap().into_vec() }, false),
+        "832390086cda71fb47625bb5ceb168e4c8e26a1a16ed34d9fc7fe92c1481579338da362cb8d9f925d7cb"
+    );
+
+    // The output is too long.
+    let actual = unsafe {
+        kdf_hkdf_sha256(
+            ikm.as_ptr(),
+            ikm.len(),
+            salt.as_ptr(),
+            salt.len(),
+            info.as_ptr(),
+            info.len(),
+            255 * 32 + 1,
+        )
+    };
+    assert_eq!(
+        actual.into_result().unwrap_err(),
+        CHashingCode::InvalidHashLength as ErrorCode
+    );
+}
+
+#[test]
+fn test_kdf_argon2id() {
+    let (password, salt) = (b"password", b"somesalt");
+    let actual = unsafe {
+        kdf_argon2id(
+            password.as_ptr(),
+            password.len(),
+            salt.as_ptr(),
+            salt.len(),
+            32,
+            3,
+            4,
+            32,
+        )
+    };
+    assert_eq!(unsafe { actual.unwrap().into_vec() }.len(), 32);
+
+    // About 4 TiB of memory exceeds the default limit.
+    let actual = unsafe {
+        kdf_argon2id(
+            password.as_ptr(),
+            password.len(),
+            salt.as_ptr(),
+            salt.len(),
+            u32::MAX,
+            3,
+            4,
+            32,
+        )
+    };
+    assert_eq!(
+        actual.into_result().unwrap_err(),
+        CHashingCode::InvalidArgument as ErrorCode
+    );
+
+    // The salt is too short.
+    let salt = b"salt";
+    let actual = unsafe {
+        kdf_argon2id(
+            password.as_ptr(),
+            password.len(),
+            salt.as_ptr(),
+            salt.len(),
+            32,
+            3,
+            4,
+            32,
+        )
+    };
+    assert_eq!(
+        actual.into_result().unwrap_err(),
+        CHashingCode::InvalidArgument as ErrorCode
+    );
+}
+
+#[test]
+fn test_tw_merkle() {
+    // Bitcoin block #100000 transaction IDs in the internal byte order.
+    let leaves = hex::decode(concat!(
+        "876dd0a3ef4a2816ffd1c12ab649825a958b0ff3bb3d6f3e1250f13ddbf0148c",
+        "c40297f730dd7b5a99567eb8d27b78758f607507c52292d02d4031895b52f2ff",
+        "c46e239ab7d28e2c019b6d66ad8fae98a56ef1f21aeecb94d1b1718186f05963",
+        "1d0cb83721529a062d9675b98d6e5c587e4a770fc84ed00abc5a5de04568a6e9",
+    ))
+    .unwrap();
+    let hash = MerkleHash::Sha256d as u32;
+    let pairing = MerklePairing::DuplicateLast as u32;
+
+    let root = unsafe {
+        tw_merkle_root(hash, pairing, leaves.as_ptr(), leaves.len())
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(
+        hex::encode(&root, false),
+        "6657a9252aacd5c0b2940996ecff952228c3067cc38d4885efb5a4ac4247e9f3"
+    );
+
+    let proof = unsafe {
+        tw_merkle_proof(hash, pairing, leaves.as_ptr(), leaves.len(), 2)
+            .unwrap()
+            .into_vec()
+    };
+    assert_eq!(proof.len(), 64);
+
+    let leaf = &leaves[64..96];
+    let verify = |index: usize, leaf: &[u8]| unsafe {
+        tw_merkle_verify_proof(
+            hash,
+            pairing,
+            leaf.as_ptr(),
+            leaf.len(),
+            index,
+            proof.as_ptr(),
+            proof.len(),
+            root.as_ptr(),
+            root.len(),
+        )
+    };
+    assert!(verify(2, leaf));
+    assert!(!verify(3, leaf));
+    assert!(!verify(2, &leaves[0..32]));
+    assert!(!verify(2, &leaf[1..]));
+}
+
+#[test]
+fn test_tw_merkle_invalid() {
+    let leaves = [1_u8; 64];
+    let hash = MerkleHash::Keccak256 as u32;
+    let pairing = MerklePairing::SortedPairs as u32;
+
+    let err = unsafe {
+        tw_merkle_root(hash, pairing, leaves.as_ptr(), 63)
+            .into_result()
+            .unwrap_err()
+    };
+    assert_eq!(err, CHashingCode::InvalidHashLength as ErrorCode);
+
+    let err = unsafe {
+        tw_merkle_root(3, pairing, leaves.as_ptr(), leaves.len())
+            .into_result()
+            .unwrap_err()
+    };
+    assert_eq!(err, CHashingCode::InvalidArgument as ErrorCode);
+
+    let err = unsafe {
+        tw_merkle_root(hash, pairing, leaves.as_ptr(), 0)
+            .into_result()
+            .unwrap_err()
+    };
+    assert_eq!(err, CHashingCode::InvalidArgument as ErrorCode);
+
+    let err = unsafe {
+        tw_merkle_proof(hash, pairing, leaves.as_ptr(), leaves.len(), 2)
+            .into_result()
+            .unwrap_err()
+    };
+    assert_eq!(err, CHashingCode::InvalidArgument as ErrorCode);
 }
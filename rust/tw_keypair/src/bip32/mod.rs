@@ -0,0 +1,149 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! Curve-agnostic parts of the [BIP32](https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki)
+//! hierarchical deterministic derivation: derivation paths, key metadata and serialization.
+
+use crate::{KeyPairError, KeyPairResult};
+use tw_encoding::base58::{self, Alphabet};
+use tw_hash::ripemd::ripemd_160;
+use tw_hash::sha2::{sha256, sha256d};
+use tw_hash::{H256, H264, H512};
+use zeroize::Zeroizing;
+
+mod path;
+mod version;
+
+pub use path::{ChildNumber, DerivationPath};
+pub use version::HDVersion;
+
+pub type Fingerprint = [u8; 4];
+
+/// Extended key metadata that is common for private and public keys.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ExtendedKeyAttrs {
+    /// The number of derivations from the master key. `0` for the master key.
+    pub depth: u8,
+    /// The first 4 bytes of the parent public key hash. Zeros for the master key.
+    pub parent_fingerprint: Fingerprint,
+    /// The child number of the key. Zero for the master key.
+    pub child_number: ChildNumber,
+    pub chain_code: H256,
+}
+
+impl ExtendedKeyAttrs {
+    /// Creates attributes of a master key.
+    pub fn master(chain_code: H256) -> ExtendedKeyAttrs {
+        ExtendedKeyAttrs {
+            depth: 0,
+            parent_fingerprint: Fingerprint::default(),
+            child_number: ChildNumber::from(0),
+            chain_code,
+        }
+    }
+
+    /// Creates attributes of a child key derived from the key with `self` attributes.
+    pub(crate) fn child(
+        &self,
+        parent_fingerprint: Fingerprint,
+        child_number: ChildNumber,
+        chain_code: H256,
+    ) -> KeyPairResult<ExtendedKeyAttrs> {
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or(KeyPairError::InvalidDerivationPath)?;
+        Ok(ExtendedKeyAttrs {
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+        })
+    }
+}
+
+/// The length of a serialized extended key without the checksum.
+const EXTENDED_KEY_LEN: usize = 78;
+
+/// Computes a fingerprint of the given serialized compressed public key.
+pub(crate) fn fingerprint(public_key: &[u8]) -> Fingerprint {
+    let hash = ripemd_160(&sha256(public_key));
+    let mut fingerprint = Fingerprint::default();
+    fingerprint.copy_from_slice(&hash[..4]);
+    fingerprint
+}
+
+/// Splits an HMAC-SHA512 output into the left (key material) and right (chain code) halves.
+pub(crate) fn split_hmac_output(output: &[u8]) -> (Zeroizing<H256>, H256) {
+    let output =
+        Zeroizing::new(H512::try_from(output).expect("Expected 64 byte HMAC-SHA512 output"));
+    let (left, right) = output.split();
+    (Zeroizing::new(left), right)
+}
+
+/// Serializes an extended key with Base58Check encoding.
+/// `key` is either a compressed public key or a private key prefixed by `0x00`.
+pub(crate) fn encode_extended_key(
+    version: HDVersion,
+    attrs: &ExtendedKeyAttrs,
+    key: &H264,
+) -> String {
+    let mut data = Zeroizing::new(Vec::with_capacity(EXTENDED_KEY_LEN));
+    data.extend_from_slice(&version.to_u32().to_be_bytes());
+    data.push(attrs.depth);
+    data.extend_from_slice(&attrs.parent_fingerprint);
+    data.extend_from_slice(&attrs.child_number.to_u32().to_be_bytes());
+    data.extend_from_slice(attrs.chain_code.as_slice());
+    data.extend_from_slice(key.as_slice());
+
+    base58::encode_check(&data, Alphabet::BITCOIN, sha256d)
+}
+
+/// Deserializes a Base58Check encoded extended key.
+/// Returns the key version, attributes and either a compressed public key or a private key prefixed by `0x00`.
+pub(crate) fn decode_extended_key(
+    s: &str,
+) -> KeyPairResult<(HDVersion, ExtendedKeyAttrs, Zeroizing<H264>)> {
+    // An invalid checksum is reported as an invalid extended key as well.
+    let payload = Zeroizing::new(
+        base58::decode_check(s, Alphabet::BITCOIN, sha256d)
+            .map_err(|_| KeyPairError::InvalidExtendedKey)?,
+    );
+    if payload.len() != EXTENDED_KEY_LEN {
+        return Err(KeyPairError::InvalidExtendedKey);
+    }
+
+    let version = u32::from_be_bytes(to_array(&payload[0..4]));
+    let version = HDVersion::from_raw(version).ok_or(KeyPairError::InvalidExtendedKey)?;
+
+    let depth = payload[4];
+    let parent_fingerprint = to_array(&payload[5..9]);
+    let child_number = ChildNumber::from(u32::from_be_bytes(to_array(&payload[9..13])));
+    let chain_code =
+        H256::try_from(&payload[13..45]).map_err(|_| KeyPairError::InvalidExtendedKey)?;
+    let key = Zeroizing::new(
+        H264::try_from(&payload[45..78]).map_err(|_| KeyPairError::InvalidExtendedKey)?,
+    );
+
+    // The master key must not have a parent.
+    if depth == 0 && (parent_fingerprint != [0; 4] || child_number.to_u32() != 0) {
+        return Err(KeyPairError::InvalidExtendedKey);
+    }
+
+    let attrs = ExtendedKeyAttrs {
+        depth,
+        parent_fingerprint,
+        child_number,
+        chain_code,
+    };
+    Ok((version, attrs, key))
+}
+
+fn to_array<const N: usize>(slice: &[u8]) -> [u8; N] {
+    let mut array = [0; N];
+    array.copy_from_slice(slice);
+    array
+}
@@ -0,0 +1,223 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::{KeyPairError, KeyPairResult};
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents a child index of an extended key.
+/// Hardened indexes have the most significant bit set.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChildNumber(u32);
+
+impl ChildNumber {
+    /// The bit that marks an index as hardened.
+    pub const HARDENED_FLAG: u32 = 1 << 31;
+
+    /// Creates a non-hardened child number.
+    /// Returns an error if the `index` has the [`ChildNumber::HARDENED_FLAG`] bit set.
+    pub fn normal(index: u32) -> KeyPairResult<ChildNumber> {
+        if index & Self::HARDENED_FLAG != 0 {
+            return Err(KeyPairError::InvalidDerivationPath);
+        }
+        Ok(ChildNumber(index))
+    }
+
+    /// Creates a hardened child number.
+    /// Returns an error if the `index` has the [`ChildNumber::HARDENED_FLAG`] bit set.
+    pub fn hardened(index: u32) -> KeyPairResult<ChildNumber> {
+        if index & Self::HARDENED_FLAG != 0 {
+            return Err(KeyPairError::InvalidDerivationPath);
+        }
+        Ok(ChildNumber(index | Self::HARDENED_FLAG))
+    }
+
+    /// Returns whether the child number is hardened.
+    pub fn is_hardened(&self) -> bool {
+        self.0 & Self::HARDENED_FLAG != 0
+    }
+
+    /// Returns the index without the [`ChildNumber::HARDENED_FLAG`] bit.
+    pub fn index(&self) -> u32 {
+        self.0 & !Self::HARDENED_FLAG
+    }
+
+    /// Returns the raw child number as it is serialized in an extended key.
+    pub fn to_u32(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for ChildNumber {
+    fn from(raw: u32) -> Self {
+        ChildNumber(raw)
+    }
+}
+
+impl FromStr for ChildNumber {
+    type Err = KeyPairError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, hardened) = match s.strip_suffix(['\'', 'h', 'H']) {
+            Some(index) => (index, true),
+            None => (s, false),
+        };
+
+        // `u32::from_str` accepts a leading `+` sign.
+        if !index.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(KeyPairError::InvalidDerivationPath);
+        }
+        let index = u32::from_str(index).map_err(|_| KeyPairError::InvalidDerivationPath)?;
+
+        if hardened {
+            ChildNumber::hardened(index)
+        } else {
+            ChildNumber::normal(index)
+        }
+    }
+}
+
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.index())
+        } else {
+            write!(f, "{}", self.index())
+        }
+    }
+}
+
+/// Represents a BIP32 derivation path like `m/44'/60'/0'/0/0`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DerivationPath {
+    path: Vec<ChildNumber>,
+}
+
+impl DerivationPath {
+    /// Returns the child numbers of the path starting from the master key.
+    pub fn as_slice(&self) -> &[ChildNumber] {
+        &self.path
+    }
+
+    /// Returns an iterator over the child numbers of the path.
+    pub fn iter(&self) -> impl Iterator<Item = &ChildNumber> {
+        self.path.iter()
+    }
+
+    /// Returns whether all child numbers of the path are hardened.
+    pub fn is_hardened_only(&self) -> bool {
+        self.path.iter().all(ChildNumber::is_hardened)
+    }
+}
+
+impl From<Vec<ChildNumber>> for DerivationPath {
+    fn from(path: Vec<ChildNumber>) -> Self {
+        DerivationPath { path }
+    }
+}
+
+/// Parses a derivation path.
+/// The `m/` prefix is optional to be compatible with `TW::DerivationPath`.
+impl FromStr for DerivationPath {
+    type Err = KeyPairError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix('m').unwrap_or(s);
+        let s = s.strip_prefix('/').unwrap_or(s);
+        if s.is_empty() {
+            return Ok(DerivationPath::default());
+        }
+
+        let path = s
+            .split('/')
+            .map(ChildNumber::from_str)
+            .collect::<KeyPairResult<Vec<_>>>()?;
+        Ok(DerivationPath { path })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for DerivationPath {
+    type Error = KeyPairError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        DerivationPath::from_str(s)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for child in self.path.iter() {
+            write!(f, "/{child}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derivation_path_from_str() {
+        let path = DerivationPath::from_str("m/44'/60'/0'/0/0").unwrap();
+        let expected = vec![
+            ChildNumber::hardened(44).unwrap(),
+            ChildNumber::hardened(60).unwrap(),
+            ChildNumber::hardened(0).unwrap(),
+            ChildNumber::normal(0).unwrap(),
+            ChildNumber::normal(0).unwrap(),
+        ];
+        assert_eq!(path.as_slice(), expected.as_slice());
+        assert_eq!(path.to_string(), "m/44'/60'/0'/0/0");
+        assert!(!path.is_hardened_only());
+
+        let path = DerivationPath::from_str("44h/501H/0'").unwrap();
+        assert_eq!(path.to_string(), "m/44'/501'/0'");
+        assert!(path.is_hardened_only());
+
+        assert_eq!(
+            DerivationPath::from_str("m").unwrap(),
+            DerivationPath::default()
+        );
+        assert_eq!(
+            DerivationPath::from_str("m/").unwrap(),
+            DerivationPath::default()
+        );
+    }
+
+    #[test]
+    fn test_derivation_path_from_str_invalid() {
+        let tests = [
+            "m/44'/60'/",
+            "m//0",
+            "m/44''",
+            "m/-1",
+            "m/+1",
+            "m/a/0",
+            "m/2147483648",
+            "m/2147483648'",
+            "m/4294967296",
+            "n/44'",
+        ];
+        for path in tests {
+            assert!(DerivationPath::from_str(path).is_err(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_child_number() {
+        let child = ChildNumber::hardened(1).unwrap();
+        assert!(child.is_hardened());
+        assert_eq!(child.index(), 1);
+        assert_eq!(child.to_u32(), 0x8000_0001);
+        assert_eq!(ChildNumber::from(0x8000_0001), child);
+
+        let child = ChildNumber::normal(1).unwrap();
+        assert!(!child.is_hardened());
+        assert_eq!(child.to_u32(), 1);
+    }
+}
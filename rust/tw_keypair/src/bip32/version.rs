@@ -0,0 +1,81 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+/// Version bytes of a serialized extended key.
+/// The values are the same as in `TWHDVersion`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HDVersion {
+    // Bitcoin
+    Xpub = 0x0488b21e,
+    Xprv = 0x0488ade4,
+    Ypub = 0x049d7cb2,
+    Yprv = 0x049d7878,
+    Zpub = 0x04b24746,
+    Zprv = 0x04b2430c,
+
+    // Litecoin
+    Ltub = 0x019da462,
+    Ltpv = 0x019d9cfe,
+    Mtub = 0x01b26ef6,
+    Mtpv = 0x01b26792,
+
+    // Decred
+    Dpub = 0x02fda926,
+    Dprv = 0x02fda4e8,
+
+    // Dogecoin
+    Dgub = 0x02facafd,
+    Dgpv = 0x02fac398,
+}
+
+impl HDVersion {
+    /// Returns `None` if the given version is unknown.
+    pub fn from_raw(version: u32) -> Option<HDVersion> {
+        let version = match version {
+            0x0488b21e => HDVersion::Xpub,
+            0x0488ade4 => HDVersion::Xprv,
+            0x049d7cb2 => HDVersion::Ypub,
+            0x049d7878 => HDVersion::Yprv,
+            0x04b24746 => HDVersion::Zpub,
+            0x04b2430c => HDVersion::Zprv,
+            0x019da462 => HDVersion::Ltub,
+            0x019d9cfe => HDVersion::Ltpv,
+            0x01b26ef6 => HDVersion::Mtub,
+            0x01b26792 => HDVersion::Mtpv,
+            0x02fda926 => HDVersion::Dpub,
+            0x02fda4e8 => HDVersion::Dprv,
+            0x02facafd => HDVersion::Dgub,
+            0x02fac398 => HDVersion::Dgpv,
+            _ => return None,
+        };
+        Some(version)
+    }
+
+    /// Returns the version as it is serialized in an extended key.
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    /// Whether the version is used to serialize extended public keys.
+    pub fn is_public(self) -> bool {
+        matches!(
+            self,
+            HDVersion::Xpub
+                | HDVersion::Ypub
+                | HDVersion::Zpub
+                | HDVersion::Ltub
+                | HDVersion::Mtub
+                | HDVersion::Dpub
+                | HDVersion::Dgub
+        )
+    }
+
+    /// Whether the version is used to serialize extended private keys.
+    pub fn is_private(self) -> bool {
+        !self.is_public()
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! `curve25519` keys that are used by Waves blockchain and X25519 key agreement.
+//! The private key is a regular `ed25519` secret, the public key is the Montgomery form
+//! of the associated `ed25519` public key.
+
+mod keypair;
+mod private;
+mod public;
+mod signature;
+
+pub use keypair::KeyPair;
+pub use private::PrivateKey;
+pub use public::PublicKey;
+pub use signature::Signature;
+
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use curve25519_dalek::scalar::Scalar;
+use tw_hash::H256;
+
+/// Computes an X25519 shared secret as defined in [RFC 7748](https://www.rfc-editor.org/rfc/rfc7748#section-5).
+/// The `secret` scalar is clamped before the multiplication.
+pub fn x25519(secret: &H256, public: &H256) -> H256 {
+    let mut bits = secret.take();
+    bits[0] &= 248;
+    bits[31] &= 127;
+    bits[31] |= 64;
+
+    let shared = Scalar::from_bits(bits) * MontgomeryPoint(public.take());
+    H256::from(shared.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{KeyPairTrait, SigningKeyTrait, VerifyingKeyTrait};
+    use tw_encoding::hex;
+    use tw_hash::H512;
+    use tw_misc::traits::ToBytesVec;
+
+    /// The test is taken from `Waves/SignerTests.cpp` (`SignTransaction`).
+    const SECRET: &str = "9864a747e1b97f131fabb6b447296c9b6f0201e79fb3c5356e6c77e89b6a806a";
+    const PUBLIC: &str = "559a50cb45a9a8e8d4f83295c354725990164d10bb505275d1a3086c08fb935d";
+    const MESSAGE: &str = "0402559a50cb45a9a8e8d4f83295c354725990164d10bb505275d1a3086c08fb935d00000000016372e852120000000005f5e1000000000005f5e10001570acc4110b78a6d38b34d879b5bba38806202ecf1732f8542000766616c6166656c";
+    const SIGNATURE: &str = "af7989256f496e103ce95096b3f52196dd9132e044905fe486da3b829b5e403bcba95ab7e650a4a33948c2d05cfca2dce4d4df747e26402974490fb4c49fbe8f";
+
+    #[test]
+    fn test_private_to_public() {
+        let private = PrivateKey::try_from(SECRET).unwrap();
+        assert_eq!(private.public().to_bytes(), H256::from(PUBLIC));
+    }
+
+    #[test]
+    fn test_key_pair_sign_verify() {
+        let key_pair = KeyPair::try_from(SECRET).unwrap();
+        assert_eq!(key_pair.public().to_bytes(), H256::from(PUBLIC));
+        let message = hex::decode(MESSAGE).unwrap();
+
+        let signature = key_pair.sign(message.clone()).unwrap();
+        assert_eq!(signature.to_bytes(), H512::from(SIGNATURE));
+        assert!(key_pair.verify(signature, message));
+    }
+
+    #[test]
+    fn test_public_verify() {
+        let public = PublicKey::try_from(PUBLIC).unwrap();
+        let message = hex::decode(MESSAGE).unwrap();
+
+        let signature = Signature::try_from(H512::from(SIGNATURE).as_slice()).unwrap();
+        assert!(public.verify(signature, message.clone()));
+
+        // Flip the sign bit.
+        let mut invalid = H512::from(SIGNATURE);
+        invalid[63] ^= 0x80;
+        let signature = Signature::try_from(invalid.as_slice()).unwrap();
+        assert!(!public.verify(signature, message));
+    }
+
+    /// The test is taken from `Waves/SignerTests.cpp` (`curve25519_pk_to_ed25519`).
+    #[test]
+    fn test_public_to_ed25519() {
+        let public = PublicKey::try_from(PUBLIC).unwrap();
+        let ed25519 = public.to_ed25519(0).unwrap();
+        assert_eq!(
+            ed25519.to_vec(),
+            H256::from("ff84c4bfc095df25b01e48807715856d95af93d88c5b57f30cb0ce567ca4ce56")
+                .into_vec()
+        );
+    }
+
+    /// The test vector is taken from [RFC 7748](https://www.rfc-editor.org/rfc/rfc7748#section-6.1).
+    #[test]
+    fn test_x25519() {
+        let alice_secret =
+            H256::from("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let bob_public =
+            H256::from("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+        let expected =
+            H256::from("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(x25519(&alice_secret, &bob_public), expected);
+    }
+
+    #[test]
+    fn test_shared_key() {
+        let alice = PrivateKey::try_from(SECRET).unwrap();
+        let bob = PrivateKey::try_from(
+            "afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5",
+        )
+        .unwrap();
+
+        let alice_shared = alice.shared_key(&bob.public());
+        let bob_shared = bob.shared_key(&alice.public());
+        assert_eq!(alice_shared, bob_shared);
+    }
+}
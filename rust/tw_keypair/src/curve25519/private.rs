@@ -0,0 +1,79 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::curve25519::{PublicKey, Signature};
+use crate::ed25519;
+use crate::traits::SigningKeyTrait;
+use crate::{KeyPairError, KeyPairResult};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use tw_encoding::hex;
+use tw_hash::H256;
+use tw_misc::traits::{ToBytesVec, ToBytesZeroizing};
+use zeroize::Zeroizing;
+
+/// Represents a `curve25519` private key.
+/// It's a regular `ed25519` secret.
+pub struct PrivateKey {
+    ed25519: ed25519::sha512::PrivateKey,
+}
+
+impl PrivateKey {
+    /// Returns an associated `curve25519` public key.
+    /// The method is ported from `ed25519_pk_to_curve25519`.
+    pub fn public(&self) -> PublicKey {
+        PublicKey::with_ed25519(&self.ed25519.public())
+    }
+
+    /// Computes an X25519 shared secret with the given `public` key.
+    /// The X25519 secret is the clamped scalar of the expanded `ed25519` secret,
+    /// so `alice.shared_key(&bob.public()) == bob.shared_key(&alice.public())`.
+    pub fn shared_key(&self, public: &PublicKey) -> H256 {
+        let shared = self.ed25519.scalar() * MontgomeryPoint(public.to_bytes().take());
+        H256::from(shared.to_bytes())
+    }
+}
+
+impl SigningKeyTrait for PrivateKey {
+    type SigningMessage = Vec<u8>;
+    type Signature = Signature;
+
+    /// Signs the `message` with the `ed25519` secret and stores the sign bit
+    /// of the `ed25519` public key in the most significant bit of the signature.
+    /// The method is ported from `TW::PrivateKey::sign` (`TWCurveCurve25519`).
+    fn sign(&self, message: Self::SigningMessage) -> KeyPairResult<Self::Signature> {
+        let ed25519_public = self.ed25519.public();
+        let signature = self
+            .ed25519
+            .sign_with_public_key(&ed25519_public, &message)?;
+
+        let sign_bit = ed25519_public.to_vec()[31] & Signature::SIGN_BIT_MASK;
+        Ok(Signature::new(signature, sign_bit))
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for PrivateKey {
+    type Error = KeyPairError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let ed25519 = ed25519::sha512::PrivateKey::try_from(data)?;
+        Ok(PrivateKey { ed25519 })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for PrivateKey {
+    type Error = KeyPairError;
+
+    fn try_from(hex: &'a str) -> Result<Self, Self::Error> {
+        let bytes = Zeroizing::new(hex::decode(hex).map_err(|_| KeyPairError::InvalidSecretKey)?);
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl ToBytesZeroizing for PrivateKey {
+    fn to_zeroizing_vec(&self) -> Zeroizing<Vec<u8>> {
+        self.ed25519.to_zeroizing_vec()
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::curve25519::Signature;
+use crate::ed25519;
+use crate::traits::VerifyingKeyTrait;
+use crate::{KeyPairError, KeyPairResult};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use tw_encoding::hex;
+use tw_hash::H256;
+use tw_misc::traits::ToBytesVec;
+use tw_misc::try_or_false;
+
+/// Represents a `curve25519` public key (the Montgomery `u` coordinate).
+pub struct PublicKey {
+    point: MontgomeryPoint,
+}
+
+/// cbindgen:ignore
+impl PublicKey {
+    /// The number of bytes in a serialized public key.
+    pub const LEN: usize = H256::len();
+
+    /// Converts the given `ed25519` public key to the Montgomery form.
+    pub(crate) fn with_ed25519(public: &ed25519::sha512::PublicKey) -> PublicKey {
+        PublicKey {
+            point: public.point().to_montgomery(),
+        }
+    }
+
+    /// Converts the public key to an `ed25519` public key with the given `sign` bit (0 or 1)
+    /// of the `x` coordinate. The method is ported from `curve25519_pk_to_ed25519`.
+    pub fn to_ed25519(&self, sign: u8) -> KeyPairResult<ed25519::sha512::PublicKey> {
+        self.point
+            .to_edwards(sign)
+            .map(ed25519::sha512::PublicKey::with_point)
+            .ok_or(KeyPairError::InvalidPublicKey)
+    }
+
+    /// Returns the raw data of the public key (32 bytes).
+    pub fn to_bytes(&self) -> H256 {
+        H256::from(self.point.to_bytes())
+    }
+}
+
+impl VerifyingKeyTrait for PublicKey {
+    type SigningMessage = Vec<u8>;
+    type VerifySignature = Signature;
+
+    /// Verifies the `signature` with the `ed25519` public key restored from the Montgomery form
+    /// and the sign bit stored in the signature.
+    /// The method is ported from `TW::PublicKey::verify` (`TWPublicKeyTypeCURVE25519`).
+    fn verify(&self, signature: Self::VerifySignature, message: Self::SigningMessage) -> bool {
+        let ed25519_public = try_or_false!(self.to_ed25519(signature.sign()));
+        ed25519_public.verify(signature.into_ed25519(), message)
+    }
+}
+
+impl ToBytesVec for PublicKey {
+    fn to_vec(&self) -> Vec<u8> {
+        self.to_bytes().into_vec()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for PublicKey {
+    type Error = KeyPairError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let bytes = H256::try_from(data).map_err(|_| KeyPairError::InvalidPublicKey)?;
+        Ok(PublicKey {
+            point: MontgomeryPoint(bytes.take()),
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for PublicKey {
+    type Error = KeyPairError;
+
+    fn try_from(hex: &'a str) -> Result<Self, Self::Error> {
+        let bytes = hex::decode(hex).map_err(|_| KeyPairError::InvalidPublicKey)?;
+        Self::try_from(bytes.as_slice())
+    }
+}
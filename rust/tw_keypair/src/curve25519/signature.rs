@@ -0,0 +1,72 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::ed25519;
+use crate::KeyPairError;
+use tw_hash::H512;
+use tw_misc::traits::ToBytesVec;
+
+/// Represents an `ed25519` signature with the sign bit of the signer's `ed25519` public key
+/// stored in the most significant bit of the last byte.
+/// That's required to restore an `ed25519` public key from a `curve25519` one on verification.
+#[derive(Debug)]
+pub struct Signature {
+    signature: ed25519::Signature,
+    /// Either `0x00` or `0x80`.
+    sign_bit: u8,
+}
+
+/// cbindgen:ignore
+impl Signature {
+    /// The number of bytes in a serialized signature.
+    pub const LEN: usize = H512::len();
+    pub(crate) const SIGN_BIT_MASK: u8 = 0x80;
+    const SIGN_BYTE: usize = Self::LEN - 1;
+
+    pub(crate) fn new(signature: ed25519::Signature, sign_bit: u8) -> Signature {
+        Signature {
+            signature,
+            sign_bit: sign_bit & Self::SIGN_BIT_MASK,
+        }
+    }
+
+    /// Returns the sign bit as `0` or `1`.
+    pub(crate) fn sign(&self) -> u8 {
+        self.sign_bit >> 7
+    }
+
+    /// Returns the inner `ed25519` signature without the sign bit.
+    pub(crate) fn into_ed25519(self) -> ed25519::Signature {
+        self.signature
+    }
+
+    /// Returns the signature data (64 bytes).
+    pub fn to_bytes(&self) -> H512 {
+        let mut bytes = self.signature.to_bytes();
+        bytes[Self::SIGN_BYTE] |= self.sign_bit;
+        bytes
+    }
+}
+
+impl ToBytesVec for Signature {
+    fn to_vec(&self) -> Vec<u8> {
+        self.to_bytes().into_vec()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Signature {
+    type Error = KeyPairError;
+
+    fn try_from(sign: &'a [u8]) -> Result<Self, Self::Error> {
+        let mut bytes = H512::try_from(sign).map_err(|_| KeyPairError::InvalidSignature)?;
+
+        let sign_bit = bytes[Self::SIGN_BYTE] & Self::SIGN_BIT_MASK;
+        bytes[Self::SIGN_BYTE] &= !Self::SIGN_BIT_MASK;
+
+        let signature = ed25519::Signature::try_from(bytes.as_slice())?;
+        Ok(Signature::new(signature, sign_bit))
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::bip32::{
+    fingerprint, split_hmac_output, ChildNumber, DerivationPath, ExtendedKeyAttrs, Fingerprint,
+};
+use crate::ed25519::private::PrivateKey;
+use crate::ed25519::public::PublicKey;
+use crate::ed25519::Hasher512;
+use crate::{KeyPairError, KeyPairResult};
+use tw_hash::hmac::hmac_sha512;
+use tw_misc::traits::{ToBytesVec, ToBytesZeroizing};
+use zeroize::Zeroizing;
+
+/// HMAC-SHA512 key that is used to generate a master key from a seed.
+const SLIP10_SEED_KEY: &[u8] = b"ed25519 seed";
+
+/// Represents an `ed25519` extended private key that is derived according to
+/// [SLIP-0010](https://github.com/satoshilabs/slips/blob/master/slip-0010.md).
+/// Only hardened derivation is supported by the standard.
+pub struct ExtendedPrivateKey<H: Hasher512> {
+    private: PrivateKey<H>,
+    attrs: ExtendedKeyAttrs,
+}
+
+/// cbindgen:ignore
+impl<H: Hasher512> ExtendedPrivateKey<H> {
+    /// Generates a master key from the given `seed` (usually derived from a BIP39 mnemonic).
+    pub fn from_seed(seed: &[u8]) -> KeyPairResult<Self> {
+        let output = Zeroizing::new(hmac_sha512(SLIP10_SEED_KEY, seed));
+        let (secret, chain_code) = split_hmac_output(&output);
+
+        Ok(ExtendedPrivateKey {
+            private: PrivateKey::try_from(secret.as_slice())?,
+            attrs: ExtendedKeyAttrs::master(chain_code),
+        })
+    }
+
+    /// Returns an associated `ed25519` public key.
+    pub fn public(&self) -> PublicKey<H> {
+        self.private.public()
+    }
+
+    /// Returns the private key.
+    pub fn private(&self) -> &PrivateKey<H> {
+        &self.private
+    }
+
+    /// Returns the key depth, parent fingerprint, child number and chain code.
+    pub fn attrs(&self) -> &ExtendedKeyAttrs {
+        &self.attrs
+    }
+
+    /// Returns the fingerprint of the key that is used as a parent fingerprint of its children.
+    /// SLIP-0010 prefixes `ed25519` public keys with `0x00` to make them 33 bytes long.
+    pub fn fingerprint(&self) -> Fingerprint {
+        let mut public = vec![0];
+        public.extend_from_slice(&self.public().to_vec());
+        fingerprint(&public)
+    }
+
+    /// Derives a child extended private key.
+    /// Returns an error if the `child` number is not hardened.
+    pub fn derive_child(&self, child: ChildNumber) -> KeyPairResult<Self> {
+        if !child.is_hardened() {
+            return Err(KeyPairError::InvalidDerivationPath);
+        }
+
+        let mut data = Zeroizing::new(vec![0]);
+        data.extend_from_slice(self.private.to_zeroizing_vec().as_slice());
+        data.extend_from_slice(&child.to_u32().to_be_bytes());
+
+        let output = Zeroizing::new(hmac_sha512(self.attrs.chain_code.as_slice(), &data));
+        let (secret, chain_code) = split_hmac_output(&output);
+
+        Ok(ExtendedPrivateKey {
+            private: PrivateKey::try_from(secret.as_slice())?,
+            attrs: self.attrs.child(self.fingerprint(), child, chain_code)?,
+        })
+    }
+
+    /// Derives an extended private key by the given `path` starting from `self`.
+    /// Returns an error if the `path` contains a non-hardened child number.
+    pub fn derive_path(&self, path: &DerivationPath) -> KeyPairResult<Self> {
+        let mut key = ExtendedPrivateKey {
+            private: PrivateKey::try_from(self.private.to_zeroizing_vec().as_slice())?,
+            attrs: self.attrs,
+        };
+        for child in path.iter() {
+            key = key.derive_child(*child)?;
+        }
+        Ok(key)
+    }
+}
@@ -6,6 +6,7 @@
 
 use digest::{consts::U64, Digest};
 
+mod extended_private;
 mod keypair;
 mod mangle;
 mod modifications;
@@ -14,6 +15,7 @@ mod public;
 mod secret;
 mod signature;
 
+pub use extended_private::ExtendedPrivateKey;
 pub use modifications::cardano;
 pub use signature::Signature;
 
@@ -21,6 +23,7 @@ pub use signature::Signature;
 pub mod sha512 {
     use sha2::Sha512;
 
+    pub type ExtendedPrivateKey = crate::ed25519::extended_private::ExtendedPrivateKey<Sha512>;
     pub type KeyPair = crate::ed25519::keypair::KeyPair<Sha512>;
     pub type PrivateKey = crate::ed25519::private::PrivateKey<Sha512>;
     pub type PublicKey = crate::ed25519::public::PublicKey<Sha512>;
@@ -30,6 +33,7 @@ pub mod sha512 {
 pub mod blake2b {
     use blake2::Blake2b;
 
+    pub type ExtendedPrivateKey = crate::ed25519::extended_private::ExtendedPrivateKey<Blake2b>;
     pub type KeyPair = crate::ed25519::keypair::KeyPair<Blake2b>;
     pub type PrivateKey = crate::ed25519::private::PrivateKey<Blake2b>;
     pub type PublicKey = crate::ed25519::public::PublicKey<Blake2b>;
@@ -0,0 +1,84 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! Helpers of the [BIP32-Ed25519](https://input-output-hk.github.io/adrestia/static/Ed25519_BIP.pdf)
+//! hierarchical deterministic derivation.
+//! Ported from `hdnode_private_ckd_cardano` of `trezor-crypto/crypto/cardano.c`.
+
+use crate::bip32::ChildNumber;
+use tw_hash::hmac::hmac_sha512;
+use tw_hash::{H256, H512};
+use zeroize::Zeroizing;
+
+/// HMAC data tags of a hardened (private) child derivation.
+const HARDENED_KEY_TAG: u8 = 0;
+const HARDENED_CHAIN_CODE_TAG: u8 = 1;
+/// HMAC data tags of a soft (public) child derivation.
+const SOFT_KEY_TAG: u8 = 2;
+const SOFT_CHAIN_CODE_TAG: u8 = 3;
+
+/// Computes `Z` and the child chain code.
+/// `key_data` is either `kL || kR` for hardened derivation or `A` for soft derivation.
+pub(crate) fn derive_z_and_chain_code(
+    chain_code: &H256,
+    key_data: &[u8],
+    child: ChildNumber,
+) -> (Zeroizing<H512>, H256) {
+    let (key_tag, chain_code_tag) = if child.is_hardened() {
+        (HARDENED_KEY_TAG, HARDENED_CHAIN_CODE_TAG)
+    } else {
+        (SOFT_KEY_TAG, SOFT_CHAIN_CODE_TAG)
+    };
+
+    let mut data = Zeroizing::new(Vec::with_capacity(1 + key_data.len() + 4));
+    data.push(key_tag);
+    data.extend_from_slice(key_data);
+    data.extend_from_slice(&child.to_u32().to_le_bytes());
+
+    let z = Zeroizing::new(hmac_sha512(chain_code.as_slice(), &data));
+    let z = Zeroizing::new(H512::try_from(z.as_slice()).expect("Expected 64 byte HMAC-SHA512"));
+
+    data[0] = chain_code_tag;
+    let output = hmac_sha512(chain_code.as_slice(), &data);
+    let (_, child_chain_code): (H256, H256) = H512::try_from(output.as_slice())
+        .expect("Expected 64 byte HMAC-SHA512")
+        .split();
+
+    (z, child_chain_code)
+}
+
+/// Computes `8 * trunc28(zl)`. Ported from `scalar_multiply8`.
+pub(crate) fn multiply8_truncated(zl: &H256) -> Zeroizing<H256> {
+    const TRUNCATED_LEN: usize = 28;
+
+    let mut res = Zeroizing::new(H256::default());
+    let mut prev_acc = 0;
+    for i in 0..TRUNCATED_LEN {
+        res[i] = (zl[i] << 3) + (prev_acc & 0x7);
+        prev_acc = zl[i] >> 5;
+    }
+    res[TRUNCATED_LEN] = zl[TRUNCATED_LEN - 1] >> 5;
+    res
+}
+
+/// Adds two little-endian 256-bit numbers ignoring the overflow. Ported from `scalar_add_256bits`.
+pub(crate) fn add_256bits(left: &H256, right: &H256) -> H256 {
+    let mut res = H256::default();
+    let mut carry: u16 = 0;
+    for i in 0..H256::len() {
+        carry += left[i] as u16 + right[i] as u16;
+        res[i] = (carry & 0xff) as u8;
+        carry >>= 8;
+    }
+    res
+}
+
+/// Clears and sets the bits of a root `kL` secret as required by BIP32-Ed25519.
+pub(crate) fn tweak_bits(secret: &mut [u8]) {
+    secret[0] &= 0xf8;
+    secret[31] &= 0x1f;
+    secret[31] |= 0x40;
+}
@@ -4,6 +4,10 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
+use crate::bip32::{ChildNumber, DerivationPath};
+use crate::ed25519::modifications::cardano::derivation::{
+    add_256bits, derive_z_and_chain_code, multiply8_truncated, tweak_bits,
+};
 use crate::ed25519::modifications::cardano::extended_public::{
     ExtendedPublicKey, ExtendedPublicPart,
 };
@@ -15,7 +19,8 @@ use crate::traits::SigningKeyTrait;
 use crate::{KeyPairError, KeyPairResult};
 use std::ops::Range;
 use tw_encoding::hex;
-use tw_hash::H256;
+use tw_hash::kdf::pbkdf2::pbkdf2_hmac_sha512;
+use tw_hash::{H256, H512};
 use tw_misc::traits::ToBytesZeroizing;
 use zeroize::{ZeroizeOnDrop, Zeroizing};
 
@@ -34,16 +39,52 @@ impl<H: Hasher512> ExtendedPrivateKey<H> {
     const KEY_RANGE: Range<usize> = 0..ExtendedSecretPart::<H>::LEN;
     const SECOND_KEY_RANGE: Range<usize> = ExtendedSecretPart::<H>::LEN..Self::LEN;
 
-    /// Returns an associated Cardano extended `ed25519` public key.
-    pub fn public(&self) -> ExtendedPublicKey<H> {
-        let key_public = PublicKey::with_expanded_secret_no_mangle(&self.key.expanded_key);
-        let second_key_public =
-            PublicKey::with_expanded_secret_no_mangle(&self.second_key.expanded_key);
+    /// Creates a private key with the given [`ExtendedSecretPart`] first and second keys.
+    pub fn new(key: ExtendedSecretPart<H>, second_key: ExtendedSecretPart<H>) -> Self {
+        ExtendedPrivateKey { key, second_key }
+    }
+
+    /// Generates a private key from the given BIP39 `entropy` the same way as `TW::HDWallet::getKeyByCurve` does.
+    /// The first key is derived by the given `path`, the second (staking) key is derived
+    /// by the same path with the role and the index replaced by `2/0`.
+    ///
+    /// Returns an error if the `path` is not of the `m/purpose'/coin'/account'/role/index` form
+    /// or the `role` is neither external (`0`) nor internal (`1`) chain.
+    pub fn from_icarus_entropy(
+        entropy: &[u8],
+        passphrase: &[u8],
+        path: &DerivationPath,
+    ) -> KeyPairResult<Self> {
+        const ROLE_INDEX: usize = 3;
+        const ADDRESS_INDEX: usize = 4;
+        const STAKING_ROLE: u32 = 2;
 
-        let key = ExtendedPublicPart::new(key_public, self.key.chain_code);
-        let second_key = ExtendedPublicPart::new(second_key_public, self.second_key.chain_code);
+        let mut staking_path = path.as_slice().to_vec();
+        if staking_path.len() <= ADDRESS_INDEX || staking_path[ROLE_INDEX].to_u32() > 1 {
+            return Err(KeyPairError::InvalidDerivationPath);
+        }
+        staking_path[ROLE_INDEX] = ChildNumber::normal(STAKING_ROLE)?;
+        staking_path[ADDRESS_INDEX] = ChildNumber::normal(0)?;
+
+        let master = ExtendedSecretPart::icarus_master(entropy, passphrase);
+        let key = master.derive_path(path);
+        let second_key = master.derive_path(&DerivationPath::from(staking_path));
+        Ok(ExtendedPrivateKey { key, second_key })
+    }
+
+    /// Returns the first half of the private key.
+    pub fn key(&self) -> &ExtendedSecretPart<H> {
+        &self.key
+    }
 
-        ExtendedPublicKey::new(key, second_key)
+    /// Returns the second half of the private key.
+    pub fn second_key(&self) -> &ExtendedSecretPart<H> {
+        &self.second_key
+    }
+
+    /// Returns an associated Cardano extended `ed25519` public key.
+    pub fn public(&self) -> ExtendedPublicKey<H> {
+        ExtendedPublicKey::new(self.key.public(), self.second_key.public())
     }
 
     /// `ed25519` signing uses a public key associated with the private key.
@@ -98,8 +139,9 @@ impl<'a, H: Hasher512> TryFrom<&'a str> for ExtendedPrivateKey<H> {
     }
 }
 
+/// Represents a BIP32-Ed25519 extended secret `kL || kR || chain_code`.
 #[derive(ZeroizeOnDrop)]
-struct ExtendedSecretPart<H: Hasher512> {
+pub struct ExtendedSecretPart<H: Hasher512> {
     secret: H256,
     extension: H256,
     chain_code: H256,
@@ -115,6 +157,80 @@ impl<H: Hasher512> ExtendedSecretPart<H> {
     const SECRET_RANGE: Range<usize> = 0..32;
     const EXTENSION_RANGE: Range<usize> = 32..64;
     const CHAIN_CODE_RANGE: Range<usize> = 64..96;
+    /// The number of PBKDF2 iterations used to generate an Icarus master key.
+    const ICARUS_PBKDF2_ROUNDS: u32 = 4096;
+
+    /// Generates a master key from the given BIP39 `entropy` according to
+    /// [CIP-0003 Icarus](https://github.com/cardano-foundation/CIPs/blob/master/CIP-0003/Icarus.md).
+    pub fn icarus_master(entropy: &[u8], passphrase: &[u8]) -> Self {
+        let mut output = Zeroizing::new(
+            pbkdf2_hmac_sha512(passphrase, entropy, Self::ICARUS_PBKDF2_ROUNDS, Self::LEN)
+                .expect("Expected valid PBKDF2 parameters"),
+        );
+        tweak_bits(&mut output[Self::SECRET_RANGE]);
+
+        Self::try_from(output.as_slice()).expect("Expected exactly `ExtendedSecretPart::LEN` bytes")
+    }
+
+    /// Returns an associated extended public key part.
+    pub fn public(&self) -> ExtendedPublicPart<H> {
+        let public = PublicKey::with_expanded_secret_no_mangle(&self.expanded_key);
+        ExtendedPublicPart::new(public, self.chain_code)
+    }
+
+    /// Returns the chain code.
+    pub fn chain_code(&self) -> H256 {
+        self.chain_code
+    }
+
+    /// Derives a child extended secret according to BIP32-Ed25519.
+    /// Both hardened and soft (non-hardened) derivation are supported.
+    pub fn derive_child(&self, child: ChildNumber) -> Self {
+        let (z, chain_code) = if child.is_hardened() {
+            let mut key_data = Zeroizing::new(Vec::with_capacity(H512::len()));
+            key_data.extend_from_slice(self.secret.as_slice());
+            key_data.extend_from_slice(self.extension.as_slice());
+            derive_z_and_chain_code(&self.chain_code, &key_data, child)
+        } else {
+            let public = self.public();
+            derive_z_and_chain_code(&self.chain_code, public.public().as_slice(), child)
+        };
+
+        let (zl, zr): (H256, H256) = z.split();
+        let (zl, zr) = (Zeroizing::new(zl), Zeroizing::new(zr));
+
+        let secret = add_256bits(&multiply8_truncated(&zl), &self.secret);
+        let extension = add_256bits(&zr, &self.extension);
+
+        let expanded_key = ExpandedSecretKey::with_extended_secret(secret, extension);
+        ExtendedSecretPart {
+            secret,
+            extension,
+            chain_code,
+            expanded_key,
+        }
+    }
+
+    /// Derives an extended secret by the given `path` starting from `self`.
+    pub fn derive_path(&self, path: &DerivationPath) -> Self {
+        let mut key = self.clone();
+        for child in path.iter() {
+            key = key.derive_child(*child);
+        }
+        key
+    }
+}
+
+impl<H: Hasher512> Clone for ExtendedSecretPart<H> {
+    fn clone(&self) -> Self {
+        let expanded_key = ExpandedSecretKey::with_extended_secret(self.secret, self.extension);
+        ExtendedSecretPart {
+            secret: self.secret,
+            extension: self.extension,
+            chain_code: self.chain_code,
+            expanded_key,
+        }
+    }
 }
 
 impl<H: Hasher512> ToBytesZeroizing for ExtendedSecretPart<H> {
@@ -4,11 +4,17 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
+use crate::bip32::{ChildNumber, DerivationPath};
+use crate::ed25519::modifications::cardano::derivation::{
+    derive_z_and_chain_code, multiply8_truncated,
+};
 use crate::ed25519::public::PublicKey;
 use crate::ed25519::signature::Signature;
 use crate::ed25519::Hasher512;
 use crate::traits::VerifyingKeyTrait;
-use crate::KeyPairError;
+use crate::{KeyPairError, KeyPairResult};
+use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+use curve25519_dalek::scalar::Scalar;
 use std::ops::Range;
 use tw_encoding::hex;
 use tw_hash::H256;
@@ -30,10 +36,20 @@ impl<H: Hasher512> ExtendedPublicKey<H> {
     const SECOND_KEY_RANGE: Range<usize> = ExtendedPublicPart::<H>::LEN..Self::LEN;
 
     /// Creates a public key with the given [`ExtendedPublicPart`] first and second keys.
-    pub(crate) fn new(key: ExtendedPublicPart<H>, second_key: ExtendedPublicPart<H>) -> Self {
+    pub fn new(key: ExtendedPublicPart<H>, second_key: ExtendedPublicPart<H>) -> Self {
         ExtendedPublicKey { key, second_key }
     }
 
+    /// Returns the first half of the public key.
+    pub fn key(&self) -> &ExtendedPublicPart<H> {
+        &self.key
+    }
+
+    /// Returns the second half of the public key.
+    pub fn second_key(&self) -> &ExtendedPublicPart<H> {
+        &self.second_key
+    }
+
     /// Returns a public key bytes (32 length) that is used in signing.
     pub(crate) fn key_for_signing(&self) -> H256 {
         self.key.public.to_bytes()
@@ -81,7 +97,8 @@ impl<'a, H: Hasher512> TryFrom<&'a str> for ExtendedPublicKey<H> {
     }
 }
 
-pub(crate) struct ExtendedPublicPart<H: Hasher512> {
+/// Represents a BIP32-Ed25519 extended public key `A || chain_code`.
+pub struct ExtendedPublicPart<H: Hasher512> {
     public: PublicKey<H>,
     chain_code: H256,
 }
@@ -95,6 +112,49 @@ impl<H: Hasher512> ExtendedPublicPart<H> {
     pub(crate) fn new(public: PublicKey<H>, chain_code: H256) -> ExtendedPublicPart<H> {
         ExtendedPublicPart { public, chain_code }
     }
+
+    /// Returns the `ed25519` public key.
+    pub fn public(&self) -> &PublicKey<H> {
+        &self.public
+    }
+
+    /// Returns the chain code.
+    pub fn chain_code(&self) -> H256 {
+        self.chain_code
+    }
+
+    /// Derives a child extended public key according to BIP32-Ed25519.
+    /// Returns an error if the `child` number is hardened.
+    pub fn derive_child(&self, child: ChildNumber) -> KeyPairResult<Self> {
+        if child.is_hardened() {
+            return Err(KeyPairError::InvalidDerivationPath);
+        }
+
+        let (z, chain_code) =
+            derive_z_and_chain_code(&self.chain_code, self.public.as_slice(), child);
+        let (zl, _): (H256, H256) = z.split();
+        let zl8 = multiply8_truncated(&zl);
+
+        let point =
+            self.public.point() + &Scalar::from_bits((*zl8).take()) * &ED25519_BASEPOINT_TABLE;
+        Ok(ExtendedPublicPart {
+            public: PublicKey::with_point(point),
+            chain_code,
+        })
+    }
+
+    /// Derives an extended public key by the given `path` starting from `self`.
+    /// Returns an error if the `path` contains a hardened child number.
+    pub fn derive_path(&self, path: &DerivationPath) -> KeyPairResult<Self> {
+        let mut key = ExtendedPublicPart {
+            public: PublicKey::with_point(*self.public.point()),
+            chain_code: self.chain_code,
+        };
+        for child in path.iter() {
+            key = key.derive_child(*child)?;
+        }
+        Ok(key)
+    }
 }
 
 impl<'a, H: Hasher512> TryFrom<&'a [u8]> for ExtendedPublicPart<H> {
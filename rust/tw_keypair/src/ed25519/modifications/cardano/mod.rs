@@ -6,6 +6,7 @@
 
 use sha2::Sha512;
 
+mod derivation;
 mod extended_keypair;
 mod extended_private;
 mod extended_public;
@@ -13,3 +14,5 @@ mod extended_public;
 pub type ExtendedKeyPair = extended_keypair::ExtendedKeyPair<Sha512>;
 pub type ExtendedPrivateKey = extended_private::ExtendedPrivateKey<Sha512>;
 pub type ExtendedPublicKey = extended_public::ExtendedPublicKey<Sha512>;
+pub type ExtendedSecretPart = extended_private::ExtendedSecretPart<Sha512>;
+pub type ExtendedPublicPart = extended_public::ExtendedPublicPart<Sha512>;
@@ -10,6 +10,7 @@ use crate::ed25519::signature::Signature;
 use crate::ed25519::Hasher512;
 use crate::traits::SigningKeyTrait;
 use crate::{KeyPairError, KeyPairResult};
+use curve25519_dalek::scalar::Scalar;
 use std::fmt;
 use tw_encoding::hex;
 use tw_hash::H256;
@@ -39,6 +40,11 @@ impl<H: Hasher512> PrivateKey<H> {
         PublicKey::with_expanded_secret(&self.expanded_key)
     }
 
+    /// Returns the clamped secret scalar that the public key is derived from.
+    pub(crate) fn scalar(&self) -> Scalar {
+        self.expanded_key.key
+    }
+
     /// `ed25519` signing uses a public key associated with the private key.
     pub(crate) fn sign_with_public_key(
         &self,
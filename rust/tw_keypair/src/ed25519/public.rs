@@ -58,6 +58,20 @@ impl<H: Hasher512> PublicKey<H> {
         PublicKey::multiply_by_basepoint_to_produce_public_key(bits)
     }
 
+    /// Creates a public key with the given curve point.
+    pub(crate) fn with_point(point: EdwardsPoint) -> Self {
+        PublicKey {
+            compressed: point.compress(),
+            point,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the curve point of the public key.
+    pub(crate) fn point(&self) -> &EdwardsPoint {
+        &self.point
+    }
+
     /// Returns the raw data of the public key (32 bytes).
     pub fn to_bytes(&self) -> H256 {
         H256::from(self.compressed.to_bytes())
@@ -86,13 +100,7 @@ impl<H: Hasher512> PublicKey<H> {
     /// Source: https://github.com/dalek-cryptography/ed25519-dalek/blob/1.0.1/src/public.rs#L157-L160
     fn multiply_by_basepoint_to_produce_public_key(bits: [u8; 32]) -> PublicKey<H> {
         let point = &Scalar::from_bits(bits) * &constants::ED25519_BASEPOINT_TABLE;
-        let compressed = point.compress();
-
-        PublicKey {
-            compressed,
-            point,
-            _phantom: PhantomData,
-        }
+        PublicKey::with_point(point)
     }
 }
 
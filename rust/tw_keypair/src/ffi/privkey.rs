@@ -7,7 +7,7 @@
 #![allow(clippy::missing_safety_doc)]
 
 use crate::ffi::pubkey::TWPublicKey;
-use crate::tw::{Curve, PrivateKey, PublicKeyType};
+use crate::tw::{Curve, PrivateKey, PublicKeyType, SigningMode};
 use tw_memory::ffi::c_byte_array::CByteArray;
 use tw_memory::ffi::c_byte_array_ref::CByteArrayRef;
 use tw_memory::ffi::RawPtrTrait;
@@ -89,6 +89,38 @@ pub unsafe extern "C" fn tw_private_key_sign(
     CByteArray::from(sig)
 }
 
+/// Signs a digest using ECDSA, given curve and signing mode.
+///
+/// \param key *non-null* pointer to a Private key
+/// \param message *non-null* byte array.
+/// \param message_len the length of the `input` array.
+/// \param curve Eliptic curve.
+/// \param mode Signing mode, for example, canonical EOS/FIO signing.
+/// \return Signature as a C-compatible byte array. Empty if the curve doesn't support the mode.
+#[no_mangle]
+pub unsafe extern "C" fn tw_private_key_sign_with_mode(
+    key: *mut TWPrivateKey,
+    message: *const u8,
+    message_len: usize,
+    curve: u32,
+    mode: u32,
+) -> CByteArray {
+    let curve = try_or_else!(Curve::from_raw(curve), CByteArray::default);
+    let mode = try_or_else!(SigningMode::from_raw(mode), CByteArray::default);
+    let private = try_or_else!(TWPrivateKey::from_ptr_as_ref(key), CByteArray::default);
+    let message_to_sign = try_or_else!(
+        CByteArrayRef::new(message, message_len).as_slice(),
+        CByteArray::default
+    );
+
+    // Return an empty signature if an error occurs.
+    let sig = private
+        .0
+        .sign_with_mode(message_to_sign, curve, mode)
+        .unwrap_or_default();
+    CByteArray::from(sig)
+}
+
 /// Returns the public key associated with the given pubkeyType and privateKey
 ///
 /// \param key *non-null* pointer to the private key.
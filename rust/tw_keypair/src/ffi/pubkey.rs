@@ -68,6 +68,34 @@ pub unsafe extern "C" fn tw_public_key_verify(
     public.0.verify(sig, msg)
 }
 
+/// Recovers a `secp256k1` extended public key from the given signature and message hash.
+///
+/// \param sig *non-null* pointer to a block of data corresponding to the recoverable signature (65 bytes).
+/// \param sig_len the length of the `sig` array.
+/// \param msg *non-null* pointer to a block of data corresponding to the signed message hash (32 bytes).
+/// \param msg_len the length of the `msg` array.
+/// \note Should be deleted with \tw_public_key_delete.
+/// \return Nullable pointer to the public key.
+#[no_mangle]
+pub unsafe extern "C" fn tw_public_key_recover(
+    sig: *const u8,
+    sig_len: usize,
+    msg: *const u8,
+    msg_len: usize,
+) -> *mut TWPublicKey {
+    let sig = try_or_else!(
+        CByteArrayRef::new(sig, sig_len).as_slice(),
+        std::ptr::null_mut
+    );
+    let msg = try_or_else!(
+        CByteArrayRef::new(msg, msg_len).as_slice(),
+        std::ptr::null_mut
+    );
+    PublicKey::recover(sig, msg)
+        .map(|public| TWPublicKey(public).into_ptr())
+        .unwrap_or_else(|_| std::ptr::null_mut())
+}
+
 /// Returns the raw data of a given public-key.
 ///
 /// \param key *non-null* pointer to a public key.
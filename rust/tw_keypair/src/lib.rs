@@ -44,9 +44,13 @@
 //! assert_eq(eth_signature.v, H256::from(EXPECTED_V));
 //! ```
 
+pub mod bip32;
+pub mod curve25519;
 pub mod ed25519;
 pub mod ffi;
+pub mod nist256p1;
 pub mod secp256k1;
+pub mod sr25519;
 pub mod starkex;
 pub mod traits;
 pub mod tw;
@@ -59,6 +63,9 @@ pub enum KeyPairError {
     InvalidPublicKey,
     InvalidSignature,
     InvalidSignMessage,
+    InvalidDerivationPath,
+    InvalidExtendedKey,
     SignatureVerifyError,
     SigningError,
+    UnsupportedSigningMode,
 }
@@ -0,0 +1,135 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+mod keypair;
+mod private;
+mod public;
+mod signature;
+
+pub use keypair::KeyPair;
+pub use private::PrivateKey;
+pub use public::PublicKey;
+pub use signature::{Signature, VerifySignature};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{KeyPairTrait, SigningKeyTrait, VerifyingKeyTrait};
+    use tw_encoding::hex;
+    use tw_hash::sha2::sha256;
+    use tw_hash::sha3::keccak256;
+    use tw_hash::{H256, H264, H520};
+    use tw_misc::traits::{ToBytesVec, ToBytesZeroizing};
+
+    #[test]
+    fn test_key_pair() {
+        let secret =
+            hex::decode("afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5")
+                .unwrap();
+        let key_pair = KeyPair::try_from(secret.as_slice()).unwrap();
+        assert_eq!(key_pair.private().to_zeroizing_vec().as_slice(), secret);
+        assert_eq!(
+            key_pair.public().compressed(),
+            H264::from("026d786ab8fda678cf50f71d13641049a393b325063b8c0d4e5070de48a2caf9ab")
+        );
+    }
+
+    #[test]
+    fn test_key_pair_sign() {
+        let key_pair =
+            KeyPair::try_from("afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5")
+                .unwrap();
+
+        let hash_to_sign = keccak256(b"hello");
+        let hash_to_sign = H256::try_from(hash_to_sign.as_slice()).unwrap();
+        let signature = key_pair.sign(hash_to_sign).unwrap();
+
+        let expected = H520::from("8859e63a0c0cc2fc7f788d7e78406157b288faa6f76f76d37c4cd1534e8d83c468f9fd6ca7dde378df594625dcde98559389569e039282275e3d87c26e36447401");
+        assert_eq!(signature.to_bytes(), expected);
+
+        let verify_signature = VerifySignature::from(signature);
+        assert!(key_pair.verify(verify_signature, hash_to_sign));
+    }
+
+    #[test]
+    fn test_private_key_sign_verify() {
+        let secret = "afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5";
+        let private = PrivateKey::try_from(secret).unwrap();
+        let public = private.public();
+
+        let hash_to_sign = sha256(b"Hello");
+        let hash_to_sign = H256::try_from(hash_to_sign.as_slice()).unwrap();
+        let signature = private.sign(hash_to_sign).unwrap();
+
+        let expected = H520::from("2e4655831f0c60729583595c103bf0d862af6313e4326f03f512682106c792822f5a9cd21e7d4a3316c2d337e5eee649b09c34f7b4407344f0d32e8d33167d8901");
+        assert_eq!(signature.to_bytes(), expected);
+
+        let verify_signature = VerifySignature::from(signature);
+        assert!(public.verify(verify_signature, hash_to_sign));
+    }
+
+    #[test]
+    fn test_public_key_from() {
+        let compressed = "026d786ab8fda678cf50f71d13641049a393b325063b8c0d4e5070de48a2caf9ab";
+        let uncompressed = "046d786ab8fda678cf50f71d13641049a393b325063b8c0d4e5070de48a2caf9ab918b4fe46ccbf56701fb210d67d91c5779468f6b3fdc7a63692b9b62543f47ae";
+        let expected_compressed = H264::from(compressed);
+        let expected_uncompressed = H520::from(uncompressed);
+
+        // From extended public key.
+        let public = PublicKey::try_from(uncompressed).unwrap();
+        assert_eq!(public.to_vec(), expected_compressed.into_vec());
+        assert_eq!(public.compressed(), expected_compressed);
+        assert_eq!(public.uncompressed(), expected_uncompressed);
+
+        // From compressed public key.
+        let public = PublicKey::try_from(compressed).unwrap();
+        assert_eq!(public.to_vec(), expected_compressed.into_vec());
+        assert_eq!(public.compressed(), expected_compressed);
+        assert_eq!(public.uncompressed(), expected_uncompressed);
+    }
+
+    #[test]
+    fn test_verify_invalid() {
+        let secret = "afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5";
+        let private = PrivateKey::try_from(secret).unwrap();
+
+        // `secp256k1` signature of the same hash.
+        let signature_bytes = H520::from("8720a46b5b3963790d94bcc61ad57ca02fd153584315bfa161ed3455e336ba624d68df010ed934b8792c5b6a57ba86c3da31d039f9612b44d1bf054132254de901");
+        let verify_sig = VerifySignature::try_from(signature_bytes.as_slice()).unwrap();
+
+        let hash_to_sign = keccak256(b"hello");
+        let hash_to_sign = H256::try_from(hash_to_sign.as_slice()).unwrap();
+
+        assert!(!private.public().verify(verify_sig, hash_to_sign));
+    }
+
+    #[test]
+    fn test_signature_from_invalid_bytes() {
+        Signature::from_bytes(b"123").unwrap_err();
+    }
+
+    #[test]
+    fn test_public_key_recover() {
+        let private = PrivateKey::try_from(
+            "afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5",
+        )
+        .unwrap();
+        let hash_to_sign =
+            H256::from("6468eb103d51c9a683b51818fdb73390151c9973831d2cfb4e9587ad54273155");
+        let signature = private.sign(hash_to_sign).unwrap();
+
+        let recovered = PublicKey::recover(&signature, hash_to_sign).unwrap();
+        assert_eq!(recovered.uncompressed(), private.public().uncompressed());
+    }
+
+    #[test]
+    fn test_public_key_recover_invalid_v() {
+        let hash = H256::from("8a5d3bb6f8da1d60a2a2b3e2aa6b2a6a3f1c8f3e1f47d1a0a26e5d3e8f4a2c11");
+        let sig = H520::from("8859e63a0c0cc2fc7f788d7e78406157b288faa6f76f76d37c4cd1534e8d83c468f9fd6ca7dde378df594625dcde98559389569e039282275e3d87c26e3644741b");
+        let sig = Signature::from_bytes(sig.as_slice()).unwrap();
+        assert!(PublicKey::recover(&sig, hash).is_err());
+    }
+}
@@ -4,15 +4,12 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
-//! This module is a Proof Of Concept that proves the possibility to implement the following in Rust:
+//! Canonical `secp256k1` signing that is used by EOS and FIO blockchains.
+//! The module is ported from:
 //! https://github.com/trustwallet/wallet-core/blob/d9e35ec485b1366dd10509192d02d9dbb6877ab3/src/PrivateKey.cpp#L253-L282
-//!
-//! # Warning
-//!
-//! **Not production ready**
 
 use crate::secp256k1::{PrivateKey, Signature};
-use crate::Error;
+use crate::{KeyPairError, KeyPairResult};
 use k256::ecdsa::hazmat::{bits2field, DigestPrimitive, SignPrimitive};
 use k256::elliptic_curve::generic_array::ArrayLength;
 use k256::elliptic_curve::subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
@@ -29,16 +26,16 @@ pub(crate) fn sign_with_canonical<F>(
     private: &PrivateKey,
     hash_to_sign: H256,
     mut is_canonical: F,
-) -> Result<Signature, Error>
+) -> KeyPairResult<Signature>
 where
     for<'a> F: FnMut(&'a Signature) -> bool,
 {
     let priv_scalar = private.secret.as_nonzero_scalar();
 
-    let nonce =
-        bits2field::<Secp256k1>(hash_to_sign.as_slice()).map_err(|_| Error::InvalidSignMessage)?;
+    let nonce = bits2field::<Secp256k1>(hash_to_sign.as_slice())
+        .map_err(|_| KeyPairError::InvalidSignMessage)?;
     let entropy_input = &priv_scalar.to_repr();
-    let n = &Secp256k1::ORDER.encode_field_bytes();
+    let n = &FieldBytesEncoding::<Secp256k1>::encode_field_bytes(&Secp256k1::ORDER);
     let additional_data = &[];
 
     let mut hmac_drbg = HmacDrbg::<<Secp256k1 as DigestPrimitive>::Digest>::new(
@@ -54,8 +51,8 @@ where
 
         let (sig, r) = priv_scalar
             .try_sign_prehashed(k_scalar, &nonce)
-            .map_err(|_| Error::SigningError)?;
-        let r = r.ok_or_else(|| Error::SigningError)?;
+            .map_err(|_| KeyPairError::SigningError)?;
+        let r = r.ok_or(KeyPairError::SigningError)?;
 
         let signature = Signature::new(sig, r.to_byte());
         if is_canonical(&signature) {
@@ -63,7 +60,27 @@ where
         }
     }
 
-    Err(Error::SigningError)
+    Err(KeyPairError::SigningError)
+}
+
+/// Checks if the given signature is canonical in terms of EOS and FIO blockchains.
+/// The method is ported from `TW::EOS::Signer::isCanonical`.
+pub fn is_eos_fio_canonical(sig: &Signature) -> bool {
+    fn is_unsigned(byte: u8) -> bool {
+        byte & 0x80 == 0
+    }
+
+    let r = sig.r();
+    let s = sig.s();
+    is_unsigned(r[0])
+        && !(r[0] == 0 && is_unsigned(r[1]))
+        && is_unsigned(s[0])
+        && !(s[0] == 0 && is_unsigned(s[1]))
+}
+
+/// Checks if the `s` component of the given signature is in the lower half of the curve order.
+pub fn is_low_s(sig: &Signature) -> bool {
+    sig.inner().normalize_s().is_none()
 }
 
 fn ct_eq<N: ArrayLength<u8>>(a: &ByteArray<N>, b: &ByteArray<N>) -> Choice {
@@ -109,29 +126,37 @@ where
 mod tests {
     use super::*;
     use tw_hash::H520;
-    use tw_misc::traits::ToBytesVec;
-
-    fn is_unsigned(byte: u8) -> bool {
-        byte & 0x80 == 0
-    }
-
-    fn fio_is_canonical(sig: &Signature) -> bool {
-        let sig = sig.to_vec();
-        is_unsigned(sig[0])
-            && !(sig[0] == 0 && is_unsigned(sig[1]))
-            && is_unsigned(sig[32])
-            && !(sig[32] == 0 && is_unsigned(sig[33]))
-    }
 
     #[test]
     fn test_sign_canonical() {
-        let private =
-            PrivateKey::from("ba0828d5734b65e3bcc2c51c93dfc26dd71bd666cc0273adee77d73d9a322035");
+        let private = PrivateKey::try_from(
+            "ba0828d5734b65e3bcc2c51c93dfc26dd71bd666cc0273adee77d73d9a322035",
+        )
+        .unwrap();
         let hash_to_sign =
             H256::from("71b7098e8150cde90f3ec00280815d3069f81c7cdb6d83bbe2b897b1afbe7cd6");
 
-        let actual = sign_with_canonical(&private, hash_to_sign, fio_is_canonical).unwrap();
+        let actual = sign_with_canonical(&private, hash_to_sign, is_eos_fio_canonical).unwrap();
         let expected = H520::from("42ceeaa4a3d0ea0429ab09e4d969abd812c65ad4efef9e95e3a19cc3c41be3770ad0222dac6aa1b350cf9273fa922801d11b6142cb0fe639e2fe3fd988e5aec400");
         assert_eq!(actual.to_bytes(), expected);
+        assert!(is_eos_fio_canonical(&actual));
+        assert!(is_low_s(&actual));
+    }
+
+    #[test]
+    fn test_is_eos_fio_canonical() {
+        let tests = [
+            ("42ceeaa4a3d0ea0429ab09e4d969abd812c65ad4efef9e95e3a19cc3c41be3770ad0222dac6aa1b350cf9273fa922801d11b6142cb0fe639e2fe3fd988e5aec400", true),
+            // `r` starts with a signed byte.
+            ("d9e4c7fa8cfe9d7b3e1e2066a00a8a8d1c5e2ae8ab0bef3a08f0c07a2bd04e8a3f0b2a6d3d0fdc58f4b16b3cdbf2b3d1a4b5f83e8fb13e0e3c2c4c8ab1b8a04d01", false),
+            // `r` starts with a zero byte followed by an unsigned byte.
+            ("0042eaa4a3d0ea0429ab09e4d969abd812c65ad4efef9e95e3a19cc3c41be3770ad0222dac6aa1b350cf9273fa922801d11b6142cb0fe639e2fe3fd988e5aec400", false),
+            // `s` starts with a zero byte followed by an unsigned byte.
+            ("42ceeaa4a3d0ea0429ab09e4d969abd812c65ad4efef9e95e3a19cc3c41be377000d222dac6aa1b350cf9273fa922801d11b6142cb0fe639e2fe3fd988e5aec400", false),
+        ];
+        for (sig, expected) in tests {
+            let sig = Signature::from_bytes(H520::from(sig).as_slice()).unwrap();
+            assert_eq!(is_eos_fio_canonical(&sig), expected, "{sig:?}");
+        }
     }
 }
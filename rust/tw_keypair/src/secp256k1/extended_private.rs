@@ -0,0 +1,128 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::bip32::{
+    decode_extended_key, encode_extended_key, fingerprint, split_hmac_output, ChildNumber,
+    DerivationPath, ExtendedKeyAttrs, Fingerprint, HDVersion,
+};
+use crate::secp256k1::extended_public::ExtendedPublicKey;
+use crate::secp256k1::private::PrivateKey;
+use crate::{KeyPairError, KeyPairResult};
+use k256::ecdsa::SigningKey;
+use k256::NonZeroScalar;
+use tw_hash::hmac::hmac_sha512;
+use tw_hash::H264;
+use zeroize::Zeroizing;
+
+/// HMAC-SHA512 key that is used to generate a master key from a seed.
+const BIP32_SEED_KEY: &[u8] = b"Bitcoin seed";
+
+/// Represents a `secp256k1` BIP32 extended private key.
+pub struct ExtendedPrivateKey {
+    private: PrivateKey,
+    attrs: ExtendedKeyAttrs,
+}
+
+/// cbindgen:ignore
+impl ExtendedPrivateKey {
+    /// Generates a master key from the given `seed` (usually derived from a BIP39 mnemonic).
+    pub fn from_seed(seed: &[u8]) -> KeyPairResult<ExtendedPrivateKey> {
+        let output = Zeroizing::new(hmac_sha512(BIP32_SEED_KEY, seed));
+        let (secret, chain_code) = split_hmac_output(&output);
+
+        let private = PrivateKey::try_from(secret.as_slice())?;
+        Ok(ExtendedPrivateKey {
+            private,
+            attrs: ExtendedKeyAttrs::master(chain_code),
+        })
+    }
+
+    /// Deserializes an extended private key from Base58Check, for example, `xprv` or `zprv`.
+    pub fn from_base58(s: &str) -> KeyPairResult<ExtendedPrivateKey> {
+        let (version, attrs, key) = decode_extended_key(s)?;
+        if !version.is_private() || key[0] != 0 {
+            return Err(KeyPairError::InvalidExtendedKey);
+        }
+
+        let private = PrivateKey::try_from(&key[1..])?;
+        Ok(ExtendedPrivateKey { private, attrs })
+    }
+
+    /// Serializes the extended private key with Base58Check using the given private `version`.
+    pub fn to_base58(&self, version: HDVersion) -> KeyPairResult<String> {
+        if !version.is_private() {
+            return Err(KeyPairError::InvalidExtendedKey);
+        }
+
+        let mut key = Zeroizing::new(H264::default());
+        key[1..].copy_from_slice(self.private.secret.to_bytes().as_slice());
+        Ok(encode_extended_key(version, &self.attrs, &key))
+    }
+
+    /// Returns an associated extended public key.
+    pub fn public(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey::new(self.private.public(), self.attrs)
+    }
+
+    /// Returns the private key.
+    pub fn private(&self) -> &PrivateKey {
+        &self.private
+    }
+
+    /// Returns the key depth, parent fingerprint, child number and chain code.
+    pub fn attrs(&self) -> &ExtendedKeyAttrs {
+        &self.attrs
+    }
+
+    /// Returns the fingerprint of the key that is used as a parent fingerprint of its children.
+    pub fn fingerprint(&self) -> Fingerprint {
+        fingerprint(self.private.public().compressed().as_slice())
+    }
+
+    /// Derives a child extended private key.
+    /// Supports both hardened and non-hardened child numbers.
+    pub fn derive_child(&self, child: ChildNumber) -> KeyPairResult<ExtendedPrivateKey> {
+        let mut data = Zeroizing::new(Vec::with_capacity(H264::len() + 4));
+        if child.is_hardened() {
+            data.push(0);
+            data.extend_from_slice(self.private.secret.to_bytes().as_slice());
+        } else {
+            data.extend_from_slice(self.private.public().compressed().as_slice());
+        }
+        data.extend_from_slice(&child.to_u32().to_be_bytes());
+
+        let output = Zeroizing::new(hmac_sha512(self.attrs.chain_code.as_slice(), &data));
+        let (tweak, chain_code) = split_hmac_output(&output);
+
+        let tweak = PrivateKey::try_from(tweak.as_slice())?;
+        let child_scalar = *tweak.secret.as_nonzero_scalar().as_ref()
+            + self.private.secret.as_nonzero_scalar().as_ref();
+        let child_scalar = Option::<NonZeroScalar>::from(NonZeroScalar::new(child_scalar))
+            .ok_or(KeyPairError::InvalidSecretKey)?;
+
+        let attrs = self.attrs.child(self.fingerprint(), child, chain_code)?;
+        Ok(ExtendedPrivateKey {
+            private: PrivateKey {
+                secret: SigningKey::from(child_scalar),
+            },
+            attrs,
+        })
+    }
+
+    /// Derives an extended private key by the given `path` starting from `self`.
+    pub fn derive_path(&self, path: &DerivationPath) -> KeyPairResult<ExtendedPrivateKey> {
+        let mut key = ExtendedPrivateKey {
+            private: PrivateKey {
+                secret: self.private.secret.clone(),
+            },
+            attrs: self.attrs,
+        };
+        for child in path.iter() {
+            key = key.derive_child(*child)?;
+        }
+        Ok(key)
+    }
+}
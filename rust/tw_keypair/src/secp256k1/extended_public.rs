@@ -0,0 +1,109 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::bip32::{
+    decode_extended_key, encode_extended_key, fingerprint, split_hmac_output, ChildNumber,
+    DerivationPath, ExtendedKeyAttrs, Fingerprint, HDVersion,
+};
+use crate::secp256k1::private::PrivateKey;
+use crate::secp256k1::public::PublicKey;
+use crate::{KeyPairError, KeyPairResult};
+use k256::ecdsa::VerifyingKey;
+use k256::ProjectivePoint;
+use tw_hash::hmac::hmac_sha512;
+use tw_hash::H264;
+
+/// Represents a `secp256k1` BIP32 extended public key.
+pub struct ExtendedPublicKey {
+    public: PublicKey,
+    attrs: ExtendedKeyAttrs,
+}
+
+/// cbindgen:ignore
+impl ExtendedPublicKey {
+    pub(crate) fn new(public: PublicKey, attrs: ExtendedKeyAttrs) -> ExtendedPublicKey {
+        ExtendedPublicKey { public, attrs }
+    }
+
+    /// Deserializes an extended public key from Base58Check, for example, `xpub` or `zpub`.
+    pub fn from_base58(s: &str) -> KeyPairResult<ExtendedPublicKey> {
+        let (version, attrs, key) = decode_extended_key(s)?;
+        if !version.is_public() {
+            return Err(KeyPairError::InvalidExtendedKey);
+        }
+
+        let public = PublicKey::try_from(key.as_slice())?;
+        Ok(ExtendedPublicKey { public, attrs })
+    }
+
+    /// Serializes the extended public key with Base58Check using the given public `version`.
+    pub fn to_base58(&self, version: HDVersion) -> KeyPairResult<String> {
+        if !version.is_public() {
+            return Err(KeyPairError::InvalidExtendedKey);
+        }
+        Ok(encode_extended_key(
+            version,
+            &self.attrs,
+            &self.public.compressed(),
+        ))
+    }
+
+    /// Returns the public key.
+    pub fn public(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// Returns the key depth, parent fingerprint, child number and chain code.
+    pub fn attrs(&self) -> &ExtendedKeyAttrs {
+        &self.attrs
+    }
+
+    /// Returns the fingerprint of the key that is used as a parent fingerprint of its children.
+    pub fn fingerprint(&self) -> Fingerprint {
+        fingerprint(self.public.compressed().as_slice())
+    }
+
+    /// Derives a child extended public key.
+    /// Returns an error if the `child` number is hardened.
+    pub fn derive_child(&self, child: ChildNumber) -> KeyPairResult<ExtendedPublicKey> {
+        if child.is_hardened() {
+            return Err(KeyPairError::InvalidDerivationPath);
+        }
+
+        let mut data = Vec::with_capacity(H264::len() + 4);
+        data.extend_from_slice(self.public.compressed().as_slice());
+        data.extend_from_slice(&child.to_u32().to_be_bytes());
+
+        let output = hmac_sha512(self.attrs.chain_code.as_slice(), &data);
+        let (tweak, chain_code) = split_hmac_output(&output);
+
+        let tweak = PrivateKey::try_from(tweak.as_slice())?;
+        let tweak_point = ProjectivePoint::GENERATOR * tweak.secret.as_nonzero_scalar().as_ref();
+        let child_point = tweak_point + ProjectivePoint::from(*self.public.public.as_affine());
+        // Fails if the result is the point at infinity.
+        let child_public = VerifyingKey::from_affine(child_point.to_affine())
+            .map_err(|_| KeyPairError::InvalidPublicKey)?;
+
+        let attrs = self.attrs.child(self.fingerprint(), child, chain_code)?;
+        Ok(ExtendedPublicKey {
+            public: PublicKey::new(child_public),
+            attrs,
+        })
+    }
+
+    /// Derives an extended public key by the given `path` starting from `self`.
+    /// Returns an error if the `path` contains a hardened child number.
+    pub fn derive_path(&self, path: &DerivationPath) -> KeyPairResult<ExtendedPublicKey> {
+        let mut key = ExtendedPublicKey {
+            public: PublicKey::new(self.public.public),
+            attrs: self.attrs,
+        };
+        for child in path.iter() {
+            key = key.derive_child(*child)?;
+        }
+        Ok(key)
+    }
+}
@@ -4,16 +4,27 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
-// mod canonical;
+mod canonical;
+mod extended_private;
+mod extended_public;
 mod keypair;
 mod private;
 mod public;
+mod schnorr_signature;
 mod signature;
+mod taproot;
+mod xonly_public;
 
+pub use canonical::{is_eos_fio_canonical, is_low_s};
+pub use extended_private::ExtendedPrivateKey;
+pub use extended_public::ExtendedPublicKey;
 pub use keypair::KeyPair;
 pub use private::PrivateKey;
 pub use public::PublicKey;
+pub use schnorr_signature::SchnorrSignature;
 pub use signature::{Signature, VerifySignature};
+pub use taproot::tap_tweak_hash;
+pub use xonly_public::XOnlyPublicKey;
 
 #[cfg(test)]
 mod tests {
@@ -21,7 +32,7 @@ mod tests {
     use crate::traits::{KeyPairTrait, SigningKeyTrait, VerifyingKeyTrait};
     use tw_encoding::hex;
     use tw_hash::sha3::keccak256;
-    use tw_hash::{H256, H264, H520};
+    use tw_hash::{H256, H264, H512, H520};
     use tw_misc::traits::{ToBytesVec, ToBytesZeroizing};
 
     #[test]
@@ -135,11 +146,102 @@ mod tests {
         assert_eq!(sign.to_bytes(), sign_bytes);
     }
 
+    #[test]
+    fn test_signature_to_der() {
+        let sign_bytes = H520::from("d93fc9ae934d4f72db91cb149e7e84b50ca83b5a8a7b873b0fdb009546e3af47786bfaf31af61eea6471dbb1bec7d94f73fb90887e4f04d0e9b85676c47ab02a00");
+        let sign = Signature::from_bytes(sign_bytes.as_slice()).unwrap();
+        assert_eq!(
+            hex::encode(sign.to_der(), false),
+            "3045022100d93fc9ae934d4f72db91cb149e7e84b50ca83b5a8a7b873b0fdb009546e3af470220786bfaf31af61eea6471dbb1bec7d94f73fb90887e4f04d0e9b85676c47ab02a"
+        );
+
+        // `r` with a leading zero byte and a short `s`.
+        let sign_bytes = H520::from("0011223344556677889900112233445566778899001122334455667788990011000000000000000000000000000000000000000000000000000000000000008001");
+        let sign = Signature::from_bytes(sign_bytes.as_slice()).unwrap();
+        assert_eq!(
+            hex::encode(sign.to_der(), false),
+            "3025021f1122334455667788990011223344556677889900112233445566778899001102020080"
+        );
+
+        // Encoded with `openssl asn1parse -genconf`.
+        // Both `r` and `s` have the highest bit set and need a leading zero byte.
+        let sign_bytes = H520::from("d93fc9ae934d4f72db91cb149e7e84b50ca83b5a8a7b873b0fdb009546e3af47886bfaf31af61eea6471dbb1bec7d94f73fb90887e4f04d0e9b85676c47ab02a00");
+        let sign = Signature::from_bytes(sign_bytes.as_slice()).unwrap();
+        assert_eq!(
+            hex::encode(sign.to_der(), false),
+            "3046022100d93fc9ae934d4f72db91cb149e7e84b50ca83b5a8a7b873b0fdb009546e3af47022100886bfaf31af61eea6471dbb1bec7d94f73fb90887e4f04d0e9b85676c47ab02a"
+        );
+
+        // A short `r` with the highest bit set.
+        let sign_bytes = H520::from("00000000000000000000000000000000000000000000000000000000000000ff7f6bfaf31af61eea6471dbb1bec7d94f73fb90887e4f04d0e9b85676c47ab02a00");
+        let sign = Signature::from_bytes(sign_bytes.as_slice()).unwrap();
+        assert_eq!(
+            hex::encode(sign.to_der(), false),
+            "3026020200ff02207f6bfaf31af61eea6471dbb1bec7d94f73fb90887e4f04d0e9b85676c47ab02a"
+        );
+    }
+
     #[test]
     fn test_signature_from_invalid_bytes() {
         Signature::from_bytes(b"123").unwrap_err();
     }
 
+    #[test]
+    fn test_public_key_recover() {
+        let private = PrivateKey::try_from(
+            "4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904",
+        )
+        .unwrap();
+        let hash_to_sign =
+            H256::from("6468eb103d51c9a683b51818fdb73390151c9973831d2cfb4e9587ad54273155");
+        let signature = private.sign(hash_to_sign).unwrap();
+
+        let recovered = PublicKey::recover(&signature, hash_to_sign).unwrap();
+        assert_eq!(recovered.uncompressed(), private.public().uncompressed());
+    }
+
+    #[test]
+    fn test_public_key_recover_eth_v() {
+        let hash = H256::from("6468eb103d51c9a683b51818fdb73390151c9973831d2cfb4e9587ad54273155");
+        let expected = H520::from("0463ade8ebc212b85e7e4278dc3dcb4f9cc18aab912ef5d302b5d1940e772e9e1a9213522efddad487bbd5dd7907e8e776f918e9a5e4cb51893724e9fe76792a4f");
+
+        // v = 0, v = 27, v = 35 + 2.
+        let signatures = [
+            "92c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c646487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e5800",
+            "92c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c646487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e581b",
+            "92c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c646487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e5825",
+        ];
+        for sig in signatures {
+            let sig = Signature::from_bytes(H520::from(sig).as_slice()).unwrap();
+            let recovered = PublicKey::recover(&sig, hash).unwrap();
+            assert_eq!(recovered.uncompressed(), expected);
+        }
+    }
+
+    #[test]
+    fn test_public_key_recover_different_v() {
+        let hash = H256::from("de4e9524586d6fce45667f9ff12f661e79870c4105fa0fb58af976619bb11432");
+        let tests = [
+            ("00000000000000000000000000000000000000000000000000000000000000020123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef01", "0456d8089137b1fd0d890f8c7d4a04d0fd4520a30b19518ee87bd168ea12ed8090329274c4c6c0d9df04515776f2741eeffc30235d596065d718c3973e19711ad0"),
+            ("00000000000000000000000000000000000000000000000000000000000000020123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef00", "043fc5bf5fec35b6ffe6fd246226d312742a8c296bfa57dd22da509a2e348529b7ddb9faf8afe1ecda3c05e7b2bda47ee1f5a87e952742b22afca560b29d972fcf"),
+            // `v = 0x80` is interpreted as an Ethereum encoded `v`.
+            ("00000000000000000000000000000000000000000000000000000000000000020123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef80", "0456d8089137b1fd0d890f8c7d4a04d0fd4520a30b19518ee87bd168ea12ed8090329274c4c6c0d9df04515776f2741eeffc30235d596065d718c3973e19711ad0"),
+        ];
+        for (sig, expected) in tests {
+            let sig = Signature::from_bytes(H520::from(sig).as_slice()).unwrap();
+            let recovered = PublicKey::recover(&sig, hash).unwrap();
+            assert_eq!(recovered.uncompressed(), H520::from(expected));
+        }
+    }
+
+    #[test]
+    fn test_public_key_recover_invalid_v() {
+        let hash = H256::from("de4e9524586d6fce45667f9ff12f661e79870c4105fa0fb58af976619bb11432");
+        let sig = H520::from("00000000000000000000000000000000000000000000000000000000000000020123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef04");
+        let sig = Signature::from_bytes(sig.as_slice()).unwrap();
+        assert!(PublicKey::recover(&sig, hash).is_err());
+    }
+
     #[test]
     fn test_shared_key_hash() {
         let private = PrivateKey::try_from(
@@ -155,4 +257,105 @@ mod tests {
             H256::from("ef2cf705af8714b35c0855030f358f2bee356ff3579cea2607b2025d80133c3a");
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_sign_schnorr_bip340() {
+        // Test vectors are taken from
+        // https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+        let tests = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000003",
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+            ),
+            (
+                "b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfef",
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a",
+            ),
+        ];
+
+        for (secret, public, aux_rand, msg, expected) in tests {
+            let private = PrivateKey::try_from(secret).unwrap();
+            let x_only = private.x_only_public();
+            assert_eq!(x_only.to_bytes(), H256::from(public));
+            assert_eq!(private.public().x_only(), x_only);
+
+            let msg = H256::from(msg);
+            let signature = private.sign_schnorr(msg, &H256::from(aux_rand)).unwrap();
+            assert_eq!(signature.to_bytes(), H512::from(expected));
+            assert!(x_only.verify(signature, msg));
+
+            let signature = SchnorrSignature::try_from(H512::from(expected).as_slice()).unwrap();
+            assert!(!x_only.verify(signature, H256::from([1; 32])));
+        }
+    }
+
+    #[test]
+    fn test_x_only_public_key_invalid() {
+        // The x-coordinate is not on the curve.
+        let not_on_curve = "eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34";
+        assert!(XOnlyPublicKey::try_from(not_on_curve).is_err());
+        // Compressed public key is not an x-only key.
+        let compressed = "0399c6f51ad6f98c9c583f8e92bb7758ab2ca9a04110c0a1126ec43e5453d196c1";
+        assert!(XOnlyPublicKey::try_from(compressed).is_err());
+    }
+
+    #[test]
+    fn test_taproot_tweak() {
+        // Test vectors are taken from
+        // https://github.com/bitcoin/bips/blob/master/bip-0341/wallet-test-vectors.json
+        let internal = XOnlyPublicKey::try_from(
+            "d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d",
+        )
+        .unwrap();
+        let (output, _) = internal.taproot_tweak(None).unwrap();
+        assert_eq!(
+            output.to_bytes(),
+            H256::from("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343")
+        );
+
+        let internal = XOnlyPublicKey::try_from(
+            "187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27",
+        )
+        .unwrap();
+        let merkle_root =
+            H256::from("5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21");
+        assert_eq!(
+            tap_tweak_hash(&internal.to_bytes(), Some(&merkle_root)),
+            H256::from("cbd8679ba636c1110ea247542cfbd964131a6be84f873f7f3b62a777528ed001")
+        );
+        let (output, _) = internal.taproot_tweak(Some(&merkle_root)).unwrap();
+        assert_eq!(
+            output.to_bytes(),
+            H256::from("147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3")
+        );
+    }
+
+    #[test]
+    fn test_taproot_tweak_private() {
+        let private = PrivateKey::try_from(
+            "6b973d88838f27366ed61c9ad6367663045cb456e28335c109e30717ae0c6baa",
+        )
+        .unwrap();
+        let tweaked = private.taproot_tweak(None).unwrap();
+        assert_eq!(
+            tweaked.to_zeroizing_vec().as_slice(),
+            H256::from("2405b971772ad26915c8dcdf10f238753a9b837e5f8e6a86fd7c0cce5b7296d9")
+                .as_slice()
+        );
+
+        // The tweaked private key must correspond to the tweaked public key.
+        let (expected, _) = private.x_only_public().taproot_tweak(None).unwrap();
+        assert_eq!(tweaked.x_only_public(), expected);
+
+        // The tweak must be less than the curve order.
+        let invalid_tweak =
+            H256::from("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        assert!(private.tweak_add(&invalid_tweak).is_err());
+    }
 }
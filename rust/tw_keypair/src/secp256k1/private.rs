@@ -4,13 +4,17 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
+use crate::secp256k1::canonical::sign_with_canonical;
 use crate::secp256k1::public::PublicKey;
+use crate::secp256k1::schnorr_signature::SchnorrSignature;
 use crate::secp256k1::signature::Signature;
+use crate::secp256k1::taproot::{tap_tweak_hash, tweak_to_scalar};
+use crate::secp256k1::xonly_public::XOnlyPublicKey;
 use crate::traits::SigningKeyTrait;
 use crate::{KeyPairError, KeyPairResult};
 use k256::ecdsa::{SigningKey, VerifyingKey};
 use k256::elliptic_curve::sec1::ToEncodedPoint;
-use k256::{AffinePoint, ProjectivePoint};
+use k256::{AffinePoint, NonZeroScalar, ProjectivePoint};
 use tw_encoding::hex;
 use tw_hash::H256;
 use tw_misc::traits::ToBytesZeroizing;
@@ -39,6 +43,56 @@ impl PrivateKey {
         let shared_secret_hash = tw_hash::sha2::sha256(shared_secret_compressed.as_bytes());
         H256::try_from(shared_secret_hash.as_slice()).expect("Expected 32 byte array sha256 hash")
     }
+
+    /// Signs the given `hash` regenerating the nonce until the signature satisfies `is_canonical`.
+    /// See [`is_eos_fio_canonical`](super::is_eos_fio_canonical) and [`is_low_s`](super::is_low_s).
+    pub fn sign_canonical<F>(&self, hash: H256, is_canonical: F) -> KeyPairResult<Signature>
+    where
+        for<'a> F: FnMut(&'a Signature) -> bool,
+    {
+        sign_with_canonical(self, hash, is_canonical)
+    }
+
+    /// Returns an associated BIP340 x-only public key.
+    pub fn x_only_public(&self) -> XOnlyPublicKey {
+        XOnlyPublicKey::new(*self.schnorr_key().verifying_key())
+    }
+
+    /// Signs the given `hash` according to [BIP340](https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki)
+    /// using the `aux_rand` auxiliary randomness.
+    pub fn sign_schnorr(&self, hash: H256, aux_rand: &H256) -> KeyPairResult<SchnorrSignature> {
+        let signature = self
+            .schnorr_key()
+            .sign_prehash_with_aux_rand(&hash.take(), &aux_rand.take())
+            .map_err(|_| KeyPairError::SigningError)?;
+        Ok(SchnorrSignature { signature })
+    }
+
+    /// Computes `d' = d + tweak`, where `d` is negated if its public key has an odd y-coordinate.
+    /// The resulting private key corresponds to [`XOnlyPublicKey::tweak_add`].
+    pub fn tweak_add(&self, tweak: &H256) -> KeyPairResult<PrivateKey> {
+        let tweak = tweak_to_scalar(tweak).ok_or(KeyPairError::InvalidSecretKey)?;
+        let secret = *self.schnorr_key().as_nonzero_scalar().as_ref() + tweak;
+        let secret = Option::<NonZeroScalar>::from(NonZeroScalar::new(secret))
+            .ok_or(KeyPairError::InvalidSecretKey)?;
+        Ok(PrivateKey {
+            secret: SigningKey::from(secret),
+        })
+    }
+
+    /// Tweaks the private key with the given script tree `merkle_root` according to
+    /// [BIP341](https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#constructing-and-spending-taproot-outputs).
+    /// The resulting private key can be used to sign a Taproot key path spending.
+    pub fn taproot_tweak(&self, merkle_root: Option<&H256>) -> KeyPairResult<PrivateKey> {
+        let internal_key = self.x_only_public().to_bytes();
+        self.tweak_add(&tap_tweak_hash(&internal_key, merkle_root))
+    }
+
+    /// Returns a BIP340 signing key. Please note its secret scalar is negated
+    /// if the public key has an odd y-coordinate.
+    fn schnorr_key(&self) -> k256::schnorr::SigningKey {
+        k256::schnorr::SigningKey::from(*self.secret.as_nonzero_scalar())
+    }
 }
 
 /// This method is inspired by [elliptic_curve::ecdh::diffie_hellman](https://github.com/RustCrypto/traits/blob/f0dbe44fea56d4c17e625ababacb580fec842137/elliptic-curve/src/ecdh.rs#L60-L70)
@@ -4,9 +4,10 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
-use crate::secp256k1::signature::VerifySignature;
+use crate::secp256k1::signature::{Signature, VerifySignature};
+use crate::secp256k1::xonly_public::XOnlyPublicKey;
 use crate::traits::VerifyingKeyTrait;
-use crate::KeyPairError;
+use crate::{KeyPairError, KeyPairResult};
 use k256::ecdsa::signature::hazmat::PrehashVerifier;
 use k256::ecdsa::VerifyingKey;
 use tw_encoding::hex;
@@ -30,6 +31,15 @@ impl PublicKey {
         PublicKey { public }
     }
 
+    /// Recovers a public key from the given `sig` recoverable signature and the signed `message` hash.
+    /// The method is ported from `TW::PublicKey::recover`.
+    pub fn recover(sig: &Signature, message: H256) -> KeyPairResult<PublicKey> {
+        let recovery_id = sig.recovery_id()?;
+        VerifyingKey::recover_from_prehash(message.as_slice(), sig.inner(), recovery_id)
+            .map(PublicKey::new)
+            .map_err(|_| KeyPairError::InvalidSignature)
+    }
+
     /// Returns the raw data of the compressed public key (33 bytes).
     pub fn compressed(&self) -> H264 {
         let compressed = true;
@@ -43,6 +53,13 @@ impl PublicKey {
         H520::try_from(self.public.to_encoded_point(compressed).as_bytes())
             .expect("Expected 65 byte array Public Key")
     }
+
+    /// Returns a BIP340 x-only public key.
+    pub fn x_only(&self) -> XOnlyPublicKey {
+        // Skip the parity tag of the compressed public key.
+        XOnlyPublicKey::try_from(&self.compressed()[1..])
+            .expect("Expected a valid x-coordinate of the public key")
+    }
 }
 
 impl VerifyingKeyTrait for PublicKey {
@@ -0,0 +1,42 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::KeyPairError;
+use tw_hash::H512;
+use tw_misc::traits::ToBytesVec;
+
+/// Represents a [BIP340](https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki) Schnorr signature.
+#[derive(Debug, PartialEq)]
+pub struct SchnorrSignature {
+    pub(crate) signature: k256::schnorr::Signature,
+}
+
+/// cbindgen:ignore
+impl SchnorrSignature {
+    /// The number of bytes for a serialized signature representation.
+    pub const LEN: usize = k256::schnorr::Signature::BYTE_SIZE;
+
+    /// Returns the signature data `r || s` (64 bytes).
+    pub fn to_bytes(&self) -> H512 {
+        H512::from(self.signature.to_bytes())
+    }
+}
+
+impl ToBytesVec for SchnorrSignature {
+    fn to_vec(&self) -> Vec<u8> {
+        self.to_bytes().into_vec()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SchnorrSignature {
+    type Error = KeyPairError;
+
+    fn try_from(sig: &'a [u8]) -> Result<Self, Self::Error> {
+        let signature =
+            k256::schnorr::Signature::try_from(sig).map_err(|_| KeyPairError::InvalidSignature)?;
+        Ok(SchnorrSignature { signature })
+    }
+}
@@ -5,6 +5,7 @@
 // file LICENSE at the root of the source code distribution tree.
 
 use crate::{KeyPairError, KeyPairResult};
+use k256::ecdsa::RecoveryId;
 use k256::FieldBytes;
 use std::ops::{Range, RangeInclusive};
 use tw_hash::{H256, H520};
@@ -19,6 +20,9 @@ const RECOVERY_LAST: usize = Signature::len() - 1;
 /// Expected signature with or without recovery byte in the end of the slice.
 /// cbindgen:ignore
 const VERIFY_SIGNATURE_LEN_RANGE: RangeInclusive<usize> = 64..=65;
+/// The minimum value of an Ethereum encoded `v`: either `27 + v` or `35 + chain_id * 2 + v`.
+/// cbindgen:ignore
+const ETH_V_OFFSET: u8 = 27;
 
 /// Represents an ECDSA signature.
 #[derive(Debug, PartialEq)]
@@ -60,6 +64,23 @@ impl Signature {
         self.v
     }
 
+    /// Returns a recovery ID normalized to the `0..=3` range.
+    /// Handles the Ethereum encoded `v` the same way as `TW::PublicKey::recover` does.
+    pub(crate) fn recovery_id(&self) -> KeyPairResult<RecoveryId> {
+        let v = if self.v >= ETH_V_OFFSET {
+            // `v & 1` is 1 for `27 + 0` and `35 + chain_id * 2 + 0`.
+            u8::from(self.v & 0x01 == 0)
+        } else {
+            self.v
+        };
+        RecoveryId::from_byte(v).ok_or(KeyPairError::InvalidSignature)
+    }
+
+    /// Returns a reference to the inner [`k256::ecdsa::Signature`].
+    pub(crate) fn inner(&self) -> &k256::ecdsa::Signature {
+        &self.signature
+    }
+
     /// Tries to create a Signature from the serialized representation.
     pub fn from_bytes(sig: &[u8]) -> KeyPairResult<Signature> {
         if sig.len() != Signature::len() {
@@ -84,6 +105,11 @@ impl Signature {
         dest
     }
 
+    /// Returns a DER encoded signature without the recovery ID, as used in Bitcoin scripts.
+    pub fn to_der(&self) -> Vec<u8> {
+        self.signature.to_der().as_bytes().to_vec()
+    }
+
     /// # Panic
     ///
     /// `r` and `s` must be 32 byte arrays, otherwise the function panics.
@@ -0,0 +1,33 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! [BIP341](https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki) key tweaking helpers.
+
+use k256::elliptic_curve::PrimeField;
+use k256::Scalar;
+use tw_hash::sha2::sha256_tagged;
+use tw_hash::H256;
+
+/// cbindgen:ignore
+const TAP_TWEAK_TAG: &[u8] = b"TapTweak";
+
+/// Computes the Taproot tweak `t = hash_TapTweak(P || merkle_root)`.
+/// The `merkle_root` should be `None` if the output has no script path.
+pub fn tap_tweak_hash(internal_key: &H256, merkle_root: Option<&H256>) -> H256 {
+    let mut data = internal_key.to_vec();
+    if let Some(merkle_root) = merkle_root {
+        data.extend_from_slice(merkle_root.as_slice());
+    }
+    let tweak = sha256_tagged(TAP_TWEAK_TAG, &data);
+    H256::try_from(tweak.as_slice()).expect("Expected 32 byte sha256 hash")
+}
+
+/// Converts the given `tweak` to a scalar.
+/// Returns `None` if the `tweak` is not less than the curve order.
+pub(crate) fn tweak_to_scalar(tweak: &H256) -> Option<Scalar> {
+    let repr = k256::FieldBytes::clone_from_slice(tweak.as_slice());
+    Scalar::from_repr(repr).into()
+}
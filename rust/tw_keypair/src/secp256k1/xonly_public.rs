@@ -0,0 +1,103 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::secp256k1::schnorr_signature::SchnorrSignature;
+use crate::secp256k1::taproot::{tap_tweak_hash, tweak_to_scalar};
+use crate::traits::VerifyingKeyTrait;
+use crate::{KeyPairError, KeyPairResult};
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::elliptic_curve::point::AffineCoordinates;
+use k256::schnorr::VerifyingKey;
+use k256::ProjectivePoint;
+use tw_encoding::hex;
+use tw_hash::H256;
+use tw_misc::traits::ToBytesVec;
+
+/// Represents a [BIP340](https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki)
+/// x-only `secp256k1` public key, i.e. a point with an even y-coordinate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct XOnlyPublicKey {
+    pub(crate) public: VerifyingKey,
+}
+
+/// cbindgen:ignore
+impl XOnlyPublicKey {
+    /// The number of bytes in an x-only public key.
+    pub const LEN: usize = H256::len();
+
+    /// Creates an x-only public key from the given [`VerifyingKey`].
+    pub(crate) fn new(public: VerifyingKey) -> XOnlyPublicKey {
+        XOnlyPublicKey { public }
+    }
+
+    /// Returns the x-coordinate of the public key (32 bytes).
+    pub fn to_bytes(&self) -> H256 {
+        H256::try_from(self.public.to_bytes().as_slice()).expect("Expected 32 byte x-coordinate")
+    }
+
+    /// Computes `Q = P + tweak * G`.
+    /// Returns the x-only `Q` public key and whether the y-coordinate of `Q` is odd.
+    pub fn tweak_add(&self, tweak: &H256) -> KeyPairResult<(XOnlyPublicKey, bool)> {
+        let tweak = tweak_to_scalar(tweak).ok_or(KeyPairError::InvalidPublicKey)?;
+        let tweaked = (ProjectivePoint::from(*self.public.as_affine())
+            + ProjectivePoint::GENERATOR * tweak)
+            .to_affine();
+
+        let is_odd = bool::from(tweaked.y_is_odd());
+        let public =
+            VerifyingKey::from_bytes(&tweaked.x()).map_err(|_| KeyPairError::InvalidPublicKey)?;
+        Ok((XOnlyPublicKey { public }, is_odd))
+    }
+
+    /// Tweaks the internal public key with the given script tree `merkle_root` according to
+    /// [BIP341](https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#constructing-and-spending-taproot-outputs).
+    /// Returns the output public key and whether its y-coordinate is odd.
+    pub fn taproot_tweak(
+        &self,
+        merkle_root: Option<&H256>,
+    ) -> KeyPairResult<(XOnlyPublicKey, bool)> {
+        self.tweak_add(&tap_tweak_hash(&self.to_bytes(), merkle_root))
+    }
+}
+
+impl VerifyingKeyTrait for XOnlyPublicKey {
+    type SigningMessage = H256;
+    type VerifySignature = SchnorrSignature;
+
+    fn verify(&self, sign: Self::VerifySignature, message: Self::SigningMessage) -> bool {
+        self.public
+            .verify_prehash(message.as_slice(), &sign.signature)
+            .is_ok()
+    }
+}
+
+impl<'a> TryFrom<&'a str> for XOnlyPublicKey {
+    type Error = KeyPairError;
+
+    fn try_from(hex: &'a str) -> Result<Self, Self::Error> {
+        let bytes = hex::decode(hex).map_err(|_| KeyPairError::InvalidPublicKey)?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for XOnlyPublicKey {
+    type Error = KeyPairError;
+
+    /// Expected `H256` x-coordinate slice.
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(KeyPairError::InvalidPublicKey);
+        }
+        let public = VerifyingKey::from_bytes(data).map_err(|_| KeyPairError::InvalidPublicKey)?;
+        Ok(XOnlyPublicKey { public })
+    }
+}
+
+impl ToBytesVec for XOnlyPublicKey {
+    fn to_vec(&self) -> Vec<u8> {
+        self.to_bytes().into_vec()
+    }
+}
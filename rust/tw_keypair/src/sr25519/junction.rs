@@ -0,0 +1,150 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::{KeyPairError, KeyPairResult};
+use std::str::FromStr;
+use tw_hash::blake2::blake2_b;
+use tw_hash::H256;
+
+/// A single step of a Substrate key derivation path.
+/// The chain code is either a SCALE-encoded junction name (padded with zeros)
+/// or its `blake2b-256` hash if the encoded name is longer than 32 bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeriveJunction {
+    /// Soft (non-hardened) derivation `/name`. Can be applied to a public key.
+    Soft(H256),
+    /// Hard derivation `//name`. Can be applied to a private key only.
+    Hard(H256),
+}
+
+impl DeriveJunction {
+    /// Creates a soft junction from the given `name`.
+    pub fn soft(name: &str) -> DeriveJunction {
+        DeriveJunction::Soft(chain_code(name))
+    }
+
+    /// Creates a hard junction from the given `name`.
+    pub fn hard(name: &str) -> DeriveJunction {
+        DeriveJunction::Hard(chain_code(name))
+    }
+
+    /// Returns whether the junction is hard.
+    pub fn is_hard(&self) -> bool {
+        matches!(self, DeriveJunction::Hard(_))
+    }
+
+    /// Returns the 32 byte chain code of the junction.
+    pub fn chain_code(&self) -> H256 {
+        match self {
+            DeriveJunction::Soft(cc) | DeriveJunction::Hard(cc) => *cc,
+        }
+    }
+
+    /// Parses a derivation path like `//Alice/soft//0`.
+    /// Returns an error if the `path` is empty or contains an empty junction name.
+    pub fn parse_path(path: &str) -> KeyPairResult<Vec<DeriveJunction>> {
+        let mut rest = path
+            .strip_prefix('/')
+            .ok_or(KeyPairError::InvalidDerivationPath)?;
+
+        let mut junctions = Vec::new();
+        loop {
+            let (hard, segment) = match rest.strip_prefix('/') {
+                Some(segment) => (true, segment),
+                None => (false, rest),
+            };
+            let (name, next) = match segment.find('/') {
+                Some(pos) => (&segment[..pos], Some(&segment[pos + 1..])),
+                None => (segment, None),
+            };
+            if name.is_empty() {
+                return Err(KeyPairError::InvalidDerivationPath);
+            }
+
+            junctions.push(if hard {
+                DeriveJunction::hard(name)
+            } else {
+                DeriveJunction::soft(name)
+            });
+
+            match next {
+                Some(next) => rest = next,
+                None => return Ok(junctions),
+            }
+        }
+    }
+}
+
+/// Encodes the junction `name` the same way as `sp_core::crypto::DeriveJunction` does:
+/// numeric names are encoded as `u64` little-endian, others as SCALE-encoded strings.
+fn chain_code(name: &str) -> H256 {
+    let encoded = match u64::from_str(name) {
+        Ok(index) => index.to_le_bytes().to_vec(),
+        Err(_) => scale_encode_bytes(name.as_bytes()),
+    };
+
+    if encoded.len() > H256::len() {
+        let hash = blake2_b(&encoded, H256::len()).expect("Expected a valid blake2b hash size");
+        return H256::try_from(hash.as_slice()).expect("Expected 32 byte blake2b hash");
+    }
+
+    let mut cc = H256::default();
+    cc[..encoded.len()].copy_from_slice(&encoded);
+    cc
+}
+
+/// Prepends the SCALE compact-encoded length to the given `bytes`.
+fn scale_encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let len = bytes.len();
+    let mut encoded = match len {
+        0..=0x3f => vec![(len as u8) << 2],
+        0x40..=0x3fff => (((len as u16) << 2) | 0b01).to_le_bytes().to_vec(),
+        _ => (((len as u32) << 2) | 0b10).to_le_bytes().to_vec(),
+    };
+    encoded.extend_from_slice(bytes);
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_path() {
+        let actual = DeriveJunction::parse_path("//Alice/soft//0").unwrap();
+        let expected = vec![
+            DeriveJunction::hard("Alice"),
+            DeriveJunction::soft("soft"),
+            DeriveJunction::hard("0"),
+        ];
+        assert_eq!(actual, expected);
+
+        for path in ["", "Alice", "//", "//Alice/", "/soft///hard"] {
+            assert!(DeriveJunction::parse_path(path).is_err(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_chain_code() {
+        // "Alice" is SCALE encoded as `0x14` length prefix and the UTF-8 bytes.
+        let mut expected = H256::default();
+        expected[..6].copy_from_slice(b"\x14Alice");
+        assert_eq!(DeriveJunction::hard("Alice").chain_code(), expected);
+
+        let mut expected = H256::default();
+        expected[0] = 42;
+        assert_eq!(DeriveJunction::soft("42").chain_code(), expected);
+
+        let long_name = "a".repeat(40);
+        let junction = DeriveJunction::soft(&long_name);
+        assert_eq!(
+            junction.chain_code().as_slice(),
+            blake2_b(&scale_encode_bytes(long_name.as_bytes()), 32)
+                .unwrap()
+                .as_slice()
+        );
+    }
+}
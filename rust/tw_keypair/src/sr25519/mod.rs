@@ -0,0 +1,111 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+//! `sr25519` (Schnorrkel) keys that are used by Substrate based blockchains like Polkadot and Kusama.
+
+mod junction;
+mod keypair;
+mod private;
+mod public;
+mod signature;
+
+pub use junction::DeriveJunction;
+pub use keypair::KeyPair;
+pub use private::PrivateKey;
+pub use public::PublicKey;
+pub use signature::Signature;
+
+/// The signing context that is used by Substrate.
+/// cbindgen:ignore
+const SIGNING_CONTEXT: &[u8] = b"substrate";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{KeyPairTrait, SigningKeyTrait, VerifyingKeyTrait};
+    use tw_hash::{H256, H512};
+    use tw_misc::traits::{ToBytesVec, ToBytesZeroizing};
+
+    /// Mini secret key of the Substrate development phrase
+    /// "bottom drive obey lake curtain smoke basket hold race lonely fit walk".
+    const DEV_SEED: &str = "fac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e";
+
+    #[test]
+    fn test_private_to_public() {
+        let private = PrivateKey::try_from(DEV_SEED).unwrap();
+        assert_eq!(
+            private.public().to_bytes(),
+            H256::from("46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a")
+        );
+    }
+
+    #[test]
+    fn test_derive_hard() {
+        let private = PrivateKey::try_from(DEV_SEED).unwrap();
+
+        let alice = private.derive_path("//Alice").unwrap();
+        assert_eq!(
+            alice.public().to_bytes(),
+            H256::from("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")
+        );
+
+        let bob = private.derive_path("//Bob").unwrap();
+        assert_eq!(
+            bob.public().to_bytes(),
+            H256::from("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48")
+        );
+    }
+
+    #[test]
+    fn test_derive_soft() {
+        let private = PrivateKey::try_from(DEV_SEED).unwrap();
+        let junctions = DeriveJunction::parse_path("/Alice").unwrap();
+
+        let derived = private.derive(&junctions);
+        assert_eq!(
+            derived.public().to_bytes(),
+            H256::from("d6c71059dbbe9ad2b0ed3f289738b800836eb425544ce694825285b958ca755e")
+        );
+
+        // Soft derivation of the public key gives the same result.
+        let public = private.public().derive(&junctions).unwrap();
+        assert_eq!(public.to_bytes(), derived.public().to_bytes());
+
+        let hard = DeriveJunction::parse_path("//Alice").unwrap();
+        assert!(private.public().derive(&hard).is_err());
+    }
+
+    #[test]
+    fn test_key_pair_sign_verify() {
+        let key_pair = KeyPair::try_from(DEV_SEED).unwrap();
+        let message = b"hello".to_vec();
+
+        let signature = key_pair.sign(message.clone()).unwrap();
+        let signature = Signature::try_from(signature.to_vec().as_slice()).unwrap();
+        assert!(key_pair.verify(signature, message));
+
+        let signature = key_pair.sign(b"hello".to_vec()).unwrap();
+        assert!(!key_pair.public().verify(signature, b"hellO".to_vec()));
+    }
+
+    #[test]
+    fn test_private_key_expanded_bytes() {
+        let private = PrivateKey::try_from(DEV_SEED).unwrap();
+        let expanded = private.to_zeroizing_vec();
+        assert_eq!(expanded.len(), PrivateKey::LEN);
+
+        let restored = PrivateKey::try_from(expanded.as_slice()).unwrap();
+        assert_eq!(restored.public().to_bytes(), private.public().to_bytes());
+    }
+
+    #[test]
+    fn test_signature_invalid() {
+        // The most significant bit of the last byte must be set for `sr25519` signatures.
+        let ed25519_like = H512::from("ea85a47dcc18b512dfea7c209162abaea4808d77c1ec903dc7ba6e2afa3f9f07e7ed7a20a4e2fa1009db3d1443e937e6abb16ff3c3eaecb798faed7fbb40b008");
+        assert!(Signature::try_from(ed25519_like.as_slice()).is_err());
+        assert!(Signature::try_from(b"123".as_slice()).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::sr25519::{DeriveJunction, PublicKey, Signature, SIGNING_CONTEXT};
+use crate::traits::SigningKeyTrait;
+use crate::{KeyPairError, KeyPairResult};
+use schnorrkel::derive::{ChainCode, Derivation};
+use schnorrkel::{ExpansionMode, MiniSecretKey, SecretKey};
+use tw_encoding::hex;
+use tw_misc::traits::ToBytesZeroizing;
+use zeroize::Zeroizing;
+
+/// Represents an `sr25519` private key.
+pub struct PrivateKey {
+    secret: SecretKey,
+}
+
+/// cbindgen:ignore
+impl PrivateKey {
+    /// The number of bytes in a mini secret key (seed).
+    pub const SEED_LEN: usize = schnorrkel::MINI_SECRET_KEY_LENGTH;
+    /// The number of bytes in an expanded secret key.
+    pub const LEN: usize = schnorrkel::SECRET_KEY_LENGTH;
+
+    /// Returns an associated `sr25519` public key.
+    pub fn public(&self) -> PublicKey {
+        PublicKey::new(self.secret.to_public())
+    }
+
+    /// Derives a private key by the given `junctions` the same way as `sp_core::sr25519::Pair::derive` does.
+    pub fn derive(&self, junctions: &[DeriveJunction]) -> PrivateKey {
+        let secret =
+            junctions
+                .iter()
+                .fold(self.secret.clone(), |secret, junction| match junction {
+                    DeriveJunction::Soft(cc) => {
+                        secret.derived_key_simple(ChainCode(cc.take()), []).0
+                    },
+                    DeriveJunction::Hard(cc) => secret
+                        .hard_derive_mini_secret_key(Some(ChainCode(cc.take())), b"")
+                        .0
+                        .expand(ExpansionMode::Ed25519),
+                });
+        PrivateKey { secret }
+    }
+
+    /// Derives a private key by the given `path` like `//Alice/soft`.
+    pub fn derive_path(&self, path: &str) -> KeyPairResult<PrivateKey> {
+        DeriveJunction::parse_path(path).map(|junctions| self.derive(&junctions))
+    }
+}
+
+impl SigningKeyTrait for PrivateKey {
+    type SigningMessage = Vec<u8>;
+    type Signature = Signature;
+
+    /// Signs the `message` with the `substrate` signing context.
+    /// Please note that `sr25519` signatures are randomized.
+    fn sign(&self, message: Self::SigningMessage) -> KeyPairResult<Self::Signature> {
+        let public = self.secret.to_public();
+        let signature = self.secret.sign_simple(SIGNING_CONTEXT, &message, &public);
+        Ok(Signature::new(signature))
+    }
+}
+
+/// Expected either a 32 byte mini secret key (seed) or a 64 byte expanded secret key.
+impl<'a> TryFrom<&'a [u8]> for PrivateKey {
+    type Error = KeyPairError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let secret = match data.len() {
+            Self::SEED_LEN => MiniSecretKey::from_bytes(data)
+                .map_err(|_| KeyPairError::InvalidSecretKey)?
+                .expand(ExpansionMode::Ed25519),
+            Self::LEN => SecretKey::from_bytes(data).map_err(|_| KeyPairError::InvalidSecretKey)?,
+            _ => return Err(KeyPairError::InvalidSecretKey),
+        };
+        Ok(PrivateKey { secret })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for PrivateKey {
+    type Error = KeyPairError;
+
+    fn try_from(hex: &'a str) -> Result<Self, Self::Error> {
+        let bytes = Zeroizing::new(hex::decode(hex).map_err(|_| KeyPairError::InvalidSecretKey)?);
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+/// Returns the 64 byte expanded secret key.
+impl ToBytesZeroizing for PrivateKey {
+    fn to_zeroizing_vec(&self) -> Zeroizing<Vec<u8>> {
+        let secret = Zeroizing::new(self.secret.to_bytes());
+        Zeroizing::new(secret.to_vec())
+    }
+}
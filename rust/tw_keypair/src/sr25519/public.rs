@@ -0,0 +1,84 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::sr25519::{DeriveJunction, Signature, SIGNING_CONTEXT};
+use crate::traits::VerifyingKeyTrait;
+use crate::{KeyPairError, KeyPairResult};
+use schnorrkel::derive::{ChainCode, Derivation};
+use tw_encoding::hex;
+use tw_hash::H256;
+use tw_misc::traits::ToBytesVec;
+
+/// Represents an `sr25519` public key.
+pub struct PublicKey {
+    public: schnorrkel::PublicKey,
+}
+
+/// cbindgen:ignore
+impl PublicKey {
+    /// The number of bytes in a serialized public key.
+    pub const LEN: usize = schnorrkel::PUBLIC_KEY_LENGTH;
+
+    pub(crate) fn new(public: schnorrkel::PublicKey) -> PublicKey {
+        PublicKey { public }
+    }
+
+    /// Derives a public key by the given soft `junctions`.
+    /// Returns an error if there is a hard junction.
+    pub fn derive(&self, junctions: &[DeriveJunction]) -> KeyPairResult<PublicKey> {
+        let mut public = self.public;
+        for junction in junctions {
+            match junction {
+                DeriveJunction::Soft(cc) => {
+                    public = public.derived_key_simple(ChainCode(cc.take()), []).0;
+                },
+                DeriveJunction::Hard(_) => return Err(KeyPairError::InvalidDerivationPath),
+            }
+        }
+        Ok(PublicKey { public })
+    }
+
+    /// Returns the raw data of the public key (32 bytes).
+    pub fn to_bytes(&self) -> H256 {
+        H256::from(self.public.to_bytes())
+    }
+}
+
+impl VerifyingKeyTrait for PublicKey {
+    type SigningMessage = Vec<u8>;
+    type VerifySignature = Signature;
+
+    fn verify(&self, signature: Self::VerifySignature, message: Self::SigningMessage) -> bool {
+        self.public
+            .verify_simple(SIGNING_CONTEXT, &message, signature.inner())
+            .is_ok()
+    }
+}
+
+impl ToBytesVec for PublicKey {
+    fn to_vec(&self) -> Vec<u8> {
+        self.to_bytes().into_vec()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for PublicKey {
+    type Error = KeyPairError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let public =
+            schnorrkel::PublicKey::from_bytes(data).map_err(|_| KeyPairError::InvalidPublicKey)?;
+        Ok(PublicKey { public })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for PublicKey {
+    type Error = KeyPairError;
+
+    fn try_from(hex: &'a str) -> Result<Self, Self::Error> {
+        let bytes = hex::decode(hex).map_err(|_| KeyPairError::InvalidPublicKey)?;
+        Self::try_from(bytes.as_slice())
+    }
+}
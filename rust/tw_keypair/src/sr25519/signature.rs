@@ -0,0 +1,50 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::KeyPairError;
+use tw_hash::H512;
+use tw_misc::traits::ToBytesVec;
+
+/// Represents an `sr25519` signature.
+#[derive(Debug)]
+pub struct Signature {
+    signature: schnorrkel::Signature,
+}
+
+/// cbindgen:ignore
+impl Signature {
+    /// The number of bytes in a serialized signature.
+    pub const LEN: usize = schnorrkel::SIGNATURE_LENGTH;
+
+    pub(crate) fn new(signature: schnorrkel::Signature) -> Signature {
+        Signature { signature }
+    }
+
+    pub(crate) fn inner(&self) -> &schnorrkel::Signature {
+        &self.signature
+    }
+
+    /// Returns the signature data (64 bytes).
+    pub fn to_bytes(&self) -> H512 {
+        H512::from(self.signature.to_bytes())
+    }
+}
+
+impl ToBytesVec for Signature {
+    fn to_vec(&self) -> Vec<u8> {
+        self.to_bytes().into_vec()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Signature {
+    type Error = KeyPairError;
+
+    fn try_from(sign: &'a [u8]) -> Result<Self, Self::Error> {
+        let signature =
+            schnorrkel::Signature::from_bytes(sign).map_err(|_| KeyPairError::InvalidSignature)?;
+        Ok(Signature { signature })
+    }
+}
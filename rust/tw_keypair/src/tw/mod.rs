@@ -17,8 +17,12 @@ pub enum Curve {
     Secp256k1 = 0,
     Ed25519 = 1,
     Ed25519Blake2bNano = 2,
+    Curve25519 = 3,
+    Nist256p1 = 4,
     Ed25519ExtendedCardano = 5,
     Starkex = 6,
+    /// `7` is reserved for `TWCurveNone`.
+    Sr25519 = 8,
 }
 
 impl Curve {
@@ -28,8 +32,39 @@ impl Curve {
             0 => Some(Curve::Secp256k1),
             1 => Some(Curve::Ed25519),
             2 => Some(Curve::Ed25519Blake2bNano),
+            3 => Some(Curve::Curve25519),
+            4 => Some(Curve::Nist256p1),
             5 => Some(Curve::Ed25519ExtendedCardano),
             6 => Some(Curve::Starkex),
+            8 => Some(Curve::Sr25519),
+            _ => None,
+        }
+    }
+}
+
+/// Specifies how a message is signed by a private key in addition to the elliptic curve.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum SigningMode {
+    /// The default signing algorithm of the elliptic curve.
+    Standard = 0,
+    /// `secp256k1` ECDSA signature that is canonical in terms of EOS and FIO blockchains.
+    CanonicalEosFio = 1,
+    /// `secp256k1` ECDSA signature with the `s` value in the lower half of the curve order.
+    CanonicalLowS = 2,
+    /// `secp256k1` BIP340 Schnorr signature with fresh auxiliary randomness.
+    Schnorr = 3,
+}
+
+impl SigningMode {
+    /// Returns `None` if the given signing mode is not supported in Rust yet.
+    pub fn from_raw(mode: u32) -> Option<SigningMode> {
+        match mode {
+            0 => Some(SigningMode::Standard),
+            1 => Some(SigningMode::CanonicalEosFio),
+            2 => Some(SigningMode::CanonicalLowS),
+            3 => Some(SigningMode::Schnorr),
             _ => None,
         }
     }
@@ -41,10 +76,16 @@ impl Curve {
 pub enum PublicKeyType {
     Secp256k1 = 0,
     Secp256k1Extended = 1,
+    Nist256p1 = 2,
+    Nist256p1Extended = 3,
     Ed25519 = 4,
     Ed25519Blake2b = 5,
+    Curve25519 = 6,
     Ed25519ExtendedCardano = 7,
     Starkex = 8,
+    Sr25519 = 9,
+    /// BIP340 x-only `secp256k1` public key.
+    Secp256k1XOnly = 10,
 }
 
 impl PublicKeyType {
@@ -53,10 +94,15 @@ impl PublicKeyType {
         match ty {
             0 => Some(PublicKeyType::Secp256k1),
             1 => Some(PublicKeyType::Secp256k1Extended),
+            2 => Some(PublicKeyType::Nist256p1),
+            3 => Some(PublicKeyType::Nist256p1Extended),
             4 => Some(PublicKeyType::Ed25519),
             5 => Some(PublicKeyType::Ed25519Blake2b),
+            6 => Some(PublicKeyType::Curve25519),
             7 => Some(PublicKeyType::Ed25519ExtendedCardano),
             8 => Some(PublicKeyType::Starkex),
+            9 => Some(PublicKeyType::Sr25519),
+            10 => Some(PublicKeyType::Secp256k1XOnly),
             _ => None,
         }
     }
@@ -72,30 +118,48 @@ mod tests {
             (0, Some(Curve::Secp256k1)),
             (1, Some(Curve::Ed25519)),
             (2, Some(Curve::Ed25519Blake2bNano)),
-            (3, None),
-            (4, None),
+            (3, Some(Curve::Curve25519)),
+            (4, Some(Curve::Nist256p1)),
             (5, Some(Curve::Ed25519ExtendedCardano)),
             (6, Some(Curve::Starkex)),
             (7, None),
+            (8, Some(Curve::Sr25519)),
+            (9, None),
         ];
         for (raw, expected) in tests {
             assert_eq!(Curve::from_raw(raw), expected);
         }
     }
 
+    #[test]
+    fn test_signing_mode_from_raw() {
+        let tests = [
+            (0, Some(SigningMode::Standard)),
+            (1, Some(SigningMode::CanonicalEosFio)),
+            (2, Some(SigningMode::CanonicalLowS)),
+            (3, Some(SigningMode::Schnorr)),
+            (4, None),
+        ];
+        for (raw, expected) in tests {
+            assert_eq!(SigningMode::from_raw(raw), expected);
+        }
+    }
+
     #[test]
     fn test_public_key_type_from_raw() {
         let tests = [
             (0, Some(PublicKeyType::Secp256k1)),
             (1, Some(PublicKeyType::Secp256k1Extended)),
-            (2, None),
-            (3, None),
+            (2, Some(PublicKeyType::Nist256p1)),
+            (3, Some(PublicKeyType::Nist256p1Extended)),
             (4, Some(PublicKeyType::Ed25519)),
             (5, Some(PublicKeyType::Ed25519Blake2b)),
-            (6, None),
+            (6, Some(PublicKeyType::Curve25519)),
             (7, Some(PublicKeyType::Ed25519ExtendedCardano)),
             (8, Some(PublicKeyType::Starkex)),
-            (9, None),
+            (9, Some(PublicKeyType::Sr25519)),
+            (10, Some(PublicKeyType::Secp256k1XOnly)),
+            (11, None),
         ];
         for (raw, expected) in tests {
             assert_eq!(PublicKeyType::from_raw(raw), expected);
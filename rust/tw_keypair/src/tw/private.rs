@@ -5,12 +5,15 @@
 // file LICENSE at the root of the source code distribution tree.
 
 use crate::traits::SigningKeyTrait;
-use crate::tw::{Curve, PublicKey, PublicKeyType};
-use crate::{ed25519, secp256k1, starkex, KeyPairError, KeyPairResult};
+use crate::tw::{Curve, PublicKey, PublicKeyType, SigningMode};
+use crate::{
+    curve25519, ed25519, nist256p1, secp256k1, sr25519, starkex, KeyPairError, KeyPairResult,
+};
+use rand_core::{OsRng, RngCore};
 use std::ops::Range;
 use tw_hash::H256;
 use tw_misc::traits::ToBytesVec;
-use zeroize::ZeroizeOnDrop;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
 
 /// Represents a private key that can be used to sign messages with different elliptic curves.
 ///
@@ -77,16 +80,30 @@ impl PrivateKey {
             Curve::Ed25519Blake2bNano => {
                 ed25519::blake2b::PrivateKey::try_from(&bytes[Self::KEY_RANGE]).is_ok()
             },
+            Curve::Curve25519 => curve25519::PrivateKey::try_from(&bytes[Self::KEY_RANGE]).is_ok(),
+            Curve::Nist256p1 => nist256p1::PrivateKey::try_from(&bytes[Self::KEY_RANGE]).is_ok(),
             Curve::Ed25519ExtendedCardano => {
                 ed25519::cardano::ExtendedPrivateKey::try_from(&bytes[Self::EXTENDED_CARDANO_RANGE])
                     .is_ok()
             },
             Curve::Starkex => starkex::PrivateKey::try_from(&bytes[Self::KEY_RANGE]).is_ok(),
+            Curve::Sr25519 => sr25519::PrivateKey::try_from(&bytes[Self::KEY_RANGE]).is_ok(),
         }
     }
 
     /// Signs a `message` with using the given elliptic curve.
     pub fn sign(&self, message: &[u8], curve: Curve) -> KeyPairResult<Vec<u8>> {
+        self.sign_with_mode(message, curve, SigningMode::Standard)
+    }
+
+    /// Signs a `message` with using the given elliptic curve and signing mode.
+    /// Returns [`KeyPairError::UnsupportedSigningMode`] if the `curve` doesn't support the `mode`.
+    pub fn sign_with_mode(
+        &self,
+        message: &[u8],
+        curve: Curve,
+        mode: SigningMode,
+    ) -> KeyPairResult<Vec<u8>> {
         fn sign_impl<Key>(signing_key: Key, message: &[u8]) -> KeyPairResult<Vec<u8>>
         where
             Key: SigningKeyTrait,
@@ -96,14 +113,65 @@ impl PrivateKey {
             signing_key.sign(hash_to_sign).map(|sig| sig.to_vec())
         }
 
-        match curve {
-            Curve::Secp256k1 => sign_impl(self.to_secp256k1_privkey()?, message),
-            Curve::Ed25519 => sign_impl(self.to_ed25519()?, message),
-            Curve::Ed25519Blake2bNano => sign_impl(self.to_ed25519_blake2b()?, message),
-            Curve::Ed25519ExtendedCardano => {
+        fn sign_secp256k1_canonical<F>(
+            signing_key: secp256k1::PrivateKey,
+            message: &[u8],
+            is_canonical: F,
+        ) -> KeyPairResult<Vec<u8>>
+        where
+            for<'a> F: FnMut(&'a secp256k1::Signature) -> bool,
+        {
+            let hash_to_sign =
+                H256::try_from(message).map_err(|_| KeyPairError::InvalidSignMessage)?;
+            signing_key
+                .sign_canonical(hash_to_sign, is_canonical)
+                .map(|sig| sig.to_vec())
+        }
+
+        match (curve, mode) {
+            (Curve::Secp256k1, SigningMode::CanonicalEosFio) => sign_secp256k1_canonical(
+                self.to_secp256k1_privkey()?,
+                message,
+                secp256k1::is_eos_fio_canonical,
+            ),
+            (Curve::Secp256k1, SigningMode::CanonicalLowS) => {
+                sign_secp256k1_canonical(self.to_secp256k1_privkey()?, message, secp256k1::is_low_s)
+            },
+            (Curve::Secp256k1, SigningMode::Schnorr) => {
+                let hash_to_sign =
+                    H256::try_from(message).map_err(|_| KeyPairError::InvalidSignMessage)?;
+                let mut aux_rand = Zeroizing::new(H256::default());
+                OsRng.fill_bytes(aux_rand.as_mut());
+                self.to_secp256k1_privkey()?
+                    .sign_schnorr(hash_to_sign, &aux_rand)
+                    .map(|sig| sig.to_vec())
+            },
+            (
+                _,
+                SigningMode::CanonicalEosFio | SigningMode::CanonicalLowS | SigningMode::Schnorr,
+            ) => Err(KeyPairError::UnsupportedSigningMode),
+            (Curve::Secp256k1, SigningMode::Standard) => {
+                sign_impl(self.to_secp256k1_privkey()?, message)
+            },
+            (Curve::Ed25519, SigningMode::Standard) => sign_impl(self.to_ed25519()?, message),
+            (Curve::Ed25519Blake2bNano, SigningMode::Standard) => {
+                sign_impl(self.to_ed25519_blake2b()?, message)
+            },
+            (Curve::Curve25519, SigningMode::Standard) => {
+                sign_impl(self.to_curve25519_privkey()?, message)
+            },
+            (Curve::Nist256p1, SigningMode::Standard) => {
+                sign_impl(self.to_nist256p1_privkey()?, message)
+            },
+            (Curve::Ed25519ExtendedCardano, SigningMode::Standard) => {
                 sign_impl(self.to_ed25519_extended_cardano()?, message)
             },
-            Curve::Starkex => sign_impl(self.to_starkex_privkey()?, message),
+            (Curve::Starkex, SigningMode::Standard) => {
+                sign_impl(self.to_starkex_privkey()?, message)
+            },
+            (Curve::Sr25519, SigningMode::Standard) => {
+                sign_impl(self.to_sr25519_privkey()?, message)
+            },
         }
     }
 
@@ -118,6 +186,18 @@ impl PrivateKey {
                 let privkey = self.to_secp256k1_privkey()?;
                 Ok(PublicKey::Secp256k1Extended(privkey.public()))
             },
+            PublicKeyType::Secp256k1XOnly => {
+                let privkey = self.to_secp256k1_privkey()?;
+                Ok(PublicKey::Secp256k1XOnly(privkey.x_only_public()))
+            },
+            PublicKeyType::Nist256p1 => {
+                let privkey = self.to_nist256p1_privkey()?;
+                Ok(PublicKey::Nist256p1(privkey.public()))
+            },
+            PublicKeyType::Nist256p1Extended => {
+                let privkey = self.to_nist256p1_privkey()?;
+                Ok(PublicKey::Nist256p1Extended(privkey.public()))
+            },
             PublicKeyType::Ed25519 => {
                 let privkey = self.to_ed25519()?;
                 Ok(PublicKey::Ed25519(privkey.public()))
@@ -126,6 +206,10 @@ impl PrivateKey {
                 let privkey = self.to_ed25519_blake2b()?;
                 Ok(PublicKey::Ed25519Blake2b(privkey.public()))
             },
+            PublicKeyType::Curve25519 => {
+                let privkey = self.to_curve25519_privkey()?;
+                Ok(PublicKey::Curve25519(privkey.public()))
+            },
             PublicKeyType::Ed25519ExtendedCardano => {
                 let privkey = self.to_ed25519_extended_cardano()?;
                 Ok(PublicKey::Ed25519ExtendedCardano(Box::new(
@@ -136,6 +220,10 @@ impl PrivateKey {
                 let privkey = self.to_starkex_privkey()?;
                 Ok(PublicKey::Starkex(privkey.public()))
             },
+            PublicKeyType::Sr25519 => {
+                let privkey = self.to_sr25519_privkey()?;
+                Ok(PublicKey::Sr25519(privkey.public()))
+            },
         }
     }
 
@@ -144,6 +232,16 @@ impl PrivateKey {
         secp256k1::PrivateKey::try_from(self.key().as_slice())
     }
 
+    /// Tries to convert [`PrivateKey::key`] to [`curve25519::PrivateKey`].
+    fn to_curve25519_privkey(&self) -> KeyPairResult<curve25519::PrivateKey> {
+        curve25519::PrivateKey::try_from(self.key().as_slice())
+    }
+
+    /// Tries to convert [`PrivateKey::key`] to [`nist256p1::PrivateKey`].
+    fn to_nist256p1_privkey(&self) -> KeyPairResult<nist256p1::PrivateKey> {
+        nist256p1::PrivateKey::try_from(self.key().as_slice())
+    }
+
     /// Tries to convert [`PrivateKey::key`] to [`ed25519::sha512::PrivateKey`].
     fn to_ed25519(&self) -> KeyPairResult<ed25519::sha512::PrivateKey> {
         ed25519::sha512::PrivateKey::try_from(self.key().as_slice())
@@ -163,4 +261,9 @@ impl PrivateKey {
     fn to_starkex_privkey(&self) -> KeyPairResult<starkex::PrivateKey> {
         starkex::PrivateKey::try_from(self.key().as_slice())
     }
+
+    /// Tries to convert [`PrivateKey::key`] to [`sr25519::PrivateKey`].
+    fn to_sr25519_privkey(&self) -> KeyPairResult<sr25519::PrivateKey> {
+        sr25519::PrivateKey::try_from(self.key().as_slice())
+    }
 }
@@ -6,7 +6,10 @@
 
 use crate::traits::VerifyingKeyTrait;
 use crate::tw::PublicKeyType;
-use crate::{ed25519, secp256k1, starkex, KeyPairError, KeyPairResult};
+use crate::{
+    curve25519, ed25519, nist256p1, secp256k1, sr25519, starkex, KeyPairError, KeyPairResult,
+};
+use tw_hash::H256;
 use tw_misc::traits::ToBytesVec;
 use tw_misc::try_or_false;
 
@@ -14,10 +17,15 @@ use tw_misc::try_or_false;
 pub enum PublicKey {
     Secp256k1(secp256k1::PublicKey),
     Secp256k1Extended(secp256k1::PublicKey),
+    Secp256k1XOnly(secp256k1::XOnlyPublicKey),
+    Nist256p1(nist256p1::PublicKey),
+    Nist256p1Extended(nist256p1::PublicKey),
     Ed25519(ed25519::sha512::PublicKey),
     Ed25519Blake2b(ed25519::blake2b::PublicKey),
+    Curve25519(curve25519::PublicKey),
     Ed25519ExtendedCardano(Box<ed25519::cardano::ExtendedPublicKey>),
     Starkex(starkex::PublicKey),
+    Sr25519(sr25519::PublicKey),
 }
 
 impl PublicKey {
@@ -34,6 +42,20 @@ impl PublicKey {
                 let pubkey = secp256k1::PublicKey::try_from(bytes.as_slice())?;
                 Ok(PublicKey::Secp256k1Extended(pubkey))
             },
+            PublicKeyType::Secp256k1XOnly if secp256k1::XOnlyPublicKey::LEN == bytes.len() => {
+                let pubkey = secp256k1::XOnlyPublicKey::try_from(bytes.as_slice())?;
+                Ok(PublicKey::Secp256k1XOnly(pubkey))
+            },
+            PublicKeyType::Nist256p1 if nist256p1::PublicKey::COMPRESSED == bytes.len() => {
+                let pubkey = nist256p1::PublicKey::try_from(bytes.as_slice())?;
+                Ok(PublicKey::Nist256p1(pubkey))
+            },
+            PublicKeyType::Nist256p1Extended
+                if nist256p1::PublicKey::UNCOMPRESSED == bytes.len() =>
+            {
+                let pubkey = nist256p1::PublicKey::try_from(bytes.as_slice())?;
+                Ok(PublicKey::Nist256p1Extended(pubkey))
+            },
             PublicKeyType::Ed25519 if ed25519::sha512::PublicKey::LEN == bytes.len() => {
                 let pubkey = ed25519::sha512::PublicKey::try_from(bytes.as_slice())?;
                 Ok(PublicKey::Ed25519(pubkey))
@@ -42,6 +64,10 @@ impl PublicKey {
                 let pubkey = ed25519::blake2b::PublicKey::try_from(bytes.as_slice())?;
                 Ok(PublicKey::Ed25519Blake2b(pubkey))
             },
+            PublicKeyType::Curve25519 if curve25519::PublicKey::LEN == bytes.len() => {
+                let pubkey = curve25519::PublicKey::try_from(bytes.as_slice())?;
+                Ok(PublicKey::Curve25519(pubkey))
+            },
             PublicKeyType::Ed25519ExtendedCardano
                 if ed25519::cardano::ExtendedPublicKey::LEN == bytes.len() =>
             {
@@ -52,10 +78,22 @@ impl PublicKey {
                 let pubkey = starkex::PublicKey::try_from(bytes.as_slice())?;
                 Ok(PublicKey::Starkex(pubkey))
             },
+            PublicKeyType::Sr25519 if sr25519::PublicKey::LEN == bytes.len() => {
+                let pubkey = sr25519::PublicKey::try_from(bytes.as_slice())?;
+                Ok(PublicKey::Sr25519(pubkey))
+            },
             _ => Err(KeyPairError::InvalidPublicKey),
         }
     }
 
+    /// Recovers a `secp256k1` extended public key from the given `sig` recoverable signature
+    /// and the signed `message` hash.
+    pub fn recover(sig: &[u8], message: &[u8]) -> KeyPairResult<PublicKey> {
+        let sig = secp256k1::Signature::from_bytes(sig)?;
+        let message = H256::try_from(message).map_err(|_| KeyPairError::InvalidSignMessage)?;
+        secp256k1::PublicKey::recover(&sig, message).map(PublicKey::Secp256k1Extended)
+    }
+
     /// Checks if the given `bytes` is valid using `ty` public key type.
     pub fn is_valid(bytes: Vec<u8>, ty: PublicKeyType) -> bool {
         PublicKey::new(bytes, ty).is_ok()
@@ -79,12 +117,18 @@ impl PublicKey {
             PublicKey::Secp256k1(secp) | PublicKey::Secp256k1Extended(secp) => {
                 verify_impl(secp, sig, message)
             },
+            PublicKey::Secp256k1XOnly(x_only) => verify_impl(x_only, sig, message),
+            PublicKey::Nist256p1(nist) | PublicKey::Nist256p1Extended(nist) => {
+                verify_impl(nist, sig, message)
+            },
             PublicKey::Ed25519(ed) => verify_impl(ed, sig, message),
             PublicKey::Ed25519Blake2b(blake) => verify_impl(blake, sig, message),
+            PublicKey::Curve25519(curve) => verify_impl(curve, sig, message),
             PublicKey::Ed25519ExtendedCardano(cardano) => {
                 verify_impl(cardano.as_ref(), sig, message)
             },
             PublicKey::Starkex(stark) => verify_impl(stark, sig, message),
+            PublicKey::Sr25519(sr) => verify_impl(sr, sig, message),
         }
     }
 
@@ -93,10 +137,15 @@ impl PublicKey {
         match self {
             PublicKey::Secp256k1(secp) => secp.compressed().into_vec(),
             PublicKey::Secp256k1Extended(secp) => secp.uncompressed().into_vec(),
+            PublicKey::Secp256k1XOnly(x_only) => x_only.to_vec(),
+            PublicKey::Nist256p1(nist) => nist.compressed().into_vec(),
+            PublicKey::Nist256p1Extended(nist) => nist.uncompressed().into_vec(),
             PublicKey::Ed25519(ed) => ed.to_vec(),
             PublicKey::Ed25519Blake2b(blake) => blake.to_vec(),
+            PublicKey::Curve25519(curve) => curve.to_vec(),
             PublicKey::Ed25519ExtendedCardano(cardano) => cardano.to_vec(),
             PublicKey::Starkex(stark) => stark.to_vec(),
+            PublicKey::Sr25519(sr) => sr.to_vec(),
         }
     }
 }
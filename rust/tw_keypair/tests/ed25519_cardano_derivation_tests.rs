@@ -0,0 +1,88 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::str::FromStr;
+use tw_encoding::hex;
+use tw_hash::H256;
+use tw_keypair::bip32::{ChildNumber, DerivationPath};
+use tw_keypair::ed25519::cardano::{ExtendedPrivateKey, ExtendedSecretPart};
+use tw_misc::traits::{ToBytesVec, ToBytesZeroizing};
+
+/// Entropy of the "cost dash dress stove morning robust group affair stomach vacant route volume yellow salute laugh" mnemonic.
+/// The test is taken from `AddressTests.cpp` (`MnemonicToAddressV3`).
+const ENTROPY: &str = "30a6f50aeb58ff7699b822d63e0ef27aeff17d9f";
+
+#[test]
+fn test_cardano_icarus_master() {
+    let entropy = hex::decode(ENTROPY).unwrap();
+    let master = ExtendedSecretPart::icarus_master(&entropy, &[]);
+
+    let bytes = master.to_zeroizing_vec();
+    assert_eq!(
+        &bytes[0..32],
+        H256::from("a018cd746e128a0be0782b228c275473205445c33b9000a33dd5668b430b5744").as_slice()
+    );
+    assert_eq!(
+        &bytes[32..64],
+        H256::from("26877cfe435fddda02409b839b7386f3738f10a30b95a225f4b720ee71d2505b").as_slice()
+    );
+}
+
+#[test]
+fn test_cardano_from_icarus_entropy() {
+    let entropy = hex::decode(ENTROPY).unwrap();
+    let path = DerivationPath::from_str("m/1852'/1815'/0'/0/0").unwrap();
+    let private = ExtendedPrivateKey::from_icarus_entropy(&entropy, &[], &path).unwrap();
+
+    let expected_private = "e8c8c5b2df13f3abed4e6b1609c808e08ff959d7e6fc3d849e3f2880550b574437aa559095324d78459b9bb2da069da32337e1cc5da78f48e1bd084670107f3110f3245ddf9132ecef98c670272ef39c03a232107733d4a1d28cb53318df26fae0d152bb611cb9ff34e945e4ff627e6fba81da687a601a879759cd76530b5744424db69a75edd4780a5fbc05d1a3c84ac4166ff8e424808481dd8e77627ce5f5bf2eea84515a4e16c4ff06c92381822d910b5cbf9e9c144e1fb76a6291af7276";
+    assert_eq!(
+        hex::encode(private.to_zeroizing_vec().as_slice(), false),
+        expected_private
+    );
+
+    let expected_public = "fafa7eb4146220db67156a03a5f7a79c666df83eb31abbfbe77c85e06d40da3110f3245ddf9132ecef98c670272ef39c03a232107733d4a1d28cb53318df26faf4b8d5201961e68f2e177ba594101f513ee70fe70a41324e8ea8eb787ffda6f4bf2eea84515a4e16c4ff06c92381822d910b5cbf9e9c144e1fb76a6291af7276";
+    assert_eq!(
+        hex::encode(private.public().to_vec(), false),
+        expected_public
+    );
+}
+
+#[test]
+fn test_cardano_from_icarus_entropy_invalid_path() {
+    let entropy = hex::decode(ENTROPY).unwrap();
+    let tests = [
+        "m/1852'/1815'/0'/0",
+        "m/1852'/1815'/0'/2/0",
+        "m/1852'/1815'/0'/0'/0",
+    ];
+    for path in tests {
+        let path = DerivationPath::from_str(path).unwrap();
+        assert!(ExtendedPrivateKey::from_icarus_entropy(&entropy, &[], &path).is_err());
+    }
+}
+
+#[test]
+fn test_cardano_public_derivation() {
+    let entropy = hex::decode(ENTROPY).unwrap();
+    let account = ExtendedSecretPart::icarus_master(&entropy, &[])
+        .derive_path(&DerivationPath::from_str("m/1852'/1815'/0'").unwrap());
+    let soft_path = DerivationPath::from_str("m/2/0").unwrap();
+
+    let expected = account.derive_path(&soft_path).public();
+    let actual = account.public().derive_path(&soft_path).unwrap();
+    assert_eq!(actual.to_vec(), expected.to_vec());
+
+    // The staking key of the `MnemonicToAddressV3` test.
+    assert_eq!(
+        actual.chain_code(),
+        H256::from("bf2eea84515a4e16c4ff06c92381822d910b5cbf9e9c144e1fb76a6291af7276")
+    );
+
+    assert!(account
+        .public()
+        .derive_child(ChildNumber::hardened(0).unwrap())
+        .is_err());
+}
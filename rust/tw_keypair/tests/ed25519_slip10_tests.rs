@@ -0,0 +1,83 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use serde::Deserialize;
+use std::str::FromStr;
+use tw_encoding::hex;
+use tw_hash::H256;
+use tw_keypair::bip32::{ChildNumber, DerivationPath};
+use tw_keypair::ed25519::{blake2b, sha512};
+use tw_misc::traits::{ToBytesVec, ToBytesZeroizing};
+
+/// The test vectors are taken from [SLIP-0010](https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-vector-1-for-ed25519).
+const ED25519_SLIP10: &str = include_str!("ed25519_slip10.json");
+
+#[derive(Deserialize)]
+struct Ed25519Slip10Test {
+    seed: String,
+    path: String,
+    fingerprint: String,
+    chain_code: H256,
+    private: H256,
+    public: H256,
+}
+
+#[test]
+fn test_ed25519_slip10_vectors() {
+    let tests: Vec<Ed25519Slip10Test> = serde_json::from_str(ED25519_SLIP10).unwrap();
+    for test in tests {
+        let seed = hex::decode(&test.seed).unwrap();
+        let path = DerivationPath::from_str(&test.path).unwrap();
+
+        let master = sha512::ExtendedPrivateKey::from_seed(&seed).unwrap();
+        let key = master.derive_path(&path).unwrap();
+
+        assert_eq!(key.attrs().chain_code, test.chain_code, "{}", test.path);
+        assert_eq!(
+            hex::encode(key.attrs().parent_fingerprint, false),
+            test.fingerprint
+        );
+        assert_eq!(
+            key.private().to_zeroizing_vec().as_slice(),
+            test.private.as_slice()
+        );
+        assert_eq!(key.public().to_vec(), test.public.into_vec());
+    }
+}
+
+/// The test is taken from `TWNanoAddressTests.cpp` (`DeriveAddress`).
+#[test]
+fn test_ed25519_blake2b_nano_derive() {
+    // Seed of the "edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur" mnemonic
+    // with the "some password" passphrase.
+    let seed = hex::decode("0dc285fde768f7ff29b66ce7252d56ed92fe003b605907f7a4f683c3dc8586d34a914d3c71fc099bb38ee4a59e5b081a3497b7a323e90cc68f67b5837690310c").unwrap();
+    let path = DerivationPath::from_str("m/44'/165'/0'").unwrap();
+
+    let master = blake2b::ExtendedPrivateKey::from_seed(&seed).unwrap();
+    let key = master.derive_path(&path).unwrap();
+
+    assert_eq!(
+        key.private().to_zeroizing_vec().as_slice(),
+        H256::from("3be4fc2ef3f3b7374e6fc4fb6e7bb153f8a2998b3b3dab50853eabe128024143").as_slice()
+    );
+    assert_eq!(
+        key.public().to_vec(),
+        H256::from("5b65b0e8173ee0802c2c3e6c9080d1a16b06de1176c938a924f58670904e82c4").into_vec()
+    );
+}
+
+#[test]
+fn test_ed25519_slip10_non_hardened() {
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = sha512::ExtendedPrivateKey::from_seed(&seed).unwrap();
+
+    assert!(master
+        .derive_child(ChildNumber::normal(0).unwrap())
+        .is_err());
+    assert!(master
+        .derive_path(&DerivationPath::from_str("m/44'/501'/0'/0").unwrap())
+        .is_err());
+}
@@ -9,10 +9,10 @@ use tw_hash::sha2::sha256;
 use tw_hash::H256;
 use tw_keypair::ffi::privkey::{
     tw_private_key_create_with_data, tw_private_key_delete, tw_private_key_get_public_key_by_type,
-    tw_private_key_is_valid, tw_private_key_sign, TWPrivateKey,
+    tw_private_key_is_valid, tw_private_key_sign, tw_private_key_sign_with_mode, TWPrivateKey,
 };
-use tw_keypair::ffi::pubkey::{tw_public_key_data, tw_public_key_delete};
-use tw_keypair::tw::{Curve, PublicKeyType};
+use tw_keypair::ffi::pubkey::{tw_public_key_data, tw_public_key_delete, tw_public_key_verify};
+use tw_keypair::tw::{Curve, PublicKeyType, SigningMode};
 use tw_memory::ffi::c_byte_array::CByteArray;
 
 struct TWPrivateKeyHelper {
@@ -84,6 +84,22 @@ fn test_tw_private_key_sign_secp256k1() {
     test_sign(Curve::Secp256k1, secret, msg, sign);
 }
 
+#[test]
+fn test_tw_private_key_sign_nist256p1() {
+    let secret = "afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5";
+    let msg = hex::encode(sha256(b"Hello"), false);
+    let sign = "2e4655831f0c60729583595c103bf0d862af6313e4326f03f512682106c792822f5a9cd21e7d4a3316c2d337e5eee649b09c34f7b4407344f0d32e8d33167d8901";
+    test_sign(Curve::Nist256p1, secret, &msg, sign);
+}
+
+#[test]
+fn test_tw_private_key_sign_curve25519() {
+    let secret = "9864a747e1b97f131fabb6b447296c9b6f0201e79fb3c5356e6c77e89b6a806a";
+    let msg = "0402559a50cb45a9a8e8d4f83295c354725990164d10bb505275d1a3086c08fb935d00000000016372e852120000000005f5e1000000000005f5e10001570acc4110b78a6d38b34d879b5bba38806202ecf1732f8542000766616c6166656c";
+    let sign = "af7989256f496e103ce95096b3f52196dd9132e044905fe486da3b829b5e403bcba95ab7e650a4a33948c2d05cfca2dce4d4df747e26402974490fb4c49fbe8f";
+    test_sign(Curve::Curve25519, secret, msg, sign);
+}
+
 #[test]
 fn test_tw_private_key_sign_ed25519() {
     let secret = "afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5";
@@ -117,6 +133,42 @@ fn test_tw_private_key_sign_starkex() {
     test_sign(Curve::Starkex, secret, msg, sign);
 }
 
+#[test]
+fn test_tw_private_key_sign_sr25519() {
+    let tw_privkey = TWPrivateKeyHelper::with_hex(
+        "fac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e",
+    );
+    let msg_raw = CByteArray::from(b"hello".to_vec());
+    // `sr25519` signatures are randomized, so verify the signature instead of comparing it.
+    let signature = unsafe {
+        tw_private_key_sign(
+            tw_privkey.ptr,
+            msg_raw.data(),
+            msg_raw.size(),
+            Curve::Sr25519 as u32,
+        )
+        .into_vec()
+    };
+    assert_eq!(signature.len(), 64);
+
+    let tw_pubkey = unsafe {
+        tw_private_key_get_public_key_by_type(tw_privkey.ptr, PublicKeyType::Sr25519 as u32)
+    };
+    assert!(!tw_pubkey.is_null());
+    let signature_raw = CByteArray::from(signature);
+    let valid = unsafe {
+        tw_public_key_verify(
+            tw_pubkey,
+            signature_raw.data(),
+            signature_raw.size(),
+            msg_raw.data(),
+            msg_raw.size(),
+        )
+    };
+    unsafe { tw_public_key_delete(tw_pubkey) };
+    assert!(valid);
+}
+
 #[test]
 fn test_tw_private_key_sign_invalid_hash() {
     let tw_privkey = TWPrivateKeyHelper::with_hex(
@@ -147,6 +199,86 @@ fn test_tw_private_key_sign_null_hash() {
     assert!(actual.is_empty());
 }
 
+#[test]
+fn test_tw_private_key_sign_with_mode() {
+    fn sign_with_mode(secret: &str, msg: &str, curve: Curve, mode: SigningMode) -> String {
+        let tw_privkey = TWPrivateKeyHelper::with_hex(secret);
+        let msg_raw = CByteArray::from(hex::decode(msg).unwrap());
+        let actual = unsafe {
+            tw_private_key_sign_with_mode(
+                tw_privkey.ptr,
+                msg_raw.data(),
+                msg_raw.size(),
+                curve as u32,
+                mode as u32,
+            )
+            .into_vec()
+        };
+        hex::encode(actual, false)
+    }
+
+    let secret = "ba0828d5734b65e3bcc2c51c93dfc26dd71bd666cc0273adee77d73d9a322035";
+    let msg = "71b7098e8150cde90f3ec00280815d3069f81c7cdb6d83bbe2b897b1afbe7cd6";
+
+    let actual = sign_with_mode(secret, msg, Curve::Secp256k1, SigningMode::CanonicalEosFio);
+    assert_eq!(actual, "42ceeaa4a3d0ea0429ab09e4d969abd812c65ad4efef9e95e3a19cc3c41be3770ad0222dac6aa1b350cf9273fa922801d11b6142cb0fe639e2fe3fd988e5aec400");
+
+    // The standard mode must be the same as `tw_private_key_sign`.
+    let secret = "afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5";
+    let msg = "1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8";
+    let expected = "8720a46b5b3963790d94bcc61ad57ca02fd153584315bfa161ed3455e336ba624d68df010ed934b8792c5b6a57ba86c3da31d039f9612b44d1bf054132254de901";
+    let actual = sign_with_mode(secret, msg, Curve::Secp256k1, SigningMode::Standard);
+    assert_eq!(actual, expected);
+    // `k256` always normalizes `s`, so the first generated signature is already low-S.
+    let actual = sign_with_mode(secret, msg, Curve::Secp256k1, SigningMode::CanonicalLowS);
+    assert_eq!(actual, expected);
+
+    // Canonical signing is not supported by `ed25519`.
+    let actual = sign_with_mode(secret, msg, Curve::Ed25519, SigningMode::CanonicalEosFio);
+    assert!(actual.is_empty());
+
+    // Schnorr signing is not supported by `ed25519`.
+    let actual = sign_with_mode(secret, msg, Curve::Ed25519, SigningMode::Schnorr);
+    assert!(actual.is_empty());
+}
+
+#[test]
+fn test_tw_private_key_sign_schnorr() {
+    let tw_privkey = TWPrivateKeyHelper::with_hex(
+        "afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5",
+    );
+    let msg_raw = CByteArray::from(sha256(b"hello"));
+    // Schnorr signatures are generated with fresh auxiliary randomness, so verify the signature instead of comparing it.
+    let signature = unsafe {
+        tw_private_key_sign_with_mode(
+            tw_privkey.ptr,
+            msg_raw.data(),
+            msg_raw.size(),
+            Curve::Secp256k1 as u32,
+            SigningMode::Schnorr as u32,
+        )
+        .into_vec()
+    };
+    assert_eq!(signature.len(), 64);
+
+    let tw_pubkey = unsafe {
+        tw_private_key_get_public_key_by_type(tw_privkey.ptr, PublicKeyType::Secp256k1XOnly as u32)
+    };
+    assert!(!tw_pubkey.is_null());
+    let signature_raw = CByteArray::from(signature);
+    let valid = unsafe {
+        tw_public_key_verify(
+            tw_pubkey,
+            signature_raw.data(),
+            signature_raw.size(),
+            msg_raw.data(),
+            msg_raw.size(),
+        )
+    };
+    unsafe { tw_public_key_delete(tw_pubkey) };
+    assert!(valid);
+}
+
 #[test]
 fn test_tw_private_key_get_public_key_by_type() {
     #[track_caller]
@@ -174,6 +306,41 @@ fn test_tw_private_key_get_public_key_by_type() {
     // secp256k1 uncompressed
     let actual = test_get_public_key_data_hex(&tw_privkey, PublicKeyType::Secp256k1Extended);
     assert_eq!(actual, "0499c6f51ad6f98c9c583f8e92bb7758ab2ca9a04110c0a1126ec43e5453d196c166b489a4b7c491e7688e6ebea3a71fc3a1a48d60f98d5ce84c93b65e423fde91");
+
+    // secp256k1 x-only
+    let actual = test_get_public_key_data_hex(&tw_privkey, PublicKeyType::Secp256k1XOnly);
+    assert_eq!(
+        actual,
+        "99c6f51ad6f98c9c583f8e92bb7758ab2ca9a04110c0a1126ec43e5453d196c1"
+    );
+
+    // nist256p1 compressed
+    let actual = test_get_public_key_data_hex(&tw_privkey, PublicKeyType::Nist256p1);
+    assert_eq!(
+        actual,
+        "026d786ab8fda678cf50f71d13641049a393b325063b8c0d4e5070de48a2caf9ab"
+    );
+
+    // nist256p1 uncompressed
+    let actual = test_get_public_key_data_hex(&tw_privkey, PublicKeyType::Nist256p1Extended);
+    assert_eq!(actual, "046d786ab8fda678cf50f71d13641049a393b325063b8c0d4e5070de48a2caf9ab918b4fe46ccbf56701fb210d67d91c5779468f6b3fdc7a63692b9b62543f47ae");
+
+    // curve25519
+    let actual = test_get_public_key_data_hex(&tw_privkey, PublicKeyType::Curve25519);
+    assert_eq!(
+        actual,
+        "686cfce9108566dd43fc6aa75e31f9a9f319c9e9c04d6ad0a52505b86bc17c3a"
+    );
+
+    // sr25519
+    let tw_privkey = TWPrivateKeyHelper::with_hex(
+        "fac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e",
+    );
+    let actual = test_get_public_key_data_hex(&tw_privkey, PublicKeyType::Sr25519);
+    assert_eq!(
+        actual,
+        "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a"
+    );
 }
 
 #[test]
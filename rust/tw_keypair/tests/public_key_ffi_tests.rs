@@ -8,7 +8,8 @@ use tw_encoding::hex;
 use tw_hash::sha2::sha256;
 use tw_hash::sha3::keccak256;
 use tw_keypair::ffi::pubkey::{
-    tw_public_key_create_with_data, tw_public_key_delete, tw_public_key_verify, TWPublicKey,
+    tw_public_key_create_with_data, tw_public_key_data, tw_public_key_delete,
+    tw_public_key_recover, tw_public_key_verify, TWPublicKey,
 };
 use tw_keypair::tw::PublicKeyType;
 use tw_memory::ffi::c_byte_array::CByteArray;
@@ -96,6 +97,25 @@ fn test_tw_public_key_create_by_type() {
         PublicKeyType::Secp256k1Extended,
     );
     assert!(tw_public.ptr.is_null());
+
+    let tw_public = TWPublicKeyHelper::with_hex(
+        "026d786ab8fda678cf50f71d13641049a393b325063b8c0d4e5070de48a2caf9ab",
+        PublicKeyType::Nist256p1,
+    );
+    assert!(!tw_public.ptr.is_null());
+
+    let tw_public = TWPublicKeyHelper::with_hex(
+        "046d786ab8fda678cf50f71d13641049a393b325063b8c0d4e5070de48a2caf9ab918b4fe46ccbf56701fb210d67d91c5779468f6b3fdc7a63692b9b62543f47ae",
+        PublicKeyType::Nist256p1Extended,
+    );
+    assert!(!tw_public.ptr.is_null());
+
+    // Pass an extended pubkey, but Nist256p1 type.
+    let tw_public = TWPublicKeyHelper::with_hex(
+        "046d786ab8fda678cf50f71d13641049a393b325063b8c0d4e5070de48a2caf9ab918b4fe46ccbf56701fb210d67d91c5779468f6b3fdc7a63692b9b62543f47ae",
+        PublicKeyType::Nist256p1,
+    );
+    assert!(tw_public.ptr.is_null());
 }
 
 #[test]
@@ -111,6 +131,32 @@ fn test_tw_public_key_verify_secp256k1() {
     test_verify(PublicKeyType::Secp256k1, public, &msg, sign);
 }
 
+#[test]
+fn test_tw_public_key_verify_secp256k1_schnorr() {
+    // The test vector is taken from
+    // https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+    let public = "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659";
+    let msg = "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89";
+    let sign = "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a";
+    test_verify(PublicKeyType::Secp256k1XOnly, public, msg, sign);
+}
+
+#[test]
+fn test_tw_public_key_verify_nist256p1() {
+    let public = "046d786ab8fda678cf50f71d13641049a393b325063b8c0d4e5070de48a2caf9ab918b4fe46ccbf56701fb210d67d91c5779468f6b3fdc7a63692b9b62543f47ae";
+    let msg = hex::encode(sha256(b"Hello").as_slice(), false);
+    let sign = "2e4655831f0c60729583595c103bf0d862af6313e4326f03f512682106c792822f5a9cd21e7d4a3316c2d337e5eee649b09c34f7b4407344f0d32e8d33167d8901";
+    test_verify(PublicKeyType::Nist256p1Extended, public, &msg, sign);
+}
+
+#[test]
+fn test_tw_public_key_verify_curve25519() {
+    let public = "559a50cb45a9a8e8d4f83295c354725990164d10bb505275d1a3086c08fb935d";
+    let msg = "0402559a50cb45a9a8e8d4f83295c354725990164d10bb505275d1a3086c08fb935d00000000016372e852120000000005f5e1000000000005f5e10001570acc4110b78a6d38b34d879b5bba38806202ecf1732f8542000766616c6166656c";
+    let sign = "af7989256f496e103ce95096b3f52196dd9132e044905fe486da3b829b5e403bcba95ab7e650a4a33948c2d05cfca2dce4d4df747e26402974490fb4c49fbe8f";
+    test_verify(PublicKeyType::Curve25519, public, msg, sign);
+}
+
 #[test]
 fn test_tw_public_key_verify_ed25519() {
     let public = "4870d56d074c50e891506d78faa4fb69ca039cc5f131eb491e166b975880e867";
@@ -135,3 +181,44 @@ fn test_tw_public_key_verify_ed25519_extended_cardano() {
     let sign = "375df53b6a4931dcf41e062b1c64288ed4ff3307f862d5c1b1c71964ce3b14c99422d0fdfeb2807e9900a26d491d5e8a874c24f98eec141ed694d7a433a90f08";
     test_verify(PublicKeyType::Ed25519ExtendedCardano, public, &msg, sign);
 }
+
+#[test]
+fn test_tw_public_key_verify_sr25519() {
+    let public = "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a";
+    let msg = "68656c6c6f";
+    let sign = "86d3ddc354d57e3a2209bd485fe51bd8c0bc19698418094fd0eb410c0f7fdb07382d2be76048f5fe77db7e089397a87bf93bec860b797a38294953d0a10de883";
+    test_verify(PublicKeyType::Sr25519, public, msg, sign);
+}
+
+#[test]
+fn test_tw_public_key_recover() {
+    fn recover(sig: &str, msg: &str) -> TWPublicKeyHelper {
+        let sig_raw = CByteArray::from(hex::decode(sig).unwrap());
+        let msg_raw = CByteArray::from(hex::decode(msg).unwrap());
+        let ptr = unsafe {
+            tw_public_key_recover(
+                sig_raw.data(),
+                sig_raw.size(),
+                msg_raw.data(),
+                msg_raw.size(),
+            )
+        };
+        TWPublicKeyHelper { ptr }
+    }
+
+    let msg = "6468eb103d51c9a683b51818fdb73390151c9973831d2cfb4e9587ad54273155";
+    let sign = "92c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c646487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e581b";
+    let tw_public = recover(sign, msg);
+    assert!(!tw_public.ptr.is_null());
+
+    let actual = unsafe { tw_public_key_data(tw_public.ptr).into_vec() };
+    assert_eq!(hex::encode(actual, false), "0463ade8ebc212b85e7e4278dc3dcb4f9cc18aab912ef5d302b5d1940e772e9e1a9213522efddad487bbd5dd7907e8e776f918e9a5e4cb51893724e9fe76792a4f");
+
+    // Signature without the recovery byte.
+    let sign = "92c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c646487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e58";
+    assert!(recover(sign, msg).ptr.is_null());
+
+    // Message is not a 32 byte hash.
+    let sign = "92c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c646487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e581b";
+    assert!(recover(sign, "6468eb").ptr.is_null());
+}
@@ -0,0 +1,253 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use std::str::FromStr;
+use tw_encoding::hex;
+use tw_keypair::bip32::{ChildNumber, DerivationPath, HDVersion};
+use tw_keypair::secp256k1::{ExtendedPrivateKey, ExtendedPublicKey};
+
+struct Bip32Vector {
+    path: &'static str,
+    xpub: &'static str,
+    xprv: &'static str,
+}
+
+/// Checks the given `vectors` derived from the `seed` against both private and public derivation.
+fn test_bip32_vectors(seed: &str, vectors: &[Bip32Vector]) {
+    let seed = hex::decode(seed).unwrap();
+    let master = ExtendedPrivateKey::from_seed(&seed).unwrap();
+
+    for vector in vectors {
+        let path = DerivationPath::from_str(vector.path).unwrap();
+        let private = master.derive_path(&path).unwrap();
+        assert_eq!(private.to_base58(HDVersion::Xprv).unwrap(), vector.xprv);
+        assert_eq!(
+            private.public().to_base58(HDVersion::Xpub).unwrap(),
+            vector.xpub
+        );
+
+        // Check if the deserialized keys are serialized back to the same strings.
+        let private = ExtendedPrivateKey::from_base58(vector.xprv).unwrap();
+        assert_eq!(private.to_base58(HDVersion::Xprv).unwrap(), vector.xprv);
+        let public = ExtendedPublicKey::from_base58(vector.xpub).unwrap();
+        assert_eq!(public.to_base58(HDVersion::Xpub).unwrap(), vector.xpub);
+
+        // Non-hardened children must be the same when derived from an extended public key.
+        let child = ChildNumber::normal(7).unwrap();
+        assert_eq!(
+            public
+                .derive_child(child)
+                .unwrap()
+                .to_base58(HDVersion::Xpub)
+                .unwrap(),
+            private
+                .derive_child(child)
+                .unwrap()
+                .public()
+                .to_base58(HDVersion::Xpub)
+                .unwrap(),
+        );
+    }
+}
+
+/// https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-1
+#[test]
+fn test_bip32_vector_1() {
+    let vectors = [
+        Bip32Vector {
+            path: "m",
+            xpub: "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+            xprv: "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+        },
+        Bip32Vector {
+            path: "m/0'",
+            xpub: "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+            xprv: "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+        },
+        Bip32Vector {
+            path: "m/0'/1",
+            xpub: "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+            xprv: "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+        },
+        Bip32Vector {
+            path: "m/0'/1/2'",
+            xpub: "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+            xprv: "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+        },
+        Bip32Vector {
+            path: "m/0'/1/2'/2",
+            xpub: "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+            xprv: "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+        },
+        Bip32Vector {
+            path: "m/0'/1/2'/2/1000000000",
+            xpub: "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+            xprv: "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+        },
+    ];
+    test_bip32_vectors("000102030405060708090a0b0c0d0e0f", &vectors);
+}
+
+/// https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-2
+#[test]
+fn test_bip32_vector_2() {
+    let vectors = [
+        Bip32Vector {
+            path: "m",
+            xpub: "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB",
+            xprv: "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+        },
+        Bip32Vector {
+            path: "m/0",
+            xpub: "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH",
+            xprv: "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt",
+        },
+        Bip32Vector {
+            path: "m/0/2147483647'",
+            xpub: "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a",
+            xprv: "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9",
+        },
+        Bip32Vector {
+            path: "m/0/2147483647'/1",
+            xpub: "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon",
+            xprv: "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+        },
+        Bip32Vector {
+            path: "m/0/2147483647'/1/2147483646'",
+            xpub: "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL",
+            xprv: "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc",
+        },
+        Bip32Vector {
+            path: "m/0/2147483647'/1/2147483646'/2",
+            xpub: "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt",
+            xprv: "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j",
+        },
+    ];
+    test_bip32_vectors(
+        "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        &vectors,
+    );
+}
+
+/// https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-3
+/// Covers the retention of leading zeros.
+#[test]
+fn test_bip32_vector_3() {
+    let vectors = [
+        Bip32Vector {
+            path: "m",
+            xpub: "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13",
+            xprv: "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+        },
+        Bip32Vector {
+            path: "m/0'",
+            xpub: "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y",
+            xprv: "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L",
+        },
+    ];
+    test_bip32_vectors(
+        "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be",
+        &vectors,
+    );
+}
+
+/// https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-4
+/// Covers the retention of leading zeros of the private keys during hardened derivation.
+#[test]
+fn test_bip32_vector_4() {
+    let vectors = [
+        Bip32Vector {
+            path: "m",
+            xpub: "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa",
+            xprv: "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+        },
+        Bip32Vector {
+            path: "m/0'",
+            xpub: "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m",
+            xprv: "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G",
+        },
+        Bip32Vector {
+            path: "m/0'/1'",
+            xpub: "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt",
+            xprv: "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1",
+        },
+    ];
+    test_bip32_vectors(
+        "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678",
+        &vectors,
+    );
+}
+
+/// The test is taken from `HDWalletTests.cpp` (`getExtendedPrivateKey`, `getExtendedPublicKey`).
+#[test]
+fn test_extended_key_zprv_zpub() {
+    // Seed of the "ripple scissors kick mammal hire column oak again sun offer wealth tomorrow wagon turn fatal" mnemonic.
+    let seed = hex::decode("354c22aedb9a37407adc61f657a6f00d10ed125efa360215f36c6919abd94d6dbc193a5f9c495e21ee74118661e327e84a5f5f11fa373ec33b80897d4697557d").unwrap();
+    let master = ExtendedPrivateKey::from_seed(&seed).unwrap();
+
+    let account0 = master
+        .derive_path(&DerivationPath::from_str("m/44'/0'/0'").unwrap())
+        .unwrap();
+    assert_eq!(account0.to_base58(HDVersion::Zprv).unwrap(), "zprvAcwsTZNaY1f7rfwsy5GseSDStYBrxwtsBZDkb3iyuQUs4NF6n58BuH7Xj54RuaSCWtU5CiQzuYQgFgqr1HokgKcVAeGeXokhJUAJeP3VmvY");
+    assert_eq!(account0.public().to_base58(HDVersion::Zpub).unwrap(), "zpub6qwDs4uUNPDR5A2M56ot1aABSa2MNQciYn9MPS8bTk1qwAaFKcSST5S1aLidvPp9twqpaumG7vikR2vHhBXjp5oGgHyMvWK3AtUkfeEgyns");
+
+    let account1 = master
+        .derive_path(&DerivationPath::from_str("m/44'/0'/1'").unwrap())
+        .unwrap();
+    assert_eq!(account1.to_base58(HDVersion::Zprv).unwrap(), "zprvAcwsTZNaY1f7sifgNNgdNa4P9mPtyg3zRVgwkx2qF9Sn7F255MzP6Zyumn6bgV5xuoS8ZrDvjzE7APcFSacXdzFYpGvyybb1bnAoh5nHxpn");
+    assert_eq!(account1.public().to_base58(HDVersion::Zpub).unwrap(), "zpub6qwDs4uUNPDR6Ck9UQDdji17hoEPP8mqnicYZLSSoUykz3MDcuJdeNJPd3BozqEafeLZkegWqzAvkgA4JZZ5tTN2rDpGKfk54essyfx1eZP");
+}
+
+/// The test is taken from `HDWalletTests.cpp` (`privateKeyFromXPRV`).
+#[test]
+fn test_extended_private_key_from_xprv() {
+    let xprv = "xprv9yqEgpMG2KCjvotCxaiMkzmKJpDXz2xZi3yUe4XsURvo9DUbPySW1qRbdeDLiSxZt88hESHUhm2AAe2EqfWM9ucdQzH3xv1HoKoLDqHMK9n";
+    let account = ExtendedPrivateKey::from_base58(xprv).unwrap();
+    let key = account
+        .derive_path(&DerivationPath::from_str("m/0/3").unwrap())
+        .unwrap();
+    assert_eq!(
+        key.private().public().compressed(),
+        "025108168f7e5aad52f7381c18d8f880744dbee21dc02c15abe512da0b1cca7e2f".into()
+    );
+}
+
+#[test]
+fn test_extended_key_fingerprint() {
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::from_seed(&seed).unwrap();
+    assert_eq!(master.fingerprint(), [0x34, 0x42, 0x19, 0x3e]);
+    assert_eq!(master.public().fingerprint(), [0x34, 0x42, 0x19, 0x3e]);
+
+    let child = master
+        .derive_child(ChildNumber::hardened(0).unwrap())
+        .unwrap();
+    assert_eq!(child.attrs().depth, 1);
+    assert_eq!(child.attrs().parent_fingerprint, master.fingerprint());
+    assert_eq!(child.attrs().child_number.to_u32(), 0x8000_0000);
+}
+
+#[test]
+fn test_extended_key_invalid() {
+    // Hardened derivation is not possible from an extended public key.
+    let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+    let public = ExtendedPublicKey::from_base58(xpub).unwrap();
+    assert!(public
+        .derive_child(ChildNumber::hardened(0).unwrap())
+        .is_err());
+    // The version doesn't match the key type.
+    assert!(ExtendedPrivateKey::from_base58(xpub).is_err());
+    assert!(public.to_base58(HDVersion::Xprv).is_err());
+
+    // Invalid private key data.
+    let xprv = "xprv9yqEgpMG2KCjvotCxaiMkzmKJpDXz2xZi3yUe4XsURvo9DUbPySW1qRbhw2dJ8QexahgVSfkjxU4FgmN4GLGN3Ui8oLqC6433CeyPUNVHHh";
+    assert!(ExtendedPrivateKey::from_base58(xprv).is_err());
+    // Too short.
+    assert!(ExtendedPrivateKey::from_base58("xprv9y0000").is_err());
+    // Unknown version.
+    let xprv = "pGoh3VZXR4mTkT4bfqj4paog12KmHkAWkdLY8HNsZagD1ihVccygLr1ioLBhVQsny47uEh5swP3KScFc4JJrazx1Y7xvzmH2y5AseLgVMwomBTg2";
+    assert!(ExtendedPrivateKey::from_base58(xprv).is_err());
+}
@@ -38,4 +38,11 @@ pub trait RawPtrTrait: Sized {
         }
         Some(&*raw)
     }
+
+    unsafe fn from_ptr_as_mut(raw: *mut Self) -> Option<&'static mut Self> {
+        if raw.is_null() {
+            return None;
+        }
+        Some(&mut *raw)
+    }
 }
@@ -4,7 +4,9 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
+pub extern crate tw_bitcoin;
 pub extern crate tw_encoding;
+pub extern crate tw_ethereum;
 pub extern crate tw_hash;
 pub extern crate tw_keypair;
 pub extern crate tw_memory;